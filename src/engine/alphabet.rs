@@ -0,0 +1,140 @@
+use std::fmt;
+
+use super::error::WordleError;
+
+/// The letters words are spelled with: A to Z, or a language's own, such
+/// as Spanish with Ñ or German with Ä, Ö and Ü. Letters are kept uppercase
+/// and in the order given.
+///
+/// ```
+/// use wordle::engine::Alphabet;
+///
+/// let german = Alphabet::new("abcdefghijklmnopqrstuvwxyzäöü".chars()).unwrap();
+/// assert!(german.spells("GRÜN"));
+/// assert!(!Alphabet::latin().spells("GRÜN"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alphabet {
+    letters: Vec<char>,
+}
+
+impl Alphabet {
+    /// A to Z, the alphabet of the built-in list.
+    pub fn latin() -> Self {
+        Alphabet {
+            letters: ('A'..='Z').collect(),
+        }
+    }
+
+    /// An alphabet of `letters` in either case, each counted once.
+    /// Anything that isn't a letter is refused.
+    pub fn new(letters: impl IntoIterator<Item = char>) -> Result<Self, WordleError> {
+        let mut alphabet = Vec::new();
+        for letter in letters {
+            if !letter.is_alphabetic() {
+                return Err(WordleError::NotLetters(letter.to_string()));
+            }
+            let letter = Alphabet::upper(letter);
+            if !alphabet.contains(&letter) {
+                alphabet.push(letter);
+            }
+        }
+        if alphabet.is_empty() {
+            return Err(WordleError::NotLetters(String::new()));
+        }
+        Ok(Alphabet { letters: alphabet })
+    }
+
+    /// `letter` in uppercase, by Unicode's rules rather than ASCII's so ñ
+    /// becomes Ñ. Letters whose uppercase is more than one letter, like ß,
+    /// are left as they are so a word keeps its length.
+    pub fn upper(letter: char) -> char {
+        let mut upper = letter.to_uppercase();
+        match (upper.next(), upper.next()) {
+            (Some(upper), None) => upper,
+            _ => letter,
+        }
+    }
+
+    /// `word` in uppercase, letter by letter as [`Alphabet::upper`] does.
+    pub fn upper_word(word: &str) -> String {
+        word.chars().map(Alphabet::upper).collect()
+    }
+
+    /// The letters, in order.
+    pub fn letters(&self) -> &[char] {
+        &self.letters
+    }
+
+    /// Where `letter`, in either case, comes in the alphabet, so tables
+    /// of one entry per letter can be indexed by it.
+    pub fn index(&self, letter: char) -> Option<usize> {
+        let letter = Alphabet::upper(letter);
+        self.letters.iter().position(|&known| known == letter)
+    }
+
+    /// Whether `letter` is a letter as [`Alphabet::upper`] leaves it, in
+    /// whichever alphabet.
+    pub fn is_upper(letter: char) -> bool {
+        letter.is_alphabetic() && Alphabet::upper(letter) == letter
+    }
+
+    /// Whether `letter`, in either case, is one of the alphabet's.
+    pub fn contains(&self, letter: char) -> bool {
+        self.letters.contains(&Alphabet::upper(letter))
+    }
+
+    /// Whether every letter of `word` is one of the alphabet's.
+    pub fn spells(&self, word: &str) -> bool {
+        word.chars().all(|letter| self.contains(letter))
+    }
+
+    /// Whether this is plain A to Z.
+    pub fn is_latin(&self) -> bool {
+        *self == Alphabet::latin()
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet::latin()
+    }
+}
+
+impl fmt::Display for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.letters
+            .iter()
+            .try_for_each(|letter| write!(f, "{letter}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn spanish_alphabet_test() {
+        let spanish = Alphabet::new("abcdefghijklmnñopqrstuvwxyz".chars()).unwrap();
+        assert_eq!(spanish.letters().len(), 27);
+        assert!(spanish.contains('ñ') && spanish.contains('Ñ'));
+        assert!(spanish.spells("niños"));
+        assert!(!spanish.spells("BÜHNE"));
+        assert!(!spanish.is_latin() && Alphabet::default().is_latin());
+        assert!(!Alphabet::latin().contains('Ñ'));
+
+        assert_eq!(spanish.index('ñ'), Some(14));
+        assert_eq!(spanish.index('Z'), Some(26));
+        assert_eq!(Alphabet::latin().index('Ñ'), None);
+        assert!(Alphabet::is_upper('Ñ') && !Alphabet::is_upper('ñ'));
+        assert!(!Alphabet::is_upper('1'));
+
+        assert_eq!(Alphabet::upper_word("añejo"), "AÑEJO");
+        // one letter stays one letter
+        assert_eq!(Alphabet::upper_word("straße"), "STRAßE");
+        assert!(matches!(
+            Alphabet::new("AB1".chars()),
+            Err(WordleError::NotLetters(_))
+        ));
+    }
+}
@@ -0,0 +1,94 @@
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+use super::internal::WORD_LENGTHS;
+use super::wordlist::SkippedLine;
+
+/// Everything the engine can refuse to do. The messages are plain
+/// descriptions; front ends can word them for players as they like.
+///
+/// New refusals may be added in a minor release, so matches need a
+/// catch-all arm.
+///
+/// ```
+/// use wordle::engine::{Word, WordleError};
+///
+/// let err = Word::parse("crane", 6).unwrap_err();
+/// assert!(matches!(err, WordleError::InvalidWordLength { expected: 6, got: 5 }));
+/// ```
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum WordleError {
+    /// a guess or word of the wrong length for the game
+    #[error("expected a word of {expected} letters, got {got}")]
+    InvalidWordLength {
+        /// letters per word in the game
+        expected: usize,
+        /// letters in the word given
+        got: usize,
+    },
+    /// a guess or word with something other than the letters A–Z in it
+    #[error("'{0}' has characters other than A-Z")]
+    NotLetters(String),
+    /// a word list entry that isn't a word of a playable length spelled
+    /// with the list's letters
+    #[error(
+        "'{0}' is not a word of {min} to {max} letters",
+        min = WORD_LENGTHS.start(),
+        max = WORD_LENGTHS.end()
+    )]
+    NotAWord(String),
+    /// a guess the word list doesn't have
+    #[error("'{0}' is not in the word list")]
+    WordNotInDictionary(String),
+    /// a guess a word filter keeps out of the game; the word itself is
+    /// left out of the message
+    #[error("word not allowed")]
+    WordNotAllowed,
+    /// a hard mode guess that leaves out what earlier rows showed, with
+    /// each rule it breaks
+    #[error("{}", .0.join(", "))]
+    HardModeViolation(Vec<String>),
+    /// a row played after the game ended
+    #[error("the game is over")]
+    GameOver,
+    /// no words to draw an answer from or pick a length for
+    #[error("the word list is empty")]
+    EmptyWordList,
+    /// a word list file with nothing but blank lines, named as given
+    #[error("{0} has no words")]
+    NoWords(String),
+    /// a word list file with more lines that aren't playable words than
+    /// lines that are, so probably not a word list at all
+    #[error("{list} has {words} words but {skipped} invalid lines, starting with {first}")]
+    TooFewWords {
+        /// the file, named as given
+        list: String,
+        /// playable words found
+        words: usize,
+        /// lines skipped as not playable
+        skipped: usize,
+        /// the first line skipped and why
+        first: SkippedLine,
+    },
+    /// a word length no game can be played with
+    #[error(
+        "word length must be between {} and {}",
+        WORD_LENGTHS.start(),
+        WORD_LENGTHS.end()
+    )]
+    UnsupportedLength(usize),
+    /// a length the word list has no words of
+    #[error("the word list has no {0}-letter words")]
+    NoWordsOfLength(usize),
+    /// a word list file that couldn't be read
+    #[error("failed to read {}", path.display())]
+    WordlistIo {
+        /// the file
+        path: PathBuf,
+        /// what reading it ran into
+        #[source]
+        source: io::Error,
+    },
+}
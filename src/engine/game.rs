@@ -0,0 +1,290 @@
+use std::collections::{HashMap, HashSet};
+
+use super::alphabet::Alphabet;
+use super::error::WordleError;
+use super::score::{is_solved, mark_used, score};
+use super::tile::TileState;
+use super::word::Word;
+
+/// Rows of a classic game.
+pub const ROUND: u8 = 6;
+
+/// What playing a row told: see [`play_row`].
+///
+/// ```
+/// use wordle::engine::{Played, Word, play_row, score};
+/// use std::collections::HashMap;
+///
+/// let (mut history, mut used_chars) = (Vec::new(), HashMap::new());
+/// let mut row = Word::from("EPOCH").unwrap();
+/// score("EPOCH", &mut row).unwrap();
+/// let played: Played = play_row(&mut history, &mut used_chars, row);
+/// assert!(played.solved);
+/// assert_eq!(played.marked.len(), 5);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Played {
+    /// letters whose keyboard state changed, in the row's order
+    pub marked: Vec<char>,
+    /// whether the row found the answer
+    pub solved: bool,
+}
+
+/// Plays a scored `row`: it goes at the end of `history` and its colors onto
+/// `used_chars`. [`Game`] keeps its board this way, and so can a front end
+/// that keeps its own, so the two can't disagree about a row.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use wordle::engine::{TileState, Word, play_row, score};
+///
+/// let (mut history, mut used_chars) = (Vec::new(), HashMap::new());
+/// let mut row = Word::from("CRANE").unwrap();
+/// score("EPOCH", &mut row).unwrap();
+/// let played = play_row(&mut history, &mut used_chars, row);
+/// assert_eq!(played.marked, ['C', 'R', 'A', 'N', 'E']);
+/// assert!(!played.solved);
+/// assert_eq!(used_chars[&'E'], TileState::Present);
+/// ```
+pub fn play_row(
+    history: &mut Vec<Word>,
+    used_chars: &mut HashMap<char, TileState>,
+    row: Word,
+) -> Played {
+    let marked = mark_used(used_chars, &row);
+    let solved = is_solved(&row);
+    history.push(row);
+    Played { marked, solved }
+}
+
+/// A game without a screen: an answer, the rows played against it and what
+/// they told about each letter. Front ends drive it with [`Game::guess`]
+/// and [`Game::pass`] and draw it however they like.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use wordle::engine::{Game, ROUND, TileState};
+///
+/// let words: HashSet<String> = ["CRANE", "EPOCH"].map(String::from).into();
+/// let mut game = Game::new("epoch", ROUND, words);
+/// game.guess("crane").unwrap();
+/// assert_eq!(game.letter_state('E'), TileState::Present);
+/// game.guess("epoch").unwrap();
+/// assert!(game.is_solved() && game.is_over());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Game {
+    answer: String,
+    rounds: u8,
+    accepted: HashSet<String>,
+    alphabet: Alphabet,
+    history: Vec<Word>,
+    used_chars: HashMap<char, TileState>,
+    solved: bool,
+}
+
+impl Game {
+    /// A game of `answer` on a board of `rounds` rows, taking guesses from
+    /// `accepted`. The answer can always be guessed, listed or not.
+    pub fn new(answer: &str, rounds: u8, accepted: HashSet<String>) -> Self {
+        let answer = Alphabet::upper_word(answer);
+        let mut accepted = accepted;
+        accepted.insert(answer.clone());
+        Game {
+            answer,
+            rounds,
+            accepted,
+            alphabet: Alphabet::latin(),
+            history: Vec::new(),
+            used_chars: HashMap::new(),
+            solved: false,
+        }
+    }
+
+    /// The same game spelled with `alphabet` instead of A to Z.
+    pub fn with_alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet;
+        self
+    }
+
+    /// Scores `guess` and plays it as the next row, returning the scored
+    /// row. Case doesn't matter; a guess of the wrong length, one not in
+    /// the accepted words, or one made after the game is over is refused
+    /// and uses up nothing.
+    pub fn guess(&mut self, guess: &str) -> Result<&Word, WordleError> {
+        if self.is_over() {
+            return Err(WordleError::GameOver);
+        }
+        let mut row = Word::parse_in(guess, self.len(), &self.alphabet)?;
+        let text = row.text();
+        if !self.accepted.contains(&text) {
+            return Err(WordleError::WordNotInDictionary(text));
+        }
+        score(&self.answer, &mut row)?;
+        Ok(self.play(row))
+    }
+
+    /// Uses up a row without guessing.
+    pub fn pass(&mut self) -> Result<&Word, WordleError> {
+        if self.is_over() {
+            return Err(WordleError::GameOver);
+        }
+        Ok(self.play(Word::pass(self.len())))
+    }
+
+    fn len(&self) -> usize {
+        self.answer.chars().count()
+    }
+
+    fn play(&mut self, row: Word) -> &Word {
+        self.solved = play_row(&mut self.history, &mut self.used_chars, row).solved;
+        &self.history[self.history.len() - 1]
+    }
+
+    /// The word being guessed.
+    pub fn answer(&self) -> &str {
+        &self.answer
+    }
+
+    /// The rows played so far, oldest first.
+    pub fn history(&self) -> &[Word] {
+        &self.history
+    }
+
+    /// The state of `letter` on the keyboard, `Unused` until it's played.
+    pub fn letter_state(&self, letter: char) -> TileState {
+        self.used_chars
+            .get(&Alphabet::upper(letter))
+            .copied()
+            .unwrap_or(TileState::Unused)
+    }
+
+    /// Rows left to play.
+    pub fn guesses_remaining(&self) -> u8 {
+        self.rounds.saturating_sub(self.history.len() as u8)
+    }
+
+    /// Whether the last row found the answer.
+    pub fn is_solved(&self) -> bool {
+        self.solved
+    }
+
+    /// Whether no more rows can be played, solved or not.
+    pub fn is_over(&self) -> bool {
+        self.solved || self.guesses_remaining() == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn game(answer: &str, rounds: u8) -> Game {
+        let words = ["CRANE", "EPOCH", "SLATE"].map(String::from).into();
+        Game::new(answer, rounds, words)
+    }
+
+    /// Plays `guess` and checks the whole keyboard against `states`, every
+    /// letter left out of it still `Unused`.
+    fn play(game: &mut Game, guess: &str, states: &[(&str, TileState)]) {
+        game.guess(guess).unwrap();
+        for letter in 'A'..='Z' {
+            let expected = states
+                .iter()
+                .find(|(letters, _)| letters.contains(letter))
+                .map_or(TileState::Unused, |&(_, state)| state);
+            assert_eq!(
+                game.letter_state(letter),
+                expected,
+                "{letter} after {guess}"
+            );
+        }
+    }
+
+    #[test]
+    fn solved_test() {
+        let words = ["ASIDE", "DEATH", "DEALT"].map(String::from).into();
+        let mut game = Game::new("DEALT", ROUND, words);
+        assert!(game.history().is_empty());
+        assert!(!game.is_solved() && !game.is_over());
+
+        use TileState::*;
+        play(&mut game, "ASIDE", &[("ADE", Present), ("IS", Absent)]);
+        assert_eq!(game.history().len(), 1);
+        assert!(!game.is_solved() && !game.is_over());
+
+        play(
+            &mut game,
+            "DEATH",
+            &[("ADE", Correct), ("T", Present), ("HIS", Absent)],
+        );
+        assert_eq!(game.history().len(), 2);
+        assert!(!game.is_solved() && !game.is_over());
+
+        play(&mut game, "DEALT", &[("ADELT", Correct), ("HIS", Absent)]);
+        assert_eq!(game.history().len(), 3);
+        assert!(game.is_solved() && game.is_over());
+    }
+
+    #[test]
+    fn unsolved_test() {
+        let words = ["BAGEL", "ROUND", "MOUNT", "CRACK", "SOLVE"]
+            .map(String::from)
+            .into();
+        let mut game = Game::new("EPOCH", ROUND, words);
+        assert!(game.history().is_empty());
+        assert!(!game.is_solved() && !game.is_over());
+
+        use TileState::*;
+        play(&mut game, "BAGEL", &[("E", Present), ("ABGL", Absent)]);
+        assert_eq!(game.history().len(), 1);
+        assert!(!game.is_over());
+
+        play(&mut game, "ROUND", &[("EO", Present), ("ABDGLNRU", Absent)]);
+        assert_eq!(game.history().len(), 2);
+        assert!(!game.is_over());
+
+        play(
+            &mut game,
+            "MOUNT",
+            &[("EO", Present), ("ABDGLMNRTU", Absent)],
+        );
+        assert_eq!(game.history().len(), 3);
+        assert!(!game.is_over());
+
+        let known = [("C", Correct), ("EO", Present), ("ABDGKLMNRTU", Absent)];
+        play(&mut game, "CRACK", &known);
+        assert_eq!(game.history().len(), 4);
+        assert!(!game.is_over());
+
+        let known = [("C", Correct), ("EO", Present), ("ABDGKLMNRSTUV", Absent)];
+        play(&mut game, "SOLVE", &known);
+        assert_eq!(game.history().len(), 5);
+        assert!(!game.is_solved() && !game.is_over());
+
+        play(&mut game, "SOLVE", &known);
+        assert_eq!(game.history().len(), 6);
+        assert!(!game.is_solved() && game.is_over());
+    }
+
+    #[test]
+    fn refused_guess_test() {
+        let mut game = game("EPOCH", 2);
+        assert!(matches!(
+            game.guess("EPOCHS"),
+            Err(WordleError::InvalidWordLength { .. })
+        ));
+        assert!(matches!(
+            game.guess("QQQQQ"),
+            Err(WordleError::WordNotInDictionary(word)) if word == "QQQQQ"
+        ));
+        assert_eq!(game.guesses_remaining(), 2);
+
+        game.pass().unwrap();
+        game.guess("crane").unwrap();
+        assert!(game.is_over() && !game.is_solved());
+        assert!(matches!(game.guess("EPOCH"), Err(WordleError::GameOver)));
+        assert_eq!(game.letter_state('e'), TileState::Present);
+    }
+}
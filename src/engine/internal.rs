@@ -0,0 +1,154 @@
+//! Helpers the terminal game shares with the engine. They aren't part of
+//! the library's API: hidden from its docs, left out of the API snapshot
+//! and free to change in any release.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use super::alphabet::Alphabet;
+use super::error::WordleError;
+use super::wordlist::SkippedLine;
+
+/// Length of the built-in list's words, and of games that don't ask for
+/// another.
+pub const WORD_LEN: usize = 5;
+/// Word lengths a game can be played with.
+pub const WORD_LENGTHS: RangeInclusive<usize> = 4..=8;
+
+/// Checks a single entry of a word list spelled with `alphabet`, returning
+/// it uppercased.
+pub fn validate_word_in(word: &str, alphabet: &Alphabet) -> Result<String, WordleError> {
+    let word = word.trim();
+    if !WORD_LENGTHS.contains(&word.chars().count()) || !alphabet.spells(word) {
+        return Err(WordleError::NotAWord(word.to_string()));
+    }
+    Ok(Alphabet::upper_word(word))
+}
+
+/// The words of a list file and the lines left out of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedList {
+    /// the playable words, uppercased
+    pub words: HashSet<String>,
+    /// lines that aren't playable words, blank lines aside
+    pub skipped: Vec<SkippedLine>,
+}
+
+impl ParsedList {
+    /// e.g. `loaded 12947 words, skipped 13 invalid lines`.
+    pub fn summary(&self) -> String {
+        let mut summary = match self.words.len() {
+            1 => String::from("loaded 1 word"),
+            words => format!("loaded {words} words"),
+        };
+        match self.skipped.len() {
+            0 => {}
+            1 => summary.push_str(", skipped 1 invalid line"),
+            skipped => {
+                let _ = write!(summary, ", skipped {skipped} invalid lines");
+            }
+        }
+        summary
+    }
+}
+
+/// The list files `path` stands for: the file itself, or every `.txt` file
+/// in it, by name, if it's a directory.
+pub fn list_files(path: &Path) -> Result<Vec<PathBuf>, WordleError> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let io_error = |source| WordleError::WordlistIo {
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut files = Vec::new();
+    for entry in fs::read_dir(path).map_err(io_error)? {
+        let file = entry.map_err(io_error)?.path();
+        if file.is_file() && file.extension().is_some_and(|ext| ext == "txt") {
+            files.push(file);
+        }
+    }
+    if files.is_empty() {
+        return Err(WordleError::NoWords(path.display().to_string()));
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// The contents of a word list file, unchecked.
+pub fn read_list(path: &Path) -> Result<String, WordleError> {
+    fs::read_to_string(path).map_err(|source| WordleError::WordlistIo {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// The words of a list spelled with `alphabet`, e.g. a language pack's,
+/// along with the lines that were skipped.
+pub fn parse_list_in(
+    contents: &str,
+    source: &str,
+    alphabet: &Alphabet,
+) -> Result<ParsedList, WordleError> {
+    let mut words = HashSet::new();
+    let mut skipped = Vec::new();
+    for (number, line) in contents.lines().enumerate() {
+        let word = line.trim();
+        if word.is_empty() {
+            continue;
+        }
+        match validate_word_in(word, alphabet) {
+            Ok(word) => {
+                words.insert(word);
+            }
+            Err(reason) => skipped.push(SkippedLine {
+                line: number + 1,
+                word: word.to_string(),
+                reason: reason.to_string(),
+            }),
+        }
+    }
+    if words.len() < skipped.len() {
+        return Err(WordleError::TooFewWords {
+            list: source.to_string(),
+            words: words.len(),
+            skipped: skipped.len(),
+            first: skipped.swap_remove(0),
+        });
+    }
+    if words.is_empty() {
+        return Err(WordleError::NoWords(source.to_string()));
+    }
+    Ok(ParsedList { words, skipped })
+}
+
+/// The length to play `words` with: `requested` if given, otherwise the
+/// most common length in the list, preferring the classic five letters on a
+/// tie.
+pub fn pick_length(
+    words: &HashSet<String>,
+    requested: Option<usize>,
+) -> Result<usize, WordleError> {
+    if let Some(len) = requested {
+        if !WORD_LENGTHS.contains(&len) {
+            return Err(WordleError::UnsupportedLength(len));
+        }
+        if !words.iter().any(|word| word.chars().count() == len) {
+            return Err(WordleError::NoWordsOfLength(len));
+        }
+        return Ok(len);
+    }
+    let mut by_length: BTreeMap<usize, usize> = BTreeMap::new();
+    for word in words {
+        *by_length.entry(word.chars().count()).or_insert(0) += 1;
+    }
+    by_length
+        .into_iter()
+        .max_by_key(|&(len, count)| (count, len == WORD_LEN, usize::MAX - len))
+        .map(|(len, _)| len)
+        .ok_or(WordleError::EmptyWordList)
+}
@@ -0,0 +1,20 @@
+//! The rules of the game with no terminal attached: rows and their tiles,
+//! scoring a guess, playing a game to the end and loading word lists.
+
+mod alphabet;
+mod error;
+mod game;
+#[doc(hidden)]
+pub mod internal;
+mod score;
+mod tile;
+mod word;
+pub mod wordlist;
+
+pub use alphabet::Alphabet;
+pub use error::WordleError;
+pub use game::{Game, Played, ROUND, play_row};
+pub use score::{is_consistent, is_solved, mark_used, score};
+pub use tile::{Tile, TileState};
+pub use word::Word;
+pub use wordlist::{WordList, WordListStats};
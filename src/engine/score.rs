@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+use super::error::WordleError;
+use super::tile::TileState;
+use super::word::Word;
+
+/// Colors every tile of `guess` against `answer` following the official
+/// duplicate-letter rules: exact matches first, then present letters up to the
+/// number of copies left in the answer.
+///
+/// # Errors
+///
+/// [`WordleError::InvalidWordLength`] if `guess` has a different number of
+/// letters than `answer`, leaving `guess` as it was.
+///
+/// ```
+/// use wordle::engine::{TileState, Word, WordleError, score};
+///
+/// let mut row = Word::parse("speed", 5).unwrap();
+/// score("ABIDE", &mut row).unwrap();
+/// // only one E is yellow, as ABIDE has only one
+/// assert_eq!(row.letters[2].state, TileState::Present);
+/// assert_eq!(row.letters[3].state, TileState::Absent);
+///
+/// let mut long = Word::parse("speeds", 6).unwrap();
+/// let err = score("ABIDE", &mut long).unwrap_err();
+/// assert!(matches!(err, WordleError::InvalidWordLength { expected: 5, got: 6 }));
+/// ```
+pub fn score(answer: &str, guess: &mut Word) -> Result<(), WordleError> {
+    let expected = answer.chars().count();
+    if guess.letters.len() != expected {
+        return Err(WordleError::InvalidWordLength {
+            expected,
+            got: guess.letters.len(),
+        });
+    }
+    let mut answer_map = HashMap::new();
+    answer.chars().for_each(|c| {
+        *answer_map.entry(c).or_insert(0) += 1;
+    });
+
+    // First pass: mark correct letters
+    let answer_vec: Vec<char> = answer.chars().collect();
+    for (i, tile) in guess.letters.iter_mut().enumerate() {
+        if answer_vec[i] == tile.letter {
+            tile.state = TileState::Correct;
+            if let Some(val) = answer_map.get_mut(&tile.letter) {
+                *val -= 1;
+            }
+        } else {
+            tile.state = TileState::Absent;
+        }
+    }
+
+    // Second pass: mark present and absent letters
+    for tile in guess.letters.iter_mut() {
+        if tile.state == TileState::Correct {
+            continue;
+        }
+
+        match answer_map.get_mut(&tile.letter) {
+            Some(val) if *val > 0 => {
+                tile.state = TileState::Present;
+                *val -= 1;
+            }
+            _ => tile.state = TileState::Absent,
+        }
+    }
+    Ok(())
+}
+
+/// Whether `word` would have produced exactly the feedback recorded in
+/// `history` had it been the answer. Passed rows say nothing; rows of
+/// another length rule it out.
+///
+/// ```
+/// use wordle::engine::{Word, is_consistent, score};
+///
+/// let mut row = Word::parse("crane", 5).unwrap();
+/// score("EPOCH", &mut row).unwrap();
+/// assert!(is_consistent("EPOCH", &[row.clone()]));
+/// // R is gray, so PORCH is out
+/// assert!(!is_consistent("PORCH", &[row]));
+/// ```
+pub fn is_consistent(word: &str, history: &[Word]) -> bool {
+    history
+        .iter()
+        .filter(|guess| !guess.is_pass())
+        .all(|guess| {
+            let mut rescored = guess.clone();
+            if score(word, &mut rescored).is_err() {
+                return false;
+            }
+            rescored
+                .letters
+                .iter()
+                .zip(guess.letters.iter())
+                .all(|(a, b)| a.state == b.state)
+        })
+}
+
+/// Whether a scored row found the answer. A pass never does.
+///
+/// ```
+/// use wordle::engine::{Word, is_solved, score};
+///
+/// let mut row = Word::from("EPOCH").unwrap();
+/// score("EPOCH", &mut row).unwrap();
+/// assert!(is_solved(&row));
+/// assert!(!is_solved(&Word::pass(5)));
+/// ```
+pub fn is_solved(row: &Word) -> bool {
+    !row.is_pass()
+        && row
+            .letters
+            .iter()
+            .all(|tile| tile.state == TileState::Correct)
+}
+
+/// Folds a scored row into the keyboard's letter states, each letter keeping
+/// the most telling state it has been played with. Returns the letters whose
+/// state changed; a pass reveals nothing.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use wordle::engine::{TileState, Word, mark_used, score};
+///
+/// let mut used = HashMap::new();
+/// let mut row = Word::from("SPEED").unwrap();
+/// score("ABIDE", &mut row).unwrap();
+/// assert_eq!(mark_used(&mut used, &row), ['S', 'P', 'E', 'D']);
+/// // the second E is gray, but the first one's yellow is kept
+/// assert_eq!(used[&'E'], TileState::Present);
+/// ```
+pub fn mark_used(used: &mut HashMap<char, TileState>, row: &Word) -> Vec<char> {
+    let mut changed = Vec::new();
+    if row.is_pass() {
+        return changed;
+    }
+    for tile in &row.letters {
+        let used_state = used.entry(tile.letter).or_insert(TileState::Unused);
+        // states have priorities. The higher the priority, the smaller the value
+        if tile.state < *used_state {
+            *used_state = tile.state;
+            changed.push(tile.letter);
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn states(answer: &str, guess: &str) -> Vec<TileState> {
+        let mut row = Word::from(guess).unwrap();
+        score(answer, &mut row).unwrap();
+        row.letters.into_iter().map(|tile| tile.state).collect()
+    }
+
+    #[test]
+    fn compare_test() {
+        use TileState::{Absent, Correct, Present};
+        assert_eq!(
+            states("CRATE", "CATER"),
+            [Correct, Present, Present, Present, Present]
+        );
+        assert_eq!(
+            states("HOUND", "AMONG"),
+            [Absent, Absent, Present, Correct, Absent]
+        );
+        assert_eq!(
+            states("TRAIT", "TXTXT"),
+            [Correct, Absent, Absent, Absent, Correct]
+        );
+        assert_eq!(
+            states("TRAIT", "TXTTX"),
+            [Correct, Absent, Present, Absent, Absent]
+        );
+    }
+
+    #[test]
+    fn enye_duplicates_test() {
+        use TileState::{Absent, Correct, Present};
+        // one Ñ in the answer: the placed copy takes it
+        assert_eq!(
+            states("NIÑOS", "ÑOÑOS"),
+            [Absent, Absent, Correct, Correct, Correct]
+        );
+        // and unplaced, only the first copy is yellow
+        assert_eq!(
+            states("AÑEJO", "ÑAÑOS"),
+            [Present, Present, Absent, Present, Absent]
+        );
+        // two in the answer, two yellows; N is a letter of its own
+        assert_eq!(
+            states("ÑAÑAS", "NÑAÑO"),
+            [Absent, Present, Present, Present, Absent]
+        );
+
+        let mut row = Word::from("ÑOÑOS").unwrap();
+        score("NIÑOS", &mut row).unwrap();
+        assert!(is_consistent("NIÑOS", &[row.clone()]));
+        assert!(!is_consistent("AÑEJO", &[row]));
+    }
+
+    #[test]
+    fn length_mismatch_test() {
+        // a longer guess is refused, not scored past the answer's end
+        let mut row = Word::from("EPOCHS").unwrap();
+        assert!(matches!(
+            score("EPOCH", &mut row),
+            Err(WordleError::InvalidWordLength {
+                expected: 5,
+                got: 6
+            })
+        ));
+        assert!(
+            row.letters
+                .iter()
+                .all(|tile| tile.state == TileState::Absent)
+        );
+        let mut row = Word::from("EPOC").unwrap();
+        assert!(score("EPOCH", &mut row).is_err());
+        assert!(!is_consistent("EPOCH", &[Word::from("EPOCHS").unwrap()]));
+    }
+
+    #[test]
+    fn mark_used_test() {
+        let mut used = HashMap::new();
+        let mut row = Word::from("ASIDE").unwrap();
+        score("DEALT", &mut row).unwrap();
+        assert_eq!(mark_used(&mut used, &row), ['A', 'S', 'I', 'D', 'E']);
+        assert!(!is_solved(&row));
+
+        // only improvements count, and a pass changes nothing
+        let mut row = Word::from("DEATH").unwrap();
+        score("DEALT", &mut row).unwrap();
+        assert_eq!(mark_used(&mut used, &row), ['D', 'E', 'A', 'T', 'H']);
+        assert_eq!(used[&'T'], TileState::Present);
+        assert!(mark_used(&mut used, &Word::pass(5)).is_empty());
+        assert!(!is_solved(&Word::pass(5)));
+
+        let mut row = Word::from("DEALT").unwrap();
+        score("DEALT", &mut row).unwrap();
+        assert_eq!(mark_used(&mut used, &row), ['L', 'T']);
+        assert!(is_solved(&row));
+        assert!(is_consistent("DEALT", &[row]));
+    }
+}
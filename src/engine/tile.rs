@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// What a guessed letter says about the answer. States are ordered by how
+/// much they tell: a smaller state overrides a larger one on the keyboard.
+///
+/// ```
+/// use wordle::engine::TileState;
+///
+/// assert!(TileState::Correct < TileState::Present);
+/// ```
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TileState {
+    /// the right letter in the right place
+    Correct,
+    /// in the answer, somewhere else
+    Present,
+    /// not in the answer, or no copies of it left
+    Absent,
+    /// not played yet
+    Unused,
+}
+
+/// One letter of a row and its color.
+///
+/// ```
+/// use wordle::engine::{Tile, TileState, Word, score};
+///
+/// let mut row = Word::from("CRANE").unwrap();
+/// score("EPOCH", &mut row).unwrap();
+/// assert_eq!(
+///     row.letters[0],
+///     Tile {
+///         letter: 'C',
+///         state: TileState::Present,
+///     }
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Tile {
+    /// an uppercase letter, or `' '` in a passed row
+    pub letter: char,
+    /// the letter's color once scored
+    pub state: TileState,
+}
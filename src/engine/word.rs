@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+
+use super::alphabet::Alphabet;
+use super::error::WordleError;
+use super::tile::{Tile, TileState};
+
+/// A row of the board: a guess and the color of each of its letters.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Word {
+    /// the row's tiles, left to right
+    pub letters: Vec<Tile>,
+}
+
+impl Word {
+    /// An empty row.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The letters of `word` as an unscored row, every tile `Absent` until
+    /// [`score`](super::score) colors it. Anything but letters is refused,
+    /// so each tile is one letter however the word was typed.
+    ///
+    /// ```
+    /// use wordle::engine::{TileState, Word};
+    ///
+    /// let row = Word::from("crane").unwrap();
+    /// assert_eq!(row.letters[0].letter, 'c');
+    /// assert!(row.letters.iter().all(|tile| tile.state == TileState::Absent));
+    /// assert!(Word::from("cr4ne").is_err());
+    /// ```
+    pub fn from(word: &str) -> Result<Self, WordleError> {
+        if !word.chars().all(char::is_alphabetic) {
+            return Err(WordleError::NotLetters(word.to_string()));
+        }
+        let letters = word
+            .chars()
+            .map(|letter| Tile {
+                letter,
+                state: TileState::Absent,
+            })
+            .collect();
+        Ok(Word { letters })
+    }
+
+    /// A typed guess as an unscored row of `len` letters A–Z. Surrounding
+    /// whitespace is ignored and case doesn't matter.
+    pub fn parse(input: &str, len: usize) -> Result<Self, WordleError> {
+        Word::parse_in(input, len, &Alphabet::latin())
+    }
+
+    /// A typed guess as an unscored row of `len` letters of `alphabet`,
+    /// checked for letters before it's counted.
+    ///
+    /// ```
+    /// use wordle::engine::{Alphabet, Word};
+    ///
+    /// let spanish = Alphabet::new("ABCDEFGHIJKLMNÑOPQRSTUVWXYZ".chars()).unwrap();
+    /// assert_eq!(Word::parse_in("niño", 4, &spanish).unwrap().text(), "NIÑO");
+    /// assert!(Word::parse("niño", 4).is_err());
+    /// ```
+    pub fn parse_in(input: &str, len: usize, alphabet: &Alphabet) -> Result<Self, WordleError> {
+        let input = input.trim();
+        if !alphabet.spells(input) {
+            return Err(WordleError::NotLetters(input.to_string()));
+        }
+        let got = input.chars().count();
+        if got != len {
+            return Err(WordleError::InvalidWordLength { expected: len, got });
+        }
+        Word::from(&Alphabet::upper_word(input))
+    }
+
+    /// The blank gray row left by passing a turn in a game of `len`-letter
+    /// words.
+    ///
+    /// ```
+    /// use wordle::engine::Word;
+    ///
+    /// let row = Word::pass(5);
+    /// assert!(row.is_pass());
+    /// assert_eq!(row.text(), "     ");
+    /// ```
+    pub fn pass(len: usize) -> Self {
+        let blank = Tile {
+            letter: ' ',
+            state: TileState::Absent,
+        };
+        Word {
+            letters: vec![blank; len],
+        }
+    }
+
+    /// Whether this is a passed turn rather than a guess.
+    pub fn is_pass(&self) -> bool {
+        self.letters.iter().all(|tile| tile.letter == ' ')
+    }
+
+    /// The guessed letters as a string.
+    ///
+    /// ```
+    /// use wordle::engine::Word;
+    ///
+    /// assert_eq!(Word::parse(" epoch", 5).unwrap().text(), "EPOCH");
+    /// ```
+    pub fn text(&self) -> String {
+        self.letters.iter().map(|tile| tile.letter).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(Word::parse(" crane\n", 5).unwrap().text(), "CRANE");
+        assert!(matches!(
+            Word::parse("CRANES", 5),
+            Err(WordleError::InvalidWordLength {
+                expected: 5,
+                got: 6
+            })
+        ));
+        assert!(matches!(
+            Word::parse("CRAN3", 5),
+            Err(WordleError::NotLetters(_))
+        ));
+        // letters outside A–Z are refused before their bytes are counted
+        for input in ["CAFÉ", "CAFÉS", "ÉTUDE", "STRAẞ"] {
+            assert!(matches!(
+                Word::parse(input, 5),
+                Err(WordleError::NotLetters(word)) if word == input
+            ));
+        }
+        assert!(matches!(
+            Word::from("CAF3"),
+            Err(WordleError::NotLetters(_))
+        ));
+        assert_eq!(Word::from("epoch").unwrap().letters.len(), 5);
+
+        // another alphabet's letters are one tile each
+        let spanish = Alphabet::new("ABCDEFGHIJKLMNÑOPQRSTUVWXYZ".chars()).unwrap();
+        assert_eq!(
+            Word::parse_in("niños", 5, &spanish).unwrap().text(),
+            "NIÑOS"
+        );
+        assert!(matches!(
+            Word::parse_in("BÜHNE", 5, &spanish),
+            Err(WordleError::NotLetters(_))
+        ));
+    }
+}
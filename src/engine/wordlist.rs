@@ -0,0 +1,454 @@
+//! Reading word list files and summing them up.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{self, Write};
+use std::path::Path;
+
+use super::alphabet::Alphabet;
+use super::error::WordleError;
+use super::internal::{parse_list_in, read_list, validate_word_in};
+
+const VOWELS: [char; 5] = ['A', 'E', 'I', 'O', 'U'];
+
+/// A word list in sorted order, the shape tooling and deterministic features
+/// (daily answers, hint ranking) want to work with.
+///
+/// ```
+/// use wordle::engine::WordList;
+///
+/// let list = WordList::new(["SLATE", "CRANE", "SLATE"]);
+/// assert_eq!(list.words(), ["CRANE", "SLATE"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordList {
+    words: Vec<String>,
+}
+
+/// Counts over a word list, for `rust_wordle words-stats`. More counts may
+/// be added in a minor release.
+///
+/// ```
+/// use wordle::engine::WordList;
+///
+/// let stats = WordList::new(["CRANE", "EPOCH", "SPEED"]).stats();
+/// assert_eq!(stats.total, 3);
+/// assert_eq!(stats.letter_frequency[&'E'], 4);
+/// assert_eq!(stats.positional_frequency[0][&'C'], 1);
+/// assert_eq!(stats.duplicate_letter_words, 1);
+/// assert!(stats.to_table().starts_with("words: 3\n"));
+/// ```
+#[derive(Debug, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct WordListStats {
+    /// number of words
+    pub total: usize,
+    /// word length -> number of words
+    pub by_length: BTreeMap<usize, usize>,
+    /// letter -> occurrences across all words
+    pub letter_frequency: BTreeMap<char, usize>,
+    /// one table per position, letter -> words with that letter there
+    pub positional_frequency: Vec<BTreeMap<char, usize>>,
+    /// words containing some letter more than once
+    pub duplicate_letter_words: usize,
+    /// number of vowels in a word -> number of words
+    pub vowel_distribution: BTreeMap<usize, usize>,
+}
+
+impl WordList {
+    /// The words sorted, with duplicates dropped.
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut words: Vec<String> = words.into_iter().map(Into::into).collect();
+        words.sort_unstable();
+        words.dedup();
+        WordList { words }
+    }
+
+    /// The words of a set, sorted.
+    pub fn from_set(words: &HashSet<String>) -> Self {
+        WordList::new(words.iter().cloned())
+    }
+
+    /// The words, in order.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Counts lengths, letters and vowels across the list.
+    pub fn stats(&self) -> WordListStats {
+        let mut stats = WordListStats {
+            total: self.words.len(),
+            by_length: BTreeMap::new(),
+            letter_frequency: BTreeMap::new(),
+            positional_frequency: Vec::new(),
+            duplicate_letter_words: 0,
+            vowel_distribution: BTreeMap::new(),
+        };
+
+        for word in &self.words {
+            let letters: Vec<char> = word.chars().collect();
+            *stats.by_length.entry(letters.len()).or_insert(0) += 1;
+
+            if stats.positional_frequency.len() < letters.len() {
+                stats
+                    .positional_frequency
+                    .resize_with(letters.len(), BTreeMap::new);
+            }
+            for (position, &letter) in letters.iter().enumerate() {
+                *stats.letter_frequency.entry(letter).or_insert(0) += 1;
+                *stats.positional_frequency[position]
+                    .entry(letter)
+                    .or_insert(0) += 1;
+            }
+
+            let unique: HashSet<&char> = letters.iter().collect();
+            if unique.len() < letters.len() {
+                stats.duplicate_letter_words += 1;
+            }
+
+            let vowels = letters.iter().filter(|ch| VOWELS.contains(ch)).count();
+            *stats.vowel_distribution.entry(vowels).or_insert(0) += 1;
+        }
+        stats
+    }
+}
+
+/// Checks a single entry of a word list, returning it uppercased.
+///
+/// ```
+/// use wordle::engine::WordleError;
+/// use wordle::engine::wordlist::validate_word;
+///
+/// assert_eq!(validate_word(" crane ").unwrap(), "CRANE");
+/// let err = validate_word("sl4te").unwrap_err();
+/// assert!(matches!(err, WordleError::NotAWord(word) if word == "sl4te"));
+/// ```
+pub fn validate_word(word: &str) -> Result<String, WordleError> {
+    validate_word_in(word, &Alphabet::latin())
+}
+
+/// A line of a word list that isn't a playable word.
+///
+/// ```
+/// use wordle::engine::WordleError;
+/// use wordle::engine::wordlist::parse_list;
+///
+/// let Err(WordleError::TooFewWords { first, .. }) = parse_list("crane\n1234\nab\n", "words.txt")
+/// else {
+///     panic!("not a word list");
+/// };
+/// assert_eq!(first.line, 2);
+/// assert_eq!(first.to_string(), "line 2: '1234' is not a word of 4 to 8 letters");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedLine {
+    /// 1-based line number
+    pub line: usize,
+    /// the line as written
+    pub word: String,
+    /// why it was skipped, naming the word
+    pub reason: String,
+}
+
+impl fmt::Display for SkippedLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+/// Reads a word list file with one word per line. Blank lines are skipped,
+/// and so is anything else that isn't a word of a playable length, but a
+/// file with more of those than words is rejected, so prose or binary files
+/// are never mistaken for a list. Lengths may be mixed; a game keeps only
+/// the words of the length it's played with.
+///
+/// ```
+/// use std::path::Path;
+/// use wordle::engine::WordleError;
+/// use wordle::engine::wordlist::load_file;
+///
+/// let err = load_file(Path::new("/nonexistent/words.txt")).unwrap_err();
+/// assert!(matches!(err, WordleError::WordlistIo { .. }));
+/// ```
+pub fn load_file(path: &Path) -> Result<HashSet<String>, WordleError> {
+    parse_list(&read_list(path)?, &path.display().to_string())
+}
+
+/// The words of a list file's `contents`, checked as `load_file` describes.
+/// `source` names the list in errors.
+///
+/// ```
+/// use wordle::engine::wordlist::parse_list;
+///
+/// let words = parse_list("crane\n\nEpoch\nsl4te\nslate\n", "words.txt").unwrap();
+/// assert_eq!(words.len(), 3);
+/// assert!(words.contains("EPOCH"));
+/// assert!(parse_list("\n", "words.txt").is_err());
+/// ```
+pub fn parse_list(contents: &str, source: &str) -> Result<HashSet<String>, WordleError> {
+    Ok(parse_list_in(contents, source, &Alphabet::latin())?.words)
+}
+
+/// Edits between `a` and `b`, counting a swap of two neighbouring letters
+/// as one edit like an insertion, deletion or substitution (the optimal
+/// string alignment form of Damerau–Levenshtein).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // three rows of the table are enough: two back for swaps
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut current = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        before = std::mem::replace(&mut previous, current);
+    }
+    previous[b.len()]
+}
+
+/// Up to `limit` of `words` at most `max_distance` edits from `word`,
+/// nearest first and alphabetical among equals. It scans every word, which
+/// takes about 1.5ms for the 2,309 built-in words in a release build;
+/// fine once per rejected guess.
+///
+/// ```
+/// use wordle::engine::wordlist::closest_words;
+///
+/// let words = ["BRAIN", "CRANE", "EPOCH"].map(String::from);
+/// // a swap of two letters is one edit
+/// assert_eq!(closest_words("CARNE", &words, 1, 3), ["CRANE"]);
+/// assert!(closest_words("QUIZZ", &words, 2, 3).is_empty());
+/// ```
+pub fn closest_words<'a>(
+    word: &str,
+    words: impl IntoIterator<Item = &'a String>,
+    max_distance: usize,
+    limit: usize,
+) -> Vec<String> {
+    let mut near: Vec<(usize, &String)> = words
+        .into_iter()
+        .map(|candidate| (edit_distance(word, candidate), candidate))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .collect();
+    near.sort();
+    near.into_iter()
+        .take(limit)
+        .map(|(_, candidate)| candidate.clone())
+        .collect()
+}
+
+impl WordListStats {
+    /// Plain-text report for `rust_wordle words-stats`.
+    pub fn to_table(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "words: {}", self.total);
+        let _ = writeln!(
+            out,
+            "duplicate-letter words: {}",
+            self.duplicate_letter_words
+        );
+
+        let _ = write!(out, "by length:");
+        for (length, count) in &self.by_length {
+            let _ = write!(out, "  {length}: {count}");
+        }
+        let _ = write!(out, "\nvowels per word:");
+        for (vowels, count) in &self.vowel_distribution {
+            let _ = write!(out, "  {vowels}: {count}");
+        }
+
+        let _ = write!(out, "\n\nletter  total");
+        for position in 1..=self.positional_frequency.len() {
+            let _ = write!(out, " {:>6}", format!("#{position}"));
+        }
+        out.push('\n');
+        for (letter, total) in &self.letter_frequency {
+            let _ = write!(out, "{letter:<6}  {total:>5}");
+            for table in &self.positional_frequency {
+                let _ = write!(out, " {:>6}", table.get(letter).unwrap_or(&0));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::engine::internal::pick_length;
+    use std::fs;
+
+    fn fixture() -> WordList {
+        WordList::new(["TOOT", "CAT", "AE", "CAT"])
+    }
+
+    #[test]
+    fn new_sorts_and_dedups_test() {
+        assert_eq!(fixture().words(), ["AE", "CAT", "TOOT"]);
+    }
+
+    #[test]
+    fn stats_test() {
+        let stats = fixture().stats();
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.by_length, BTreeMap::from([(2, 1), (3, 1), (4, 1)]));
+        assert_eq!(
+            stats.letter_frequency,
+            BTreeMap::from([('A', 2), ('C', 1), ('E', 1), ('O', 2), ('T', 3)])
+        );
+        assert_eq!(
+            stats.positional_frequency,
+            vec![
+                BTreeMap::from([('A', 1), ('C', 1), ('T', 1)]),
+                BTreeMap::from([('A', 1), ('E', 1), ('O', 1)]),
+                BTreeMap::from([('O', 1), ('T', 1)]),
+                BTreeMap::from([('T', 1)]),
+            ]
+        );
+        assert_eq!(stats.duplicate_letter_words, 1);
+        assert_eq!(stats.vowel_distribution, BTreeMap::from([(1, 1), (2, 2)]));
+    }
+
+    #[test]
+    fn load_file_test() {
+        let dir = std::env::temp_dir().join(format!(
+            "rust_wordle_wordlist_load_file_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let good = dir.join("good.txt");
+        fs::write(&good, "crane\n\nEPOCH\ncrane\n").unwrap();
+        let words = load_file(&good).unwrap();
+        assert_eq!(
+            words,
+            HashSet::from(["CRANE".to_string(), "EPOCH".to_string()])
+        );
+
+        let mixed = dir.join("mixed.txt");
+        fs::write(&mixed, "crane\nplanet\nstrand\nepoch\nbake\n").unwrap();
+        let words = load_file(&mixed).unwrap();
+        assert_eq!(pick_length(&words, None).unwrap(), 5);
+        assert_eq!(pick_length(&words, Some(6)).unwrap(), 6);
+        assert!(matches!(
+            pick_length(&words, Some(7)),
+            Err(WordleError::NoWordsOfLength(7))
+        ));
+        assert!(matches!(
+            pick_length(&words, Some(9)),
+            Err(WordleError::UnsupportedLength(9))
+        ));
+        let tie = HashSet::from(["BAKE".to_string(), "PLANET".to_string()]);
+        assert_eq!(pick_length(&tie, None).unwrap(), 4);
+
+        let prose = dir.join("notes.txt");
+        fs::write(&prose, "remember to buy milk\n").unwrap();
+        assert!(matches!(
+            load_file(&prose),
+            Err(WordleError::TooFewWords {
+                words: 0,
+                skipped: 1,
+                ..
+            })
+        ));
+        let empty = dir.join("empty.txt");
+        fs::write(&empty, "\n").unwrap();
+        assert!(matches!(load_file(&empty), Err(WordleError::NoWords(_))));
+        assert!(matches!(
+            load_file(&dir.join("missing.txt")),
+            Err(WordleError::WordlistIo { .. })
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn garbage_lines_test() {
+        let dir = std::env::temp_dir().join(format!(
+            "rust_wordle_wordlist_garbage_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("words.txt");
+        fs::write(&path, "crane\n\n  \nab\nepoch\nsl4te\nslate\ndon't\n").unwrap();
+        let parsed =
+            parse_list_in(&read_list(&path).unwrap(), "words.txt", &Alphabet::latin()).unwrap();
+        assert_eq!(
+            parsed.words,
+            HashSet::from(["CRANE", "EPOCH", "SLATE"].map(String::from))
+        );
+        let skipped: Vec<_> = parsed.skipped.iter().map(|line| line.line).collect();
+        assert_eq!(skipped, [4, 6, 8]);
+        assert_eq!(parsed.skipped[1].word, "sl4te");
+        assert_eq!(parsed.summary(), "loaded 3 words, skipped 3 invalid lines");
+        assert_eq!(
+            parse_list_in("crane\n", "words.txt", &Alphabet::latin())
+                .unwrap()
+                .summary(),
+            "loaded 1 word"
+        );
+
+        // more garbage than words isn't a list
+        fs::write(&path, "crane\n1234\nhello world\n").unwrap();
+        let err = load_file(&path).unwrap_err();
+        assert!(matches!(
+            err,
+            WordleError::TooFewWords {
+                words: 1,
+                skipped: 2,
+                ..
+            }
+        ));
+        assert!(err.to_string().contains("line 2: '1234'"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn table_and_json_test() {
+        let stats = fixture().stats();
+        let table = stats.to_table();
+        assert!(table.starts_with("words: 3\n"));
+        assert!(table.contains("\nT           3      1      0      1      1\n"));
+
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["letter_frequency"]["T"], 3);
+        assert_eq!(json["positional_frequency"][3]["T"], 1);
+    }
+
+    #[test]
+    fn closest_words_test() {
+        assert_eq!(edit_distance("CRAIN", "BRAIN"), 1);
+        assert_eq!(edit_distance("CRAIN", "CRANE"), 2);
+        // a swap is one edit, not two
+        assert_eq!(edit_distance("CARNE", "CRANE"), 1);
+        assert_eq!(edit_distance("", "ABC"), 3);
+
+        let words: Vec<String> = [
+            "GRAIN", "CRANE", "BRAIN", "TRAIN", "DRAIN", "CHAIN", "EPOCH",
+        ]
+        .map(String::from)
+        .to_vec();
+        // five are one edit away; the first three alphabetically are offered
+        assert_eq!(
+            closest_words("CRAIN", &words, 2, 3),
+            ["BRAIN", "CHAIN", "DRAIN"]
+        );
+        assert_eq!(closest_words("CARNE", &words, 2, 3)[0], "CRANE");
+        assert!(closest_words("QUIZZ", &words, 2, 3).is_empty());
+    }
+}
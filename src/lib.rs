@@ -0,0 +1,5 @@
+//! Wordle's game logic, shared by the terminal game and anything else that
+//! wants to score guesses or play a game headlessly.
+#![warn(missing_docs)]
+
+pub mod engine;
@@ -1,8 +1,804 @@
 mod utils;
-use utils::wordle::Wordle;
+use anyhow::{Context, Error, Result, anyhow, bail};
+use chrono::{DateTime, Local};
+use ratatui::crossterm::terminal;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::Duration;
+use utils::assets::{ANSWERS, ASSETS, WORDS};
+use utils::bot::{SolverStrategy, play_all, play_headless, transcript};
+use utils::challenge::Challenge;
+use utils::commitment::{commitment, verify};
+use utils::compare::Comparison;
+use utils::config::Config;
+use utils::debug::DebugOverlay;
+use utils::difficulty::Difficulty;
+use utils::events::TerminalEvents;
+use utils::feedback::Feedback;
+use utils::import::{ImportFormat, import_words};
+use utils::integrations::DryRunExecutor;
+use utils::leaderboard::{Metric, ProfileRow, render_table, sort_rows};
+use utils::messages::MessageLog;
+use utils::protocol::run_protocol;
+use utils::race::Race;
+use utils::record::{GameRecord, find_game};
+use utils::replay::Replay;
+use utils::stats::Stats;
+use utils::storage::{DEFAULT_PROFILE, Storage, is_valid_profile_name};
+use utils::transfer::StatsFormat;
+use utils::wordle::{Setup, Wordle};
+use wordle::engine::internal::list_files;
+use wordle::engine::{WordList, WordleError};
 
-fn main() {
-    if let Err(e) = Wordle::new().run() {
-        eprintln!("{:#}", e);
+const HELP: &str = "\
+usage: rust_wordle [options] [--daily | --adaptive | --json [--strict] | --bot [--all]]
+       rust_wordle <command> [arguments]
+
+options:
+  --profile NAME            keep stats for another player
+  --wordlist FILE           guesses (and answers) from FILE, or from every
+                            .txt file in a directory; repeat to merge lists
+  --answers FILE            draw answers from FILE only
+  --language DIR            play with the words and keyboard of a language
+                            pack: DIR/words.txt and DIR/layout.toml
+  --rounds N                rows on the board, 1 to 12
+  --length N                letters per word, 4 to 8
+  --seed N                  replay the same answers
+  --challenge CODE          play a friend's challenge code
+  --blitz                   a time limit on every guess
+  --timed                   solve as many words as you can in three minutes
+  --zen                     no row limit, play until the word is found
+  --hot-seat [--any-word]   two players take turns picking each other's word
+  --host ADDR               race a friend who joins at ADDR, e.g. 0.0.0.0:7777
+  --join ADDR               race the friend hosting at ADDR
+  --record FILE             write each game to FILE as it's played
+  --replay FILE             play back a game written by --record
+  --high-contrast           orange and blue tiles instead of green and yellow
+  --difficulty LEVEL        answers from the built-in list: easy draws
+                            everyday words, normal any common word, hard
+                            the whole list; --adaptive moves between them
+  --family-mode             refuse excluded words as guesses too; they are
+                            never drawn as answers either way
+  --reset-history           deal answers that were already played again
+  --dry-run-integrations    show what hooks and uploads would do
+  --debug                   show the debug overlay
+  --simulate-latency MS     delay integrations and dictionary requests
+
+commands:
+  score                     print a guess's colors against an answer
+  compare MINE THEIRS       compare two recorded games
+  words-stats [--json]      word list statistics
+  import-words INPUT        convert a word list
+  leaderboard [--sort M]    compare every profile
+  history GAME_ID           print a recorded game
+  stats export [--format json|csv] [--out FILE]
+                            write the profile's stats, to stdout by default
+  stats import FILE [--merge]
+                            replace the profile's stats with an export, or
+                            add its games to them with --merge
+  verify-commitment A N C   check a game's commitment, given its nonce
+  restore-backup [NAME]     list or restore backups
+  assets list               show which word list, answers, theme colors and
+                            keyboard layouts are used
+  assets install [--force]  copy the built-in files to the data directory
+  doctor                    check the data directory
+
+exit codes:
+  0  success; in --json mode, the game was won
+  1  in --json mode, the game was lost or left unfinished
+  2  usage error: bad arguments or input
+  3  environment error: a missing or unreadable file, or a terminal
+     that can't show the screen
+  4  a word list given with --wordlist or --answers can't be read
+";
+
+/// How a successful run ends.
+enum Exit {
+    /// commands, and the interactive game however it was quit
+    Done,
+    Won,
+    Lost,
+}
+
+impl Exit {
+    fn code(&self) -> u8 {
+        match self {
+            Exit::Done | Exit::Won => 0,
+            Exit::Lost => 1,
+        }
+    }
+}
+
+/// Why a run failed, each with its own exit code.
+enum Failure {
+    Usage(Error),
+    Environment(Error),
+    MissingWordList(Error),
+}
+
+impl Failure {
+    fn code(&self) -> u8 {
+        match self {
+            Failure::Usage(_) => 2,
+            Failure::Environment(_) => 3,
+            Failure::MissingWordList(_) => 4,
+        }
+    }
+
+    fn error(&self) -> &Error {
+        match self {
+            Failure::Usage(error)
+            | Failure::Environment(error)
+            | Failure::MissingWordList(error) => error,
+        }
+    }
+}
+
+/// A word list that can't be read has its own code; other errors caused by
+/// the file system or the terminal are the environment's, and anything else
+/// was wrong with what the user asked for.
+impl From<Error> for Failure {
+    fn from(error: Error) -> Self {
+        let missing_list = error.chain().any(|cause| {
+            matches!(
+                cause.downcast_ref::<WordleError>(),
+                Some(WordleError::WordlistIo { .. })
+            )
+        });
+        if missing_list {
+            Failure::MissingWordList(error)
+        } else if error.chain().any(|cause| cause.is::<io::Error>()) {
+            Failure::Environment(error)
+        } else {
+            Failure::Usage(error)
+        }
+    }
+}
+
+fn compare(mine: &str, theirs: &str) -> Result<()> {
+    let mine = GameRecord::load(Path::new(mine))?;
+    let theirs = GameRecord::load(Path::new(theirs))?;
+    let words = Wordle::load_words()?;
+    Comparison::new(&mine, &theirs, &words)?.run()
+}
+
+fn words_stats(json: bool) -> Result<()> {
+    let stats = WordList::from_set(&Wordle::load_words()?).stats();
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        print!("{}", stats.to_table());
+    }
+    Ok(())
+}
+
+fn json_protocol(profile: &str, setup: Setup, strict: bool) -> Result<Exit> {
+    let mut game = Wordle::with_config(Storage::open_profile(profile), setup)?;
+    run_protocol(&mut game, io::stdin().lock(), io::stdout().lock(), strict)?;
+    Ok(if game.solved { Exit::Won } else { Exit::Lost })
+}
+
+/// Lets the solver play the game's word, or with `all` every answer, and
+/// prints how it went.
+fn bot(profile: &str, setup: Setup, all: bool) -> Result<()> {
+    let mut game = Wordle::with_config(Storage::open_profile(profile), setup)?;
+    let mut strategy = SolverStrategy::new(game.alphabet.clone());
+    if all {
+        let answers: Vec<String> = game.candidates().into_iter().map(String::from).collect();
+        print!("{}", play_all(&mut game, &answers, &mut strategy));
+    } else {
+        let answer = game.answer.clone();
+        play_headless(&mut game, &answer, &mut strategy);
+        print!("{}", transcript(&game));
+    }
+    Ok(())
+}
+
+fn leaderboard(profile: &str, args: &[String]) -> Result<()> {
+    let metric = match args {
+        [] => Metric::WinRate,
+        [flag, metric] if flag == "--sort" => Metric::parse(metric)?,
+        _ => bail!("usage: rust_wordle leaderboard [--sort win-rate|avg-guesses|streak|daily]"),
+    };
+
+    let mut rows = Vec::new();
+    for (name, dir) in Storage::profiles()? {
+        rows.push(ProfileRow::load(&name, &Storage::existing(dir))?);
+    }
+    sort_rows(&mut rows, metric);
+    let width = terminal::size().map_or(80, |(width, _)| width as usize);
+    print!("{}", render_table(&rows, metric, profile, width));
+    Ok(())
+}
+
+/// Opens `profile` keeping as many backups as its config asks for; a config
+/// that doesn't load keeps the default.
+fn open_configured(profile: &str) -> Storage {
+    let mut storage = Storage::open_profile(profile);
+    if let Ok(config) = Config::load(&storage) {
+        config.apply_backups(&mut storage);
+    }
+    storage
+}
+
+/// Lists backups, or restores the named one after asking for confirmation.
+fn restore_backup(profile: &str, name: Option<&String>) -> Result<()> {
+    let storage = open_configured(profile);
+    let backups = storage.backups()?;
+    let Some(name) = name else {
+        if backups.is_empty() {
+            println!("no backups");
+        }
+        for backup in &backups {
+            let modified = backup.modified.map_or("unknown time".to_string(), |time| {
+                DateTime::<Local>::from(time)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            });
+            println!("{:<20} {}", backup.name, modified);
+        }
+        return Ok(());
+    };
+
+    let Some(backup) = backups.iter().find(|backup| &backup.name == name) else {
+        bail!("no backup named '{name}', run `rust_wordle restore-backup` to list them");
+    };
+    print!("Replace {} with {}? [y/N] ", backup.original, backup.name);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    if !answer.trim().eq_ignore_ascii_case("y") {
+        println!("nothing restored");
+        return Ok(());
+    }
+    storage.restore(backup)?;
+    println!(
+        "restored {} (the replaced version is now {}.1)",
+        backup.original, backup.original
+    );
+    Ok(())
+}
+
+/// Converts a CSV, JSON or token word list into a clean one-word-per-line
+/// list, reporting what was rejected on stderr.
+fn import(mut args: Vec<String>) -> Result<()> {
+    let format = take_option(&mut args, "--format")?;
+    let column = take_option(&mut args, "--column")?;
+    let out = take_option(&mut args, "--out")?;
+    let [input] = args.as_slice() else {
+        bail!(
+            "usage: rust_wordle import-words <input> [--format csv|json|tokens] [--column NAME] [--out FILE]"
+        );
+    };
+
+    let path = Path::new(input);
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let format = match format {
+        Some(name) => ImportFormat::parse(&name)?,
+        None => ImportFormat::sniff(path, &contents),
+    };
+    let import = import_words(&contents, format, column.as_deref())
+        .with_context(|| format!("failed to import {}", path.display()))?;
+    if import.list.words().is_empty() {
+        eprint!("{}", import.report());
+        bail!("no valid words in {}", path.display());
+    }
+
+    let mut list = import.list.words().join("\n");
+    list.push('\n');
+    match out {
+        Some(out) => fs::write(&out, list).with_context(|| format!("failed to write {out}"))?,
+        None => print!("{list}"),
+    }
+    eprint!("{}", import.report());
+    Ok(())
+}
+
+const STATS_USAGE: &str = "usage: rust_wordle stats export [--format json|csv] [--out FILE]
+       rust_wordle stats import <file> [--merge]";
+
+/// `stats export` and `stats import`. An import replaces the profile's stats,
+/// keeping the old file as a backup, unless `--merge` adds to them.
+fn stats(profile: &str, mut args: Vec<String>) -> Result<()> {
+    let storage = open_configured(profile);
+    match args.first().map(String::as_str) {
+        Some("export") => {
+            args.remove(0);
+            let format = take_option(&mut args, "--format")?;
+            let out = take_option(&mut args, "--out")?;
+            if !args.is_empty() {
+                bail!(STATS_USAGE);
+            }
+            let format = match format {
+                Some(name) => StatsFormat::parse(&name)?,
+                None => StatsFormat::Json,
+            };
+            let exported = Stats::load(&storage)?.export(format)?;
+            match out {
+                Some(out) => {
+                    fs::write(&out, exported).with_context(|| format!("failed to write {out}"))?
+                }
+                None => print!("{exported}"),
+            }
+        }
+        Some("import") => {
+            args.remove(0);
+            let merge = take_flag(&mut args, "--merge");
+            let [input] = args.as_slice() else {
+                bail!(STATS_USAGE);
+            };
+            let contents =
+                fs::read_to_string(input).with_context(|| format!("failed to read {input}"))?;
+            let imported =
+                Stats::import(&contents).with_context(|| format!("failed to import {input}"))?;
+            let stats = if merge {
+                let mut stats = Stats::load(&storage)?;
+                stats.merge(&imported);
+                stats
+            } else {
+                imported
+            };
+            stats.save(&storage)?;
+            println!(
+                "{} games played, {} won",
+                stats.games_played, stats.games_won
+            );
+        }
+        _ => bail!(STATS_USAGE),
+    }
+    Ok(())
+}
+
+/// Prints the recorded game with `game_id` from the profile's history.
+fn history(profile: &str, game_id: Option<&String>) -> Result<()> {
+    let Some(game_id) = game_id else {
+        bail!("usage: rust_wordle history <game-id>");
+    };
+    match find_game(&Storage::open_profile(profile), game_id)? {
+        Some(record) => {
+            println!("{}", serde_json::to_string_pretty(&record)?);
+            Ok(())
+        }
+        None => bail!("no game with id {game_id} in the history"),
+    }
+}
+
+fn verify_commitment(args: &[String]) -> Result<()> {
+    let [answer, nonce, expected] = args else {
+        bail!("usage: rust_wordle verify-commitment <answer> <nonce> <commitment>");
+    };
+    if !verify(answer, nonce, expected) {
+        bail!(
+            "commitment mismatch: {} and {} give {}, not {}",
+            answer,
+            nonce,
+            commitment(answer, nonce),
+            expected
+        );
+    }
+    println!("commitment {expected} matches {answer}");
+    Ok(())
+}
+
+/// Words given with `option <word>`, or one per line of the file given with
+/// `file_option <path>`.
+fn take_words(args: &mut Vec<String>, option: &str, file_option: &str) -> Result<Vec<String>> {
+    match (take_option(args, option)?, take_option(args, file_option)?) {
+        (Some(word), None) => Ok(vec![word]),
+        (None, Some(path)) => {
+            let contents =
+                fs::read_to_string(&path).with_context(|| format!("failed to read {path}"))?;
+            if contents.trim().is_empty() {
+                bail!("{path} has no words");
+            }
+            Ok(contents.lines().map(String::from).collect())
+        }
+        (Some(_), Some(_)) => bail!("{option} can't be combined with {file_option}"),
+        (None, None) => bail!("{option} or {file_option} is required"),
+    }
+}
+
+/// Prints the colors of each guess against its answer, one line per pair.
+/// With two files, line n of one is paired with line n of the other; a
+/// single word is paired with every line of the other file.
+fn score(mut args: Vec<String>) -> Result<()> {
+    let usage = "usage: rust_wordle score (--answer WORD | --answer-file FILE) (--guess WORD | --guess-file FILE)";
+    let answers = take_words(&mut args, "--answer", "--answer-file").context(usage)?;
+    let guesses = take_words(&mut args, "--guess", "--guess-file").context(usage)?;
+    if !args.is_empty() {
+        bail!("unexpected argument '{}'\n{usage}", args[0]);
+    }
+    let pairs = match (answers.len(), guesses.len()) {
+        (1, _) => guesses.len(),
+        (_, 1) => answers.len(),
+        (a, g) if a == g => a,
+        (a, g) => bail!("{a} answers but {g} guesses; the files should have one word per line"),
+    };
+
+    // check every pair before printing, so a script never gets half a batch
+    let mut lines = Vec::new();
+    for index in 0..pairs {
+        let answer = &answers[index.min(answers.len() - 1)];
+        let guess = &guesses[index.min(guesses.len() - 1)];
+        let feedback = Feedback::new(answer, guess);
+        lines.push(if pairs == 1 {
+            feedback?
+        } else {
+            feedback.with_context(|| format!("line {}", index + 1))?
+        });
+    }
+    let color = io::stdout().is_terminal();
+    for feedback in lines {
+        println!("{}", feedback.line(color));
+    }
+    Ok(())
+}
+
+/// `assets list` shows where each asset is taken from, `assets install`
+/// copies the built-in ones into the data directory to be edited.
+fn assets(profile: &str, setup: &Setup, args: &[String]) -> Result<()> {
+    let storage = Storage::open_profile(profile);
+    match args {
+        [command] if command == "list" => {
+            for asset in ASSETS {
+                // every list given with --wordlist, a directory's one by one
+                let mut cli = Vec::new();
+                if asset.name == WORDS.name {
+                    for path in &setup.wordlist_paths {
+                        cli.extend(list_files(path)?.into_iter().map(Some));
+                    }
+                } else if asset.name == ANSWERS.name {
+                    cli.push(setup.answer_list.clone());
+                }
+                if cli.is_empty() {
+                    cli.push(None);
+                }
+                for path in cli {
+                    let active = asset.active(path.as_deref(), &storage)?;
+                    let source = asset.describe(&active.source);
+                    println!("{} ({}): {source}", asset.name, asset.kind);
+                    if let Some(note) = active.value {
+                        println!("  {note}");
+                    }
+                    for warning in active.warnings {
+                        println!("  skipped: {warning}");
+                    }
+                }
+            }
+            Ok(())
+        }
+        [command, flags @ ..]
+            if command == "install" && (flags.is_empty() || flags == ["--force"]) =>
+        {
+            let force = !flags.is_empty();
+            // answers have no built-in copy, they're the whole word list
+            for asset in ASSETS.iter().filter(|asset| !asset.embedded.is_empty()) {
+                let path = storage
+                    .path(asset.file)
+                    .context("there's no data directory to install into")?;
+                if asset.install(&storage, force)? {
+                    println!("installed {}", path.display());
+                } else {
+                    println!("kept {}, use --force to overwrite it", path.display());
+                }
+            }
+            Ok(())
+        }
+        _ => bail!("usage: rust_wordle assets list | assets install [--force]"),
+    }
+}
+
+/// Checks the data directory and prints the messages saved by the last run.
+fn doctor(profile: &str) -> Result<()> {
+    let storage = Storage::open_profile(profile);
+    println!("{}", storage.doctor());
+    let messages = MessageLog::load(&storage)?;
+    if !messages.is_empty() {
+        println!("\nrecent messages (answers redacted):");
+    }
+    for message in messages {
+        println!("{} {:?}: {}", message.at, message.level, message.text);
+    }
+    Ok(())
+}
+
+/// Removes `option <value>` from `args`, returning the value.
+fn take_option(args: &mut Vec<String>, option: &str) -> Result<Option<String>> {
+    let Some(index) = args.iter().position(|arg| arg == option) else {
+        return Ok(None);
+    };
+    if index + 1 >= args.len() {
+        bail!("{option} needs a value");
+    }
+    Ok(args.drain(index..index + 2).nth(1))
+}
+
+/// Removes every `option <value>` from `args`, returning the values in order.
+fn take_options(args: &mut Vec<String>, option: &str) -> Result<Vec<String>> {
+    let mut values = Vec::new();
+    while let Some(value) = take_option(args, option)? {
+        values.push(value);
+    }
+    Ok(values)
+}
+
+/// Removes `option <value>` from `args`, parsing the value as a number;
+/// `what` names the expected value in the error.
+fn take_number<T: FromStr>(args: &mut Vec<String>, option: &str, what: &str) -> Result<Option<T>> {
+    match take_option(args, option)? {
+        Some(value) => match value.parse() {
+            Ok(number) => Ok(Some(number)),
+            Err(_) => bail!("{option} takes {what}, not '{value}'"),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Removes `--profile <name>` from `args`, returning the chosen profile.
+fn take_profile(args: &mut Vec<String>) -> Result<String> {
+    let Some(profile) = take_option(args, "--profile")? else {
+        return Ok(DEFAULT_PROFILE.to_string());
+    };
+    if !is_valid_profile_name(&profile) {
+        bail!("invalid profile name '{profile}': use letters, digits, '-' and '_'");
+    }
+    Ok(profile)
+}
+
+/// Removes `flag` from `args`, returning whether it was there.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != before
+}
+
+/// Flags that change how the interactive game runs rather than what is
+/// played.
+struct GameOptions {
+    setup: Setup,
+    dry_run: bool,
+    debug: bool,
+    blitz: bool,
+    timed: bool,
+    zen: bool,
+    hot_seat: bool,
+    any_word: bool,
+    host: Option<String>,
+    join: Option<String>,
+    record: Option<PathBuf>,
+    replay: Option<Replay>,
+    high_contrast: bool,
+    reset_history: bool,
+    latency: Option<Duration>,
+}
+
+impl GameOptions {
+    fn take(args: &mut Vec<String>) -> Result<Self> {
+        let mut setup = Setup {
+            wordlist_paths: take_options(args, "--wordlist")?
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+            answer_list: take_option(args, "--answers")?.map(PathBuf::from),
+            language: take_option(args, "--language")?.map(PathBuf::from),
+            family_mode: take_flag(args, "--family-mode"),
+            difficulty: take_option(args, "--difficulty")?
+                .map(|name| Difficulty::parse(&name))
+                .transpose()?,
+            rounds: take_number(args, "--rounds", "a number of rows")?,
+            seed: take_number(args, "--seed", "a number")?,
+            length: take_number(args, "--length", "a number of letters")?,
+            challenge: take_option(args, "--challenge")?
+                .map(|code| Challenge::decode(&code))
+                .transpose()?,
+        };
+        let replay = take_option(args, "--replay")?
+            .map(|path| Replay::load(Path::new(&path)))
+            .transpose()?;
+        // a board the replay fits, unless asked for another
+        if let Some(replay) = &replay {
+            setup.length = setup.length.or(Some(replay.word_len));
+            setup.rounds = setup.rounds.or(Some(replay.rounds));
+        }
+        Ok(GameOptions {
+            setup,
+            dry_run: take_flag(args, "--dry-run-integrations"),
+            debug: take_flag(args, "--debug"),
+            blitz: take_flag(args, "--blitz"),
+            timed: take_flag(args, "--timed"),
+            zen: take_flag(args, "--zen"),
+            hot_seat: take_flag(args, "--hot-seat"),
+            any_word: take_flag(args, "--any-word"),
+            host: take_option(args, "--host")?,
+            join: take_option(args, "--join")?,
+            record: take_option(args, "--record")?.map(PathBuf::from),
+            replay,
+            high_contrast: take_flag(args, "--high-contrast"),
+            reset_history: take_flag(args, "--reset-history"),
+            latency: take_number(args, "--simulate-latency", "milliseconds")?
+                .map(Duration::from_millis),
+        })
+    }
+}
+
+/// A game on `profile`, with integrations captured instead of run and
+/// debugging aids turned on if asked.
+fn open_game(profile: &str, options: &GameOptions) -> Result<Wordle> {
+    let mut game = Wordle::with_config(Storage::open_profile(profile), options.setup.clone())?;
+    if options.dry_run {
+        game.integrations = Box::new(DryRunExecutor::default());
+    }
+    if options.debug {
+        game.debug = Some(DebugOverlay::default());
+    }
+    if options.reset_history {
+        game.reset_played();
+    }
+    if options.high_contrast {
+        game.toggle_high_contrast();
+    }
+    if options.blitz {
+        game.start_blitz();
+    }
+    if options.timed {
+        game.start_timed();
+    }
+    game.zen = options.zen;
+    if options.hot_seat {
+        game.start_hot_seat(options.any_word);
+    }
+    if let Some(addr) = &options.host {
+        let listener =
+            TcpListener::bind(addr).with_context(|| format!("failed to listen on {addr}"))?;
+        println!("waiting for a player to join {}...", listener.local_addr()?);
+        game.start_race(Race::accept(&listener)?, None)?;
+    }
+    if let Some(path) = &options.record {
+        game.start_recording(path.clone());
+    }
+    if let Some(replay) = &options.replay {
+        game.start_replay(replay.clone())?;
+    }
+    if let Some(addr) = &options.join {
+        let (race, answer) = Race::join(addr)?;
+        game.start_race(race, Some(answer))?;
+    }
+    if let Some(latency) = options.latency {
+        game.simulate_latency(latency);
+    }
+    Ok(game)
+}
+
+fn run(mut args: Vec<String>) -> Result<Exit, Failure> {
+    if args
+        .iter()
+        .skip(1)
+        .any(|arg| arg == "--help" || arg == "-h")
+    {
+        print!("{HELP}");
+        return Ok(Exit::Done);
+    }
+    let profile = take_profile(&mut args)?;
+    let options = GameOptions::take(&mut args)?;
+    if args.iter().any(|arg| arg == "--adaptive") && args.iter().any(|arg| arg == "--daily") {
+        return Err(Failure::Usage(anyhow!(
+            "--adaptive can't be combined with --daily"
+        )));
+    }
+    if (options.timed || options.zen) && args.iter().any(|arg| arg == "--daily") {
+        return Err(Failure::Usage(anyhow!(
+            "--timed and --zen can't be combined with --daily"
+        )));
+    }
+    if options.hot_seat
+        && (options.timed
+            || options.setup.challenge.is_some()
+            || args
+                .iter()
+                .any(|arg| arg == "--adaptive" || arg == "--daily"))
+    {
+        return Err(Failure::Usage(anyhow!(
+            "--hot-seat can't be combined with --daily, --adaptive, --timed or --challenge"
+        )));
+    }
+    let racing = options.host.is_some() || options.join.is_some();
+    if racing
+        && (options.hot_seat
+            || options.host.is_some() && options.join.is_some()
+            || options.setup.challenge.is_some()
+            || args
+                .iter()
+                .any(|arg| arg == "--adaptive" || arg == "--daily"))
+    {
+        return Err(Failure::Usage(anyhow!(
+            "--host and --join can't be combined with each other, --daily, --adaptive, --hot-seat or --challenge"
+        )));
+    }
+    if options.any_word && !options.hot_seat {
+        return Err(Failure::Usage(anyhow!(
+            "--any-word only applies to --hot-seat"
+        )));
+    }
+    if options.setup.challenge.is_some()
+        && args
+            .iter()
+            .any(|arg| arg == "--adaptive" || arg == "--daily")
+    {
+        return Err(Failure::Usage(anyhow!(
+            "--challenge can't be combined with --daily or --adaptive"
+        )));
+    }
+    if options.setup.difficulty.is_some() && args.iter().any(|arg| arg == "--adaptive") {
+        return Err(Failure::Usage(anyhow!(
+            "--difficulty can't be combined with --adaptive, which sets it itself"
+        )));
+    }
+    let done = match args.get(1).map(String::as_str) {
+        Some("doctor") => doctor(&profile),
+        Some("compare") => match (args.get(2), args.get(3)) {
+            (Some(mine), Some(theirs)) => compare(mine, theirs),
+            _ => Err(anyhow!(
+                "usage: rust_wordle compare <mine.json> <theirs.json>"
+            )),
+        },
+        Some("words-stats") => words_stats(args.get(2).map(String::as_str) == Some("--json")),
+        Some("import-words") => import(args[2..].to_vec()),
+        Some("restore-backup") => restore_backup(&profile, args.get(2)),
+        Some("verify-commitment") => verify_commitment(&args[2..]),
+        Some("score") => score(args[2..].to_vec()),
+        Some("history") => history(&profile, args.get(2)),
+        Some("stats") => stats(&profile, args[2..].to_vec()),
+        Some("leaderboard") => leaderboard(&profile, &args[2..]),
+        Some("assets") => assets(&profile, &options.setup, &args[2..]),
+        Some("--json") => {
+            return Ok(json_protocol(
+                &profile,
+                options.setup,
+                args.get(2).map(String::as_str) == Some("--strict"),
+            )?);
+        }
+        Some("--bot") => match &args[2..] {
+            [] => bot(&profile, options.setup, false),
+            [all] if all == "--all" => bot(&profile, options.setup, true),
+            _ => Err(anyhow!("usage: rust_wordle --bot [--all]")),
+        },
+        Some("--daily") => {
+            let mut game = open_game(&profile, &options)?;
+            game.start_daily(Local::now().date_naive());
+            game.run(&mut TerminalEvents)
+        }
+        Some("--adaptive") => {
+            let mut game = open_game(&profile, &options)?;
+            game.start_adaptive();
+            game.run(&mut TerminalEvents)
+        }
+        Some(arg) if arg.starts_with('-') => {
+            return Err(Failure::Usage(anyhow!(
+                "unknown option '{arg}', see rust_wordle --help"
+            )));
+        }
+        Some(command) => {
+            return Err(Failure::Usage(anyhow!(
+                "unknown command '{command}', see rust_wordle --help"
+            )));
+        }
+        None => open_game(&profile, &options)?.run(&mut TerminalEvents),
+    };
+    done?;
+    Ok(Exit::Done)
+}
+
+fn main() -> ExitCode {
+    match run(std::env::args().collect()) {
+        Ok(exit) => ExitCode::from(exit.code()),
+        Err(failure) => {
+            eprintln!("{:#}", failure.error());
+            ExitCode::from(failure.code())
+        }
     }
 }
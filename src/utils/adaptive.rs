@@ -0,0 +1,146 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::io;
+
+use super::difficulty::Difficulty;
+use super::storage::{SaveOutcome, Storage};
+
+pub const ADAPTIVE_FILE: &str = "adaptive.json";
+
+/// A win in this many guesses or fewer counts towards levelling up.
+const FAST_WIN: usize = 3;
+/// Fast wins in a row needed to move to a harder difficulty.
+const FAST_WINS_TO_LEVEL_UP: u8 = 2;
+/// Levels kept for the chart.
+const HISTORY_LEN: usize = 30;
+
+/// Adaptive difficulty: two fast wins in a row move the answers up a
+/// [`Difficulty`], a loss moves them down. Persisted so the level survives
+/// restarts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Adaptive {
+    pub level: Difficulty,
+    /// fast wins in a row at the current level
+    pub fast_wins: u8,
+    /// level after each game, oldest first
+    pub levels: Vec<Difficulty>,
+}
+
+impl Adaptive {
+    pub fn load(storage: &Storage) -> Result<Self> {
+        match storage.load(ADAPTIVE_FILE)? {
+            Some(contents) => Ok(serde_json::from_str(&contents)?),
+            None => Ok(Adaptive::default()),
+        }
+    }
+
+    pub fn save(&self, storage: &Storage) -> io::Result<SaveOutcome> {
+        let json = serde_json::to_string_pretty(self)?;
+        storage.save(ADAPTIVE_FILE, &json)
+    }
+
+    /// The state after a game that took `guesses` guesses.
+    pub fn next(&self, solved: bool, guesses: usize) -> Adaptive {
+        let (level, fast_wins) = if !solved {
+            (self.level.easier(), 0)
+        } else if guesses > FAST_WIN {
+            (self.level, 0)
+        } else if self.fast_wins + 1 >= FAST_WINS_TO_LEVEL_UP {
+            (self.level.harder(), 0)
+        } else {
+            (self.level, self.fast_wins + 1)
+        };
+
+        let mut levels = self.levels.clone();
+        levels.push(level);
+        if levels.len() > HISTORY_LEN {
+            levels.drain(..levels.len() - HISTORY_LEN);
+        }
+        Adaptive {
+            level,
+            fast_wins,
+            levels,
+        }
+    }
+
+    /// The recent levels as a one-line chart, e.g. `▁▄▄█`.
+    pub fn chart(&self) -> String {
+        self.levels
+            .iter()
+            .map(|level| match level {
+                Difficulty::Easy => '▁',
+                Difficulty::Normal => '▄',
+                Difficulty::Hard => '█',
+            })
+            .collect()
+    }
+
+    /// The last `width` levels as a row per difficulty, hardest first, with
+    /// a block for each game that ended at that level and a dot otherwise.
+    pub fn level_rows(&self, width: usize) -> Vec<(Difficulty, String)> {
+        let recent = &self.levels[self.levels.len().saturating_sub(width)..];
+        [Difficulty::Hard, Difficulty::Normal, Difficulty::Easy]
+            .into_iter()
+            .map(|difficulty| {
+                let row = recent
+                    .iter()
+                    .map(|&level| if level == difficulty { '█' } else { '·' })
+                    .collect();
+                (difficulty, row)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::testing::temp_dir;
+    use std::fs;
+
+    #[test]
+    fn transitions_test() {
+        let start = Adaptive::default();
+        let one = start.next(true, 3);
+        assert_eq!((one.level, one.fast_wins), (Difficulty::Normal, 1));
+        let two = one.next(true, 2);
+        assert_eq!((two.level, two.fast_wins), (Difficulty::Hard, 0));
+
+        // a slow win breaks the run without changing the level
+        let slow = one.next(true, 4);
+        assert_eq!((slow.level, slow.fast_wins), (Difficulty::Normal, 0));
+
+        let lost = two.next(false, 6);
+        assert_eq!(lost.level, Difficulty::Normal);
+        assert_eq!(lost.chart(), "▄█▄");
+        assert_eq!(lost.next(false, 6).next(false, 6).level, Difficulty::Easy);
+
+        let rows: Vec<String> = lost.level_rows(2).into_iter().map(|(_, row)| row).collect();
+        assert_eq!(rows, ["█·", "·█", "··"]);
+        assert_eq!(
+            lost.level_rows(10)[1],
+            (Difficulty::Normal, String::from("█·█"))
+        );
+    }
+
+    #[test]
+    fn history_cap_test() {
+        let mut state = Adaptive::default();
+        for _ in 0..HISTORY_LEN + 5 {
+            state = state.next(false, 6);
+        }
+        assert_eq!(state.levels.len(), HISTORY_LEN);
+    }
+
+    #[test]
+    fn save_load_test() {
+        let dir = temp_dir("adaptive_save_load");
+        let storage = Storage::open(dir.clone());
+        assert_eq!(Adaptive::load(&storage).unwrap(), Adaptive::default());
+        let state = Adaptive::default().next(true, 1).next(true, 1);
+        state.save(&storage).unwrap();
+        assert_eq!(Adaptive::load(&storage).unwrap(), state);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,458 @@
+use anyhow::{Context, Error, Result};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use wordle::engine::internal::{ParsedList, parse_list_in, read_list};
+use wordle::engine::{Alphabet, WordleError};
+
+use super::config::Config;
+use super::keyboard::parse_layouts;
+use super::storage::{SaveOutcome, Storage};
+use super::theme::parse_palettes;
+
+/// A file the game ships a default of, which players can replace with
+/// their own copy in the data directory.
+pub struct Asset {
+    /// what `assets list` calls it
+    pub name: &'static str,
+    /// file name in the data directory
+    pub file: &'static str,
+    /// what the user means by it, e.g. "word list"
+    pub kind: &'static str,
+    /// how messages name the embedded default
+    pub builtin: &'static str,
+    /// empty when nothing is built in, as for answers, which are drawn
+    /// from the whole word list unless a list is given
+    pub embedded: &'static str,
+    /// the path `config.json` sets for it, if any
+    pub configured: fn(&Config) -> Option<&Path>,
+    /// reads a copy from a file
+    pub read: fn(&Path) -> Result<String>,
+    /// whether contents from `origin` load, for `assets list`, with a note
+    /// on anything left out of them
+    pub check: fn(&str, &str) -> Result<Option<String>>,
+}
+
+/// The word list guesses are checked against and answers drawn from.
+pub const WORDS: Asset = Asset {
+    name: "words",
+    file: "words.txt",
+    kind: "word list",
+    builtin: "the built-in list",
+    embedded: include_str!("../../words.txt"),
+    configured: configured_word_list,
+    read: read_word_list,
+    check: check_word_list,
+};
+
+/// The list answers are drawn from, when it isn't the whole word list.
+pub const ANSWERS: Asset = Asset {
+    name: "answers",
+    file: "answers.txt",
+    kind: "answer list",
+    builtin: "the whole word list",
+    embedded: "",
+    configured: configured_answer_list,
+    read: read_word_list,
+    check: check_answer_list,
+};
+
+/// Colors of the themes, over the ones built into the code.
+pub const THEME_COLORS: Asset = Asset {
+    name: "themes",
+    file: "themes.toml",
+    kind: "theme colors",
+    builtin: "the built-in colors",
+    embedded: include_str!("../../themes.toml"),
+    configured: not_configured,
+    read: read_file,
+    check: check_theme_colors,
+};
+
+/// Rows of the on-screen keyboard's layouts.
+pub const LAYOUTS: Asset = Asset {
+    name: "layouts",
+    file: "layouts.toml",
+    kind: "keyboard layouts",
+    builtin: "the built-in layouts",
+    embedded: include_str!("../../layouts.toml"),
+    configured: not_configured,
+    read: read_file,
+    check: check_layouts,
+};
+
+fn configured_word_list(config: &Config) -> Option<&Path> {
+    config.word_list.as_deref()
+}
+
+fn configured_answer_list(config: &Config) -> Option<&Path> {
+    config.answer_list.as_deref()
+}
+
+fn not_configured(_: &Config) -> Option<&Path> {
+    None
+}
+
+fn read_word_list(path: &Path) -> Result<String> {
+    Ok(read_list(path)?)
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+}
+
+fn check_word_list(contents: &str, origin: &str) -> Result<Option<String>> {
+    let parsed = parse_list_in(contents, origin, &Alphabet::latin())?;
+    Ok((!parsed.skipped.is_empty()).then(|| parsed.summary()))
+}
+
+fn check_answer_list(contents: &str, origin: &str) -> Result<Option<String>> {
+    let parsed = parse_answers(contents, origin, &Alphabet::latin())?;
+    Ok(parsed
+        .filter(|parsed| !parsed.skipped.is_empty())
+        .map(|parsed| parsed.summary()))
+}
+
+fn check_theme_colors(contents: &str, origin: &str) -> Result<Option<String>> {
+    parse_palettes(contents, origin).map(|_| None)
+}
+
+fn check_layouts(contents: &str, origin: &str) -> Result<Option<String>> {
+    parse_layouts(contents, origin).map(|_| None)
+}
+
+/// The answers in `contents`, or `None` for the default of drawing from
+/// the whole word list.
+pub fn parse_answers(
+    contents: &str,
+    origin: &str,
+    alphabet: &Alphabet,
+) -> Result<Option<ParsedList>, WordleError> {
+    if origin == ANSWERS.builtin {
+        return Ok(None);
+    }
+    parse_list_in(contents, origin, alphabet).map(Some)
+}
+
+/// Every asset, in the order `assets list` shows them.
+pub const ASSETS: [&Asset; 4] = [&WORDS, &ANSWERS, &THEME_COLORS, &LAYOUTS];
+
+/// Where an asset was taken from, highest precedence first.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// a path given on the command line
+    Cli(PathBuf),
+    /// a path set in `config.json`
+    Config(PathBuf),
+    /// the asset's file in the data directory
+    DataDir(PathBuf),
+    Embedded,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Cli(path) => write!(f, "{} (command line)", path.display()),
+            Source::Config(path) => write!(f, "{} (config.json)", path.display()),
+            Source::DataDir(path) => write!(f, "{} (data directory)", path.display()),
+            Source::Embedded => f.write_str("built in"),
+        }
+    }
+}
+
+/// An asset as loaded, with where it came from and what was skipped on the
+/// way there.
+pub struct Resolved<T> {
+    pub value: T,
+    pub source: Source,
+    /// overrides that failed to load, each naming what was used instead
+    pub warnings: Vec<String>,
+}
+
+impl Asset {
+    /// The places to look, highest precedence first; the embedded default
+    /// always comes last.
+    pub fn candidates(
+        &self,
+        cli: Option<&Path>,
+        config: Option<&Path>,
+        storage: &Storage,
+    ) -> Vec<Source> {
+        let mut sources = Vec::new();
+        if let Some(path) = cli {
+            sources.push(Source::Cli(path.to_path_buf()));
+        }
+        if let Some(path) = config {
+            sources.push(Source::Config(path.to_path_buf()));
+        }
+        if let Some(path) = storage.path(self.file).filter(|path| path.exists()) {
+            sources.push(Source::DataDir(path));
+        }
+        sources.push(Source::Embedded);
+        sources
+    }
+
+    /// Loads the asset from the first of `candidates` that `parse` accepts.
+    /// A path from the command line has to work; the others fall through to
+    /// the next place with a warning.
+    pub fn resolve<T, E: Into<Error>>(
+        &self,
+        cli: Option<&Path>,
+        config: Option<&Path>,
+        storage: &Storage,
+        parse: impl Fn(&str, &str) -> Result<T, E>,
+    ) -> Result<Resolved<T>> {
+        let sources = self.candidates(cli, config, storage);
+        let mut warnings = Vec::new();
+        for (index, source) in sources.iter().enumerate() {
+            let loaded = self.read(source).and_then(|contents| {
+                let origin = match source {
+                    Source::Embedded => self.builtin.to_string(),
+                    Source::Cli(path) | Source::Config(path) | Source::DataDir(path) => {
+                        path.display().to_string()
+                    }
+                };
+                parse(&contents, &origin).map_err(Into::into)
+            });
+            match loaded {
+                Ok(value) => {
+                    return Ok(Resolved {
+                        value,
+                        source: source.clone(),
+                        warnings,
+                    });
+                }
+                Err(err) if matches!(source, Source::Cli(_) | Source::Embedded) => return Err(err),
+                Err(err) => {
+                    let next = match &sources[index + 1] {
+                        Source::Embedded => self.builtin.to_string(),
+                        next => next.to_string(),
+                    };
+                    warnings.push(format!("{err:#}, using {next}"));
+                }
+            }
+        }
+        unreachable!("the embedded default is always a candidate")
+    }
+
+    /// Where the asset would be loaded from for `storage`'s profile, given
+    /// the path on the command line if any.
+    pub fn active(
+        &self,
+        cli: Option<&Path>,
+        storage: &Storage,
+    ) -> Result<Resolved<Option<String>>> {
+        let config = Config::load(storage)?;
+        self.resolve(cli, (self.configured)(&config), storage, self.check)
+    }
+
+    fn read(&self, source: &Source) -> Result<String> {
+        match source {
+            Source::Cli(path) | Source::Config(path) | Source::DataDir(path) => (self.read)(path),
+            Source::Embedded => Ok(self.embedded.to_string()),
+        }
+    }
+
+    /// How `assets list` names `source`: the embedded default of an asset
+    /// with nothing built in says what's used instead.
+    pub fn describe(&self, source: &Source) -> String {
+        match source {
+            Source::Embedded if self.embedded.is_empty() => self.builtin.to_string(),
+            source => source.to_string(),
+        }
+    }
+
+    /// Writes the embedded default into the data directory to be edited,
+    /// unless a copy is already there and `force` isn't set. Returns whether
+    /// it was written.
+    pub fn install(&self, storage: &Storage, force: bool) -> Result<bool> {
+        if !force && storage.path(self.file).is_some_and(|path| path.exists()) {
+            return Ok(false);
+        }
+        let outcome = storage
+            .save(self.file, self.embedded)
+            .with_context(|| format!("failed to install {}", self.file))?;
+        Ok(outcome == SaveOutcome::Saved)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::keyboard::builtin_layouts;
+    use crate::utils::messages::Level;
+    use crate::utils::testing::temp_dir;
+    use crate::utils::theme::builtin_palettes;
+    use crate::utils::wordle::{Setup, Wordle};
+    use wordle::engine::wordlist::parse_list;
+
+    fn words(resolved: &Resolved<std::collections::HashSet<String>>) -> usize {
+        resolved.value.len()
+    }
+
+    #[test]
+    fn precedence_test() {
+        let dir = temp_dir("assets_precedence");
+        fs::create_dir_all(&dir).unwrap();
+        let storage = Storage::open(dir.clone());
+        let cli = dir.join("cli.txt");
+        let config = dir.join("config.txt");
+        fs::write(&cli, "crane\n").unwrap();
+        fs::write(&config, "crane\nepoch\n").unwrap();
+        storage.save(WORDS.file, "crane\nepoch\nslate\n").unwrap();
+        let resolve = |cli: Option<&Path>, config: Option<&Path>| {
+            WORDS.resolve(cli, config, &storage, parse_list).unwrap()
+        };
+
+        let all = resolve(Some(&cli), Some(&config));
+        assert_eq!((words(&all), &all.source), (1, &Source::Cli(cli.clone())));
+        let config_first = resolve(None, Some(&config));
+        assert_eq!(config_first.source, Source::Config(config.clone()));
+        assert_eq!(words(&config_first), 2);
+        let data_dir = resolve(None, None);
+        assert_eq!(data_dir.source, Source::DataDir(dir.join(WORDS.file)));
+        assert_eq!(words(&data_dir), 3);
+
+        fs::remove_file(dir.join(WORDS.file)).unwrap();
+        let embedded = resolve(None, None);
+        assert_eq!(embedded.source, Source::Embedded);
+        assert!(words(&embedded) > 2000);
+        assert!(embedded.warnings.is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn partial_overrides_test() {
+        let dir = temp_dir("assets_partial");
+        fs::create_dir_all(&dir).unwrap();
+        let storage = Storage::open(dir.clone());
+        let missing = dir.join("missing.txt");
+        storage.save(WORDS.file, "crane\nepoch\n").unwrap();
+
+        // a broken config path falls through to the data directory
+        let resolved = WORDS
+            .resolve(None, Some(&missing), &storage, parse_list)
+            .unwrap();
+        assert_eq!(resolved.source, Source::DataDir(dir.join(WORDS.file)));
+        assert_eq!(resolved.warnings.len(), 1);
+        assert!(resolved.warnings[0].contains("missing.txt"));
+        assert!(resolved.warnings[0].ends_with("(data directory)"));
+
+        // and a broken data directory copy to the built-in list
+        storage.save(WORDS.file, "not a word list\n").unwrap();
+        let resolved = WORDS
+            .resolve(None, Some(&missing), &storage, parse_list)
+            .unwrap();
+        assert_eq!(resolved.source, Source::Embedded);
+        assert_eq!(resolved.warnings.len(), 2);
+        assert!(resolved.warnings[1].ends_with("using the built-in list"));
+
+        // but one given on the command line has to load
+        assert!(
+            WORDS
+                .resolve(Some(&missing), None, &storage, parse_list)
+                .is_err()
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn answers_test() {
+        let dir = temp_dir("assets_answers");
+        fs::create_dir_all(&dir).unwrap();
+        let storage = Storage::open(dir.clone());
+        let resolve = |cli: Option<&Path>, config: Option<&Path>| {
+            ANSWERS
+                .resolve(cli, config, &storage, |contents: &str, origin: &str| {
+                    parse_answers(contents, origin, &Alphabet::latin())
+                })
+                .unwrap()
+        };
+        let count = |resolved: &Resolved<Option<ParsedList>>| {
+            resolved.value.as_ref().map(|list| list.words.len())
+        };
+
+        // nothing given: the whole word list
+        let whole = resolve(None, None);
+        assert_eq!((count(&whole), &whole.source), (None, &Source::Embedded));
+        assert_eq!(ANSWERS.describe(&whole.source), "the whole word list");
+
+        storage.save(ANSWERS.file, "epoch\ncrane\n").unwrap();
+        let data_dir = resolve(None, None);
+        assert_eq!(data_dir.source, Source::DataDir(dir.join(ANSWERS.file)));
+        assert_eq!(count(&data_dir), Some(2));
+        let cli = dir.join("cli.txt");
+        fs::write(&cli, "slate\n").unwrap();
+        let missing = dir.join("missing.txt");
+        let first = resolve(Some(&cli), Some(&missing));
+        assert_eq!((count(&first), first.source), (Some(1), Source::Cli(cli)));
+        let fallen = resolve(None, Some(&missing));
+        assert_eq!(fallen.source, Source::DataDir(dir.join(ANSWERS.file)));
+        assert_eq!(fallen.warnings.len(), 1);
+
+        // a broken copy falls through to drawing from everything
+        storage.save(ANSWERS.file, "not a word list\n").unwrap();
+        let broken = resolve(None, None);
+        assert_eq!(count(&broken), None);
+        assert!(broken.warnings[0].ends_with("using the whole word list"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn theme_and_layout_overrides_test() {
+        let dir = temp_dir("assets_themes");
+        fs::create_dir_all(&dir).unwrap();
+        let storage = Storage::open(dir.clone());
+        let ocean = "[ocean]\ncorrect = [1, 2, 3]\npresent = [4, 5, 6]\nabsent = [7, 8, 9]\nunused = [0, 0, 0]\n";
+        storage.save(THEME_COLORS.file, ocean).unwrap();
+        storage
+            .save(LAYOUTS.file, "dvorak = [\"PYFGCRL\", \"AOEUIDHTNS\"]\n")
+            .unwrap();
+
+        // the copies' themes and layouts, the rest built in
+        let game = Wordle::with_config(Storage::open(dir.clone()), Setup::default()).unwrap();
+        let builtin = builtin_palettes();
+        assert_eq!(game.palettes["ocean"].correct, [1, 2, 3]);
+        assert_eq!(game.palettes["sunset"], builtin["sunset"]);
+        assert_eq!(game.layouts["dvorak"].len(), 2);
+        assert_eq!(game.layouts["qwerty"], builtin_layouts()["qwerty"]);
+
+        // broken copies are reported and the built-in ones used
+        storage.save(THEME_COLORS.file, "[ocean]\n").unwrap();
+        storage.save(LAYOUTS.file, "qwerty = [\"QW3\"]\n").unwrap();
+        let game = Wordle::with_config(Storage::open(dir.clone()), Setup::default()).unwrap();
+        assert_eq!(game.palettes, builtin);
+        assert_eq!(game.layouts, builtin_layouts());
+        let error = game.messages.current().unwrap();
+        assert_eq!(error.level, Level::Error);
+        assert!(error.text.contains("layouts.toml"), "{}", error.text);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn install_test() {
+        let dir = temp_dir("assets_install");
+        let storage = Storage::open(dir.clone());
+        assert!(WORDS.install(&storage, false).unwrap());
+        assert_eq!(
+            fs::read_to_string(dir.join(WORDS.file)).unwrap(),
+            WORDS.embedded
+        );
+
+        // an edited copy is kept unless forced
+        storage.save(WORDS.file, "crane\n").unwrap();
+        assert!(!WORDS.install(&storage, false).unwrap());
+        assert_eq!(fs::read_to_string(dir.join(WORDS.file)).unwrap(), "crane\n");
+        WORDS.install(&storage, true).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join(WORDS.file)).unwrap(),
+            WORDS.embedded
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::io::{self, IsTerminal};
+use std::time::Duration;
+
+use super::theme::{Background, THEMES};
+use super::wordle::Wordle;
+
+/// How long to wait for the terminal to report its background color; ones
+/// that ignore the query are treated as dark.
+const QUERY_TIMEOUT: Duration = Duration::from_millis(150);
+
+/// `background` in `config.json`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackgroundSetting {
+    /// ask the terminal, then look at `COLORFGBG`
+    #[default]
+    Auto,
+    Light,
+    Dark,
+}
+
+impl BackgroundSetting {
+    /// The background to start with, before anything is detected.
+    pub fn initial(&self) -> Background {
+        match self {
+            BackgroundSetting::Light => Background::Light,
+            BackgroundSetting::Auto | BackgroundSetting::Dark => Background::Dark,
+        }
+    }
+}
+
+/// The terminal's background, from an OSC 11 query or else `COLORFGBG`.
+/// Needs raw mode, so the reply isn't echoed or held back until Enter.
+pub fn detect() -> Option<Background> {
+    if io::stdin().is_terminal()
+        && io::stdout().is_terminal()
+        && let Some(background) = query(QUERY_TIMEOUT)
+    {
+        return Some(background);
+    }
+    from_colorfgbg(&env::var("COLORFGBG").ok()?)
+}
+
+/// `COLORFGBG` is `fg;bg` (some terminals put another field in between),
+/// with ANSI color numbers; 7 and the bright colors from 9 up are light.
+fn from_colorfgbg(value: &str) -> Option<Background> {
+    let bg: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+    if bg == 7 || (9..=15).contains(&bg) {
+        Some(Background::Light)
+    } else {
+        Some(Background::Dark)
+    }
+}
+
+/// Reads `rgb:RRRR/GGGG/BBBB` out of a reply to OSC 11, each channel having
+/// one to four hex digits.
+fn parse_reply(reply: &[u8]) -> Option<Background> {
+    let reply = String::from_utf8_lossy(reply);
+    let start = reply.find("]11;rgb:")? + "]11;rgb:".len();
+    let body = &reply[start..];
+    let end = body
+        .find(|ch: char| !(ch.is_ascii_hexdigit() || ch == '/'))
+        .unwrap_or(body.len());
+    let channels: Vec<f32> = body[..end]
+        .split('/')
+        .map(|channel| {
+            let max = 16f32.powi(channel.len() as i32) - 1.0;
+            u32::from_str_radix(channel, 16)
+                .ok()
+                .filter(|_| (1..=4).contains(&channel.len()))
+                .map(|value| value as f32 / max)
+        })
+        .collect::<Option<_>>()?;
+    let [r, g, b] = channels[..] else {
+        return None;
+    };
+    let luma = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    Some(if luma > 0.5 {
+        Background::Light
+    } else {
+        Background::Dark
+    })
+}
+
+/// Asks for the background color, followed by a device attributes request
+/// that nearly every terminal answers: once that reply is in there's no
+/// point waiting any longer for the first.
+#[cfg(unix)]
+fn query(timeout: Duration) -> Option<Background> {
+    use std::io::Write;
+    use std::os::fd::AsRawFd;
+    use std::time::Instant;
+
+    let mut stdout = io::stdout();
+    stdout.write_all(b"\x1b]11;?\x1b\\\x1b[c").ok()?;
+    stdout.flush().ok()?;
+
+    let fd = io::stdin().as_raw_fd();
+    let deadline = Instant::now() + timeout;
+    let mut reply = Vec::new();
+    while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+        let mut poll_fd = libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: one valid pollfd for the duration of the call
+        let ready = unsafe { libc::poll(&mut poll_fd, 1, left.as_millis() as libc::c_int) };
+        if ready <= 0 {
+            break;
+        }
+        let mut chunk = [0u8; 64];
+        // SAFETY: `chunk` is writable for its whole length; reading the fd
+        // directly keeps std's buffer from holding on to later key presses
+        let read = unsafe { libc::read(fd, chunk.as_mut_ptr().cast(), chunk.len()) };
+        if read <= 0 {
+            break;
+        }
+        reply.extend_from_slice(&chunk[..read as usize]);
+        if device_attributes_done(&reply) {
+            break;
+        }
+    }
+    parse_reply(&reply)
+}
+
+#[cfg(not(unix))]
+fn query(_timeout: Duration) -> Option<Background> {
+    None
+}
+
+/// Whether `reply` ends with the `ESC [ ? ... c` answer to the device
+/// attributes request.
+#[cfg(unix)]
+fn device_attributes_done(reply: &[u8]) -> bool {
+    reply
+        .windows(3)
+        .rposition(|window| window == b"\x1b[?")
+        .is_some_and(|start| reply[start..].contains(&b'c'))
+}
+
+impl Wordle {
+    /// Detects the background when `config.json` leaves it on auto, and
+    /// redoes the theme for it. Run once, in raw mode, before the first draw.
+    pub fn detect_background(&mut self) {
+        if self.config.background == BackgroundSetting::Auto {
+            self.set_background(detect().unwrap_or_default());
+        }
+    }
+
+    pub fn set_background(&mut self, background: Background) {
+        self.background = background;
+        if let Some(entry) = THEMES.iter().find(|entry| entry.id == self.theme_id) {
+            self.theme = entry.theme(background, &self.palettes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::theme::Theme;
+
+    #[test]
+    fn colorfgbg_test() {
+        assert_eq!(from_colorfgbg("0;15"), Some(Background::Light));
+        assert_eq!(from_colorfgbg("0;default;7"), Some(Background::Light));
+        assert_eq!(from_colorfgbg("15;0"), Some(Background::Dark));
+        assert_eq!(from_colorfgbg("7;8"), Some(Background::Dark));
+        assert_eq!(from_colorfgbg("default;default"), None);
+        assert_eq!(from_colorfgbg(""), None);
+    }
+
+    #[test]
+    fn osc_reply_test() {
+        assert_eq!(
+            parse_reply(b"\x1b]11;rgb:ffff/ffff/ffff\x1b\\\x1b[?62;22c"),
+            Some(Background::Light)
+        );
+        assert_eq!(
+            parse_reply(b"\x1b]11;rgb:1e1e/1e1e/2e2e\x07"),
+            Some(Background::Dark)
+        );
+        assert_eq!(
+            parse_reply(b"\x1b]11;rgb:fd/f6/e3\x07"),
+            Some(Background::Light)
+        );
+        // terminals that ignore the query only answer the second request
+        assert_eq!(parse_reply(b"\x1b[?1;2c"), None);
+        assert_eq!(parse_reply(b"\x1b]11;rgb:ffff/ffff\x07"), None);
+        assert_eq!(parse_reply(b"\x1b]11;rgb:fffff/0/0\x07"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn device_attributes_test() {
+        assert!(device_attributes_done(b"\x1b]11;rgb:0/0/0\x07\x1b[?62;22c"));
+        assert!(!device_attributes_done(b"\x1b]11;rgb:0/0/0\x07\x1b[?62;2"));
+    }
+
+    #[test]
+    fn config_override_test() {
+        let mut game = Wordle::new();
+        game.config.background = BackgroundSetting::Light;
+        game.set_background(game.config.background.initial());
+        // auto detection leaves a forced background alone
+        game.detect_background();
+        assert_eq!(game.theme, Theme::light());
+
+        game.select_theme(0);
+        assert_eq!(game.theme, Theme::light());
+        game.set_background(Background::Dark);
+        assert_eq!(game.theme, Theme::default());
+
+        let config: crate::utils::config::Config =
+            serde_json::from_str("{\"background\": \"dark\"}").unwrap();
+        assert_eq!(config.background, BackgroundSetting::Dark);
+    }
+}
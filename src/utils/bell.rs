@@ -0,0 +1,124 @@
+use std::io::{self, IsTerminal, Write};
+use std::time::Instant;
+
+use super::wordle::Wordle;
+
+/// Ticks the border stays inverted after a bell.
+const FLASH_TICKS: u32 = 4;
+
+/// What the player is told about with a bell when `sound` is on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameEvent {
+    InvalidGuess,
+    Won,
+    Lost,
+}
+
+impl GameEvent {
+    /// Times the bell rings; a win gets two so it can be told apart.
+    pub fn rings(self) -> u8 {
+        match self {
+            GameEvent::Won => 2,
+            GameEvent::InvalidGuess | GameEvent::Lost => 1,
+        }
+    }
+}
+
+/// Bells still to ring and the border flash that goes with them, handed out
+/// by the game loop a tick at a time.
+#[derive(Debug, Default)]
+pub struct Bell {
+    pub pending: u8,
+    /// the border is drawn inverted until then, for terminals with the
+    /// bell turned off
+    pub flash_until: Option<Instant>,
+}
+
+impl Bell {
+    pub fn is_busy(&self) -> bool {
+        self.pending > 0 || self.flash_until.is_some()
+    }
+
+    /// Rings once if a ring is due, so two rings land on separate ticks and
+    /// are heard as two, and ends the flash once `now` is past it.
+    pub fn tick(&mut self, now: Instant) {
+        if self.pending > 0 {
+            self.pending -= 1;
+            ring();
+        }
+        if self.flash_until.is_some_and(|until| until <= now) {
+            self.flash_until = None;
+        }
+    }
+}
+
+/// Sends BEL to the terminal, when there is one to hear it.
+fn ring() {
+    let mut stdout = io::stdout();
+    if stdout.is_terminal() {
+        // a bell that fails to ring isn't worth reporting
+        let _ = stdout.write_all(b"\x07").and_then(|()| stdout.flush());
+    }
+}
+
+impl Wordle {
+    /// Rings the bell and flashes the border for `event`, if `sound` is on
+    /// in `config.json`. Replays stay quiet.
+    pub fn notify(&mut self, event: GameEvent) {
+        if !self.config.sound || self.replay.is_some() {
+            return;
+        }
+        self.bell.pending += event.rings();
+        self.bell.flash_until = Some(Instant::now() + self.tick_rate() * FLASH_TICKS);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::replay::ReplayPlayer;
+    use ratatui::{Terminal, backend::TestBackend, style::Modifier};
+    use std::time::Duration;
+
+    #[test]
+    fn notify_test() {
+        let mut game = Wordle::new();
+        // off by default
+        game.notify(GameEvent::InvalidGuess);
+        assert!(!game.bell.is_busy());
+
+        game.config.sound = true;
+        game.notify(GameEvent::Won);
+        assert_eq!(game.bell.pending, 2);
+        let until = game.bell.flash_until.unwrap();
+        assert!(game.is_busy());
+        let flashed = |game: &Wordle| {
+            let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+            terminal.draw(|frame| game.render_terminal(frame)).unwrap();
+            let buf = terminal.backend().buffer();
+            let corner = buf
+                .content
+                .iter()
+                .find(|cell| cell.symbol() == "╭")
+                .unwrap();
+            corner.modifier.contains(Modifier::REVERSED)
+        };
+        assert!(flashed(&game));
+
+        // the flash outlasts the rings; silenced so the test doesn't beep
+        game.bell.pending = 0;
+        game.bell.tick(until - Duration::from_millis(1));
+        assert!(game.bell.flash_until.is_some());
+        game.bell.tick(until);
+        assert!(!game.bell.is_busy());
+        assert!(!flashed(&game));
+
+        game.replay = Some(ReplayPlayer {
+            steps: Vec::new(),
+            next: 0,
+            playing_since: None,
+        });
+        game.notify(GameEvent::Lost);
+        assert!(!game.bell.is_busy());
+    }
+}
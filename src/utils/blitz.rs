@@ -0,0 +1,158 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::Span,
+    widgets::Widget,
+};
+use std::time::{Duration, Instant};
+
+use super::ui::InputState;
+use super::wordle::Wordle;
+
+/// Seconds per guess when `blitz_seconds` isn't set in `config.json`.
+pub const DEFAULT_BUDGET_SECS: u64 = 20;
+
+/// The per-guess clock of a blitz game: every row gets the same budget,
+/// starting when the row before it is played.
+#[derive(Debug, Clone, Copy)]
+pub struct Blitz {
+    pub budget: Duration,
+    pub deadline: Instant,
+}
+
+impl Blitz {
+    pub fn new(budget: Duration, now: Instant) -> Self {
+        Blitz {
+            budget,
+            deadline: now + budget,
+        }
+    }
+
+    /// Gives the next row the full budget.
+    pub fn restart(&mut self, now: Instant) {
+        self.deadline = now + self.budget;
+    }
+
+    pub fn remaining(&self, now: Instant) -> Duration {
+        self.deadline.saturating_duration_since(now)
+    }
+
+    /// Green for the first half of the budget, yellow down to a quarter,
+    /// red after that.
+    pub fn color(&self, now: Instant) -> Color {
+        let left = self.remaining(now).as_secs_f64() / self.budget.as_secs_f64().max(f64::EPSILON);
+        if left > 0.5 {
+            Color::Green
+        } else if left > 0.25 {
+            Color::Yellow
+        } else {
+            Color::Red
+        }
+    }
+}
+
+impl Wordle {
+    /// Plays with a clock on every guess, `blitz_seconds` from the config
+    /// or `DEFAULT_BUDGET_SECS` long.
+    pub fn start_blitz(&mut self) {
+        let secs = self
+            .config
+            .blitz_seconds
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_BUDGET_SECS);
+        self.blitz = Some(Blitz::new(Duration::from_secs(secs), Instant::now()));
+    }
+
+    /// What running out of time on the active row does at `now`: the row
+    /// is submitted if it holds a valid complete word, and passed otherwise.
+    pub fn blitz_expired(&mut self, now: Instant) -> Option<InputState> {
+        let blitz = self.blitz?;
+        if blitz.deadline > now
+            || !self.is_accepting_guesses()
+            || self.confirm_challenge
+            || self.confirm_restart
+        {
+            return None;
+        }
+        let valid = self
+            .parse_input(&self.current_guess)
+            .and_then(|guess| self.validate_hard_mode(&guess))
+            .is_ok();
+        if valid {
+            return Some(InputState::Submit);
+        }
+        self.show_status(String::from("time's up, the row is passed"));
+        Some(InputState::Pass)
+    }
+
+    /// Seconds left on the active row, to the right of `last_tile`.
+    pub fn render_countdown(&self, blitz: &Blitz, last_tile: Rect, area: Rect, buf: &mut Buffer) {
+        let now = Instant::now();
+        let secs = blitz.remaining(now).as_secs_f64().ceil() as u64;
+        let spot = Rect {
+            x: last_tile.right() + 1,
+            y: last_tile.y + last_tile.height / 2,
+            width: 4,
+            height: 1,
+        };
+        Span::raw(format!("{secs:>2}s"))
+            .fg(blitz.color(now))
+            .bold()
+            .render(spot.intersection(area), buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn countdown_color_test() {
+        let now = Instant::now();
+        let blitz = Blitz::new(Duration::from_secs(20), now);
+        assert_eq!(blitz.color(now), Color::Green);
+        assert_eq!(blitz.color(now + Duration::from_secs(12)), Color::Yellow);
+        assert_eq!(blitz.color(now + Duration::from_secs(16)), Color::Red);
+        assert_eq!(
+            blitz.remaining(now + Duration::from_secs(30)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn expiry_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        game.start_blitz();
+        let budget = Duration::from_secs(DEFAULT_BUDGET_SECS);
+        let start = game.blitz.unwrap().deadline - budget;
+        assert!(game.blitz_expired(start).is_none());
+
+        // an unfinished row is passed
+        game.current_guess = String::from("CRA");
+        let late = start + budget;
+        assert!(matches!(game.blitz_expired(late), Some(InputState::Pass)));
+        game.pass_turn();
+        assert!(game.guess_history[0].is_pass());
+        // and the next row has the whole budget again
+        assert!(game.blitz.unwrap().deadline >= late);
+
+        // a complete word is played
+        game.current_guess = String::from("CRANE");
+        let deadline = game.blitz.unwrap().deadline;
+        assert!(matches!(
+            game.blitz_expired(deadline),
+            Some(InputState::Submit)
+        ));
+        // but not one that isn't a word
+        game.current_guess = String::from("CRANQ");
+        assert!(matches!(
+            game.blitz_expired(deadline),
+            Some(InputState::Pass)
+        ));
+
+        game.is_game_over = true;
+        assert!(game.blitz_expired(deadline).is_none());
+    }
+}
@@ -0,0 +1,209 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+use wordle::engine::{Alphabet, Word};
+
+use super::share::tile_symbol;
+use super::solver::{feedback_pattern, rank_guesses, row_pattern};
+use super::wordle::Wordle;
+
+/// How the bot picks its next guess.
+pub trait Strategy {
+    /// A guess given the words that could still be the answer, sorted, and
+    /// the rows played so far.
+    fn next_guess(&mut self, candidates: &[&str], history: &[Word]) -> String;
+}
+
+/// Guesses the candidate the solver panel would put first. The opening
+/// guess only depends on the word list, so it's worked out once.
+#[derive(Default)]
+pub struct SolverStrategy {
+    opener: Option<String>,
+    /// the letters the candidates are spelled with
+    alphabet: Alphabet,
+}
+
+impl SolverStrategy {
+    pub fn new(alphabet: Alphabet) -> Self {
+        SolverStrategy {
+            opener: None,
+            alphabet,
+        }
+    }
+}
+
+impl Strategy for SolverStrategy {
+    fn next_guess(&mut self, candidates: &[&str], history: &[Word]) -> String {
+        if history.is_empty()
+            && let Some(opener) = &self.opener
+        {
+            return opener.clone();
+        }
+        let guess = rank_guesses(candidates, candidates, &self.alphabet, 1)
+            .pop()
+            .map(|(word, _)| word)
+            .unwrap_or_default();
+        if history.is_empty() {
+            self.opener = Some(guess.clone());
+        }
+        guess
+    }
+}
+
+/// Plays `game` through to the end with `answer`, taking every guess from
+/// `strategy`, without drawing anything or recording the game. Returns the
+/// rows it took, `None` when it wasn't solved.
+pub fn play_headless(
+    game: &mut Wordle,
+    answer: &str,
+    strategy: &mut dyn Strategy,
+) -> Option<usize> {
+    game.answer = answer.to_string();
+    game.reset_board();
+    let words: Vec<String> = game.candidates().into_iter().map(String::from).collect();
+    let mut candidates: Vec<&str> = words.iter().map(String::as_str).collect();
+    while game.is_accepting_guesses() {
+        let guess = strategy.next_guess(&candidates, &game.guess_history);
+        let rows = game.guess_history.len();
+        game.current_guess = guess.clone();
+        game.submit_guess();
+        // a guess the game turned down would only be offered again
+        let Some(row) = game.guess_history.get(rows) else {
+            break;
+        };
+        let observed = row_pattern(row);
+        candidates.retain(|candidate| feedback_pattern(&guess, candidate) == observed);
+    }
+    game.solved.then_some(game.guess_history.len())
+}
+
+/// Results of the bot playing a list of answers.
+#[derive(Debug, Default)]
+pub struct BotSummary {
+    pub played: usize,
+    pub failed: usize,
+    /// solved games by rows taken, the first entry for one row
+    pub distribution: Vec<usize>,
+    pub elapsed: Duration,
+}
+
+impl BotSummary {
+    pub fn average(&self) -> f64 {
+        let solved = self.played - self.failed;
+        let rows: usize = (1..)
+            .zip(&self.distribution)
+            .map(|(rows, count)| rows * count)
+            .sum();
+        rows as f64 / solved.max(1) as f64
+    }
+}
+
+impl fmt::Display for BotSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "played {} words in {:.2}s",
+            self.played,
+            self.elapsed.as_secs_f64()
+        )?;
+        writeln!(
+            f,
+            "average {:.3} guesses, {} failed",
+            self.average(),
+            self.failed
+        )?;
+        for (rows, count) in (1..).zip(&self.distribution) {
+            writeln!(f, "{rows:>2} {count}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Plays every word of `answers` with `strategy`.
+pub fn play_all(game: &mut Wordle, answers: &[String], strategy: &mut dyn Strategy) -> BotSummary {
+    let start = Instant::now();
+    let mut summary = BotSummary {
+        distribution: vec![0; game.rounds as usize],
+        ..BotSummary::default()
+    };
+    for answer in answers {
+        summary.played += 1;
+        match play_headless(game, answer, strategy) {
+            Some(rows) => summary.distribution[rows - 1] += 1,
+            None => summary.failed += 1,
+        }
+    }
+    summary.elapsed = start.elapsed();
+    summary
+}
+
+/// The bot's game as text: each guess with its colors, then the outcome.
+pub fn transcript(game: &Wordle) -> String {
+    let mut text = String::new();
+    for row in &game.guess_history {
+        let word: String = row.letters.iter().map(|tile| tile.letter).collect();
+        let colors: String = row
+            .letters
+            .iter()
+            .map(|tile| tile_symbol(tile.state, false))
+            .collect();
+        text.push_str(&format!("{word}  {colors}\n"));
+    }
+    if game.solved {
+        text.push_str(&format!(
+            "solved {} in {}\n",
+            game.answer,
+            game.guess_history.len()
+        ));
+    } else {
+        text.push_str(&format!("failed, the word was {}\n", game.answer));
+    }
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::storage::Storage;
+    use crate::utils::testing::temp_dir;
+    use crate::utils::wordle::Setup;
+    use std::fs;
+
+    #[test]
+    fn play_headless_test() {
+        let mut game = Wordle::new();
+        let mut strategy = SolverStrategy::default();
+        let rows = play_headless(&mut game, "EPOCH", &mut strategy).unwrap();
+        assert_eq!(game.guess_history.len(), rows);
+        assert!(game.solved);
+        assert!(transcript(&game).ends_with(&format!("solved EPOCH in {rows}\n")));
+        // the next game starts from a clean board
+        play_headless(&mut game, "CRANE", &mut strategy).unwrap();
+        assert_eq!(game.answer, "CRANE");
+        assert!(strategy.opener.is_some());
+    }
+
+    #[test]
+    fn play_all_test() {
+        let dir = temp_dir("bot");
+        fs::create_dir_all(&dir).unwrap();
+        let list = dir.join("words.txt");
+        fs::write(&list, "CRANE\nCRATE\nGRATE\nGRAPE\nEPOCH\n").unwrap();
+        let setup = Setup {
+            wordlist_paths: vec![list],
+            rounds: Some(2),
+            ..Setup::default()
+        };
+        let mut game = Wordle::with_config(Storage::open(dir.clone()), setup).unwrap();
+        let answers: Vec<String> = game.candidates().into_iter().map(String::from).collect();
+        let summary = play_all(&mut game, &answers, &mut SolverStrategy::default());
+
+        assert_eq!(summary.played, 5);
+        assert_eq!(summary.distribution.len(), 2);
+        let solved: usize = summary.distribution.iter().sum();
+        assert_eq!(solved + summary.failed, 5);
+        assert!(summary.average() >= 1.0 && summary.average() <= 2.0);
+        assert!(summary.to_string().starts_with("played 5 words in "));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
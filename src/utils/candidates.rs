@@ -0,0 +1,171 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Layout, Margin, Rect},
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{
+        Block, BorderType, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        StatefulWidget, Widget,
+    },
+};
+use wordle::engine::is_consistent;
+
+use super::definition::DefinitionView;
+use super::ui::InputState;
+use super::wordle::Wordle;
+
+/// Spaces between the columns of words.
+const COLUMN_GAP: usize = 2;
+
+/// `words` laid out across as many columns as fit `width`, one line per
+/// row, with `answer` in green.
+fn word_grid(words: &[&str], answer: &str, width: usize) -> Vec<Line<'static>> {
+    let word_width = words
+        .iter()
+        .map(|word| word.chars().count())
+        .max()
+        .unwrap_or(0);
+    let columns = ((width + COLUMN_GAP) / (word_width + COLUMN_GAP)).max(1);
+    words
+        .chunks(columns)
+        .map(|row| {
+            let mut spans = Vec::with_capacity(2 * row.len());
+            for (index, word) in row.iter().enumerate() {
+                if index > 0 {
+                    spans.push(Span::raw(" ".repeat(COLUMN_GAP)));
+                }
+                let cell = format!("{word:<word_width$}");
+                spans.push(if *word == answer {
+                    Span::raw(cell).fg(Color::Green).bold()
+                } else {
+                    Span::raw(cell)
+                });
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+impl Wordle {
+    /// The possible answers that fit the first `rounds` rows and the
+    /// letters revealed before the next row was played, sorted
+    /// alphabetically.
+    pub fn candidates_at_round(&self, rounds: usize) -> Vec<&str> {
+        let rows = &self.guess_history[..rounds.min(self.guess_history.len())];
+        let mut candidates: Vec<&str> = self
+            .answer_words
+            .iter()
+            .map(String::as_str)
+            .filter(|word| {
+                is_consistent(word, rows)
+                    && self
+                        .revealed
+                        .iter()
+                        .filter(|reveal| reveal.round as usize <= rounds + 1)
+                        .all(|reveal| reveal.fits(word))
+            })
+            .collect();
+        candidates.sort_unstable();
+        candidates
+    }
+
+    /// The words the final guess was choosing between.
+    pub fn final_candidates(&self) -> Vec<&str> {
+        self.candidates_at_round(self.guess_history.len().saturating_sub(1))
+    }
+
+    pub fn on_candidates_key(&mut self, key: KeyEvent) -> InputState {
+        let Some(view) = &mut self.candidate_page else {
+            return InputState::None;
+        };
+        if !view.scroll(key.code) && matches!(key.code, KeyCode::Esc | KeyCode::Char('L')) {
+            self.candidate_page = None;
+        }
+        InputState::None
+    }
+
+    /// Every word that still fit before the final guess, scrolled like the
+    /// definition page.
+    pub fn render_candidates_page(&self, view: &DefinitionView, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let [heading_area, list_area, keys_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .margin(1)
+        .areas(area);
+        let candidates = self.final_candidates();
+        let heading = match candidates.len() {
+            1 => String::from("Only 1 word fit before your last guess"),
+            count => format!("{count} words fit before your last guess"),
+        };
+        Paragraph::new(Line::from(heading)).render(heading_area, buf);
+
+        // a column spare for the scrollbar, which sits on the border
+        let width = list_area.width.saturating_sub(1) as usize;
+        let lines = word_grid(&candidates, &self.answer, width);
+        let page = list_area.height as usize;
+        let overflow = lines.len().saturating_sub(page);
+        view.page.set(page);
+        view.overflow.set(overflow);
+        let offset = view.offset.min(overflow);
+        let shown: Vec<Line> = lines.into_iter().skip(offset).take(page).collect();
+        Paragraph::new(shown).render(list_area, buf);
+
+        Paragraph::new(Line::from(vec![Span::raw("Back "), "<Esc>".blue().bold()]))
+            .render(keys_area, buf);
+        Block::bordered()
+            .title("Candidates")
+            .border_type(BorderType::Rounded)
+            .render(area, buf);
+        if overflow > 0 {
+            let mut state = ScrollbarState::new(overflow + 1)
+                .position(offset)
+                .viewport_content_length(page);
+            Scrollbar::new(ScrollbarOrientation::VerticalRight).render(
+                area.inner(Margin::new(0, 1)),
+                buf,
+                &mut state,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn candidates_at_round_test() {
+        let words = ["CRANE", "CRATE", "GRATE", "GRAPE", "SLATE"];
+        let mut game = Wordle::with_words("GRATE", &words);
+        game.answer_words = game.accepted_words.clone();
+        game.current_guess = String::from("SLATE");
+        game.submit_guess();
+        game.current_guess = String::from("CRATE");
+        game.submit_guess();
+        game.current_guess = String::from("GRATE");
+        game.submit_guess();
+        assert!(game.is_game_over);
+
+        assert_eq!(game.candidates_at_round(0).len(), 5);
+        assert_eq!(game.candidates_at_round(1), ["CRATE", "GRATE"]);
+        assert_eq!(game.final_candidates(), ["GRATE"]);
+        assert_eq!(game.candidates_at_round(9), ["GRATE"]);
+
+        // a letter revealed in round 2 narrows the second guess's choice,
+        // not the first's
+        let mut game = Wordle::with_words("GRATE", &words);
+        game.answer_words = game.accepted_words.clone();
+        game.current_guess = String::from("SLATE");
+        game.submit_guess();
+        game.reveal_letter();
+        game.current_guess = String::from("GRATE");
+        game.submit_guess();
+        assert_eq!(game.candidates_at_round(0).len(), 5);
+        assert_eq!(game.candidates_at_round(1), ["GRATE"]);
+    }
+}
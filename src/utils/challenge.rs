@@ -0,0 +1,384 @@
+use anyhow::{Context, Result, bail};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Rect},
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+use std::collections::HashSet;
+use std::time::Instant;
+use wordle::engine::WordList;
+use wordle::engine::internal::{WORD_LEN, WORD_LENGTHS};
+
+use super::record::word_list_hash;
+use super::timing::RowClock;
+use super::ui::InputState;
+use super::wordle::{MAX_ROUNDS, Wordle};
+
+/// First part of every code, naming its format.
+const PREFIX: &str = "W1";
+/// Newest settings block this build reads.
+const SETTINGS_VERSION: u32 = 1;
+/// Hex digits of the word list hash kept in a code.
+const HASH_LEN: usize = 8;
+
+/// Rules a challenge is played under, so both players face the same game.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChallengeSettings {
+    pub hard_mode: bool,
+    pub word_length: usize,
+    pub rounds: u8,
+}
+
+impl ChallengeSettings {
+    /// e.g. "6 letters, 5 rounds, hard mode"
+    pub fn describe(&self) -> String {
+        let mode = if self.hard_mode {
+            "hard mode"
+        } else {
+            "normal mode"
+        };
+        format!(
+            "{} letters, {} rounds, {mode}",
+            self.word_length, self.rounds
+        )
+    }
+}
+
+/// A word from a friend's game, as `W1-<list hash>-<answer index>` with an
+/// optional settings block: `S1` followed by `H` (hard) or `N` (normal), the
+/// word length and the number of rounds as a base-36 digit, e.g.
+/// `W1-3fa9c2d1-1jk-S1H65` or `W1-3fa9c2d1-1jk-S1N5a` for ten rounds.
+///
+/// The index points into the sorted list of words of the game's length, so
+/// the code only works with the same word list, and doesn't give the answer
+/// away to anyone reading it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Challenge {
+    pub list_hash: String,
+    pub index: usize,
+    pub settings: Option<ChallengeSettings>,
+}
+
+impl Challenge {
+    /// Letters per word; codes without settings are for classic games.
+    pub fn word_length(&self) -> usize {
+        self.settings
+            .map_or(WORD_LEN, |settings| settings.word_length)
+    }
+
+    pub fn encode(&self) -> String {
+        let mut code = format!("{PREFIX}-{}-{}", self.list_hash, to_base36(self.index));
+        if let Some(settings) = self.settings {
+            let mode = if settings.hard_mode { 'H' } else { 'N' };
+            code.push_str(&format!(
+                "-S{SETTINGS_VERSION}{mode}{}{}",
+                settings.word_length,
+                to_base36(settings.rounds as usize)
+            ));
+        }
+        code
+    }
+
+    pub fn decode(code: &str) -> Result<Self> {
+        let parts: Vec<&str> = code.trim().split('-').collect();
+        if parts.len() < 3 || parts.len() > 4 || !parts[0].eq_ignore_ascii_case(PREFIX) {
+            bail!("'{}' is not a challenge code", code.trim());
+        }
+        let list_hash = parts[1].to_ascii_lowercase();
+        if list_hash.len() != HASH_LEN || !list_hash.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            bail!("invalid word list hash '{}' in challenge code", parts[1]);
+        }
+        let index = usize::from_str_radix(parts[2], 36)
+            .with_context(|| format!("invalid answer index '{}' in challenge code", parts[2]))?;
+        let settings = parts
+            .get(3)
+            .map(|block| decode_settings(block))
+            .transpose()?;
+        Ok(Challenge {
+            list_hash,
+            index,
+            settings,
+        })
+    }
+
+    /// The answer this challenge points at in `words`. A list that doesn't
+    /// match is reported before anything else, as rules the build can't
+    /// play usually just mean the friend's list differs.
+    pub fn answer(&self, words: &HashSet<String>) -> Result<String> {
+        let length = self.word_length();
+        let words = WordList::new(
+            words
+                .iter()
+                .filter(|word| word.chars().count() == length)
+                .cloned(),
+        );
+        let hash = word_list_hash(&words.words().iter().cloned().collect());
+        if hash[..HASH_LEN] != self.list_hash {
+            bail!(
+                "this challenge was made with a different word list ({} in the code, {} here)",
+                self.list_hash,
+                &hash[..HASH_LEN]
+            );
+        }
+        if let Some(settings) = self.settings {
+            if !WORD_LENGTHS.contains(&settings.word_length) {
+                bail!(
+                    "this challenge needs {}-letter words, which this build can't play",
+                    settings.word_length
+                );
+            }
+            if !(1..=MAX_ROUNDS).contains(&settings.rounds) {
+                bail!(
+                    "this challenge needs {} rounds, this build plays at most {MAX_ROUNDS}",
+                    settings.rounds
+                );
+            }
+        }
+        words
+            .words()
+            .get(self.index)
+            .cloned()
+            .context("the challenge's answer is missing from the word list")
+    }
+}
+
+fn decode_settings(block: &str) -> Result<ChallengeSettings> {
+    let invalid = || format!("invalid settings '{block}' in challenge code");
+    let body = block.strip_prefix(['S', 's']).with_context(invalid)?;
+    let digits = body.chars().take_while(char::is_ascii_digit).count();
+    let version: u32 = body[..digits].parse().with_context(invalid)?;
+    if version > SETTINGS_VERSION {
+        bail!("this challenge uses settings format {version}; update rust_wordle to accept it");
+    }
+    let fields: Vec<char> = body[digits..].chars().collect();
+    let [mode, length, rounds] = fields[..] else {
+        bail!(invalid());
+    };
+    let hard_mode = match mode.to_ascii_uppercase() {
+        'H' => true,
+        'N' => false,
+        _ => bail!(invalid()),
+    };
+    Ok(ChallengeSettings {
+        hard_mode,
+        word_length: length.to_digit(10).with_context(invalid)? as usize,
+        rounds: rounds.to_digit(36).with_context(invalid)? as u8,
+    })
+}
+
+fn to_base36(mut value: usize) -> String {
+    let mut digits = Vec::new();
+    loop {
+        digits.push(std::char::from_digit((value % 36) as u32, 36).unwrap_or('0'));
+        value /= 36;
+        if value == 0 {
+            break;
+        }
+    }
+    digits.iter().rev().collect()
+}
+
+impl Wordle {
+    /// A code for a friend to play this game's word under the same rules.
+    pub fn challenge_code(&self) -> Option<String> {
+        let words = WordList::from_set(&self.answer_words);
+        let index = words.words().binary_search(&self.answer).ok()?;
+        let hash = word_list_hash(&self.answer_words);
+        let challenge = Challenge {
+            list_hash: hash[..HASH_LEN].to_string(),
+            index,
+            settings: Some(ChallengeSettings {
+                hard_mode: self.hard_mode(),
+                word_length: self.word_len,
+                rounds: self.rounds,
+            }),
+        };
+        Some(challenge.encode())
+    }
+
+    /// Keys while the challenge waits to be accepted: Enter starts it, Esc
+    /// declines and quits.
+    pub fn on_challenge_key(&mut self, key: KeyEvent) -> InputState {
+        match key.code {
+            KeyCode::Enter => {
+                self.confirm_challenge = false;
+                self.row_clock = RowClock::new(Instant::now());
+            }
+            KeyCode::Esc => return InputState::Quit,
+            _ => {}
+        }
+        InputState::None
+    }
+
+    pub fn render_challenge_prompt(&self, area: Rect, buf: &mut Buffer) {
+        let rules = match self
+            .challenge
+            .as_ref()
+            .and_then(|challenge| challenge.settings)
+        {
+            Some(settings) => settings.describe(),
+            None => format!(
+                "{} letters, {} rounds, your own settings",
+                self.word_len, self.rounds
+            ),
+        };
+        let lines = vec![
+            Line::from("A friend challenged you").bold(),
+            Line::from(rules),
+            Line::from(""),
+            Line::from(vec![
+                Span::raw("Start "),
+                "<Enter>".blue().bold(),
+                Span::raw(" Decline "),
+                "<Esc>".blue().bold(),
+            ]),
+        ];
+        let popup = area.centered(Constraint::Length(40), Constraint::Length(6));
+        Clear.render(popup, buf);
+        Paragraph::new(lines)
+            .centered()
+            .fg(Color::White)
+            .block(
+                Block::bordered()
+                    .title("Challenge")
+                    .border_type(BorderType::Rounded),
+            )
+            .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::storage::Storage;
+    use crate::utils::wordle::Setup;
+    use ratatui::crossterm::event::KeyModifiers;
+
+    fn settings(hard_mode: bool, word_length: usize, rounds: u8) -> Option<ChallengeSettings> {
+        Some(ChallengeSettings {
+            hard_mode,
+            word_length,
+            rounds,
+        })
+    }
+
+    #[test]
+    fn round_trip_test() {
+        for challenge in [
+            Challenge {
+                list_hash: String::from("3fa9c2d1"),
+                index: 1999,
+                settings: settings(true, 6, 5),
+            },
+            Challenge {
+                list_hash: String::from("3fa9c2d1"),
+                index: 7,
+                settings: settings(false, 5, 12),
+            },
+            Challenge {
+                list_hash: String::from("00000000"),
+                index: 0,
+                settings: None,
+            },
+        ] {
+            let code = challenge.encode();
+            assert_eq!(Challenge::decode(&code).unwrap(), challenge);
+        }
+        assert_eq!(
+            Challenge::decode("W1-3fa9c2d1-1jj-S1H65").unwrap().encode(),
+            "W1-3fa9c2d1-1jj-S1H65"
+        );
+        // case doesn't matter when typing a code in
+        assert_eq!(
+            Challenge::decode("w1-3FA9C2D1-1JJ-s1h65").unwrap().index,
+            1999
+        );
+
+        for bad in [
+            "",
+            "hello",
+            "W2-3fa9c2d1-1jj",
+            "W1-3fa9c2-1jj",
+            "W1-3fa9c2d1-!!",
+            "W1-3fa9c2d1-1jj-S1X65",
+            "W1-3fa9c2d1-1jj-S1H6",
+        ] {
+            assert!(Challenge::decode(bad).is_err(), "{bad}");
+        }
+        let newer = Challenge::decode("W1-3fa9c2d1-1jj-S2H65Q").unwrap_err();
+        assert!(newer.to_string().contains("update rust_wordle"));
+    }
+
+    #[test]
+    fn accept_challenge_test() {
+        let mut friend = Wordle::new();
+        friend.config.hard_mode = true;
+        friend.rounds = 4;
+        friend.answer = String::from("EPOCH");
+        let code = friend.challenge_code().unwrap();
+
+        let setup = Setup {
+            challenge: Some(Challenge::decode(&code).unwrap()),
+            ..Setup::default()
+        };
+        let mut game = Wordle::with_config(Storage::in_memory("test"), setup).unwrap();
+        assert_eq!(game.answer, "EPOCH");
+        assert_eq!(game.rounds, 4);
+        assert!(game.hard_mode());
+        assert!(game.rules_locked());
+
+        // nothing reaches the board until the challenge is accepted
+        let press = |game: &mut Wordle, code| game.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+        press(&mut game, KeyCode::Char('c'));
+        assert!(game.current_guess.is_empty());
+        assert!(matches!(press(&mut game, KeyCode::Esc), InputState::Quit));
+        press(&mut game, KeyCode::Enter);
+        press(&mut game, KeyCode::Char('c'));
+        assert_eq!(game.current_guess, "C");
+    }
+
+    #[test]
+    fn list_mismatch_reported_first_test() {
+        let words: HashSet<String> = ["CRANE", "EPOCH", "SLATE"].map(String::from).into();
+        let challenge = Challenge {
+            list_hash: String::from("00000000"),
+            index: 0,
+            settings: settings(false, 9, 9),
+        };
+        let err = challenge.answer(&words).unwrap_err();
+        assert!(err.to_string().contains("different word list"));
+
+        let hash = word_list_hash(&words)[..HASH_LEN].to_string();
+        let too_many_rounds = Challenge {
+            list_hash: hash.clone(),
+            index: 1,
+            settings: settings(false, 5, 30),
+        };
+        let err = too_many_rounds.answer(&words).unwrap_err();
+        assert!(err.to_string().contains("needs 30 rounds"));
+
+        let classic = Challenge {
+            list_hash: hash,
+            index: 1,
+            settings: None,
+        };
+        assert_eq!(classic.answer(&words).unwrap(), "EPOCH");
+    }
+
+    #[test]
+    fn non_ascii_round_trip_test() {
+        let words: HashSet<String> = ["NIÑOS", "AÑEJO", "CRANE", "SLATE", "EPOCH"]
+            .map(String::from)
+            .into();
+        for answer in ["NIÑOS", "AÑEJO", "EPOCH"] {
+            let mut friend = Wordle::with_words(answer, &[]);
+            friend.answer_words = words.clone();
+            let code = friend.challenge_code().unwrap();
+            let challenge = Challenge::decode(&code).unwrap();
+            assert_eq!(challenge.answer(&words).unwrap(), answer);
+        }
+    }
+}
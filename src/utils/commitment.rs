@@ -0,0 +1,87 @@
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::wordle::Wordle;
+
+/// Hex digits of the hash shown to players.
+const COMMITMENT_LEN: usize = 8;
+
+/// A fresh secret for `commitment`: 122 random bits as hex.
+pub fn new_nonce() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+/// Short SHA-256 commitment to `answer` salted with `nonce`, shown before
+/// the first guess so the answer provably can't change afterwards. The
+/// nonce stays secret until the game is over; with a salt players could
+/// see, hashing every word on the list would find the answer.
+pub fn commitment(answer: &str, nonce: &str) -> String {
+    let digest = Sha256::digest(format!("{}{}", answer.to_ascii_uppercase(), nonce));
+    digest
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>()[..COMMITMENT_LEN]
+        .to_string()
+}
+
+/// Whether the revealed `answer` and `nonce` produce `expected`.
+pub fn verify(answer: &str, nonce: &str, expected: &str) -> bool {
+    commitment(answer, nonce).eq_ignore_ascii_case(expected.trim())
+}
+
+impl Wordle {
+    pub fn commitment(&self) -> String {
+        commitment(&self.answer, &self.nonce)
+    }
+
+    /// The commitment's secret, once the game is over and it can be told.
+    pub fn revealed_nonce(&self) -> Option<&str> {
+        self.is_game_over.then_some(self.nonce.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const NONCE: &str = "6f1c2a4e8d3b4c7a9e0f123456789abc";
+
+    #[test]
+    fn round_trip_test() {
+        let committed = commitment("EPOCH", NONCE);
+        assert_eq!(committed.len(), COMMITMENT_LEN);
+        assert!(committed.chars().all(|ch| ch.is_ascii_hexdigit()));
+        assert!(verify("epoch", NONCE, &committed.to_ascii_uppercase()));
+        assert_eq!(new_nonce().len(), 32);
+        assert_ne!(new_nonce(), new_nonce());
+    }
+
+    #[test]
+    fn mismatch_test() {
+        let committed = commitment("EPOCH", NONCE);
+        assert!(!verify("CRANE", NONCE, &committed));
+        assert!(!verify("EPOCH", "another-nonce", &committed));
+        assert_ne!(commitment("EPOCH", "another-nonce"), committed);
+    }
+
+    #[test]
+    fn follows_the_game_test() {
+        let mut game = Wordle::new();
+        let first = game.commitment();
+        assert!(verify(&game.answer, &game.nonce, &first));
+        // the game id is on screen, so it's no part of the commitment
+        assert_ne!(first, commitment(&game.answer, &game.game_id));
+        let nonce = game.nonce.clone();
+        game.game_restart();
+        assert_ne!(game.nonce, nonce);
+        assert!(verify(&game.answer, &game.nonce, &game.commitment()));
+
+        // kept secret until the game is over
+        assert_eq!(game.revealed_nonce(), None);
+        assert!(game.record().nonce.is_empty());
+        game.current_guess = game.answer.clone();
+        game.submit_guess();
+        assert_eq!(game.revealed_nonce(), Some(game.nonce.as_str()));
+        assert_eq!(game.record().nonce, game.nonce);
+    }
+}
@@ -0,0 +1,260 @@
+use anyhow::{Result, bail};
+use ratatui::{
+    Frame,
+    buffer::Buffer,
+    crossterm::event::{self, Event, KeyCode},
+    crossterm::terminal,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use std::collections::HashSet;
+use std::io;
+use wordle::engine::{ROUND, Word, is_consistent};
+
+use super::record::GameRecord;
+use super::session::install_panic_hook;
+use super::theme::Theme;
+use super::ui::{TileSize, render_board};
+
+/// Size of the side-by-side view; smaller terminals can't show it.
+const MIN_WIDTH: u16 = 90;
+const MIN_HEIGHT: u16 = 30;
+
+/// Two games on the same answer, with the number of candidates each player
+/// had left after every round.
+pub struct Comparison {
+    pub answer: String,
+    pub mine: Vec<Word>,
+    pub theirs: Vec<Word>,
+    pub mine_counts: Vec<usize>,
+    pub theirs_counts: Vec<usize>,
+}
+
+impl Comparison {
+    pub fn new(mine: &GameRecord, theirs: &GameRecord, words: &HashSet<String>) -> Result<Self> {
+        if mine.word_list_hash != theirs.word_list_hash {
+            bail!("the games were played with different word lists");
+        }
+        if mine.answer != theirs.answer {
+            bail!("the games have different answers");
+        }
+
+        let mine_rows = mine.rows()?;
+        let theirs_rows = theirs.rows()?;
+        Ok(Comparison {
+            answer: mine.answer.clone(),
+            mine_counts: candidate_counts(&mine_rows, words),
+            theirs_counts: candidate_counts(&theirs_rows, words),
+            mine: mine_rows,
+            theirs: theirs_rows,
+        })
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let (width, height) = terminal::size()?;
+        if width < MIN_WIDTH || height < MIN_HEIGHT {
+            return Err(io::Error::other(format!(
+                "the comparison needs a terminal of at least {MIN_WIDTH}x{MIN_HEIGHT}, this one is {width}x{height}"
+            ))
+            .into());
+        }
+        install_panic_hook();
+        let mut terminal = ratatui::try_init()?;
+        loop {
+            terminal.draw(|frame| self.render(frame))?;
+            if let Event::Key(key) = event::read()?
+                && matches!(key.code, KeyCode::Esc | KeyCode::Char('q'))
+            {
+                break;
+            }
+        }
+        ratatui::restore();
+        Ok(())
+    }
+
+    fn render(&self, frame: &mut Frame) {
+        let area = frame.area().centered(
+            Constraint::Length(MIN_WIDTH),
+            Constraint::Length(MIN_HEIGHT),
+        );
+        let [header_area, boards_area, footer_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(26),
+            Constraint::Length(2),
+        ])
+        .areas(area);
+        let [mine_area, rounds_area, theirs_area] = Layout::horizontal([
+            Constraint::Length(37),
+            Constraint::Fill(1),
+            Constraint::Length(37),
+        ])
+        .areas(boards_area);
+        let [mine_header, _, theirs_header] = Layout::horizontal([
+            Constraint::Length(37),
+            Constraint::Fill(1),
+            Constraint::Length(37),
+        ])
+        .areas(header_area);
+
+        let buf = frame.buffer_mut();
+        Paragraph::new("Mine")
+            .alignment(Alignment::Center)
+            .bold()
+            .render(mine_header, buf);
+        Paragraph::new("Theirs")
+            .alignment(Alignment::Center)
+            .bold()
+            .render(theirs_header, buf);
+
+        let theme = Theme::default();
+        render_board(
+            &theme,
+            &self.mine,
+            rows_left(&self.mine),
+            self.answer.chars().count(),
+            None,
+            &[],
+            TileSize::NORMAL,
+            mine_area,
+            buf,
+        );
+        render_board(
+            &theme,
+            &self.theirs,
+            rows_left(&self.theirs),
+            self.answer.chars().count(),
+            None,
+            &[],
+            TileSize::NORMAL,
+            theirs_area,
+            buf,
+        );
+        self.render_rounds(rounds_area, buf);
+
+        Paragraph::new(vec![
+            Line::from(vec![
+                Span::raw("Answer: "),
+                Span::raw(&self.answer).bold(),
+                Span::raw("  fewer candidates left = more information"),
+            ]),
+            Line::from(vec![Span::raw("Quit "), "<Esc>".blue().bold()]),
+        ])
+        .alignment(Alignment::Center)
+        .render(footer_area, buf);
+    }
+
+    /// Candidate counts beside each board row, the better-informed side in green.
+    fn render_rounds(&self, area: Rect, buf: &mut Buffer) {
+        let rounds = self.mine_counts.len().max(self.theirs_counts.len());
+        for round in 0..rounds {
+            // vertically centered on the tile row drawn by `render_board`
+            let y = area.y + 2 + round as u16 * 4 + 1;
+            if y >= area.bottom() {
+                break;
+            }
+
+            let mine = self.mine_counts.get(round);
+            let theirs = self.theirs_counts.get(round);
+            let style = |own: Option<&usize>, other: Option<&usize>| match (own, other) {
+                (Some(a), Some(b)) if a < b => Color::Green,
+                _ => Color::Reset,
+            };
+            let count = |value: Option<&usize>| match value {
+                Some(value) => value.to_string(),
+                None => String::from("-"),
+            };
+
+            Paragraph::new(Line::from(vec![
+                Span::raw(format!("{:>5}", count(mine))).fg(style(mine, theirs)),
+                Span::raw(format!(" R{} ", round + 1)).dark_gray(),
+                Span::raw(format!("{:<5}", count(theirs))).fg(style(theirs, mine)),
+            ]))
+            .alignment(Alignment::Center)
+            .render(
+                Rect {
+                    y,
+                    height: 1,
+                    ..area
+                },
+                buf,
+            );
+        }
+    }
+}
+
+/// Number of words from `words` still possible after each row of `rows`.
+pub fn candidate_counts(rows: &[Word], words: &HashSet<String>) -> Vec<usize> {
+    (1..=rows.len())
+        .map(|round| {
+            words
+                .iter()
+                .filter(|word| is_consistent(word, &rows[..round]))
+                .count()
+        })
+        .collect()
+}
+
+fn rows_left(rows: &[Word]) -> usize {
+    (ROUND as usize).saturating_sub(rows.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn words() -> HashSet<String> {
+        ["CRATE", "CRANE", "GRATE", "SLATE", "EPOCH"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect()
+    }
+
+    fn record(answer: &str, guesses: &[&str], hash: &str) -> GameRecord {
+        GameRecord {
+            game_id: String::new(),
+            nonce: String::new(),
+            answer: answer.to_string(),
+            guesses: guesses.iter().map(|guess| guess.to_string()).collect(),
+            solved: guesses.last() == Some(&answer),
+            word_list_hash: hash.to_string(),
+            timings: Vec::new(),
+            blitz_seconds: None,
+            revealed: Vec::new(),
+            zen: false,
+            daily_rules: None,
+        }
+    }
+
+    #[test]
+    fn candidate_counts_test() {
+        let comparison = Comparison::new(
+            &record("CRATE", &["SLATE", "CRANE", "CRATE"], "h"),
+            &record("CRATE", &["EPOCH", "CRATE"], "h"),
+            &words(),
+        )
+        .unwrap();
+        // SLATE leaves CRATE and GRATE, CRANE pins the C
+        assert_eq!(comparison.mine_counts, vec![2, 1, 1]);
+        assert_eq!(comparison.theirs_counts, vec![2, 1]);
+    }
+
+    #[test]
+    fn mismatched_games_test() {
+        let words = words();
+        let different_list = Comparison::new(
+            &record("CRATE", &["CRATE"], "a"),
+            &record("CRATE", &["CRATE"], "b"),
+            &words,
+        );
+        assert!(different_list.is_err());
+
+        let different_answer = Comparison::new(
+            &record("CRATE", &["CRATE"], "h"),
+            &record("EPOCH", &["EPOCH"], "h"),
+            &words,
+        );
+        assert!(different_answer.is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+
+use super::background::BackgroundSetting;
+use super::difficulty::Difficulty;
+use super::keyboard::KeyboardLayout;
+use super::storage::{SaveOutcome, Storage};
+use super::transcript::TranscriptFormat;
+use super::zoom::BoardLayout;
+
+pub const CONFIG_FILE: &str = "config.json";
+
+/// Preferences changed from inside the game, persisted next to the stats.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// word list file in use, `None` for the built-in list
+    pub word_list: Option<PathBuf>,
+    /// answers are only drawn from this list when set; guesses are still
+    /// checked against `word_list` and this one together
+    pub answer_list: Option<PathBuf>,
+    /// language pack directory, with `words.txt` and `layout.toml`; `None`
+    /// plays in English on a QWERTY keyboard
+    pub language: Option<PathBuf>,
+    /// where the word list switcher looks for `*.txt` lists, defaults to
+    /// `wordlists` in the data directory
+    pub word_list_dir: Option<PathBuf>,
+    /// shell command run after every game, with the result in `WORDLE_*`
+    /// environment variables
+    pub on_game_end: Option<String>,
+    /// endpoint each finished game's record is POSTed to
+    pub upload_url: Option<String>,
+    /// keep the answer out of anything shown on screen outside the board
+    pub privacy: bool,
+    /// Esc alone no longer quits, Ctrl+Q does; for tmux and screen, where
+    /// stray Escs are common
+    pub quit_requires_modifier: bool,
+    pub hard_mode: bool,
+    /// how common the answers drawn from the built-in list are; unset
+    /// draws from all of it
+    pub difficulty: Option<Difficulty>,
+    /// a game abandoned for a new one counts as a loss instead of not at
+    /// all
+    pub abandon_counts_as_loss: bool,
+    /// turns off hints and suggestions
+    pub no_assists: bool,
+    /// shows how many answers still fit the board; off by default, as some
+    /// consider it a spoiler
+    pub show_remaining: bool,
+    /// stops counting key presses for the heatmap
+    pub no_key_stats: bool,
+    /// share results as `G`/`Y`/`B` letters instead of emoji
+    pub share_ascii: bool,
+    /// adds how long the game took to the shared result
+    pub share_time: bool,
+    /// lets the on-screen keyboard be clicked; off by default, as capturing
+    /// the mouse stops the terminal from selecting text
+    pub mouse: bool,
+    /// rings the terminal bell and flashes the border on an invalid guess
+    /// or a finished game, twice for a win
+    pub sound: bool,
+    pub layout: BoardLayout,
+    /// the on-screen keyboard's letters; `None` is the language pack's
+    /// own, or QWERTY without one
+    pub keyboard_layout: Option<KeyboardLayout>,
+    /// picks the light or dark variant of the theme
+    pub background: BackgroundSetting,
+    /// seconds per guess in `--blitz` games
+    pub blitz_seconds: Option<u64>,
+    /// seconds an error stays on screen, 3 when unset; 0 keeps it until
+    /// the next guess
+    pub error_seconds: Option<u64>,
+    /// milliseconds between the game loop's ticks, 50 when unset
+    pub tick_ms: Option<u64>,
+    /// length of a `--timed` run in seconds
+    pub timed_seconds: Option<u64>,
+    /// previous versions kept of each file overwritten, 3 when unset; 0
+    /// keeps none
+    pub backups: Option<usize>,
+    /// letters `F7` may reveal per game, 1 when unset
+    pub reveals_per_game: Option<u8>,
+    /// board rows when `--rounds` isn't given
+    pub rounds: Option<u8>,
+    /// what `e` writes after a game
+    pub transcript_format: TranscriptFormat,
+    /// lets `e` replace a transcript already written for the same answer
+    pub overwrite_transcripts: bool,
+    /// keys rebound from the defaults, e.g. `"new_game": "F5"`
+    pub keys: BTreeMap<String, String>,
+}
+
+impl Config {
+    pub fn load(storage: &Storage) -> Result<Self> {
+        match storage.load(CONFIG_FILE)? {
+            Some(contents) => Ok(serde_json::from_str(&contents)?),
+            None => Ok(Config::default()),
+        }
+    }
+
+    /// Sets how many backups `storage`, opened before the config could be
+    /// read, keeps.
+    pub fn apply_backups(&self, storage: &mut Storage) {
+        if let Some(backups) = self.backups {
+            storage.backups = backups;
+        }
+    }
+
+    pub fn save(&self, storage: &Storage) -> io::Result<SaveOutcome> {
+        let json = serde_json::to_string_pretty(self)?;
+        storage.save_with_backup(CONFIG_FILE, &json)
+    }
+}
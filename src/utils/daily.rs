@@ -0,0 +1,522 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use wordle::engine::{Alphabet, Word, WordList, WordleError};
+
+use super::challenge::ChallengeSettings;
+use super::config::Config;
+use super::hint::Reveal;
+use super::messages::describe;
+use super::wordle::Wordle;
+
+/// An attempt at one day's puzzle, saved after every guess so quitting and
+/// relaunching continues the same board instead of offering a fresh one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailySave {
+    #[serde(default)]
+    pub game_id: String,
+    /// the commitment's secret, so a resumed daily keeps its commitment
+    #[serde(default)]
+    pub nonce: String,
+    pub date: String,
+    pub answer: String,
+    pub guesses: Vec<String>,
+    /// rules locked when the attempt started; `None` in older saves
+    #[serde(default)]
+    pub rules: Option<DailyRules>,
+    /// letters of the answer revealed as hints
+    #[serde(default)]
+    pub revealed: Vec<Reveal>,
+}
+
+/// Settings that change how hard a daily is. They're locked when the daily
+/// starts so results played under different rules aren't compared.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DailyRules {
+    pub hard_mode: bool,
+    pub assists: bool,
+    pub word_length: usize,
+}
+
+impl DailyRules {
+    pub fn from_config(config: &Config, word_length: usize) -> Self {
+        DailyRules {
+            hard_mode: config.hard_mode,
+            assists: !config.no_assists,
+            word_length,
+        }
+    }
+
+    /// Suffix for a shared score, `*` for hard mode like the official game.
+    pub fn markers(&self) -> &'static str {
+        if self.hard_mode { "*" } else { "" }
+    }
+}
+
+/// Puzzle #0, the first day of the original game.
+fn first_puzzle() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2021, 6, 19).unwrap()
+}
+
+/// The number shown as "Wordle #123" for the puzzle of `date`.
+pub fn puzzle_number(date: NaiveDate) -> i64 {
+    date.signed_duration_since(first_puzzle()).num_days()
+}
+
+pub fn slot_name(date: NaiveDate) -> String {
+    format!("daily-{}.json", date.format("%Y-%m-%d"))
+}
+
+/// The answer for `date`, picked from the sorted word list so every player
+/// gets the same word regardless of `HashSet` iteration order.
+pub fn daily_word(words: &HashSet<String>, date: NaiveDate) -> Result<String, WordleError> {
+    let list = WordList::from_set(words);
+    let sorted = list.words();
+    if sorted.is_empty() {
+        return Err(WordleError::EmptyWordList);
+    }
+
+    let days = date.signed_duration_since(NaiveDate::default()).num_days() as u64;
+    let index = days.wrapping_mul(2654435761) % sorted.len() as u64;
+    Ok(sorted[index as usize].clone())
+}
+
+impl Wordle {
+    /// Switches to the daily puzzle for `date`, resuming a saved attempt for
+    /// that date if there is one.
+    pub fn start_daily(&mut self, date: NaiveDate) {
+        self.answer = match daily_word(&self.answer_words, date) {
+            Ok(answer) => answer,
+            Err(err) => {
+                self.show_error(describe(&err));
+                return;
+            }
+        };
+        self.daily = Some(date);
+        self.reset_board();
+        self.daily_rules = Some(DailyRules::from_config(&self.config, self.word_len));
+
+        let save = match self.storage.load(&slot_name(date)) {
+            Ok(Some(contents)) => serde_json::from_str::<DailySave>(&contents),
+            Ok(None) => return,
+            Err(err) => {
+                self.show_error(format!("failed to load daily save: {err}"));
+                return;
+            }
+        };
+        match save {
+            Ok(save) => self.resume_daily(save),
+            Err(err) => self.show_error(format!("corrupt daily save: {err}")),
+        }
+    }
+
+    fn resume_daily(&mut self, save: DailySave) {
+        if save.answer.chars().count() != self.word_len {
+            let len = save.answer.chars().count();
+            self.show_error(format!(
+                "today's daily was started with {len}-letter words, run with --length {len} to resume it"
+            ));
+            return;
+        }
+        if let Err(reason) = self.check_save(&save) {
+            self.show_error(format!("corrupt daily save: {reason}"));
+            return;
+        }
+        self.answer = save.answer;
+        if !save.game_id.is_empty() {
+            self.game_id = save.game_id;
+        }
+        if !save.nonce.is_empty() {
+            self.nonce = save.nonce;
+        }
+        if save.rules.is_some() {
+            self.daily_rules = save.rules;
+        }
+        for guess in &save.guesses {
+            // check_save let through nothing but words and passed turns
+            let word = Word::from(guess)
+                .and_then(|mut word| self.check_guess(&mut word).map(|()| word))
+                .unwrap_or_else(|_| Word::pass(self.word_len));
+            self.update_status(&word);
+        }
+        for reveal in save.revealed {
+            self.apply_reveal(reveal);
+        }
+    }
+
+    /// Checks everything the board relies on before any row of `save` is
+    /// played, so a hand-edited file is refused instead of drawn half-way.
+    fn check_save(&self, save: &DailySave) -> Result<(), String> {
+        let is_word = |word: &str| {
+            word.chars()
+                .all(|ch| self.alphabet.contains(ch) && Alphabet::is_upper(ch))
+        };
+        if !is_word(&save.answer) {
+            return Err(format!("answer '{}' isn't a word", save.answer));
+        }
+        if save.guesses.len() > self.rounds as usize {
+            return Err(format!(
+                "{} rows on a board of {}",
+                save.guesses.len(),
+                self.rounds
+            ));
+        }
+        for (index, guess) in save.guesses.iter().enumerate() {
+            let row = index + 1;
+            if guess.chars().count() != self.word_len {
+                return Err(format!(
+                    "row {row} has {} letters instead of {}",
+                    guess.chars().count(),
+                    self.word_len
+                ));
+            }
+            if !is_word(guess) && !guess.chars().all(|ch| ch == ' ') {
+                return Err(format!("row {row} has characters other than A-Z"));
+            }
+            if *guess == save.answer && row < save.guesses.len() {
+                return Err(format!("rows after the answer was found in row {row}"));
+            }
+        }
+        for (
+            index,
+            &Reveal {
+                column,
+                letter,
+                round,
+            },
+        ) in save.revealed.iter().enumerate()
+        {
+            if save.answer.chars().nth(column) != Some(letter) {
+                return Err(format!(
+                    "revealed {letter} isn't the answer's letter {}",
+                    column + 1
+                ));
+            }
+            if save.revealed[..index]
+                .iter()
+                .any(|reveal| reveal.column == column)
+            {
+                return Err(format!("letter {} is revealed twice", column + 1));
+            }
+            if round == 0 || round as usize > save.guesses.len() + 1 {
+                return Err(format!(
+                    "letter {} is revealed in round {round} of {} played",
+                    column + 1,
+                    save.guesses.len()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Hard mode as locked by the daily or a challenge, or as configured
+    /// otherwise.
+    pub fn hard_mode(&self) -> bool {
+        if let Some(rules) = self.daily_rules {
+            return rules.hard_mode;
+        }
+        self.challenge_rules()
+            .map_or(self.config.hard_mode, |rules| rules.hard_mode)
+    }
+
+    fn challenge_rules(&self) -> Option<ChallengeSettings> {
+        self.challenge.as_ref()?.settings
+    }
+
+    pub fn assists_allowed(&self) -> bool {
+        self.daily_rules
+            .map_or(!self.config.no_assists, |rules| rules.assists)
+    }
+
+    /// Whether a setting change would alter the rules of a daily or a
+    /// challenge in progress.
+    pub fn rules_locked(&self) -> bool {
+        (self.daily_rules.is_some() || self.challenge_rules().is_some()) && !self.is_game_over
+    }
+
+    /// What locked the rules, for messages: "today's daily" or "the
+    /// challenge".
+    pub fn rules_locked_by(&self) -> &'static str {
+        if self.daily_rules.is_some() {
+            "today's daily"
+        } else {
+            "the challenge"
+        }
+    }
+
+    /// "Wordle #123" while playing a daily.
+    pub fn daily_title(&self) -> Option<String> {
+        self.daily
+            .map(|date| format!("Wordle #{}", puzzle_number(date)))
+    }
+
+    /// The daily result as shared, e.g. `4/6*`, once the game is over.
+    pub fn daily_score(&self) -> Option<String> {
+        let rules = self.daily_rules.filter(|_| self.is_game_over)?;
+        let guesses = if self.solved {
+            self.guess_history.len().to_string()
+        } else {
+            String::from("X")
+        };
+        Some(format!("{guesses}/{}{}", self.rounds, rules.markers()))
+    }
+
+    pub fn save_daily(&mut self) {
+        let Some(date) = self.daily else {
+            return;
+        };
+        let save = DailySave {
+            game_id: self.game_id.clone(),
+            nonce: self.nonce.clone(),
+            date: date.format("%Y-%m-%d").to_string(),
+            answer: self.answer.clone(),
+            guesses: self.record().guesses,
+            rules: self.daily_rules,
+            revealed: self.revealed.clone(),
+        };
+        let result = serde_json::to_string(&save)
+            .map_err(|err| err.to_string())
+            .and_then(|json| {
+                self.storage
+                    .save(&slot_name(date), &json)
+                    .map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            self.show_error(format!("failed to save daily: {err}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::storage::Storage;
+    use crate::utils::testing::temp_dir;
+    use std::fs;
+    use std::path::Path;
+
+    fn launch(dir: &Path, date: NaiveDate) -> Wordle {
+        let mut game = Wordle::new();
+        game.storage = Storage::open(dir.to_path_buf());
+        game.start_daily(date);
+        game
+    }
+
+    fn submit(game: &mut Wordle, word: &str) {
+        game.current_guess = word.to_string();
+        game.submit_guess();
+    }
+
+    /// Valid guesses that are not `answer`.
+    fn misses(game: &Wordle, count: usize) -> Vec<String> {
+        let mut words: Vec<&String> = game.accepted_words.iter().collect();
+        words.sort_unstable();
+        words
+            .into_iter()
+            .filter(|word| **word != game.answer)
+            .take(count)
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn daily_word_test() {
+        let words = Wordle::load_words().unwrap();
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let tomorrow = today.succ_opt().unwrap();
+        assert_eq!(
+            daily_word(&words, today).unwrap(),
+            daily_word(&words, today).unwrap()
+        );
+        assert_ne!(
+            daily_word(&words, today).unwrap(),
+            daily_word(&words, tomorrow).unwrap()
+        );
+        assert!(matches!(
+            daily_word(&HashSet::new(), today),
+            Err(WordleError::EmptyWordList)
+        ));
+
+        assert_eq!(puzzle_number(first_puzzle()), 0);
+        assert_eq!(puzzle_number(today), 1945);
+        assert_eq!(puzzle_number(tomorrow), 1946);
+    }
+
+    #[test]
+    fn same_date_same_answer_test() {
+        let dir = temp_dir("daily_same_date");
+        let other = temp_dir("daily_same_date_other");
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let first = launch(&dir, today);
+        let second = launch(&other, today);
+        assert_eq!(first.answer, second.answer);
+        assert_ne!(
+            first.answer,
+            launch(&other, today.succ_opt().unwrap()).answer
+        );
+        assert_eq!(first.daily_title().as_deref(), Some("Wordle #1945"));
+
+        // Tab keeps the board and says why
+        let mut game = first;
+        game.game_restart();
+        assert_eq!(game.answer, second.answer);
+        assert!(
+            game.messages
+                .status()
+                .unwrap()
+                .contains("come back tomorrow")
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&other).unwrap();
+    }
+
+    #[test]
+    fn resume_mid_daily_test() {
+        let dir = temp_dir("daily_resume");
+        let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+
+        let mut game = launch(&dir, date);
+        let answer = game.answer.clone();
+        let committed = game.commitment();
+        for word in misses(&game, 2) {
+            submit(&mut game, &word);
+        }
+        game.reveal_letter();
+        let revealed = game.revealed.clone();
+        assert_eq!(revealed.len(), 1);
+        drop(game);
+
+        let mut game = launch(&dir, date);
+        assert_eq!(game.answer, answer);
+        assert_eq!(game.commitment(), committed);
+        assert_eq!(game.guess_history.len(), 2);
+        assert_eq!(game.round, 3);
+        assert!(!game.is_game_over);
+        // the hint stays used
+        assert_eq!(game.revealed, revealed);
+        assert_eq!(game.assists_used, 1);
+        game.reveal_letter();
+        assert_eq!(game.messages.error(), Some("no reveals left this game"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn corrupt_saves_rejected_test() {
+        let dir = temp_dir("daily_corrupt");
+        let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        for (save, reason) in [
+            (
+                include_str!("../../tests/fixtures/daily_too_many_rows.json"),
+                "9 rows on a board of 6",
+            ),
+            (
+                include_str!("../../tests/fixtures/daily_short_row.json"),
+                "row 2 has 4 letters instead of 5",
+            ),
+            (
+                include_str!("../../tests/fixtures/daily_bad_letters.json"),
+                "row 2 has characters other than A-Z",
+            ),
+            (
+                include_str!("../../tests/fixtures/daily_after_solved.json"),
+                "rows after the answer was found in row 1",
+            ),
+            (
+                include_str!("../../tests/fixtures/daily_wrong_reveal.json"),
+                "revealed X isn't the answer's letter 2",
+            ),
+        ] {
+            Storage::open(dir.clone())
+                .save(&slot_name(date), save)
+                .unwrap();
+            let game = launch(&dir, date);
+            assert_eq!(
+                game.messages.error(),
+                Some(format!("corrupt daily save: {reason}").as_str())
+            );
+            assert!(game.guess_history.is_empty());
+            assert!(game.is_accepting_guesses());
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn relaunch_completed_daily_test() {
+        let dir = temp_dir("daily_completed");
+        let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+
+        let mut game = launch(&dir, date);
+        let answer = game.answer.clone();
+        let miss = misses(&game, 1).remove(0);
+        submit(&mut game, &miss);
+        submit(&mut game, &answer);
+        assert!(game.solved);
+
+        let mut game = launch(&dir, date);
+        assert!(game.is_game_over);
+        assert!(game.solved);
+        assert_eq!(game.guess_history.len(), 2);
+
+        // no fresh board for the same date
+        game.game_restart();
+        assert_eq!(game.answer, answer);
+        assert_eq!(game.guess_history.len(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rollover_test() {
+        let dir = temp_dir("daily_rollover");
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+
+        let mut game = launch(&dir, today);
+        let miss = misses(&game, 1).remove(0);
+        submit(&mut game, &miss);
+
+        let game = launch(&dir, today.succ_opt().unwrap());
+        assert!(game.guess_history.is_empty());
+        assert!(!game.is_game_over);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rules_locked_for_daily_test() {
+        use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        let dir = temp_dir("daily_rules");
+        let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let press = |game: &mut Wordle, code| game.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+
+        let mut game = Wordle::new();
+        game.storage = Storage::open(dir.clone());
+        game.config.hard_mode = true;
+        game.start_daily(date);
+        let miss = misses(&game, 1).remove(0);
+        submit(&mut game, &miss);
+
+        press(&mut game, KeyCode::F(2));
+        press(&mut game, KeyCode::Char('h'));
+        assert!(game.config.hard_mode);
+        assert!(game.messages.error().unwrap().contains("locked"));
+
+        // relaunching with hard mode now off keeps the rules the daily began with
+        let mut game = launch(&dir, date);
+        assert!(!game.config.hard_mode);
+        assert!(game.hard_mode());
+        let answer = game.answer.clone();
+        submit(&mut game, &answer);
+        assert_eq!(game.daily_score().as_deref(), Some("2/6*"));
+        assert_eq!(game.record().daily_rules, game.daily_rules);
+
+        // once it's over the settings are free again
+        press(&mut game, KeyCode::F(2));
+        press(&mut game, KeyCode::Char('h'));
+        assert!(game.config.hard_mode);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,219 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+use std::mem;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::definition::{DefinitionQueue, PREFETCH_DELAY, fetch_definitions};
+use super::events::AppEvent;
+use super::integrations::{Delayed, SystemExecutor};
+use super::wordle::Wordle;
+
+const OVERLAY_WIDTH: u16 = 34;
+/// Pending definitions listed by name; the rest are only counted.
+const SHOWN_PENDING: usize = 3;
+
+/// Internals shown by `--debug`.
+#[derive(Debug, Default)]
+pub struct DebugOverlay {
+    /// the last terminal event and when it arrived
+    pub last_event: Option<(String, Instant)>,
+    pub frames: u64,
+    /// set by `--simulate-latency`
+    pub latency: Option<Duration>,
+}
+
+impl DebugOverlay {
+    /// Notes `event` as the last one, leaving out ticks.
+    pub fn record_event(&mut self, event: &AppEvent, now: Instant) {
+        let text = match (event, event.key_event()) {
+            (AppEvent::Tick, _) => return,
+            (_, Some(key)) if key.modifiers.is_empty() => format!("key {}", key.code),
+            (_, Some(key)) => format!("key {}+{}", key.modifiers, key.code),
+            (AppEvent::Resize(width, height), _) => format!("resize {width}x{height}"),
+            // what was pasted may be the answer
+            (AppEvent::Paste(text), _) => format!("paste of {} characters", text.chars().count()),
+            (other, _) => format!("{other:?}"),
+        };
+        self.last_event = Some((text, now));
+    }
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.1}s", duration.as_secs_f64())
+}
+
+impl Wordle {
+    /// Delays every integration and definition request by `delay`, to shake
+    /// out races that fast local runs hide.
+    pub fn simulate_latency(&mut self, delay: Duration) {
+        let inner = mem::replace(&mut self.integrations, Box::new(SystemExecutor::default()));
+        self.integrations = Box::new(Delayed { inner, delay });
+        self.definitions = DefinitionQueue::new(
+            move |word: &str| {
+                thread::sleep(delay);
+                fetch_definitions(word)
+            },
+            PREFETCH_DELAY,
+        );
+        if let Some(debug) = &mut self.debug {
+            debug.latency = Some(delay);
+        }
+    }
+
+    /// What currently receives keys, in the order `on_key` checks.
+    pub fn phase(&self) -> String {
+        let phase = if self.confirm_challenge {
+            "challenge prompt"
+        } else if self.confirm_restart {
+            "restart prompt"
+        } else if self.celebration.is_some() {
+            "celebration"
+        } else if self.integration_preview.is_some() {
+            "dry-run preview"
+        } else if self.word_lists.is_some() {
+            "word lists"
+        } else if self.show_messages {
+            "messages"
+        } else if self.show_settings {
+            "settings"
+        } else if self.review_row.is_some() {
+            "review"
+        } else if self.show_analysis {
+            "analysis"
+        } else if self.show_stats {
+            "stats"
+        } else if self.candidate_page.is_some() {
+            "candidates"
+        } else if self.suggestions.is_some() {
+            "suggestions"
+        } else if self.show_word_def {
+            "definitions"
+        } else if self.is_game_over {
+            "game over"
+        } else {
+            return format!("playing, round {}", self.round);
+        };
+        phase.to_string()
+    }
+
+    pub fn debug_lines(&self, overlay: &DebugOverlay, now: Instant) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::from(format!("phase: {}", self.phase()))];
+        lines.push(Line::from(match &overlay.last_event {
+            Some((event, at)) => format!("last: {event} {} ago", seconds(now - *at)),
+            None => String::from("last: no events yet"),
+        }));
+
+        let pending = self.definitions.pending();
+        lines.push(Line::from(format!(
+            "definitions: {} pending",
+            pending.len()
+        )));
+        for (word, waited) in pending.iter().take(SHOWN_PENDING) {
+            lines.push(Line::from(format!("  {word} {}", seconds(*waited))));
+        }
+        if pending.len() > SHOWN_PENDING {
+            lines.push(Line::from(format!(
+                "  and {} more",
+                pending.len() - SHOWN_PENDING
+            )));
+        }
+        if let Some(switcher) = &self.word_lists
+            && switcher.is_scanning()
+        {
+            lines.push(Line::from("word lists: scanning"));
+        }
+
+        let redraw = if self.escape_filter.is_holding() {
+            "holding Esc"
+        } else if self.is_busy() {
+            "polling"
+        } else {
+            "on change"
+        };
+        lines.push(Line::from(format!(
+            "redraw: {redraw}, {} frames",
+            overlay.frames
+        )));
+        if let Some(latency) = overlay.latency {
+            lines.push(Line::from(format!("latency: +{}ms", latency.as_millis())));
+        }
+        lines
+    }
+
+    /// Draws the overlay in the top right corner of `area`, over whatever is
+    /// there.
+    pub fn render_debug(&self, overlay: &DebugOverlay, area: Rect, buf: &mut Buffer) {
+        let lines = self.debug_lines(overlay, Instant::now());
+        let width = OVERLAY_WIDTH.min(area.width);
+        let popup = Rect {
+            x: area.right() - width,
+            y: area.y,
+            width,
+            height: (lines.len() as u16 + 2).min(area.height),
+        };
+        Clear.render(popup, buf);
+        Paragraph::new(lines)
+            .fg(Color::DarkGray)
+            .block(
+                Block::bordered()
+                    .title("debug")
+                    .border_type(BorderType::Rounded),
+            )
+            .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::definition::WordDefinition;
+
+    #[test]
+    fn overlay_lines_test() {
+        let mut game = Wordle::new();
+        game.definitions = DefinitionQueue::new(
+            |_: &str| {
+                thread::sleep(Duration::from_secs(1));
+                Ok(WordDefinition::default())
+            },
+            Duration::ZERO,
+        );
+        game.definitions.prefetch(&["CRANE", "EPOCH"]);
+
+        let mut overlay = DebugOverlay::default();
+        let start = Instant::now();
+        overlay.record_event(&AppEvent::Char('A'), start);
+        overlay.frames = 3;
+        let lines: Vec<String> = game
+            .debug_lines(&overlay, start + Duration::from_millis(1500))
+            .iter()
+            .map(Line::to_string)
+            .collect();
+        assert_eq!(lines[0], "phase: playing, round 1");
+        assert_eq!(lines[1], "last: key A 1.5s ago");
+        assert_eq!(lines[2], "definitions: 2 pending");
+        assert!(lines[3].starts_with("  CRANE "));
+        assert_eq!(lines[5], "redraw: polling, 3 frames");
+
+        game.show_settings = true;
+        assert_eq!(game.phase(), "settings");
+    }
+
+    #[test]
+    fn overlay_stays_in_corner_test() {
+        let game = Wordle::new();
+        let overlay = DebugOverlay::default();
+        let area = Rect::new(0, 0, 80, 24);
+        let mut buf = Buffer::empty(area);
+        buf.set_string(0, 0, "board", ratatui::style::Style::default());
+        game.render_debug(&overlay, area, &mut buf);
+        assert_eq!(buf[(0, 0)].symbol(), "b");
+        assert_eq!(buf[(80 - OVERLAY_WIDTH, 0)].symbol(), "╭");
+    }
+}
@@ -0,0 +1,623 @@
+use ratatui::{
+    crossterm::event::KeyCode,
+    style::{Color, Stylize},
+    text::{Line, Span},
+};
+use reqwest::{StatusCode, blocking};
+use serde::Deserialize;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::ui::wrap_message;
+
+/// Pause between two dictionary requests so a finished game doesn't burst.
+pub const PREFETCH_DELAY: Duration = Duration::from_millis(500);
+
+/// Longest a dictionary request may take before it's given up on.
+pub const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Why a word's definitions couldn't be looked up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LookupError {
+    /// no answer within `FETCH_TIMEOUT`
+    Timeout,
+    /// the dictionary couldn't be reached at all, e.g. no network or DNS
+    Offline,
+    /// the dictionary doesn't know the word
+    NotFound,
+    /// any other HTTP error status
+    Status(u16),
+    /// the response was cut off or unreadable
+    Unreadable,
+}
+
+impl LookupError {
+    fn from_request(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            LookupError::Timeout
+        } else if err.is_connect() {
+            LookupError::Offline
+        } else {
+            LookupError::Unreadable
+        }
+    }
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LookupError::Timeout => write!(
+                f,
+                "The dictionary didn't answer within {}s, try again later",
+                FETCH_TIMEOUT.as_secs()
+            ),
+            LookupError::Offline => f.write_str("Connect to the internet to get word definitions"),
+            LookupError::NotFound => f.write_str("The dictionary has no entry for this word"),
+            LookupError::Status(status) => {
+                write!(f, "The dictionary answered with an error (HTTP {status})")
+            }
+            LookupError::Unreadable => f.write_str("The dictionary's answer couldn't be read"),
+        }
+    }
+}
+
+pub type Definitions = Result<WordDefinition, LookupError>;
+
+pub fn fetch_definitions(word: &str) -> Definitions {
+    let url = format!("https://api.dictionaryapi.dev/api/v2/entries/en/{}", word);
+    let client = blocking::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .map_err(LookupError::from_request)?;
+    let response = client.get(url).send().map_err(LookupError::from_request)?;
+    match response.status() {
+        StatusCode::NOT_FOUND => return Err(LookupError::NotFound),
+        status if !status.is_success() => return Err(LookupError::Status(status.as_u16())),
+        _ => {}
+    }
+    let content = response.text().map_err(LookupError::from_request)?;
+    parse_definition(&content)
+}
+
+/// Reads the dictionary's answer: a list of entries for the word, whose
+/// meanings are put together under the first entry's spelling.
+pub fn parse_definition(json: &str) -> Definitions {
+    let entries: Vec<Entry> = serde_json::from_str(json).map_err(|_| LookupError::Unreadable)?;
+    let Some(first) = entries.first() else {
+        return Err(LookupError::NotFound);
+    };
+    let phonetic = entries
+        .iter()
+        .flat_map(|entry| {
+            entry
+                .phonetic
+                .iter()
+                .chain(entry.phonetics.iter().flat_map(|p| &p.text))
+        })
+        .find(|text| !text.trim().is_empty())
+        .cloned();
+    Ok(WordDefinition {
+        word: first.word.clone(),
+        phonetic,
+        meanings: entries
+            .into_iter()
+            .flat_map(|entry| entry.meanings)
+            .collect(),
+    })
+}
+
+/// One entry of the dictionary's answer, as it sends it.
+#[derive(Deserialize)]
+struct Entry {
+    word: String,
+    phonetic: Option<String>,
+    #[serde(default)]
+    phonetics: Vec<Phonetic>,
+    #[serde(default)]
+    meanings: Vec<Meaning>,
+}
+
+#[derive(Deserialize)]
+struct Phonetic {
+    text: Option<String>,
+}
+
+/// What the dictionary says about a word.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WordDefinition {
+    pub word: String,
+    /// pronunciation, e.g. `/ˈiːpɒk/`
+    pub phonetic: Option<String>,
+    pub meanings: Vec<Meaning>,
+}
+
+/// The senses of a word as one part of speech.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Meaning {
+    pub part_of_speech: String,
+    #[serde(default)]
+    pub definitions: Vec<Sense>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Sense {
+    pub definition: String,
+    /// a sentence using the word this way
+    pub example: Option<String>,
+}
+
+impl WordDefinition {
+    /// The page's text wrapped to `width` columns: the word and its
+    /// pronunciation, then each part of speech with its numbered senses and
+    /// their examples, wrapped lines indented under their number.
+    pub fn lines(&self, width: usize) -> Vec<Line<'static>> {
+        let mut heading = vec![Span::raw(self.word.to_lowercase()).bold()];
+        if let Some(phonetic) = &self.phonetic {
+            heading.push(Span::raw(format!("  {phonetic}")).fg(Color::DarkGray));
+        }
+        let mut lines = vec![Line::from(heading)];
+        for meaning in &self.meanings {
+            lines.push(Line::default());
+            lines.push(Line::from(meaning.part_of_speech.clone()).cyan().italic());
+            for (number, sense) in meaning.definitions.iter().enumerate() {
+                let number = format!("{}. ", number + 1);
+                let indent = number.len();
+                let text =
+                    wrap_message(&sense.definition, width.saturating_sub(indent), usize::MAX);
+                for (index, line) in text.into_iter().enumerate() {
+                    let prefix = if index == 0 {
+                        number.clone()
+                    } else {
+                        " ".repeat(indent)
+                    };
+                    lines.push(Line::from(prefix + &line));
+                }
+                if let Some(example) = &sense.example {
+                    let example = format!("\"{example}\"");
+                    for line in wrap_message(&example, width.saturating_sub(indent), usize::MAX) {
+                        lines.push(Line::from(" ".repeat(indent) + &line).dim());
+                    }
+                }
+            }
+        }
+        lines
+    }
+}
+
+/// How far down the definition page is scrolled. The page only knows how
+/// many lines its text wraps to once drawn, so each draw leaves the room it
+/// had for the keys to clamp against.
+#[derive(Debug, Default)]
+pub struct DefinitionView {
+    /// first line shown
+    pub offset: usize,
+    /// lines that didn't fit below the first page, as of the last draw
+    pub overflow: Cell<usize>,
+    /// lines shown at once, as of the last draw
+    pub page: Cell<usize>,
+}
+
+impl DefinitionView {
+    /// Moves by a line with Up and Down or a page with PageUp and PageDown,
+    /// never past the last line. Returns whether `code` was a scroll key.
+    pub fn scroll(&mut self, code: KeyCode) -> bool {
+        let page = self.page.get().max(1);
+        self.offset = match code {
+            KeyCode::Up => self.offset.saturating_sub(1),
+            KeyCode::Down => self.offset + 1,
+            KeyCode::PageUp => self.offset.saturating_sub(page),
+            KeyCode::PageDown => self.offset + page,
+            _ => return false,
+        }
+        .min(self.overflow.get());
+        true
+    }
+}
+
+/// Fetches definitions on a background thread, one request at a time with a
+/// pause in between, and caches the results.
+///
+/// Words are fetched in the order they were queued. `cancel` drops every
+/// queued word that hasn't been fetched yet.
+pub struct DefinitionQueue {
+    jobs: Sender<(u64, String)>,
+    results: Receiver<(String, Definitions)>,
+    generation: Arc<AtomicU64>,
+    /// queued words and when they were queued
+    pending: HashMap<String, Instant>,
+    cache: HashMap<String, Definitions>,
+}
+
+impl DefinitionQueue {
+    pub fn new<F>(fetch: F, delay: Duration) -> Self
+    where
+        F: Fn(&str) -> Definitions + Send + 'static,
+    {
+        let (jobs, job_rx) = mpsc::channel::<(u64, String)>();
+        let (result_tx, results) = mpsc::channel();
+        let generation = Arc::new(AtomicU64::new(0));
+
+        let current = Arc::clone(&generation);
+        thread::spawn(move || {
+            let mut last_request: Option<Instant> = None;
+            for (job_generation, word) in job_rx {
+                if job_generation != current.load(Ordering::SeqCst) {
+                    continue;
+                }
+                if let Some(last_request) = last_request {
+                    thread::sleep(delay.saturating_sub(last_request.elapsed()));
+                    // cancelled while waiting
+                    if job_generation != current.load(Ordering::SeqCst) {
+                        continue;
+                    }
+                }
+
+                let definitions = fetch(&word);
+                last_request = Some(Instant::now());
+                if result_tx.send((word, definitions)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        DefinitionQueue {
+            jobs,
+            results,
+            generation,
+            pending: HashMap::new(),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// A queue that already knows `entries` and finds every other word
+    /// offline, so tests never reach the network.
+    #[cfg(test)]
+    pub fn offline(entries: impl IntoIterator<Item = WordDefinition>) -> Self {
+        let mut queue = DefinitionQueue::new(|_: &str| Err(LookupError::Offline), Duration::ZERO);
+        for entry in entries {
+            queue
+                .cache
+                .insert(entry.word.to_ascii_uppercase(), Ok(entry));
+        }
+        queue
+    }
+
+    /// Queues `words` in order, skipping ones already cached or queued.
+    pub fn prefetch<S: AsRef<str>>(&mut self, words: &[S]) {
+        let generation = self.generation.load(Ordering::SeqCst);
+        for word in words {
+            let word = word.as_ref();
+            if self.cache.contains_key(word) || self.pending.contains_key(word) {
+                continue;
+            }
+            if self.jobs.send((generation, word.to_string())).is_ok() {
+                self.pending.insert(word.to_string(), Instant::now());
+            }
+        }
+    }
+
+    /// Drops everything still queued. A request already in flight still lands
+    /// in the cache.
+    pub fn cancel(&mut self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.pending.clear();
+    }
+
+    /// Moves finished requests into the cache.
+    pub fn poll(&mut self) {
+        while let Ok((word, definitions)) = self.results.try_recv() {
+            self.pending.remove(&word);
+            self.cache.insert(word, definitions);
+        }
+    }
+
+    pub fn is_busy(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Words still queued or in flight, longest waiting first, with how long
+    /// they have been waiting.
+    pub fn pending(&self) -> Vec<(&str, Duration)> {
+        let mut pending: Vec<(&str, Duration)> = self
+            .pending
+            .iter()
+            .map(|(word, queued)| (word.as_str(), queued.elapsed()))
+            .collect();
+        pending.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        pending
+    }
+
+    /// Queues `word` if it isn't cached, looking it up again if the last
+    /// try failed for any reason but the dictionary not knowing it.
+    pub fn request(&mut self, word: &str) {
+        if let Some(Err(err)) = self.cache.get(word)
+            && *err != LookupError::NotFound
+        {
+            self.cache.remove(word);
+        }
+        self.prefetch(&[word]);
+    }
+
+    /// How long `word` has been queued or in flight, `None` when it isn't.
+    pub fn waiting(&self, word: &str) -> Option<Duration> {
+        self.pending.get(word).map(Instant::elapsed)
+    }
+
+    /// `None` while the word hasn't been fetched yet.
+    pub fn get(&self, word: &str) -> Option<&Definitions> {
+        self.cache.get(word)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    type Calls = Arc<Mutex<Vec<(String, Instant)>>>;
+
+    /// `word` as a noun with `senses`.
+    fn entry(word: &str, senses: &[&str]) -> WordDefinition {
+        WordDefinition {
+            word: word.to_string(),
+            phonetic: None,
+            meanings: vec![Meaning {
+                part_of_speech: String::from("noun"),
+                definitions: senses
+                    .iter()
+                    .map(|sense| Sense {
+                        definition: sense.to_string(),
+                        example: None,
+                    })
+                    .collect(),
+            }],
+        }
+    }
+
+    #[test]
+    fn parse_definition_test() {
+        let json = r#"[
+            {"word": "epoch", "phonetics": [{"audio": ""}, {"text": "/ˈiːpɒk/"}],
+             "meanings": [
+                {"partOfSpeech": "noun", "definitions": [
+                    {"definition": "A particular period of history.",
+                     "example": "the Victorian epoch", "synonyms": []},
+                    {"definition": "The beginning of a period."}]}]},
+            {"word": "epoch", "meanings": [
+                {"partOfSpeech": "verb", "definitions": [{"definition": "To begin."}]}]}
+        ]"#;
+        let definition = parse_definition(json).unwrap();
+        assert_eq!(definition.word, "epoch");
+        assert_eq!(definition.phonetic.as_deref(), Some("/ˈiːpɒk/"));
+        let parts: Vec<&str> = definition
+            .meanings
+            .iter()
+            .map(|meaning| meaning.part_of_speech.as_str())
+            .collect();
+        assert_eq!(parts, ["noun", "verb"]);
+        assert_eq!(
+            definition.meanings[0].definitions[0].example.as_deref(),
+            Some("the Victorian epoch")
+        );
+
+        let text: Vec<String> = definition
+            .lines(20)
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        assert_eq!(
+            text,
+            [
+                "epoch  /ˈiːpɒk/",
+                "",
+                "noun",
+                "1. A particular",
+                "   period of",
+                "   history.",
+                "   \"the Victorian",
+                "   epoch\"",
+                "2. The beginning of",
+                "   a period.",
+                "",
+                "verb",
+                "1. To begin.",
+            ]
+        );
+        let heading = &definition.lines(20)[0].spans[0];
+        assert!(
+            heading
+                .style
+                .add_modifier
+                .contains(ratatui::style::Modifier::BOLD)
+        );
+
+        assert_eq!(parse_definition("[]"), Err(LookupError::NotFound));
+        assert_eq!(parse_definition("<html>"), Err(LookupError::Unreadable));
+    }
+
+    fn mock_queue(delay: Duration) -> (DefinitionQueue, Calls) {
+        let calls: Calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&calls);
+        let queue = DefinitionQueue::new(
+            move |word: &str| {
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push((word.to_string(), Instant::now()));
+                Ok(entry(word, &[]))
+            },
+            delay,
+        );
+        (queue, calls)
+    }
+
+    fn wait_idle(queue: &mut DefinitionQueue) {
+        let start = Instant::now();
+        while queue.is_busy() && start.elapsed() < Duration::from_secs(5) {
+            queue.poll();
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn prefetch_order_and_delay_test() {
+        let delay = Duration::from_millis(30);
+        let (mut queue, calls) = mock_queue(delay);
+        queue.prefetch(&["CRATE", "SLATE", "CRANE", "SLATE"]);
+        wait_idle(&mut queue);
+
+        let calls = calls.lock().unwrap();
+        let words: Vec<&str> = calls.iter().map(|(word, _)| word.as_str()).collect();
+        assert_eq!(words, vec!["CRATE", "SLATE", "CRANE"]);
+        for pair in calls.windows(2) {
+            assert!(pair[1].1 - pair[0].1 >= delay);
+        }
+        assert_eq!(queue.get("CRANE"), Some(&Ok(entry("CRANE", &[]))));
+    }
+
+    #[test]
+    fn cached_words_are_not_refetched_test() {
+        let (mut queue, calls) = mock_queue(Duration::ZERO);
+        queue.prefetch(&["CRATE"]);
+        wait_idle(&mut queue);
+        queue.prefetch(&["CRATE"]);
+        assert!(!queue.is_busy());
+        assert_eq!(calls.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn request_retries_failures_test() {
+        let calls: Calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&calls);
+        let mut queue = DefinitionQueue::new(
+            move |word: &str| {
+                let mut calls = recorded.lock().unwrap();
+                calls.push((word.to_string(), Instant::now()));
+                match (word, calls.len()) {
+                    ("QXZZY", _) => Err(LookupError::NotFound),
+                    (_, 1) => Err(LookupError::Timeout),
+                    _ => Ok(entry(word, &[])),
+                }
+            },
+            Duration::ZERO,
+        );
+        queue.prefetch(&["CRANE"]);
+        wait_idle(&mut queue);
+        assert_eq!(queue.get("CRANE"), Some(&Err(LookupError::Timeout)));
+        assert!(queue.waiting("CRANE").is_none());
+
+        // opening the page again tries once more
+        queue.request("CRANE");
+        assert!(queue.waiting("CRANE").is_some());
+        wait_idle(&mut queue);
+        assert_eq!(queue.get("CRANE"), Some(&Ok(entry("CRANE", &[]))));
+        queue.request("CRANE");
+        assert!(!queue.is_busy());
+
+        // but a word the dictionary doesn't have isn't asked for again
+        queue.request("QXZZY");
+        wait_idle(&mut queue);
+        queue.request("QXZZY");
+        assert!(!queue.is_busy());
+        assert_eq!(calls.lock().unwrap().len(), 3);
+
+        let messages: HashSet<String> = [
+            LookupError::Timeout,
+            LookupError::Offline,
+            LookupError::NotFound,
+            LookupError::Status(500),
+            LookupError::Unreadable,
+        ]
+        .iter()
+        .map(LookupError::to_string)
+        .collect();
+        assert_eq!(messages.len(), 5);
+    }
+
+    #[test]
+    fn scroll_definitions_test() {
+        use crate::utils::wordle::Wordle;
+        use ratatui::crossterm::event::{KeyEvent, KeyModifiers};
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        game.is_game_over = true;
+        game.show_word_def = true;
+        game.definitions = DefinitionQueue::new(
+            |word: &str| {
+                let senses: Vec<String> = (1..=40).map(|n| format!("sense {n}")).collect();
+                Ok(entry(
+                    word,
+                    &senses.iter().map(String::as_str).collect::<Vec<_>>(),
+                ))
+            },
+            Duration::ZERO,
+        );
+        game.definitions.prefetch(&["EPOCH"]);
+        wait_idle(&mut game.definitions);
+        let screen = |game: &Wordle, height: u16| {
+            let mut terminal = Terminal::new(TestBackend::new(60, height)).unwrap();
+            terminal.draw(|frame| game.render_terminal(frame)).unwrap();
+            let buf = terminal.backend().buffer();
+            (0..height)
+                .map(|y| (0..60).map(|x| buf[(x, y)].symbol()).collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let press = |game: &mut Wordle, code| game.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+
+        let first = screen(&game, 45);
+        assert!(first.contains("sense 1 ") && !first.contains("sense 40"));
+        // past the word, the gap and the part of speech above the first sense
+        for _ in 0..4 {
+            press(&mut game, KeyCode::Down);
+        }
+        assert!(!screen(&game, 45).contains("sense 1 "));
+        // the last line is as far as it goes
+        for _ in 0..10 {
+            press(&mut game, KeyCode::PageDown);
+        }
+        let last = screen(&game, 45);
+        assert!(last.contains("sense 40"));
+        let overflow = game.definition_view.overflow.get();
+        assert_eq!(game.definition_view.offset, overflow);
+        press(&mut game, KeyCode::Up);
+        assert_eq!(game.definition_view.offset, overflow - 1);
+
+        // a shorter terminal has more to scroll through
+        screen(&game, 30);
+        assert!(game.definition_view.overflow.get() > overflow);
+        for _ in 0..3 {
+            press(&mut game, KeyCode::PageUp);
+        }
+        assert_eq!(game.definition_view.offset, 0);
+    }
+
+    #[test]
+    fn cancel_test() {
+        let (mut queue, calls) = mock_queue(Duration::from_millis(50));
+        queue.prefetch(&["CRATE", "SLATE", "CRANE"]);
+        queue.cancel();
+        assert!(!queue.is_busy());
+        queue.prefetch(&["EPOCH"]);
+        wait_idle(&mut queue);
+
+        let calls = calls.lock().unwrap();
+        assert!(
+            calls
+                .iter()
+                .all(|(word, _)| word != "SLATE" && word != "CRANE")
+        );
+        assert_eq!(calls.last().unwrap().0, "EPOCH");
+        assert!(queue.get("SLATE").is_none());
+    }
+}
@@ -0,0 +1,200 @@
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use super::wordle::Wordle;
+
+/// Common answer words in two bands, each alphabetical: everyday words,
+/// then words most players still know.
+const COMMON_WORDS: &str = include_str!("../../common.txt");
+/// Words in the everyday band, which easy games draw from.
+const EASY_WORDS: usize = 700;
+
+/// How common the answers drawn from the built-in list are, set with
+/// `--difficulty` or in `config.json`; unset draws from the whole list.
+/// Guesses are checked against the whole list either way.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    /// everyday words only
+    Easy,
+    /// any word most players know; `--adaptive`'s starting level, saved as
+    /// `medium` before it shared this scale
+    #[default]
+    #[serde(alias = "medium")]
+    Normal,
+    /// anything on the list
+    Hard,
+}
+
+impl Difficulty {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => bail!("unknown difficulty '{name}', expected easy, normal or hard"),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    /// One level up, for `--adaptive`.
+    pub fn harder(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal | Difficulty::Hard => Difficulty::Hard,
+        }
+    }
+
+    /// One level down, for `--adaptive`.
+    pub fn easier(self) -> Self {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => Difficulty::Easy,
+            Difficulty::Hard => Difficulty::Normal,
+        }
+    }
+
+    /// How many lines of `COMMON_WORDS` answers come from, `None` for the
+    /// whole list.
+    fn ranks(self) -> Option<usize> {
+        match self {
+            Difficulty::Easy => Some(EASY_WORDS),
+            Difficulty::Normal => Some(usize::MAX),
+            Difficulty::Hard => None,
+        }
+    }
+}
+
+/// The words of `words` answers are drawn from at `difficulty`. A list with
+/// none of the ranked words, e.g. another language's, is drawn from whole.
+pub fn difficulty_pool(words: &HashSet<String>, difficulty: Difficulty) -> HashSet<String> {
+    let Some(ranks) = difficulty.ranks() else {
+        return words.clone();
+    };
+    let pool: HashSet<String> = COMMON_WORDS
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .take(ranks)
+        .map(str::to_uppercase)
+        .filter(|word| words.contains(word))
+        .collect();
+    if pool.is_empty() { words.clone() } else { pool }
+}
+
+impl Wordle {
+    /// The answers new random games are drawn from.
+    pub fn answer_pool(&self) -> HashSet<String> {
+        match self.difficulty {
+            Some(difficulty) => difficulty_pool(&self.answer_words, difficulty),
+            None => self.answer_words.clone(),
+        }
+    }
+
+    /// "Wordle · Easy" for random games played at a difficulty.
+    pub fn difficulty_title(&self) -> Option<String> {
+        let (_, difficulty) = self.replay_seed()?;
+        difficulty.map(|difficulty| format!("Wordle · {}", difficulty.name()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::storage::Storage;
+    use crate::utils::testing::temp_dir;
+    use crate::utils::wordle::Setup;
+    use std::fs;
+
+    #[test]
+    fn easy_answers_test() {
+        let dir = temp_dir("easy_answers");
+        let setup = |difficulty| Setup {
+            difficulty: Some(difficulty),
+            seed: Some(7),
+            ..Setup::default()
+        };
+        let easy: HashSet<String> = COMMON_WORDS
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .take(EASY_WORDS)
+            .map(str::to_uppercase)
+            .collect();
+        let mut game =
+            Wordle::with_config(Storage::open(dir.clone()), setup(Difficulty::Easy)).unwrap();
+        assert_eq!(game.answer_pool().len(), EASY_WORDS);
+        for _ in 0..(EASY_WORDS + 50) {
+            assert!(
+                easy.contains(&game.answer),
+                "{} isn't an everyday word",
+                game.answer
+            );
+            game.game_restart();
+        }
+        assert!(game.difficulty_title().unwrap().ends_with("Easy"));
+        // guesses are still checked against the whole list
+        let rare = game
+            .accepted_words
+            .iter()
+            .find(|word| !difficulty_pool(&game.answer_words, Difficulty::Normal).contains(*word))
+            .unwrap()
+            .clone();
+        assert!(game.parse_input(&rare).is_ok());
+
+        let game =
+            Wordle::with_config(Storage::open(dir.clone()), setup(Difficulty::Hard)).unwrap();
+        assert_eq!(game.answer_pool(), game.answer_words);
+
+        // unset, the whole list as before, so seeds deal the same words
+        let game = Wordle::with_config(Storage::open(dir.clone()), Setup::default()).unwrap();
+        assert_eq!(game.difficulty, None);
+        assert_eq!(game.difficulty_title(), None);
+        assert_eq!(game.answer_pool(), game.answer_words);
+
+        // a list of the player's own is drawn from whole
+        let answers = dir.join("answers.txt");
+        fs::write(&answers, "crane\nzonal\nxysti\nqophs\nfjeld\n").unwrap();
+        let own = Setup {
+            answer_list: Some(answers),
+            ..setup(Difficulty::Easy)
+        };
+        let game = Wordle::with_config(Storage::open(dir.clone()), own).unwrap();
+        assert_eq!(game.difficulty, None);
+        assert_eq!(game.answer_pool().len(), 5);
+        assert!(game.messages.status().unwrap().contains("built-in list"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn difficulty_pool_test() {
+        let words: HashSet<String> = ["ABOUT", "XYSTI", "ZONAE"].map(String::from).into();
+        assert_eq!(
+            difficulty_pool(&words, Difficulty::Easy),
+            HashSet::from([String::from("ABOUT")])
+        );
+        assert_eq!(difficulty_pool(&words, Difficulty::Hard), words);
+        // nothing ranked, so nothing to leave out
+        let rare: HashSet<String> = ["XYSTI", "ZONAE"].map(String::from).into();
+        assert_eq!(difficulty_pool(&rare, Difficulty::Easy), rare);
+        assert_eq!(Difficulty::parse("easy").unwrap(), Difficulty::Easy);
+        assert!(Difficulty::parse("expert").is_err());
+    }
+
+    #[test]
+    fn step_test() {
+        assert_eq!(Difficulty::Easy.harder(), Difficulty::Normal);
+        assert_eq!(Difficulty::Hard.harder(), Difficulty::Hard);
+        assert_eq!(Difficulty::Easy.easier(), Difficulty::Easy);
+        assert_eq!(Difficulty::Hard.easier(), Difficulty::Normal);
+        // levels saved before adaptive games used difficulties still load
+        let saved: Difficulty = serde_json::from_str("\"medium\"").unwrap();
+        assert_eq!(saved, Difficulty::Normal);
+    }
+}
@@ -0,0 +1,172 @@
+use std::time::{Duration, Instant};
+
+use super::wordle::Wordle;
+
+/// Time since a start that can be paused, which the game clock and a
+/// `--timed` run both keep. Time spent paused doesn't count.
+#[derive(Debug, Clone, Copy)]
+pub struct PausableClock {
+    started: Instant,
+    /// time spent paused before the current pause, if any
+    paused: Duration,
+    paused_since: Option<Instant>,
+}
+
+impl PausableClock {
+    /// A clock running from `now`.
+    pub fn new(now: Instant) -> Self {
+        PausableClock {
+            started: now,
+            paused: Duration::ZERO,
+            paused_since: None,
+        }
+    }
+
+    /// Time run at `now`, pauses left out.
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        let paused = self.paused
+            + self
+                .paused_since
+                .map_or(Duration::ZERO, |since| now.saturating_duration_since(since));
+        now.saturating_duration_since(self.started)
+            .saturating_sub(paused)
+    }
+
+    /// Stops the clock; nothing if it already is.
+    pub fn pause(&mut self, now: Instant) {
+        self.paused_since.get_or_insert(now);
+    }
+
+    /// Starts the clock again after `pause`.
+    pub fn resume(&mut self, now: Instant) {
+        if let Some(since) = self.paused_since.take() {
+            self.paused += now.saturating_duration_since(since);
+        }
+    }
+}
+
+/// How long the game on the board has taken, from the first letter typed
+/// to the last row. Time spent paused doesn't count.
+#[derive(Debug, Clone, Default)]
+pub struct GameClock {
+    /// running from the first letter
+    clock: Option<PausableClock>,
+    /// what the game took, once it's over
+    pub finished: Option<Duration>,
+    /// whole seconds as of the last `tick`
+    shown: u64,
+}
+
+impl GameClock {
+    /// Starts the clock at `now`, unless it already started.
+    pub fn start(&mut self, now: Instant) {
+        self.clock.get_or_insert(PausableClock::new(now));
+    }
+
+    /// Time the game has taken at `now`, `None` before the first letter.
+    pub fn elapsed(&self, now: Instant) -> Option<Duration> {
+        if self.finished.is_some() {
+            return self.finished;
+        }
+        self.clock.map(|clock| clock.elapsed(now))
+    }
+
+    /// Keeps the time at `now` as the game's, for good.
+    pub fn stop(&mut self, now: Instant) {
+        if self.finished.is_none() {
+            self.finished = self.elapsed(now);
+        }
+    }
+
+    /// Whether the clock shows another second at `now` than at the last
+    /// tick, so it needs redrawing.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        let secs = self.elapsed(now).map_or(0, |elapsed| elapsed.as_secs());
+        let changed = secs != self.shown;
+        self.shown = secs;
+        changed
+    }
+}
+
+/// `m:ss`, counting whole seconds.
+pub fn stopwatch(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+impl Wordle {
+    /// Keeps the game clock in step at `now`: stopped while the definition
+    /// or stats page is open. Returns whether the second shown changed.
+    pub fn tick_game_clock(&mut self, now: Instant) -> bool {
+        if let Some(clock) = &mut self.game_clock.clock {
+            if self.show_word_def || self.show_stats {
+                clock.pause(now);
+            } else {
+                clock.resume(now);
+            }
+        }
+        self.game_clock.tick(now)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn clock_pauses_test() {
+        let start = Instant::now();
+        let secs = Duration::from_secs;
+        let mut clock = PausableClock::new(start);
+        assert_eq!(clock.elapsed(start + secs(5)), secs(5));
+        clock.pause(start + secs(10));
+        clock.pause(start + secs(20));
+        assert_eq!(clock.elapsed(start + secs(40)), secs(10));
+        clock.resume(start + secs(40));
+        clock.resume(start + secs(45));
+        assert_eq!(clock.elapsed(start + secs(50)), secs(20));
+    }
+
+    #[test]
+    fn game_clock_test() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let mut clock = GameClock::default();
+        assert_eq!(clock.elapsed(at(5)), None);
+        assert!(!clock.tick(at(5)));
+
+        clock.start(at(10));
+        clock.start(at(12));
+        assert_eq!(clock.elapsed(at(15)), Some(Duration::from_secs(5)));
+        assert!(clock.tick(at(15)));
+        assert!(!clock.tick(at(15)));
+
+        clock.stop(at(50));
+        assert_eq!(clock.finished, Some(Duration::from_secs(40)));
+        assert_eq!(clock.elapsed(at(500)), Some(Duration::from_secs(40)));
+        assert_eq!(stopwatch(Duration::from_millis(40_900)), "0:40");
+        assert_eq!(stopwatch(Duration::from_secs(754)), "12:34");
+    }
+
+    #[test]
+    fn clock_starts_with_typing_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        let now = Instant::now();
+        assert!(!game.tick_game_clock(now + Duration::from_secs(5)));
+        assert_eq!(game.game_clock.elapsed(now), None);
+
+        game.type_letter('C');
+        assert!(game.game_clock.elapsed(Instant::now()).is_some());
+        game.show_word_def = true;
+        game.tick_game_clock(Instant::now());
+        let paused = game
+            .game_clock
+            .elapsed(Instant::now() + Duration::from_secs(60));
+        assert!(paused.unwrap() < Duration::from_secs(1));
+        game.show_word_def = false;
+
+        game.game_restart();
+        assert_eq!(game.game_clock.elapsed(Instant::now()), None);
+    }
+}
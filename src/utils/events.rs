@@ -0,0 +1,320 @@
+use ratatui::crossterm::event::{
+    self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent, KeyEventKind,
+    KeyEventState, KeyModifiers, MouseEvent,
+};
+use ratatui::crossterm::execute;
+use std::collections::VecDeque;
+use std::io;
+use std::time::Duration;
+
+/// What the game loop reacts to. The keys a game is played with have
+/// variants of their own; anything else pressed, e.g. a function key or a
+/// Ctrl combination, comes as `Key` for the bindings to look at.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppEvent {
+    Char(char),
+    Enter,
+    Backspace,
+    Tab,
+    Esc,
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+    /// text pasted with bracketed paste on, in one piece
+    Paste(String),
+    Resize(u16, u16),
+    /// time passed without input
+    Tick,
+}
+
+impl AppEvent {
+    /// The key pressed, as `on_key` takes it.
+    pub fn key_event(&self) -> Option<KeyEvent> {
+        let code = match *self {
+            AppEvent::Char(ch) => KeyCode::Char(ch),
+            AppEvent::Enter => KeyCode::Enter,
+            AppEvent::Backspace => KeyCode::Backspace,
+            AppEvent::Tab => KeyCode::Tab,
+            AppEvent::Esc => KeyCode::Esc,
+            AppEvent::Key(key) => return Some(key),
+            AppEvent::Mouse(_) | AppEvent::Paste(_) | AppEvent::Resize(..) | AppEvent::Tick => {
+                return None;
+            }
+        };
+        Some(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    /// The terminal's event, `None` for ones the game has no use for.
+    pub fn from_terminal(event: Event) -> Option<Self> {
+        match event {
+            Event::Key(key) => Some(AppEvent::from(key)),
+            Event::Mouse(mouse) => Some(AppEvent::Mouse(mouse)),
+            Event::Paste(text) => Some(AppEvent::Paste(text)),
+            Event::Resize(width, height) => Some(AppEvent::Resize(width, height)),
+            _ => None,
+        }
+    }
+}
+
+impl From<KeyEvent> for AppEvent {
+    /// Plain presses of the playing keys get their own variants; the rest
+    /// are kept whole so nothing about them is lost.
+    fn from(key: KeyEvent) -> Self {
+        let plain = key.modifiers == KeyModifiers::NONE
+            && key.kind == KeyEventKind::Press
+            && key.state == KeyEventState::NONE;
+        match key.code {
+            KeyCode::Char(ch) if plain => AppEvent::Char(ch),
+            KeyCode::Enter if plain => AppEvent::Enter,
+            KeyCode::Backspace if plain => AppEvent::Backspace,
+            KeyCode::Tab if plain => AppEvent::Tab,
+            KeyCode::Esc if plain => AppEvent::Esc,
+            _ => AppEvent::Key(key),
+        }
+    }
+}
+
+/// How often the game loop wakes up without input, unless `tick_ms` in
+/// `config.json` says otherwise.
+pub const TICK_RATE: Duration = Duration::from_millis(50);
+
+/// Has the terminal send a paste as one `Event::Paste` rather than a key
+/// per character, or stops it.
+pub fn bracketed_paste(on: bool) -> io::Result<()> {
+    if on {
+        execute!(io::stdout(), EnableBracketedPaste)
+    } else {
+        execute!(io::stdout(), DisableBracketedPaste)
+    }
+}
+
+/// Where the game loop gets its events.
+pub trait EventSource {
+    /// The next event, waiting at most `timeout` (forever when `None`)
+    /// before giving a `Tick`. `None` once there will be no more, which
+    /// ends the game like quitting.
+    fn next_event(&mut self, timeout: Option<Duration>) -> Option<AppEvent>;
+}
+
+/// The terminal's keyboard, mouse and resizes, through crossterm.
+pub struct TerminalEvents;
+
+impl EventSource for TerminalEvents {
+    fn next_event(&mut self, timeout: Option<Duration>) -> Option<AppEvent> {
+        if let Some(timeout) = timeout
+            && !event::poll(timeout).unwrap_or(false)
+        {
+            return Some(AppEvent::Tick);
+        }
+        // a failed read or an event of no use only redraws
+        let event = event::read().ok().and_then(AppEvent::from_terminal);
+        Some(event.unwrap_or(AppEvent::Tick))
+    }
+}
+
+/// Events given up front, for driving the loop in tests; runs out when the
+/// queue does.
+impl EventSource for VecDeque<AppEvent> {
+    fn next_event(&mut self, _timeout: Option<Duration>) -> Option<AppEvent> {
+        self.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::debug::DebugOverlay;
+    use crate::utils::wordle::Wordle;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    /// `word` typed and entered.
+    fn typed(word: &str) -> Vec<AppEvent> {
+        let mut events: Vec<AppEvent> = word.chars().map(AppEvent::Char).collect();
+        events.push(AppEvent::Enter);
+        events
+    }
+
+    /// Runs the game loop until `events` run out.
+    fn play(game: &mut Wordle, events: Vec<AppEvent>) {
+        let mut events = VecDeque::from(events);
+        let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+        game.event_loop(&mut terminal, &mut events).unwrap();
+    }
+
+    #[test]
+    fn scripted_win_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        let mut events = typed("crane");
+        // a word that isn't one stays to be corrected and takes no row
+        events.extend(typed("xyzzy"));
+        events.extend(vec![AppEvent::Backspace; 5]);
+        events.push(AppEvent::Tick);
+        events.extend(typed("EPOCH"));
+        play(&mut game, events);
+        assert!(game.solved && game.is_game_over);
+        assert_eq!(game.guess_history.len(), 2);
+        assert_eq!(game.row_timings.len(), 2);
+        assert_eq!(game.stats.games_played, 1);
+    }
+
+    #[test]
+    fn untimed_rows_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        // a row that didn't come from the keyboard, as a resumed daily's
+        game.current_guess = String::from("CRANE");
+        game.submit_guess();
+        play(&mut game, typed("porch"));
+        assert_eq!(game.guess_history.len(), 2);
+        assert_eq!(game.row_timings.len(), 2);
+        assert!(game.row_timings[0].is_none() && game.row_timings[1].is_some());
+    }
+
+    #[test]
+    fn scripted_loss_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        let words = [
+            "CRANE", "SLATE", "TRAIN", "GHOST", "PORCH", "COUCH", "EPOCH",
+        ];
+        play(
+            &mut game,
+            words.iter().flat_map(|word| typed(word)).collect(),
+        );
+        // the seventh row is never played
+        assert!(game.is_game_over && !game.solved);
+        assert_eq!(game.guess_history.len(), 6);
+        assert_eq!(game.stats.games_played, 1);
+    }
+
+    #[test]
+    fn key_round_trip_test() {
+        for key in [
+            KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT),
+            KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL),
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::F(7), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
+        ] {
+            assert_eq!(AppEvent::from(key).key_event(), Some(key));
+        }
+        assert_eq!(
+            AppEvent::from(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE)),
+            AppEvent::Char('a')
+        );
+        assert!(matches!(
+            AppEvent::from(KeyEvent::new(KeyCode::Tab, KeyModifiers::SHIFT)),
+            AppEvent::Key(_)
+        ));
+        assert_eq!(AppEvent::Tick.key_event(), None);
+    }
+
+    #[test]
+    fn press_only_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        let mut events = Vec::new();
+        for ch in "crane".chars() {
+            let press = KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE);
+            events.push(AppEvent::from(press));
+            for kind in [KeyEventKind::Repeat, KeyEventKind::Release] {
+                events.push(AppEvent::from(KeyEvent { kind, ..press }));
+            }
+        }
+        // shortcuts the terminal passes on aren't letters
+        for modifiers in [KeyModifiers::CONTROL, KeyModifiers::ALT] {
+            events.push(AppEvent::Key(KeyEvent::new(KeyCode::Char('s'), modifiers)));
+        }
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        events.push(AppEvent::from(enter));
+        events.push(AppEvent::from(KeyEvent {
+            kind: KeyEventKind::Release,
+            ..enter
+        }));
+        play(&mut game, events);
+        assert_eq!(game.guess_history.len(), 1);
+        assert!(game.current_guess.is_empty());
+        assert!(game.messages.error().is_none());
+    }
+
+    #[test]
+    fn paste_test() {
+        let paste = |text: &str| AppEvent::Paste(String::from(text));
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        play(&mut game, vec![paste("crane"), AppEvent::Enter]);
+        assert_eq!(game.guess_history.len(), 1);
+        assert!(game.messages.status().is_none());
+
+        // the rest of a long paste is dropped, and the player told
+        play(&mut game, vec![paste("slatecrane")]);
+        assert_eq!(game.current_guess, "SLATE");
+        assert_eq!(
+            game.messages.status(),
+            Some("pasted 10 letters, the row had room for 5")
+        );
+        game.current_guess.clear();
+
+        // only letters are typed
+        play(&mut game, vec![AppEvent::Char('e'), paste(" p-o.c!h\n")]);
+        assert_eq!(game.current_guess, "EPOCH");
+        play(&mut game, vec![AppEvent::Enter]);
+        assert!(game.is_game_over);
+
+        play(&mut game, vec![paste("crane")]);
+        assert!(game.current_guess.is_empty());
+    }
+
+    /// Scripted events, noting how long the loop was willing to wait for
+    /// each.
+    #[derive(Default)]
+    struct Recorded {
+        events: VecDeque<AppEvent>,
+        timeouts: Vec<Option<Duration>>,
+    }
+
+    impl EventSource for Recorded {
+        fn next_event(&mut self, timeout: Option<Duration>) -> Option<AppEvent> {
+            self.timeouts.push(timeout);
+            self.events.pop_front()
+        }
+    }
+
+    #[test]
+    fn idle_ticks_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        game.debug = Some(DebugOverlay::default());
+        play(&mut game, typed("crane"));
+        play(&mut game, vec![AppEvent::Char('e'), AppEvent::Char('p')]);
+        let frames = game.debug.as_ref().unwrap().frames;
+        let messages = game.messages.entries().count();
+
+        play(&mut game, vec![AppEvent::Tick; 100]);
+        assert_eq!(game.current_guess, "EP");
+        assert_eq!(game.guess_history.len(), 1);
+        assert_eq!(game.messages.entries().count(), messages);
+        assert!(!game.is_game_over);
+        // drawn once for the new terminal and not again
+        assert_eq!(game.debug.as_ref().unwrap().frames, frames + 1);
+    }
+
+    #[test]
+    fn queued_key_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        let mut source = Recorded::default();
+        source.events.extend(vec![AppEvent::Tick; 20]);
+        source.events.extend(typed("epoch"));
+        let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+        game.event_loop(&mut terminal, &mut source).unwrap();
+        assert!(game.solved);
+        // never blocked for longer than a tick
+        assert_eq!(source.timeouts.len(), 27);
+        assert!(source.timeouts.iter().all(|t| *t == Some(TICK_RATE)));
+
+        game.config.tick_ms = Some(0);
+        assert_eq!(game.tick_rate(), Duration::from_millis(1));
+    }
+}
@@ -0,0 +1,316 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Rect},
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Widget, Wrap},
+};
+use std::collections::HashMap;
+use wordle::engine::{TileState, Word};
+
+use super::ui::InputState;
+use super::wordle::Wordle;
+
+pub fn ordinal(n: usize) -> String {
+    let suffix = match n {
+        1 => "st",
+        2 => "nd",
+        3 => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
+
+pub fn copies(count: usize, letter: char) -> String {
+    if count == 1 {
+        format!("1 {letter}")
+    } else {
+        format!("{count} {letter}s")
+    }
+}
+
+/// One-line description of a scored row for screen readers and the zoom
+/// layout: `✓` right spot, `~` wrong spot, `✗` not in the answer.
+pub fn describe_row(row: &Word) -> String {
+    if row.is_pass() {
+        return String::from("passed");
+    }
+    row.letters
+        .iter()
+        .map(|tile| {
+            let mark = match tile.state {
+                TileState::Correct => '✓',
+                TileState::Present => '~',
+                TileState::Absent => '✗',
+                TileState::Unused => '?',
+            };
+            format!("{}{mark}", tile.letter)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Plain-language walk through how a scored guess got its colors.
+///
+/// Works from the row's own colors only, never the answer, so it can be
+/// shown mid-game without giving anything away. The steps follow `score`:
+/// exact matches first, then present letters while copies last, then gray.
+pub fn explain_row(row: &Word) -> Vec<String> {
+    if row.is_pass() {
+        return vec![String::from(
+            "This turn was passed, so the row tells nothing.",
+        )];
+    }
+    let letters: Vec<(char, TileState)> = row
+        .letters
+        .iter()
+        .map(|tile| (tile.letter, tile.state))
+        .collect();
+    // copies of each letter the answer is shown to have by this guess
+    let mut found: HashMap<char, usize> = HashMap::new();
+    for &(letter, state) in &letters {
+        if matches!(state, TileState::Correct | TileState::Present) {
+            *found.entry(letter).or_insert(0) += 1;
+        }
+    }
+
+    let mut lines = vec![String::from("1. Letters in the right spot turn green:")];
+    let greens: Vec<String> = letters
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, state))| *state == TileState::Correct)
+        .map(|(position, (letter, _))| format!("   {letter} in the {} spot", ordinal(position + 1)))
+        .collect();
+    if greens.is_empty() {
+        lines.push(String::from("   none"));
+    }
+    lines.extend(greens);
+
+    lines.push(String::from(
+        "2. Remaining letters that the answer still has copies of turn yellow, left to right:",
+    ));
+    let yellows: Vec<String> = letters
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, state))| *state == TileState::Present)
+        .map(|(position, (letter, _))| {
+            format!(
+                "   {letter} in the {} spot is in the answer, just somewhere else",
+                ordinal(position + 1)
+            )
+        })
+        .collect();
+    if yellows.is_empty() {
+        lines.push(String::from("   none"));
+    }
+    lines.extend(yellows);
+
+    lines.push(String::from("3. Everything else turns gray:"));
+    for (position, &(letter, state)) in letters.iter().enumerate() {
+        if state != TileState::Absent {
+            continue;
+        }
+        let spot = ordinal(position + 1);
+        match found.get(&letter) {
+            None => lines.push(format!(
+                "   {letter} in the {spot} spot: there is no {letter} in the answer"
+            )),
+            Some(&count) => {
+                let earlier: Vec<String> = letters
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (other, state))| {
+                        *other == letter && matches!(state, TileState::Correct | TileState::Present)
+                    })
+                    .map(|(position, (_, state))| {
+                        let color = if *state == TileState::Correct {
+                            "green"
+                        } else {
+                            "yellow"
+                        };
+                        format!("the {color} {letter} in the {} spot", ordinal(position + 1))
+                    })
+                    .collect();
+                lines.push(format!(
+                    "   {letter} in the {spot} spot: the answer has exactly {}, already used by {}",
+                    copies(count, letter),
+                    earlier.join(" and ")
+                ));
+            }
+        }
+    }
+    lines
+}
+
+impl Wordle {
+    /// Keys while reviewing past rows: pick a row with the arrows or its
+    /// number, leave with Esc or Enter.
+    pub fn on_review_key(&mut self, key: KeyEvent) -> InputState {
+        let rows = self.guess_history.len();
+        let Some(selected) = self.review_row else {
+            return InputState::None;
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter => self.review_row = None,
+            KeyCode::Up => self.review_row = Some(selected.saturating_sub(1)),
+            KeyCode::Down => self.review_row = Some((selected + 1).min(rows - 1)),
+            KeyCode::Char(ch) => {
+                if let Some(row) = ch.to_digit(10).map(|digit| digit as usize)
+                    && (1..=rows).contains(&row)
+                {
+                    self.review_row = Some(row - 1);
+                }
+            }
+            _ => {}
+        }
+        InputState::None
+    }
+
+    /// The keys that pick a row, as the review popup shows them. Digits
+    /// only reach nine rows, so longer games list the arrows alone.
+    fn review_keys(&self) -> String {
+        match self.rounds {
+            rounds @ 1..=9 => format!("<↑/↓/1-{rounds}>"),
+            _ => String::from("<↑/↓>"),
+        }
+    }
+
+    pub fn render_explanation(&self, row: usize, area: Rect, buf: &mut Buffer) {
+        let Some(word) = self.guess_history.get(row) else {
+            return;
+        };
+        let popup = area.centered(
+            Constraint::Length(area.width.saturating_sub(2)),
+            Constraint::Length(area.height.saturating_sub(4)),
+        );
+        Clear.render(popup, buf);
+
+        let guess: Vec<Span> = word
+            .letters
+            .iter()
+            .map(|tile| {
+                let style = self.theme.tile(tile.state);
+                Span::raw(format!(" {} ", tile.letter))
+                    .bg(style.bg)
+                    .fg(style.fg)
+                    .bold()
+            })
+            .collect();
+        let mut lines = vec![Line::from(guess), Line::from("")];
+        lines.extend(explain_row(word).into_iter().map(Line::from));
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::raw("Row "),
+            self.review_keys().blue().bold(),
+            Span::raw(" Back "),
+            "<Esc>".blue().bold(),
+        ]));
+
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::bordered()
+                    .title(format!("Why these colors? Row {}", row + 1))
+                    .border_type(BorderType::Rounded),
+            )
+            .fg(Color::White)
+            .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wordle::engine::score;
+
+    fn explain(answer: &str, guess: &str) -> Vec<String> {
+        let mut word = Word::from(guess).unwrap();
+        score(answer, &mut word).unwrap();
+        explain_row(&word)
+    }
+
+    #[test]
+    fn surplus_copy_after_green_test() {
+        // CRANE has one E, taken by the green one at the end
+        let lines = explain("CRANE", "EERIE");
+        assert!(lines.contains(&String::from("   E in the 5th spot")));
+        assert!(lines.contains(&String::from(
+            "   E in the 1st spot: the answer has exactly 1 E, already used by the green E in the 5th spot"
+        )));
+        assert!(lines.contains(&String::from(
+            "   R in the 3rd spot is in the answer, just somewhere else"
+        )));
+        assert!(lines.contains(&String::from(
+            "   I in the 4th spot: there is no I in the answer"
+        )));
+    }
+
+    #[test]
+    fn surplus_copy_after_yellow_test() {
+        // the first E takes ABIDE's only E, so the second one is gray
+        let lines = explain("ABIDE", "SPEED");
+        assert!(lines.contains(&String::from(
+            "   E in the 3rd spot is in the answer, just somewhere else"
+        )));
+        assert!(lines.contains(&String::from(
+            "   E in the 4th spot: the answer has exactly 1 E, already used by the yellow E in the 3rd spot"
+        )));
+    }
+
+    #[test]
+    fn two_copies_test() {
+        let lines = explain("GEESE", "EERIE");
+        assert!(lines.contains(&String::from(
+            "   E in the 1st spot is in the answer, just somewhere else"
+        )));
+        assert!(!lines.iter().any(|line| line.contains("exactly")));
+    }
+
+    #[test]
+    fn nothing_about_other_letters_test() {
+        // only letters of the guess are ever named
+        for line in explain("ABIDE", "SPEED") {
+            for word in line.split_whitespace() {
+                if word.len() == 1 && word.chars().all(|ch| ch.is_ascii_uppercase()) {
+                    assert!("SPEED".contains(word), "{line}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn review_keys_test() {
+        use ratatui::crossterm::event::KeyModifiers;
+        let press = |game: &mut Wordle, code| game.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        press(&mut game, KeyCode::Up);
+        assert_eq!(game.review_row, None);
+        for word in ["BAGEL", "ROUND", "MOUNT"] {
+            game.current_guess = word.to_string();
+            game.submit_guess();
+        }
+
+        press(&mut game, KeyCode::Up);
+        assert_eq!(game.review_row, Some(2));
+        press(&mut game, KeyCode::Up);
+        assert_eq!(game.review_row, Some(1));
+        press(&mut game, KeyCode::Char('1'));
+        assert_eq!(game.review_row, Some(0));
+        press(&mut game, KeyCode::Char('9'));
+        assert_eq!(game.review_row, Some(0));
+        // letters don't reach the guess while reviewing
+        press(&mut game, KeyCode::Char('a'));
+        assert!(game.current_guess.is_empty());
+        press(&mut game, KeyCode::Esc);
+        assert_eq!(game.review_row, None);
+
+        assert_eq!(game.review_keys(), format!("<↑/↓/1-{}>", game.rounds));
+        game.rounds = 8;
+        assert_eq!(game.review_keys(), "<↑/↓/1-8>");
+        game.rounds = 12;
+        assert_eq!(game.review_keys(), "<↑/↓>");
+    }
+}
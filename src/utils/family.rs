@@ -0,0 +1,104 @@
+use std::collections::HashSet;
+use std::io;
+use wordle::engine::Alphabet;
+
+use super::storage::Storage;
+use super::wordle::Wordle;
+
+/// The exclusion list: the built-in one, and the player's additions in the
+/// data directory.
+pub const EXCLUDED_FILE: &str = "excluded.txt";
+const EMBEDDED_EXCLUDED: &str = include_str!("../../excluded.txt");
+
+/// The words of an exclusion list, uppercased so any case matches. One per
+/// line; blank lines and lines starting with `#` are skipped.
+pub fn parse_excluded(contents: &str) -> HashSet<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(Alphabet::upper_word)
+        .collect()
+}
+
+/// The exclusion list built into the binary.
+pub fn builtin_excluded() -> HashSet<String> {
+    parse_excluded(EMBEDDED_EXCLUDED)
+}
+
+/// The built-in exclusion list plus the player's own `excluded.txt`, if
+/// there is one. These are never drawn as answers.
+pub fn load_excluded(storage: &Storage) -> io::Result<HashSet<String>> {
+    let mut excluded = builtin_excluded();
+    if let Some(contents) = storage.load(EXCLUDED_FILE)? {
+        excluded.extend(parse_excluded(&contents));
+    }
+    Ok(excluded)
+}
+
+impl Wordle {
+    /// Whether `word` is kept out of guesses, as it is with `--family-mode`
+    /// when excluded.
+    pub fn is_refused(&self, word: &str) -> bool {
+        self.family_mode && self.excluded.contains(&Alphabet::upper_word(word))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::testing::temp_dir;
+    use crate::utils::wordle::{Setup, Wordle};
+    use std::fs;
+    use wordle::engine::WordleError;
+
+    #[test]
+    fn parse_excluded_test() {
+        let excluded = parse_excluded("# a comment\ncrane\n  SlAtE \n\n");
+        assert_eq!(
+            excluded,
+            HashSet::from(["CRANE", "SLATE"].map(String::from))
+        );
+        assert!(
+            builtin_excluded()
+                .iter()
+                .all(|word| word.chars().all(|ch| ch.is_ascii_uppercase()))
+        );
+    }
+
+    #[test]
+    fn excluded_answers_test() {
+        let dir = temp_dir("excluded_answers");
+        fs::create_dir_all(&dir).unwrap();
+        let list = dir.join("list.txt");
+        fs::write(&list, "crane\nepoch\nslate\n").unwrap();
+        let storage = Storage::open(dir.clone());
+        storage.save(EXCLUDED_FILE, "EpOcH\nslate\n").unwrap();
+        let setup = |family_mode| Setup {
+            wordlist_paths: vec![list.clone()],
+            family_mode,
+            ..Setup::default()
+        };
+
+        let mut game = Wordle::with_config(Storage::open(dir.clone()), setup(false)).unwrap();
+        assert_eq!(game.answer_words, HashSet::from([String::from("CRANE")]));
+        for _ in 0..5 {
+            assert_eq!(game.answer, "CRANE");
+            game.game_restart();
+        }
+        // still fine to guess
+        assert_eq!(game.parse_input("epoch").unwrap().text(), "EPOCH");
+
+        let mut game = Wordle::with_config(Storage::open(dir.clone()), setup(true)).unwrap();
+        assert!(matches!(
+            game.parse_input("epoch"),
+            Err(WordleError::WordNotAllowed)
+        ));
+        game.current_guess = String::from("SLATE");
+        game.submit_guess();
+        assert!(game.guess_history.is_empty());
+        assert_eq!(game.messages.error(), Some("word not allowed"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
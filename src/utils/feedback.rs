@@ -0,0 +1,101 @@
+use anyhow::{Result, bail};
+use wordle::engine::{TileState, Word, score};
+
+use super::share::tile_symbol;
+
+/// A guess colored against an answer by the game's own `score`, as printed
+/// by `rust_wordle score`.
+pub struct Feedback {
+    pub answer: String,
+    pub guess: Word,
+}
+
+impl Feedback {
+    /// Scores `guess` against `answer`, both given in either case. They have
+    /// to be words of the same length, but needn't be on any word list.
+    pub fn new(answer: &str, guess: &str) -> Result<Self> {
+        let answer = answer.trim().to_ascii_uppercase();
+        let typed = guess.trim().to_ascii_uppercase();
+        for (what, word) in [("answer", &answer), ("guess", &typed)] {
+            if word.is_empty() || !word.chars().all(|ch| ch.is_ascii_uppercase()) {
+                bail!("{what} '{}' should only have the letters A-Z", word);
+            }
+        }
+        if answer.chars().count() != typed.chars().count() {
+            bail!(
+                "guess '{typed}' has {} letters but answer '{answer}' has {}",
+                typed.chars().count(),
+                answer.chars().count()
+            );
+        }
+        let mut guess = Word::from(&typed)?;
+        score(&answer, &mut guess)?;
+        Ok(Feedback { answer, guess })
+    }
+
+    /// e.g. "GYBYY"
+    pub fn letters(&self) -> String {
+        self.map_tiles(|state| tile_symbol(state, false))
+    }
+
+    /// e.g. "🟩🟨⬛🟨🟨"
+    pub fn emoji(&self) -> String {
+        self.map_tiles(|state| tile_symbol(state, true))
+    }
+
+    /// The guess as colored tiles, using ANSI escapes.
+    pub fn tiles(&self) -> String {
+        let mut tiles = String::new();
+        for tile in &self.guess.letters {
+            let background = match tile.state {
+                TileState::Correct => 42,
+                TileState::Present => 43,
+                _ => 100,
+            };
+            tiles.push_str(&format!("\x1b[1;30;{background}m {} \x1b[0m", tile.letter));
+        }
+        tiles
+    }
+
+    /// e.g. "CRANE CATER GYBYY 🟩🟨⬛🟨🟨", followed by the tiles if `color`.
+    pub fn line(&self, color: bool) -> String {
+        let guess: String = self.guess.letters.iter().map(|tile| tile.letter).collect();
+        let mut line = format!(
+            "{} {guess} {} {}",
+            self.answer,
+            self.letters(),
+            self.emoji()
+        );
+        if color {
+            line.push(' ');
+            line.push_str(&self.tiles());
+        }
+        line
+    }
+
+    fn map_tiles(&self, symbol: impl Fn(TileState) -> &'static str) -> String {
+        self.guess
+            .letters
+            .iter()
+            .map(|tile| symbol(tile.state))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn feedback_test() {
+        let feedback = Feedback::new("crane", " CATER").unwrap();
+        assert_eq!(feedback.letters(), "GYBYY");
+        assert_eq!(feedback.emoji(), "🟩🟨⬛🟨🟨");
+        assert_eq!(feedback.line(false), "CRANE CATER GYBYY 🟩🟨⬛🟨🟨");
+        assert!(feedback.line(true).ends_with("\x1b[1;30;43m R \x1b[0m"));
+
+        assert!(Feedback::new("CRANE", "CRANES").is_err());
+        assert!(Feedback::new("CRANE", "CR4NE").is_err());
+        assert!(Feedback::new("", "").is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use std::collections::HashMap;
+use wordle::engine::Alphabet;
+
+/// Presses of each key this session, for the keyboard heatmap. Never saved.
+#[derive(Debug, Default)]
+pub struct KeyCounts {
+    letters: HashMap<char, u32>,
+    pub enter: u32,
+    pub backspace: u32,
+}
+
+impl KeyCounts {
+    /// Counts `key` if it's a letter, Enter or Backspace. Either case of a
+    /// letter is the same physical key.
+    pub fn record(&mut self, key: &KeyEvent) {
+        match key.code {
+            KeyCode::Char(ch) if ch.is_alphabetic() => {
+                *self.letters.entry(Alphabet::upper(ch)).or_default() += 1;
+            }
+            KeyCode::Enter => self.enter += 1,
+            KeyCode::Backspace => self.backspace += 1,
+            _ => {}
+        }
+    }
+
+    /// Presses of `letter`, given in uppercase.
+    pub fn letter(&self, letter: char) -> u32 {
+        self.letters.get(&letter).copied().unwrap_or(0)
+    }
+
+    /// How hot `letter` is next to the most pressed letter, from 0 to 1.
+    pub fn heat(&self, letter: char) -> f32 {
+        let most = self.letters.values().copied().max().unwrap_or(0);
+        if most == 0 {
+            return 0.0;
+        }
+        self.letter(letter) as f32 / most as f32
+    }
+
+    /// e.g. "34 letters, 6 Enter, 3 Backspace; most pressed: E (8)"
+    pub fn summary(&self) -> String {
+        let letters: u32 = self.letters.values().sum();
+        let mut summary = format!(
+            "{letters} letters, {} Enter, {} Backspace",
+            self.enter, self.backspace
+        );
+        // ties go to the earlier letter
+        let most = self
+            .letters
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)));
+        if let Some((most, &count)) = most
+            && count > 0
+        {
+            summary.push_str(&format!("; most pressed: {most} ({count})"));
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::wordle::Wordle;
+    use ratatui::crossterm::event::KeyModifiers;
+
+    fn press(game: &mut Wordle, code: KeyCode) {
+        game.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn counts_test() {
+        let mut game = Wordle::new();
+        for ch in ['c', 'r', 'E', 'e', 'x'] {
+            press(&mut game, KeyCode::Char(ch));
+        }
+        press(&mut game, KeyCode::Backspace);
+        press(&mut game, KeyCode::Enter);
+        // counted even while a popup swallows the key
+        game.show_settings = true;
+        press(&mut game, KeyCode::Char('e'));
+        game.show_settings = false;
+
+        let counts = &game.key_counts;
+        assert_eq!(counts.letter('E'), 3);
+        assert_eq!(counts.letter('C'), 1);
+        assert_eq!(counts.heat('E'), 1.0);
+        assert_eq!(counts.heat('Q'), 0.0);
+        assert_eq!(
+            counts.summary(),
+            "6 letters, 1 Enter, 1 Backspace; most pressed: E (3)"
+        );
+        assert_eq!(
+            KeyCounts::default().summary(),
+            "0 letters, 0 Enter, 0 Backspace"
+        );
+
+        game.config.no_key_stats = true;
+        press(&mut game, KeyCode::Char('e'));
+        assert_eq!(game.key_counts.letter('E'), 3);
+    }
+}
@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+use wordle::engine::{TileState, Word};
+
+use super::messages::Level;
+use super::wordle::Wordle;
+
+/// A letter of the answer shown as a hint.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct Reveal {
+    /// 0-based column on the board
+    pub column: usize,
+    pub letter: char,
+    /// round it was shown in, so before that round's row was played
+    pub round: u8,
+}
+
+impl Reveal {
+    /// Whether `word` has the revealed letter in its column.
+    pub fn fits(&self, word: &str) -> bool {
+        word.chars().nth(self.column) == Some(self.letter)
+    }
+}
+
+/// A suggested position to try for a letter known to be in the answer.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PositionHint {
+    pub letter: char,
+    /// 0-based column on the board
+    pub position: usize,
+}
+
+/// Picks the untried position where one of `letters` appears most often
+/// among `candidates`.
+///
+/// Positions where a letter was already guessed are skipped for that letter.
+/// Ties go to the letter listed first, then to the leftmost position.
+pub fn suggest_position(
+    candidates: &[&str],
+    history: &[Word],
+    letters: &[char],
+) -> Option<PositionHint> {
+    let len = candidates.iter().map(|word| word.len()).max().unwrap_or(0);
+    let mut best: Option<(usize, PositionHint)> = None;
+    for &letter in letters {
+        for position in 0..len {
+            let tried = history
+                .iter()
+                .any(|word| word.letters.get(position).map(|tile| tile.letter) == Some(letter));
+            if tried {
+                continue;
+            }
+
+            let count = candidates
+                .iter()
+                .filter(|word| word.chars().nth(position) == Some(letter))
+                .count();
+            if count > 0 && best.is_none_or(|(best_count, _)| count > best_count) {
+                best = Some((count, PositionHint { letter, position }));
+            }
+        }
+    }
+    best.map(|(_, hint)| hint)
+}
+
+impl Wordle {
+    /// Letters marked Present on the keyboard that have not been placed yet.
+    fn outstanding_letters(&self) -> Vec<char> {
+        let mut letters: Vec<char> = self
+            .used_chars
+            .iter()
+            .filter(|(_, state)| **state == TileState::Present)
+            .map(|(&ch, _)| ch)
+            .collect();
+        letters.sort_unstable();
+        letters
+    }
+
+    /// Shows a "try E in position 4" suggestion for a yellow letter.
+    pub fn position_hint(&mut self) {
+        if !self.assists_allowed() {
+            self.show_error("assists are turned off");
+            return;
+        }
+        let letters = self.outstanding_letters();
+        if letters.is_empty() {
+            self.show_error(String::from("no yellow letters to place"));
+            return;
+        }
+
+        let candidates = self.candidates();
+        match suggest_position(&candidates, &self.guess_history, &letters) {
+            Some(hint) => {
+                self.messages.clear(Level::Error);
+                self.show_status(format!(
+                    "try {} in position {}",
+                    hint.letter,
+                    hint.position + 1
+                ));
+                self.assists_used += 1;
+            }
+            None => self.show_error(String::from("no position left to suggest")),
+        }
+    }
+}
+
+impl Wordle {
+    /// How many letters `reveal_letter` may show in one game.
+    pub fn reveal_limit(&self) -> usize {
+        self.config.reveals_per_game.unwrap_or(1) as usize
+    }
+
+    /// Shows the answer's letter in the leftmost column not yet solved or
+    /// revealed, as a green tile on the active row and a green key.
+    pub fn reveal_letter(&mut self) {
+        if !self.assists_allowed() {
+            self.show_error("assists are turned off");
+            return;
+        }
+        if self.revealed.len() >= self.reveal_limit() {
+            self.show_error(String::from("no reveals left this game"));
+            return;
+        }
+        let solved_column = |column: usize| {
+            self.guess_history
+                .iter()
+                .any(|word| !word.is_pass() && word.letters[column].state == TileState::Correct)
+        };
+        let Some((column, letter)) = self.answer.chars().enumerate().find(|&(column, _)| {
+            !solved_column(column) && !self.revealed.iter().any(|reveal| reveal.column == column)
+        }) else {
+            self.show_error(String::from("every letter is already placed"));
+            return;
+        };
+
+        self.apply_reveal(Reveal {
+            column,
+            letter,
+            round: self.round,
+        });
+        self.messages.clear(Level::Error);
+        self.show_status(format!("{letter} is in position {}", column + 1));
+        self.save_daily();
+    }
+
+    /// Shows `reveal` on the board, counting it as an assist. Also
+    /// restores the reveals of a resumed daily.
+    pub fn apply_reveal(&mut self, reveal: Reveal) {
+        self.revealed.push(reveal);
+        self.solver_cache.take();
+        self.remaining_cache.take();
+        self.used_chars.insert(reveal.letter, TileState::Correct);
+        self.letter_rounds.insert(reveal.letter, reveal.round);
+        self.assists_used += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn guessed(words: &[&str]) -> Vec<Word> {
+        words.iter().map(|word| Word::from(word).unwrap()).collect()
+    }
+
+    #[test]
+    fn suggest_most_common_position_test() {
+        let candidates = ["ABCDE", "XBEDA", "YYEYY"];
+        let hint = suggest_position(&candidates, &[], &['E']);
+        assert_eq!(
+            hint,
+            Some(PositionHint {
+                letter: 'E',
+                position: 2
+            })
+        );
+    }
+
+    #[test]
+    fn suggest_skips_tried_positions_test() {
+        let candidates = ["ABCDE", "XBEDA", "YYEYY"];
+        let history = guessed(&["QQEQQ"]);
+        let hint = suggest_position(&candidates, &history, &['E']);
+        assert_eq!(
+            hint,
+            Some(PositionHint {
+                letter: 'E',
+                position: 4
+            })
+        );
+    }
+
+    #[test]
+    fn suggest_tie_break_test() {
+        // A and E both appear once in untried positions; A comes first
+        let candidates = ["AQQQE"];
+        let hint = suggest_position(&candidates, &[], &['A', 'E']);
+        assert_eq!(
+            hint,
+            Some(PositionHint {
+                letter: 'A',
+                position: 0
+            })
+        );
+    }
+
+    #[test]
+    fn suggest_nothing_left_test() {
+        let candidates = ["QQEQQ"];
+        let history = guessed(&["QQEQQ"]);
+        assert_eq!(suggest_position(&candidates, &history, &['E']), None);
+    }
+
+    #[test]
+    fn position_hint_refuses_without_yellow_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        game.position_hint();
+        assert!(game.messages.status().is_none());
+        assert_eq!(game.assists_used, 0);
+        assert!(game.messages.error().is_some());
+    }
+
+    #[test]
+    fn position_hint_counts_assist_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        game.current_guess = "BAGEL".to_string();
+        game.submit_guess();
+        game.position_hint();
+        let status = game.messages.status().unwrap();
+        assert!(status.starts_with("try E in position "));
+        assert_ne!(status, "try E in position 4");
+        assert_eq!(game.assists_used, 1);
+    }
+
+    #[test]
+    fn reveal_letter_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        game.current_guess = "PORCH".to_string();
+        game.submit_guess();
+
+        // C and H are already placed, so the first column is shown
+        game.reveal_letter();
+        let reveal = |column, letter, round| Reveal {
+            column,
+            letter,
+            round,
+        };
+        assert_eq!(game.revealed, [reveal(0, 'E', 2)]);
+        assert_eq!(game.used_chars[&'E'], TileState::Correct);
+        assert_eq!(game.messages.status(), Some("E is in position 1"));
+        game.reveal_letter();
+        assert_eq!(game.messages.error(), Some("no reveals left this game"));
+        game.config.reveals_per_game = Some(3);
+        game.reveal_letter();
+        assert_eq!(game.revealed, [reveal(0, 'E', 2), reveal(1, 'P', 2)]);
+        assert_eq!(game.assists_used, 2);
+
+        // a later yellow P doesn't take its key's green away
+        game.current_guess = "SPORE".to_string();
+        game.submit_guess();
+        game.current_guess = "APPLE".to_string();
+        game.submit_guess();
+        assert_eq!(game.used_chars[&'P'], TileState::Correct);
+        game.current_guess = "EPOCH".to_string();
+        game.submit_guess();
+        assert!(game.solved);
+        assert_eq!(game.record().revealed, [0, 1]);
+        assert!(
+            game.share_text()
+                .starts_with("Wordle 4/6 (2 letters revealed)\n")
+        );
+    }
+}
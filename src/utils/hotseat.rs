@@ -0,0 +1,272 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Constraint, Rect},
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+use wordle::engine::Alphabet;
+
+use super::keys::{Action, KeyBinding};
+use super::messages::Level;
+use super::ui::InputState;
+use super::wordle::Wordle;
+
+/// Two players at one keyboard, taking turns to pick the word the other
+/// guesses.
+#[derive(Debug, Clone)]
+pub struct HotSeat {
+    /// the player picking the word, 1 or 2
+    pub setter: u8,
+    /// take any letters as the word, not just words from the list
+    pub any_word: bool,
+    /// the word being typed on the entry screen, open while `Some`
+    pub entry: Option<String>,
+}
+
+impl HotSeat {
+    /// The player guessing this round's word.
+    pub fn guesser(&self) -> u8 {
+        3 - self.setter
+    }
+}
+
+impl Wordle {
+    /// Starts with player one typing a word for player two.
+    pub fn start_hot_seat(&mut self, any_word: bool) {
+        self.hot_seat = Some(HotSeat {
+            setter: 1,
+            any_word,
+            entry: Some(String::new()),
+        });
+    }
+
+    /// Whether the word entry screen is up, hiding the board.
+    pub fn is_entering_word(&self) -> bool {
+        self.hot_seat
+            .as_ref()
+            .is_some_and(|seat| seat.entry.is_some())
+    }
+
+    /// The new game key in a hot-seat game: after a game the players swap,
+    /// mid-game the same player picks again.
+    pub fn next_hot_seat_word(&mut self) {
+        let over = self.is_game_over;
+        let Some(seat) = &mut self.hot_seat else {
+            return;
+        };
+        if over {
+            seat.setter = seat.guesser();
+        }
+        seat.entry = Some(String::new());
+        self.messages.clear(Level::Error);
+    }
+
+    /// Keys on the word entry screen. Only quitting works as bound; every
+    /// other key edits the hidden word.
+    pub fn on_word_entry_key(&mut self, key: KeyEvent, binding: KeyBinding) -> InputState {
+        if self.keymap.action(binding) == Some(Action::Quit) {
+            return self.on_action(binding).unwrap_or(InputState::None);
+        }
+        let word_len = self.word_len;
+        let Some(entry) = self.hot_seat.as_mut().and_then(|seat| seat.entry.as_mut()) else {
+            return InputState::None;
+        };
+        match key.code {
+            KeyCode::Char(ch)
+                if self.alphabet.contains(ch)
+                    && key.modifiers == KeyModifiers::NONE
+                    && entry.chars().count() < word_len =>
+            {
+                entry.push(Alphabet::upper(ch));
+            }
+            KeyCode::Backspace => {
+                entry.pop();
+            }
+            KeyCode::Enter => self.set_secret_word(),
+            _ => {}
+        }
+        InputState::None
+    }
+
+    /// Makes the typed word the answer and hands the keyboard over, unless
+    /// it's too short or, without `any_word`, not in the word list.
+    fn set_secret_word(&mut self) {
+        let Some(seat) = &self.hot_seat else {
+            return;
+        };
+        let word = seat.entry.clone().unwrap_or_default();
+        if word.chars().count() != self.word_len {
+            self.show_error(format!("the word needs {} letters", self.word_len));
+            return;
+        }
+        if self.is_refused(&word) {
+            self.show_error("word not allowed, pick another");
+            return;
+        }
+        if !seat.any_word && !self.accepted_words.contains(&word) {
+            self.show_error("not in the word list, pick another");
+            return;
+        }
+        let guesser = seat.guesser();
+        self.challenge = None;
+        self.answer = word;
+        self.reset_board();
+        if let Some(seat) = &mut self.hot_seat {
+            seat.entry = None;
+        }
+        self.show_status(format!("player {guesser}, your turn"));
+    }
+
+    /// The entry screen: who picks, and a star per letter typed so the word
+    /// never reaches the screen or the terminal's scrollback.
+    pub fn render_word_entry(&self, seat: &HotSeat, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let typed = seat.entry.as_deref().unwrap_or_default();
+        let slots: Vec<&str> = (0..self.word_len)
+            .map(|index| if index < typed.len() { "*" } else { "_" })
+            .collect();
+        let error = match self.messages.current() {
+            Some(message) if message.level == Level::Error => message.text.as_str(),
+            _ => "",
+        };
+        let lines = vec![
+            Line::from(format!(
+                "Player {}, pick a word for player {}",
+                seat.setter,
+                seat.guesser()
+            ))
+            .bold(),
+            Line::from(format!("player {} looks away", seat.guesser())).fg(Color::DarkGray),
+            Line::from(""),
+            Line::from(slots.join(" ")).bold(),
+            Line::from(""),
+            Line::from(error).fg(Color::Red),
+            Line::from(vec![
+                Span::raw("Start "),
+                Span::raw(self.keymap.label(Action::Submit)).blue().bold(),
+            ]),
+        ];
+        Paragraph::new(lines)
+            .centered()
+            .block(
+                Block::bordered()
+                    .title("Hot seat")
+                    .border_type(BorderType::Rounded),
+            )
+            .render(area, buf);
+    }
+
+    /// After a hot-seat game, over the keyboard: how it went and the key
+    /// that swaps the players.
+    pub fn render_swap_prompt(&self, seat: &HotSeat, area: Rect, buf: &mut Buffer) {
+        let result = if self.solved {
+            format!(
+                "Player {} found it in {}",
+                seat.guesser(),
+                self.guess_history.len()
+            )
+        } else {
+            format!("Player {} stumped player {}", seat.setter, seat.guesser())
+        };
+        let lines = vec![
+            Line::from(result).bold(),
+            Line::from(vec![
+                Span::raw("Swap roles "),
+                Span::raw(self.keymap.label(Action::NewGame)).blue().bold(),
+            ]),
+            Line::from(format!("player {} picks next", seat.guesser())).fg(Color::DarkGray),
+        ];
+        let popup = area.centered(Constraint::Length(34), Constraint::Length(5));
+        Clear.render(popup, buf);
+        Paragraph::new(lines)
+            .centered()
+            .block(Block::bordered().border_type(BorderType::Rounded))
+            .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::stats::Stats;
+    use ratatui::backend::TestBackend;
+
+    fn press(game: &mut Wordle, code: KeyCode) {
+        game.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn enter_word(game: &mut Wordle, word: &str) {
+        for ch in word.chars() {
+            press(game, KeyCode::Char(ch));
+        }
+        press(game, KeyCode::Enter);
+    }
+
+    fn screen(game: &Wordle) -> String {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(60, 45)).unwrap();
+        terminal.draw(|frame| game.render_terminal(frame)).unwrap();
+        let buf = terminal.backend().buffer();
+        buf.content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn masked_entry_test() {
+        let mut game = Wordle::new();
+        game.start_hot_seat(false);
+        assert!(!game.is_accepting_guesses());
+
+        // not a word, so player one picks again
+        enter_word(&mut game, "qzxvk");
+        assert!(game.is_entering_word());
+        assert!(screen(&game).contains("not in the word list"));
+        for _ in 0..5 {
+            press(&mut game, KeyCode::Backspace);
+        }
+
+        for ch in "epoc".chars() {
+            press(&mut game, KeyCode::Char(ch));
+        }
+        let masked = screen(&game);
+        assert!(masked.contains("* * * * _"));
+        assert!(!masked.contains("EPOC"));
+        press(&mut game, KeyCode::Char('h'));
+        press(&mut game, KeyCode::Enter);
+        assert!(!game.is_entering_word());
+        assert_eq!(game.answer, "EPOCH");
+        assert!(game.is_accepting_guesses());
+        assert_eq!(game.replay_seed(), None);
+    }
+
+    #[test]
+    fn swap_roles_test() {
+        let mut game = Wordle::new();
+        game.start_hot_seat(true);
+        // any letters will do
+        enter_word(&mut game, "qzxvk");
+        assert_eq!(game.answer, "QZXVK");
+        game.current_guess = String::from("QZXVK");
+        game.submit_guess();
+        let over = screen(&game);
+        assert!(over.contains("Player 2 found it in 1"));
+        // the made-up word was for that game only, and isn't counted
+        assert!(!game.accepted_words.contains("QZXVK"));
+        assert_eq!(game.stats, Stats::default());
+        let mut next = Wordle::new();
+        next.answer = String::from("EPOCH");
+        assert!(next.parse_input("qzxvk").is_err());
+        assert!(over.contains("Swap roles"));
+
+        press(&mut game, KeyCode::Tab);
+        let seat = game.hot_seat.as_ref().unwrap();
+        assert_eq!((seat.setter, seat.guesser()), (2, 1));
+        assert!(screen(&game).contains("Player 2, pick a word for player 1"));
+
+        // quitting still works from the entry screen
+        assert!(matches!(
+            game.on_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+            InputState::Quit
+        ));
+    }
+}
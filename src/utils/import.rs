@@ -0,0 +1,293 @@
+use anyhow::{Result, bail};
+use serde_json::Value;
+use std::fmt::Write;
+use std::path::Path;
+use wordle::engine::WordList;
+use wordle::engine::wordlist::validate_word;
+
+/// Shapes of word list `import-words` understands.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ImportFormat {
+    /// comma-separated with optional quoting, one word per row
+    Csv,
+    /// an array of strings
+    Json,
+    /// whitespace-separated words; numbers (e.g. frequencies) are skipped
+    Tokens,
+}
+
+impl ImportFormat {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "csv" => Ok(ImportFormat::Csv),
+            "json" => Ok(ImportFormat::Json),
+            "tokens" => Ok(ImportFormat::Tokens),
+            _ => bail!("unknown format '{name}', expected csv, json or tokens"),
+        }
+    }
+
+    /// Guesses the format from the file extension, then from the contents.
+    pub fn sniff(path: &Path, contents: &str) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("csv") => return ImportFormat::Csv,
+            Some("json") => return ImportFormat::Json,
+            _ => {}
+        }
+        let contents = strip_bom(contents).trim_start();
+        if contents.starts_with('[') {
+            ImportFormat::Json
+        } else if contents
+            .lines()
+            .next()
+            .is_some_and(|line| line.contains(','))
+        {
+            ImportFormat::Csv
+        } else {
+            ImportFormat::Tokens
+        }
+    }
+}
+
+/// A word that didn't make it into the imported list.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Reject {
+    /// where it was found, e.g. "row 3"
+    pub location: String,
+    pub text: String,
+    /// why, naming the word as trimmed
+    pub reason: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Import {
+    pub list: WordList,
+    pub rejects: Vec<Reject>,
+    /// valid words seen more than once
+    pub duplicates: usize,
+}
+
+impl Import {
+    /// Summary of what was kept and everything that was rejected.
+    pub fn report(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "kept {} words, rejected {}, skipped {} duplicates",
+            self.list.words().len(),
+            self.rejects.len(),
+            self.duplicates
+        );
+        for reject in &self.rejects {
+            let _ = writeln!(out, "  {}: {}", reject.location, reject.reason);
+        }
+        out
+    }
+}
+
+fn strip_bom(contents: &str) -> &str {
+    contents.strip_prefix('\u{feff}').unwrap_or(contents)
+}
+
+/// Extracts words from `contents` and runs them through the same validation
+/// as word list files. `column` picks a CSV column by header name; without
+/// one a `word` column is used if there is one, otherwise the first column.
+pub fn import_words(contents: &str, format: ImportFormat, column: Option<&str>) -> Result<Import> {
+    let contents = strip_bom(contents);
+    let entries = match format {
+        ImportFormat::Csv => csv_entries(contents, column)?,
+        ImportFormat::Json => json_entries(contents)?,
+        ImportFormat::Tokens => {
+            if column.is_some() {
+                bail!("--column only applies to csv input");
+            }
+            token_entries(contents)
+        }
+    };
+
+    let mut words = Vec::new();
+    let mut rejects = Vec::new();
+    for (location, text) in entries {
+        match validate_word(&text) {
+            Ok(word) => words.push(word),
+            Err(err) => rejects.push(Reject {
+                location,
+                text,
+                reason: err.to_string(),
+            }),
+        }
+    }
+    let list = WordList::new(words.iter().cloned());
+    Ok(Import {
+        duplicates: words.len() - list.words().len(),
+        list,
+        rejects,
+    })
+}
+
+fn json_entries(contents: &str) -> Result<Vec<(String, String)>> {
+    let Value::Array(items) = serde_json::from_str(contents)? else {
+        bail!("expected a JSON array of words");
+    };
+    Ok(items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let text = match item {
+                Value::String(text) => text,
+                other => other.to_string(),
+            };
+            (format!("item {}", index + 1), text)
+        })
+        .collect())
+}
+
+fn token_entries(contents: &str) -> Vec<(String, String)> {
+    let is_number = |token: &str| token.parse::<f64>().is_ok();
+    contents
+        .lines()
+        .enumerate()
+        .flat_map(|(number, line)| {
+            line.split_whitespace()
+                .filter(|token| !is_number(token))
+                .map(move |token| (format!("line {}", number + 1), token.to_string()))
+        })
+        .collect()
+}
+
+fn csv_entries(contents: &str, column: Option<&str>) -> Result<Vec<(String, String)>> {
+    let mut rows = parse_csv(contents)?.into_iter().enumerate();
+    let Some((_, header)) = rows.next() else {
+        return Ok(Vec::new());
+    };
+    let find = |name: &str| {
+        header
+            .iter()
+            .position(|field| field.trim().eq_ignore_ascii_case(name))
+    };
+    // without a header the first row is data
+    let (index, first) = match column {
+        Some(name) => match find(name) {
+            Some(index) => (index, None),
+            None => bail!("no column named '{name}' in {}", header.join(",")),
+        },
+        None => match find("word") {
+            Some(index) => (index, None),
+            None => (0, Some((0, header))),
+        },
+    };
+
+    Ok(first
+        .into_iter()
+        .chain(rows)
+        .filter(|(_, row)| !(row.len() == 1 && row[0].trim().is_empty()))
+        .map(|(number, row)| {
+            let text = row.get(index).cloned().unwrap_or_default();
+            (format!("row {}", number + 1), text)
+        })
+        .collect())
+}
+
+/// Splits CSV into rows of fields. Quoted fields may contain commas, line
+/// breaks and doubled quotes.
+fn parse_csv(contents: &str) -> Result<Vec<Vec<String>>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = contents.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            ch => field.push(ch),
+        }
+    }
+    if quoted {
+        bail!("unterminated quoted field in row {}", rows.len() + 1);
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn words(import: &Import) -> Vec<&str> {
+        import.list.words().iter().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn csv_test() {
+        let contents = "\u{feff}id,word,note\r\n1,crane,\"bird, tall\"\r\n2,\"EPOCH\",\"said \"\"hi\"\"\"\r\n3,\"two words\",x\r\n";
+        let import = import_words(contents, ImportFormat::Csv, None).unwrap();
+        assert_eq!(words(&import), vec!["CRANE", "EPOCH"]);
+        assert_eq!(import.rejects.len(), 1);
+        assert_eq!(import.rejects[0].location, "row 4");
+        assert_eq!(import.rejects[0].text, "two words");
+
+        let by_name = import_words(contents, ImportFormat::Csv, Some("note")).unwrap();
+        assert!(by_name.list.words().is_empty());
+        assert_eq!(by_name.rejects[1].text, "said \"hi\"");
+        assert!(import_words(contents, ImportFormat::Csv, Some("missing")).is_err());
+
+        // no header: the first row is data
+        let headerless = import_words("slate,12\ncrane,9\n", ImportFormat::Csv, None).unwrap();
+        assert_eq!(words(&headerless), vec!["CRANE", "SLATE"]);
+        assert!(import_words("\"crane\n", ImportFormat::Csv, None).is_err());
+    }
+
+    #[test]
+    fn json_test() {
+        let import = import_words(
+            "\u{feff}[\"crane\", \"Epoch\", 5, \"crane\"]",
+            ImportFormat::Json,
+            None,
+        )
+        .unwrap();
+        assert_eq!(words(&import), vec!["CRANE", "EPOCH"]);
+        assert_eq!(import.duplicates, 1);
+        assert_eq!(import.rejects[0].location, "item 3");
+        assert!(import_words("{\"words\": []}", ImportFormat::Json, None).is_err());
+    }
+
+    #[test]
+    fn tokens_test() {
+        let import = import_words(
+            "\u{feff}crane 1203\nepoch\t88.5 slate\ncafé\n",
+            ImportFormat::Tokens,
+            None,
+        )
+        .unwrap();
+        assert_eq!(words(&import), vec!["CRANE", "EPOCH", "SLATE"]);
+        assert_eq!(import.rejects.len(), 1);
+        assert_eq!(import.rejects[0].location, "line 3");
+        assert!(import.report().starts_with("kept 3 words, rejected 1"));
+    }
+
+    #[test]
+    fn sniff_test() {
+        let sniff = |name: &str, contents: &str| ImportFormat::sniff(Path::new(name), contents);
+        assert_eq!(sniff("list.csv", "crane"), ImportFormat::Csv);
+        assert_eq!(sniff("list.json", "crane"), ImportFormat::Json);
+        assert_eq!(
+            sniff("list.txt", "\u{feff} [\"crane\"]"),
+            ImportFormat::Json
+        );
+        assert_eq!(sniff("list", "word,count\n"), ImportFormat::Csv);
+        assert_eq!(sniff("list", "crane 12\n"), ImportFormat::Tokens);
+    }
+}
@@ -0,0 +1,317 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventState, KeyModifiers};
+use std::time::{Duration, Instant};
+use wordle::engine::Alphabet;
+
+/// How long an Esc is held back to see whether it starts an escape sequence.
+pub const ESC_WINDOW: Duration = Duration::from_millis(40);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Idle,
+    /// Esc seen at this time, waiting to see what follows
+    Esc(Instant),
+    /// inside `ESC [` (or a bare `[`), until the final byte
+    Csi,
+    /// after `ESC O`, one more byte to drop
+    Ss3,
+    /// inside `ESC ]`, until BEL or the Esc of `ESC \`
+    Osc,
+}
+
+/// Drops the fragments of escape sequences that terminal multiplexers leak
+/// as separate key presses, e.g. tmux copy-mode turning a mouse scroll into
+/// `Esc`, `[`, `<`, `6`, `4`, `;` … `M`.
+///
+/// An Esc is held for `ESC_WINDOW`. If `[`, `]` or `O` follows, the whole
+/// sequence is swallowed; otherwise the Esc is released as a real key.
+/// Characters outside the guess alphabet are left for `on_key` to ignore.
+#[derive(Debug)]
+pub struct EscapeFilter {
+    state: State,
+}
+
+impl Default for EscapeFilter {
+    fn default() -> Self {
+        EscapeFilter { state: State::Idle }
+    }
+}
+
+impl EscapeFilter {
+    /// Whether an Esc is being held and `flush` should be called once the
+    /// window has passed without another key.
+    pub fn is_holding(&self) -> bool {
+        matches!(self.state, State::Esc(_))
+    }
+
+    /// Feeds a key received at `now` and returns the keys that should
+    /// actually be handled.
+    pub fn feed(&mut self, key: KeyEvent, now: Instant) -> Vec<KeyEvent> {
+        let ch = match key.code {
+            KeyCode::Char(ch) if key.modifiers - KeyModifiers::SHIFT == KeyModifiers::NONE => {
+                Some(ch)
+            }
+            _ => None,
+        };
+
+        match self.state {
+            State::Csi => {
+                // parameters and intermediates, then one final byte
+                match ch {
+                    Some(' '..='?') => {}
+                    Some('@'..='~') => self.state = State::Idle,
+                    _ => {
+                        self.state = State::Idle;
+                        return self.feed(key, now);
+                    }
+                }
+                Vec::new()
+            }
+            State::Osc => {
+                let bell = key.code == KeyCode::Char('\x07')
+                    || (key.code == KeyCode::Char('g') && key.modifiers == KeyModifiers::CONTROL);
+                match key.code {
+                    // the `\` of an `ESC \` terminator is dropped like SS3's byte
+                    KeyCode::Esc => self.state = State::Ss3,
+                    _ if bell => self.state = State::Idle,
+                    KeyCode::Char(_) => {}
+                    _ => {
+                        self.state = State::Idle;
+                        return self.feed(key, now);
+                    }
+                }
+                Vec::new()
+            }
+            State::Ss3 => {
+                self.state = State::Idle;
+                match ch {
+                    // keypad Enter in application keypad mode
+                    Some('M') => vec![enter()],
+                    Some(_) => Vec::new(),
+                    None => self.feed(key, now),
+                }
+            }
+            State::Esc(at) if now.duration_since(at) <= ESC_WINDOW => match ch {
+                Some('[') => {
+                    self.state = State::Csi;
+                    Vec::new()
+                }
+                Some(']') => {
+                    self.state = State::Osc;
+                    Vec::new()
+                }
+                Some('O') => {
+                    self.state = State::Ss3;
+                    Vec::new()
+                }
+                _ => {
+                    self.state = State::Idle;
+                    let mut keys = vec![esc()];
+                    keys.extend(self.feed(key, now));
+                    keys
+                }
+            },
+            State::Esc(_) => {
+                self.state = State::Idle;
+                let mut keys = vec![esc()];
+                keys.extend(self.feed(key, now));
+                keys
+            }
+            State::Idle => match (key.code, ch) {
+                (KeyCode::Esc, _) => {
+                    self.state = State::Esc(now);
+                    Vec::new()
+                }
+                // `[` is never valid input, so it marks a sequence that lost
+                // its Esc
+                (_, Some('[')) => {
+                    self.state = State::Csi;
+                    Vec::new()
+                }
+                _ => vec![key],
+            },
+        }
+    }
+
+    /// Releases a held Esc once nothing followed it within the window.
+    pub fn flush(&mut self, now: Instant) -> Option<KeyEvent> {
+        match self.state {
+            State::Esc(at) if now.duration_since(at) > ESC_WINDOW => {
+                self.state = State::Idle;
+                Some(esc())
+            }
+            _ => None,
+        }
+    }
+}
+
+fn esc() -> KeyEvent {
+    KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)
+}
+
+fn enter() -> KeyEvent {
+    KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)
+}
+
+/// Folds the different ways terminals report the same key into one:
+/// every flavor of Enter (keypad, with modifiers, raw CR/LF, Ctrl+M,
+/// Ctrl+J) becomes a plain Enter, and a shifted letter becomes the
+/// uppercase letter without SHIFT.
+pub fn normalize(key: KeyEvent) -> KeyEvent {
+    let control = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Enter | KeyCode::Char('\r' | '\n') => KeyEvent {
+            code: KeyCode::Enter,
+            modifiers: KeyModifiers::NONE,
+            state: key.state - KeyEventState::KEYPAD,
+            ..key
+        },
+        KeyCode::Char('m' | 'j')
+            if control && key.modifiers - KeyModifiers::CONTROL == KeyModifiers::NONE =>
+        {
+            KeyEvent {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::NONE,
+                ..key
+            }
+        }
+        KeyCode::Char(ch) if ch.is_alphabetic() => KeyEvent {
+            code: KeyCode::Char(Alphabet::upper(ch)),
+            modifiers: key.modifiers - KeyModifiers::SHIFT,
+            ..key
+        },
+        _ => key,
+    }
+}
+
+/// Whether `key` is Ctrl+C, which raw mode delivers as a key instead of
+/// a signal.
+pub fn is_interrupt(key: KeyEvent) -> bool {
+    key.modifiers.contains(KeyModifiers::CONTROL) && matches!(key.code, KeyCode::Char('c' | 'C'))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Splits a raw byte sequence into the key events crossterm reports
+    /// when it can't parse it as one sequence.
+    fn keys(raw: &str) -> Vec<KeyEvent> {
+        raw.chars()
+            .map(|ch| match ch {
+                '\x1b' => esc(),
+                '\r' => KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+                ch => KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE),
+            })
+            .collect()
+    }
+
+    fn run(raw: &str) -> Vec<KeyCode> {
+        let mut filter = EscapeFilter::default();
+        let now = Instant::now();
+        let mut out: Vec<KeyCode> = keys(raw)
+            .into_iter()
+            .flat_map(|key| filter.feed(key, now))
+            .map(|key| key.code)
+            .collect();
+        out.extend(filter.flush(now + ESC_WINDOW * 2).map(|key| key.code));
+        out
+    }
+
+    #[test]
+    fn tmux_fragments_test() {
+        // captured in tmux copy-mode: SGR mouse wheel up/down, cursor keys
+        // in normal and application mode, page up, focus in/out
+        for fragment in [
+            "\x1b[<64;37;12M",
+            "\x1b[<65;37;12M",
+            "\x1b[<0;10;5m",
+            "\x1b[A",
+            "\x1bOB",
+            "\x1b[5~",
+            "\x1b[I",
+            "\x1b[O",
+            "\x1b]11;rgb:0000/0000/0000\x07",
+            "\x1b]52;c;?\x1b\\",
+        ] {
+            assert_eq!(run(fragment), vec![], "{fragment:?}");
+        }
+        // the same fragments wedged between typed letters
+        assert_eq!(
+            run("CR\x1b[<64;37;12MANE"),
+            keys("CRANE")
+                .into_iter()
+                .map(|key| key.code)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn lost_esc_test() {
+        // the Esc went missing but the `[` still gives the fragment away
+        assert_eq!(run("[<65;37;12MA"), vec![KeyCode::Char('A')]);
+    }
+
+    #[test]
+    fn keypad_enter_sequence_test() {
+        assert_eq!(run("\x1bOM"), vec![KeyCode::Enter]);
+    }
+
+    #[test]
+    fn normalize_enter_test() {
+        let keypad = KeyEvent::new_with_kind_and_state(
+            KeyCode::Enter,
+            KeyModifiers::NONE,
+            ratatui::crossterm::event::KeyEventKind::Press,
+            KeyEventState::KEYPAD,
+        );
+        for key in [
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT),
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL),
+            keypad,
+            KeyEvent::new(KeyCode::Char('\r'), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Char('\n'), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Char('m'), KeyModifiers::CONTROL),
+            KeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL),
+        ] {
+            assert_eq!(normalize(key), enter(), "{key:?}");
+        }
+    }
+
+    #[test]
+    fn normalize_letters_test() {
+        let plain = KeyEvent::new(KeyCode::Char('E'), KeyModifiers::NONE);
+        for key in [
+            KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Char('E'), KeyModifiers::SHIFT),
+            KeyEvent::new(KeyCode::Char('e'), KeyModifiers::SHIFT),
+            KeyEvent::new(KeyCode::Char('E'), KeyModifiers::NONE),
+        ] {
+            assert_eq!(normalize(key), plain, "{key:?}");
+        }
+        // other modifiers are kept so Ctrl+Q still means quit
+        assert_eq!(
+            normalize(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL)).modifiers,
+            KeyModifiers::CONTROL
+        );
+        let question = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT);
+        assert_eq!(normalize(question), question);
+    }
+
+    #[test]
+    fn real_esc_test() {
+        assert_eq!(run("\x1b"), vec![KeyCode::Esc]);
+        assert_eq!(run("\x1bA"), vec![KeyCode::Esc, KeyCode::Char('A')]);
+        assert_eq!(run("\x1b\r"), vec![KeyCode::Esc, KeyCode::Enter]);
+
+        // a `[` typed well after Esc isn't treated as a sequence
+        let mut filter = EscapeFilter::default();
+        let start = Instant::now();
+        assert!(filter.feed(esc(), start).is_empty());
+        assert!(filter.is_holding());
+        let later = filter.feed(keys("A")[0], start + ESC_WINDOW * 2);
+        assert_eq!(later.len(), 2);
+        assert_eq!(later[0].code, KeyCode::Esc);
+        assert_eq!(filter.flush(start), None);
+    }
+}
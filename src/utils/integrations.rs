@@ -0,0 +1,473 @@
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph, Widget, Wrap},
+};
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use super::wordle::Wordle;
+
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(5);
+/// Clipboard tools tried in turn, with the arguments that make them read
+/// stdin into the clipboard.
+const CLIPBOARD_TOOLS: [(&str, &[&str]); 5] = [
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip.exe", &[]),
+];
+/// Dry-run previews are also appended here so they outlive the popup.
+pub const DRY_RUN_LOG: &str = "dry-run.log";
+
+/// An outbound side effect, labeled with the integration that wants it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Effect {
+    Command {
+        integration: &'static str,
+        program: String,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+    },
+    HttpPost {
+        integration: &'static str,
+        url: String,
+        body: String,
+    },
+    Clipboard {
+        integration: &'static str,
+        text: String,
+    },
+}
+
+impl Effect {
+    /// One-line description for previews, with every occurrence of `answer`
+    /// masked when `redact` is set.
+    pub fn describe(&self, answer: &str, redact: bool) -> String {
+        let text = match self {
+            Effect::Command {
+                integration,
+                program,
+                args,
+                env,
+            } => {
+                let env: Vec<String> = env
+                    .iter()
+                    .map(|(key, value)| format!("{key}={value}"))
+                    .collect();
+                format!(
+                    "[{integration}] {} {} {}",
+                    env.join(" "),
+                    program,
+                    args.join(" ")
+                )
+            }
+            Effect::HttpPost {
+                integration,
+                url,
+                body,
+            } => format!("[{integration}] POST {url} {body}"),
+            Effect::Clipboard { integration, text } => {
+                let text: Vec<&str> = text.split_whitespace().collect();
+                format!("[{integration}] copy to clipboard: {}", text.join(" "))
+            }
+        };
+        if redact && !answer.is_empty() {
+            text.replace(answer, &"*".repeat(answer.chars().count()))
+        } else {
+            text
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Executed,
+    /// recorded instead of run, for the dry-run preview
+    Captured,
+}
+
+/// Carries out side effects. Every integration goes through one of these so
+/// `--dry-run-integrations` can swap in a capturing one.
+pub trait Executor {
+    fn execute(&mut self, effect: &Effect) -> Result<Outcome>;
+
+    /// Errors of effects that finished in the background since the last
+    /// call.
+    fn failures(&mut self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Waits `delay` before handing each effect to `inner`, for
+/// `--simulate-latency`. Wraps whichever executor is in use, so every
+/// integration sees the delay.
+pub struct Delayed {
+    pub inner: Box<dyn Executor>,
+    pub delay: Duration,
+}
+
+impl Executor for Delayed {
+    fn execute(&mut self, effect: &Effect) -> Result<Outcome> {
+        thread::sleep(self.delay);
+        self.inner.execute(effect)
+    }
+
+    fn failures(&mut self) -> Vec<String> {
+        self.inner.failures()
+    }
+}
+
+/// An upload's integration, URL and body.
+type Upload = (&'static str, String, String);
+
+/// Actually runs commands and sends requests.
+///
+/// Uploads are sent one at a time from a background thread, started with
+/// the first one, so a slow server can't freeze the UI; their errors come
+/// back through `failures`.
+#[derive(Default)]
+pub struct SystemExecutor {
+    uploads: Option<(Sender<Upload>, Receiver<String>)>,
+}
+
+impl SystemExecutor {
+    fn upload(&mut self, upload: Upload) -> Result<()> {
+        let (uploads, _) = self.uploads.get_or_insert_with(|| {
+            let (uploads, upload_rx) = mpsc::channel::<Upload>();
+            let (failure_tx, failures) = mpsc::channel();
+            thread::spawn(move || {
+                for (integration, url, body) in upload_rx {
+                    if let Err(err) = post(&url, body)
+                        .with_context(|| format!("{integration}: upload to {url} failed"))
+                        && failure_tx.send(format!("{err:#}")).is_err()
+                    {
+                        break;
+                    }
+                }
+            });
+            (uploads, failures)
+        });
+        uploads
+            .send(upload)
+            .map_err(|_| anyhow::anyhow!("the upload thread stopped"))
+    }
+}
+
+impl Executor for SystemExecutor {
+    fn execute(&mut self, effect: &Effect) -> Result<Outcome> {
+        match effect {
+            Effect::Command {
+                integration,
+                program,
+                args,
+                env,
+            } => {
+                // spawned and left running so a slow hook can't stall the UI
+                Command::new(program)
+                    .args(args)
+                    .envs(env.iter().cloned())
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .spawn()
+                    .with_context(|| format!("{integration}: failed to run {program}"))?;
+            }
+            Effect::HttpPost {
+                integration,
+                url,
+                body,
+            } => self.upload((integration, url.clone(), body.clone()))?,
+            Effect::Clipboard { integration, text } => {
+                copy_to_clipboard(text).with_context(|| format!("{integration}: copy failed"))?
+            }
+        }
+        Ok(Outcome::Executed)
+    }
+
+    fn failures(&mut self) -> Vec<String> {
+        match &self.uploads {
+            Some((_, failures)) => failures.try_iter().collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+fn post(url: &str, body: String) -> reqwest::Result<()> {
+    reqwest::blocking::Client::builder()
+        .timeout(UPLOAD_TIMEOUT)
+        .build()?
+        .post(url)
+        .header("content-type", "application/json")
+        .body(body)
+        .send()?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Hands `text` to the first clipboard tool that is installed, or else asks
+/// the terminal to copy it with OSC 52, which also works over SSH.
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    for (program, args) in CLIPBOARD_TOOLS {
+        let mut child = match Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err).with_context(|| format!("failed to run {program}")),
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if !child.wait()?.success() {
+            bail!("{program} failed");
+        }
+        return Ok(());
+    }
+    let mut stdout = io::stdout();
+    if !stdout.is_terminal() {
+        bail!("no clipboard tool found");
+    }
+    write!(stdout, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Records effects without running them.
+#[derive(Debug, Default)]
+pub struct DryRunExecutor {
+    pub effects: Vec<Effect>,
+}
+
+impl Executor for DryRunExecutor {
+    fn execute(&mut self, effect: &Effect) -> Result<Outcome> {
+        self.effects.push(effect.clone());
+        Ok(Outcome::Captured)
+    }
+}
+
+impl Wordle {
+    /// Side effects the configured integrations want once a game ends.
+    pub fn game_end_effects(&self) -> Vec<Effect> {
+        let mut effects = Vec::new();
+        if let Some(hook) = &self.config.on_game_end {
+            effects.push(Effect::Command {
+                integration: "on_game_end",
+                program: String::from("sh"),
+                args: vec![String::from("-c"), hook.clone()],
+                env: vec![
+                    (String::from("WORDLE_GAME_ID"), self.game_id.clone()),
+                    (String::from("WORDLE_ANSWER"), self.answer.clone()),
+                    (String::from("WORDLE_SOLVED"), self.solved.to_string()),
+                    (
+                        String::from("WORDLE_GUESSES"),
+                        self.guess_history.len().to_string(),
+                    ),
+                ],
+            });
+        }
+        if let Some(url) = &self.config.upload_url {
+            effects.push(Effect::HttpPost {
+                integration: "upload",
+                url: url.clone(),
+                body: serde_json::to_string(&self.record()).unwrap_or_default(),
+            });
+        }
+        effects
+    }
+
+    /// Hands the game-end effects to the executor. Captured ones are shown
+    /// in a preview popup and logged instead.
+    pub fn run_integrations(&mut self) {
+        let mut preview = Vec::new();
+        for effect in self.game_end_effects() {
+            match self.integrations.execute(&effect) {
+                Ok(Outcome::Executed) => {}
+                Ok(Outcome::Captured) => {
+                    preview.push(effect.describe(&self.answer, self.config.privacy));
+                }
+                Err(err) => self.show_error(format!("{:#}", err)),
+            }
+        }
+        if preview.is_empty() {
+            return;
+        }
+        for line in &preview {
+            if let Err(err) = self.storage.append(DRY_RUN_LOG, line) {
+                self.show_error(format!("failed to log dry run: {err}"));
+            }
+        }
+        self.integration_preview = Some(preview);
+    }
+
+    /// Shows the errors of integrations that finished in the background.
+    pub fn poll_integrations(&mut self) {
+        for failure in self.integrations.failures() {
+            self.show_error(failure);
+            self.redraw = true;
+        }
+    }
+
+    pub fn render_integration_preview(&self, lines: &[String], area: Rect, buf: &mut Buffer) {
+        let popup = area.centered(
+            Constraint::Length(area.width.saturating_sub(4)),
+            Constraint::Length(lines.len() as u16 * 3 + 4),
+        );
+        Clear.render(popup, buf);
+
+        let mut text: Vec<Line> = lines.iter().map(|line| Line::from(line.as_str())).collect();
+        text.push(Line::from(""));
+        text.push(Line::from("nothing was sent").fg(Color::DarkGray));
+        Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::bordered()
+                    .title("Dry run")
+                    .border_type(BorderType::Rounded),
+            )
+            .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::storage::Storage;
+    use crate::utils::testing::temp_dir;
+    use std::fs;
+    use std::net::TcpListener;
+    use std::time::Instant;
+
+    fn finished_game(name: &str) -> (Wordle, std::path::PathBuf) {
+        let dir = temp_dir(name);
+        let mut game = Wordle::new();
+        game.storage = Storage::open(dir.clone());
+        game.integrations = Box::new(DryRunExecutor::default());
+        game.config.on_game_end = Some(String::from("notify-send done"));
+        game.config.upload_url = Some(String::from("https://example.com/results"));
+        game.answer = "EPOCH".to_string();
+        game.current_guess = "EPOCH".to_string();
+        game.submit_guess();
+        (game, dir)
+    }
+
+    #[test]
+    fn effects_test() {
+        let (game, dir) = finished_game("integrations_effects");
+        let effects = game.game_end_effects();
+        assert_eq!(effects.len(), 2);
+        let Effect::Command {
+            program, args, env, ..
+        } = &effects[0]
+        else {
+            panic!("expected the hook first");
+        };
+        assert_eq!(program, "sh");
+        assert_eq!(
+            args,
+            &vec!["-c".to_string(), "notify-send done".to_string()]
+        );
+        assert!(env.contains(&("WORDLE_SOLVED".to_string(), "true".to_string())));
+        let Effect::HttpPost { body, .. } = &effects[1] else {
+            panic!("expected the upload second");
+        };
+        assert!(body.contains("\"answer\":\"EPOCH\""));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn dry_run_preview_test() {
+        let (mut game, dir) = finished_game("integrations_dry_run");
+        game.config.privacy = true;
+        game.run_integrations();
+
+        let preview = game.integration_preview.clone().unwrap();
+        assert_eq!(preview.len(), 2);
+        assert!(preview[0].starts_with("[on_game_end] "));
+        assert!(preview[1].starts_with("[upload] POST https://example.com/results"));
+        assert!(preview.iter().all(|line| !line.contains("EPOCH")));
+        assert!(preview[0].contains("WORDLE_ANSWER=*****"));
+
+        let log = game.storage.load(DRY_RUN_LOG).unwrap().unwrap();
+        assert_eq!(log.lines().count(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn capture_executor_test() {
+        let mut executor = DryRunExecutor::default();
+        let effect = Effect::HttpPost {
+            integration: "upload",
+            url: String::from("https://example.com"),
+            body: String::from("{}"),
+        };
+        assert_eq!(executor.execute(&effect).unwrap(), Outcome::Captured);
+        assert_eq!(executor.effects, vec![effect]);
+    }
+
+    #[test]
+    fn delayed_executor_test() {
+        let delay = Duration::from_millis(30);
+        let mut executor = Delayed {
+            inner: Box::new(DryRunExecutor::default()),
+            delay,
+        };
+        let effect = Effect::HttpPost {
+            integration: "upload",
+            url: String::from("https://example.com"),
+            body: String::from("{}"),
+        };
+        let start = std::time::Instant::now();
+        assert_eq!(executor.execute(&effect).unwrap(), Outcome::Captured);
+        assert!(start.elapsed() >= delay);
+    }
+
+    #[test]
+    fn upload_in_background_test() {
+        let upload = |url: String| Effect::HttpPost {
+            integration: "upload",
+            url,
+            body: String::from("{}"),
+        };
+        let mut executor = SystemExecutor::default();
+        assert!(executor.failures().is_empty());
+
+        // a failed upload is reported once it's done
+        let closed = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", closed.local_addr().unwrap());
+        drop(closed);
+        let start = Instant::now();
+        assert_eq!(
+            executor.execute(&upload(url.clone())).unwrap(),
+            Outcome::Executed
+        );
+        let mut failures = Vec::new();
+        while failures.is_empty() && start.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(10));
+            failures = executor.failures();
+        }
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].starts_with(&format!("upload: upload to {url} failed")));
+
+        // a server that never answers doesn't hold up the caller
+        let silent = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", silent.local_addr().unwrap());
+        let start = Instant::now();
+        executor.execute(&upload(url)).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(executor.failures().is_empty());
+    }
+}
@@ -0,0 +1,331 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use wordle::engine::Alphabet;
+
+use super::assets::LAYOUTS;
+use super::mouse::keyboard_rows;
+use super::wordle::Wordle;
+
+/// Keys in a row of letters the layout leaves out, before it wraps.
+const EXTRA_ROW_KEYS: usize = 10;
+
+/// Where the on-screen keyboard puts its letters. A language pack's own
+/// layout is used while none is chosen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Azerty,
+    Dvorak,
+    /// the alphabet in order, in three rows
+    Alphabetical,
+}
+
+impl KeyboardLayout {
+    pub const ALL: [KeyboardLayout; 4] = [
+        KeyboardLayout::Qwerty,
+        KeyboardLayout::Azerty,
+        KeyboardLayout::Dvorak,
+        KeyboardLayout::Alphabetical,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyboardLayout::Qwerty => "qwerty",
+            KeyboardLayout::Azerty => "azerty",
+            KeyboardLayout::Dvorak => "dvorak",
+            KeyboardLayout::Alphabetical => "alphabetical",
+        }
+    }
+
+    /// The letters of each row from the top, for `alphabet`, as `layouts`
+    /// has them.
+    pub fn rows(&self, alphabet: &Alphabet, layouts: &Layouts) -> Vec<Vec<char>> {
+        if *self == KeyboardLayout::Alphabetical {
+            let letters = alphabet.letters();
+            return letters
+                .chunks(letters.len().div_ceil(3).max(1))
+                .map(<[char]>::to_vec)
+                .collect();
+        }
+        let rows = layouts
+            .get(self.name())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        rows.iter()
+            .map(|row| row.chars().map(Alphabet::upper).collect())
+            .collect()
+    }
+}
+
+/// Rows of the layouts by name, as `layouts.toml` writes them.
+pub type Layouts = BTreeMap<String, Vec<String>>;
+
+/// The built-in rows with those in `contents` over them, so a file can
+/// change one layout and leave the rest. Layouts that don't exist, or rows
+/// of anything but letters, are refused.
+pub fn parse_layouts(contents: &str, origin: &str) -> Result<Layouts> {
+    let mut layouts = builtin_layouts();
+    layouts.extend(read_layouts(contents, origin)?);
+    Ok(layouts)
+}
+
+/// The rows of the built-in `layouts.toml`.
+pub fn builtin_layouts() -> Layouts {
+    read_layouts(LAYOUTS.embedded, LAYOUTS.builtin).expect("the built-in layouts parse")
+}
+
+fn read_layouts(contents: &str, origin: &str) -> Result<Layouts> {
+    let layouts: Layouts = toml::from_str(contents)
+        .with_context(|| format!("invalid keyboard layouts in {origin}"))?;
+    for (name, rows) in &layouts {
+        let fixed = KeyboardLayout::ALL
+            .iter()
+            .any(|layout| layout.name() == name && *layout != KeyboardLayout::Alphabetical);
+        if !fixed {
+            bail!("{origin} has rows for {name}, which isn't a layout with rows to set");
+        }
+        if rows.iter().all(|row| row.is_empty()) {
+            bail!("{origin} gives {name} no keys");
+        }
+        if !rows
+            .iter()
+            .flat_map(|row| row.chars())
+            .all(char::is_alphabetic)
+        {
+            bail!("{origin} has a key in {name} that isn't a letter");
+        }
+    }
+    Ok(layouts)
+}
+
+/// The keyboard for `rows` of letters, drawn as `keyboard_rows` does. Keys
+/// for letters outside `alphabet` are dropped, and the alphabet's letters
+/// the rows leave out get rows of their own at the bottom.
+pub fn keyboard_for(rows: &[Vec<char>], alphabet: &Alphabet) -> Vec<String> {
+    let rows: Vec<Vec<char>> = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .copied()
+                .filter(|&ch| alphabet.contains(ch))
+                .collect::<Vec<_>>()
+        })
+        .filter(|row| !row.is_empty())
+        .collect();
+    let missing: Vec<char> = alphabet
+        .letters()
+        .iter()
+        .copied()
+        .filter(|ch| !rows.iter().flatten().any(|key| key == ch))
+        .collect();
+    let extra = missing.chunks(EXTRA_ROW_KEYS).map(<[char]>::to_vec);
+    if rows.is_empty() {
+        // nothing of the layout is left, so Enter and Backspace go with these
+        return keyboard_rows(&extra.collect::<Vec<_>>());
+    }
+    let mut keyboard = keyboard_rows(&rows);
+    keyboard.extend(extra.map(|row| {
+        row.iter()
+            .map(char::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }));
+    keyboard
+}
+
+impl Wordle {
+    /// The keyboard for the chosen layout, or the language pack's own when
+    /// none is chosen.
+    pub fn build_keyboard(&self) -> Vec<String> {
+        let rows = match (self.config.keyboard_layout, &self.language) {
+            (None, Some(pack)) => pack.rows.clone(),
+            (layout, _) => layout
+                .unwrap_or_default()
+                .rows(&self.alphabet, &self.layouts),
+        };
+        keyboard_for(&rows, &self.alphabet)
+    }
+
+    /// The layout's name as settings shows it.
+    pub fn keyboard_name(&self) -> &str {
+        match (self.config.keyboard_layout, &self.language) {
+            (None, Some(pack)) => &pack.name,
+            (layout, _) => layout.unwrap_or_default().name(),
+        }
+    }
+
+    /// Moves to the next layout, then back to the language pack's own if
+    /// one is in use, and remembers the choice in `config.json`.
+    pub fn next_keyboard_layout(&mut self) {
+        let has_own = self.language.is_some();
+        let current = match self.config.keyboard_layout {
+            Some(layout) => layout,
+            None if has_own => {
+                self.set_keyboard_layout(Some(KeyboardLayout::ALL[0]));
+                return;
+            }
+            None => KeyboardLayout::default(),
+        };
+        let index = KeyboardLayout::ALL
+            .iter()
+            .position(|layout| *layout == current)
+            .unwrap_or(0);
+        let next = match KeyboardLayout::ALL.get(index + 1) {
+            Some(layout) => Some(*layout),
+            None if has_own => None,
+            None => Some(KeyboardLayout::ALL[0]),
+        };
+        self.set_keyboard_layout(next);
+    }
+
+    pub fn set_keyboard_layout(&mut self, layout: Option<KeyboardLayout>) {
+        self.config.keyboard_layout = layout;
+        self.keyboard = self.build_keyboard();
+        self.show_status(format!("keyboard {}", self.keyboard_name()));
+        if let Err(err) = self.config.save(&self.storage) {
+            self.show_error(format!("failed to save config: {err}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::language::LanguagePack;
+    use crate::utils::mouse::key_boxes;
+    use crate::utils::storage::Storage;
+    use crate::utils::testing::temp_dir;
+    use crate::utils::wordle::Setup;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use std::fs;
+    use wordle::engine::TileState;
+
+    #[test]
+    fn extra_row_test() {
+        let latin = Alphabet::latin();
+        let layouts = builtin_layouts();
+        for layout in KeyboardLayout::ALL {
+            let keyboard = keyboard_for(&layout.rows(&latin, &layouts), &latin);
+            assert_eq!(keyboard.len(), 3, "{}", layout.name());
+            let keys: Vec<char> = keyboard
+                .concat()
+                .chars()
+                .filter(|ch| latin.contains(*ch))
+                .collect();
+            assert_eq!(keys.len(), 26, "{}", layout.name());
+        }
+
+        // Ñ isn't on a QWERTY keyboard, so it gets a row under it
+        let spanish = Alphabet::new("ABCDEFGHIJKLMNÑOPQRSTUVWXYZ".chars()).unwrap();
+        let keyboard = keyboard_for(&KeyboardLayout::Qwerty.rows(&spanish, &layouts), &spanish);
+        assert_eq!(keyboard.len(), 4);
+        assert_eq!(keyboard[2], "⏎ Z X C V B N M ⌫");
+        assert_eq!(keyboard[3], "Ñ");
+        let keyboard = keyboard_for(
+            &KeyboardLayout::Alphabetical.rows(&spanish, &layouts),
+            &spanish,
+        );
+        assert_eq!(keyboard.len(), 3);
+
+        // none of the layout's letters: the alphabet is the keyboard
+        let greek = Alphabet::new("ΑΒΓΔΕΖΗΘΙΚΛΜ".chars()).unwrap();
+        let keyboard = keyboard_for(&KeyboardLayout::Dvorak.rows(&greek, &layouts), &greek);
+        assert_eq!(keyboard, ["Α Β Γ Δ Ε Ζ Η Θ Ι Κ", "⏎ Λ Μ ⌫"]);
+    }
+
+    #[test]
+    fn layouts_file_test() {
+        let latin = Alphabet::latin();
+        // one layout changed, the others as they were
+        let own = parse_layouts(
+            "azerty = [\"azertyuiop\", \"qsdfghjklm\", \"wxcvbn\"]\ndvorak = [\"abc\"]\n",
+            "layouts.toml",
+        )
+        .unwrap();
+        assert_eq!(
+            KeyboardLayout::Dvorak.rows(&latin, &own),
+            [vec!['A', 'B', 'C']]
+        );
+        assert_eq!(
+            KeyboardLayout::Azerty.rows(&latin, &own),
+            KeyboardLayout::Azerty.rows(&latin, &builtin_layouts())
+        );
+        assert_eq!(own["qwerty"], builtin_layouts()["qwerty"]);
+
+        assert!(parse_layouts("colemak = [\"QWFPG\"]\n", "layouts.toml").is_err());
+        assert!(parse_layouts("alphabetical = [\"ABC\"]\n", "layouts.toml").is_err());
+        assert!(parse_layouts("qwerty = [\"QW3RTY\"]\n", "layouts.toml").is_err());
+        assert!(parse_layouts("qwerty = []\n", "layouts.toml").is_err());
+    }
+
+    #[test]
+    fn layout_colors_test() {
+        let mut game = Wordle::new();
+        game.used_chars.insert('Q', TileState::Correct);
+        game.used_chars.insert('Z', TileState::Present);
+        game.used_chars.insert('M', TileState::Absent);
+        for layout in KeyboardLayout::ALL {
+            game.set_keyboard_layout(Some(layout));
+            let mut terminal = Terminal::new(TestBackend::new(60, 45)).unwrap();
+            terminal.draw(|frame| game.render_terminal(frame)).unwrap();
+            let area = game.keyboard_area.get().unwrap();
+            let buf = terminal.backend().buffer();
+            let boxes: Vec<(char, _)> = key_boxes(area, &game.keyboard).collect();
+            assert_eq!(boxes.len(), 28, "{}", layout.name());
+            for (key, spot) in boxes {
+                let Some(state) = game.used_chars.get(&key) else {
+                    continue;
+                };
+                let label = &buf[(spot.x + 1, spot.y)];
+                assert_eq!(label.symbol(), key.to_string());
+                assert_eq!(
+                    label.bg,
+                    game.theme.key(*state).bg,
+                    "{key} on {}",
+                    layout.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn layout_persists_test() {
+        let dir = temp_dir("keyboard_layout");
+        let mut game = Wordle::new();
+        game.storage = Storage::open(dir.clone());
+        assert_eq!(game.keyboard_name(), "qwerty");
+        game.next_keyboard_layout();
+        assert_eq!(game.keyboard_name(), "azerty");
+        assert!(game.keyboard[0].starts_with("A Z E"));
+        for _ in KeyboardLayout::ALL {
+            game.next_keyboard_layout();
+        }
+        assert_eq!(game.config.keyboard_layout, Some(KeyboardLayout::Azerty));
+        // and the next run starts with it
+        let relaunched = Wordle::with_config(Storage::open(dir.clone()), Setup::default()).unwrap();
+        assert_eq!(relaunched.keyboard, game.keyboard);
+        fs::remove_dir_all(&dir).unwrap();
+        game.storage = Storage::in_memory("test");
+
+        // with a language pack, its own layout is one of the stops
+        game.language = Some(
+            LanguagePack::from_layout(
+                "name = \"Español\"\nrows = [\"QWERTYUIOP\", \"ASDFGHJKLÑ\", \"ZXCVBNM\"]",
+            )
+            .unwrap(),
+        );
+        game.alphabet = game.language.as_ref().unwrap().alphabet.clone();
+        game.set_keyboard_layout(Some(KeyboardLayout::Alphabetical));
+        game.next_keyboard_layout();
+        assert_eq!(game.config.keyboard_layout, None);
+        assert_eq!(game.keyboard_name(), "Español");
+        assert_eq!(game.keyboard.len(), 3);
+        game.next_keyboard_layout();
+        assert_eq!(game.config.keyboard_layout, Some(KeyboardLayout::Qwerty));
+        assert_eq!(game.keyboard[3], "Ñ");
+    }
+}
@@ -0,0 +1,626 @@
+use anyhow::{Context, Result, bail};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::input::normalize;
+use super::messages::Level;
+use super::ui::InputState;
+use super::wordle::Wordle;
+
+/// Something a key can be bound to, set under its `name` in the `keys`
+/// object of `config.json`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    Submit,
+    NewGame,
+    Quit,
+    Pass,
+    Settings,
+    Suggestions,
+    Hint,
+    Reveal,
+    Solver,
+    LetterRounds,
+    HighContrast,
+    KeyboardLayout,
+    Remaining,
+    Help,
+}
+
+impl Action {
+    pub const ALL: [Action; 14] = [
+        Action::Submit,
+        Action::NewGame,
+        Action::Quit,
+        Action::Pass,
+        Action::Settings,
+        Action::Suggestions,
+        Action::Hint,
+        Action::Reveal,
+        Action::Solver,
+        Action::LetterRounds,
+        Action::HighContrast,
+        Action::KeyboardLayout,
+        Action::Remaining,
+        Action::Help,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Submit => "submit",
+            Action::NewGame => "new_game",
+            Action::Quit => "quit",
+            Action::Pass => "pass",
+            Action::Settings => "settings",
+            Action::Suggestions => "suggestions",
+            Action::Hint => "hint",
+            Action::LetterRounds => "letter_rounds",
+            Action::Reveal => "reveal",
+            Action::Solver => "solver",
+            Action::HighContrast => "high_contrast",
+            Action::KeyboardLayout => "keyboard_layout",
+            Action::Remaining => "remaining",
+            Action::Help => "help",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::Submit => "Submit",
+            Action::NewGame => "New game",
+            Action::Quit => "Quit",
+            Action::Pass => "Pass",
+            Action::Settings => "Settings",
+            Action::Suggestions => "Suggestions",
+            Action::Hint => "Hint",
+            Action::LetterRounds => "Rounds on keys",
+            Action::Reveal => "Reveal letter",
+            Action::Solver => "Solver panel",
+            Action::HighContrast => "High contrast",
+            Action::KeyboardLayout => "Keyboard layout",
+            Action::Remaining => "Answers left",
+            Action::Help => "Help",
+        }
+    }
+
+    fn default_binding(&self) -> KeyBinding {
+        let (code, modifiers) = match self {
+            Action::Submit => (KeyCode::Enter, KeyModifiers::NONE),
+            Action::NewGame => (KeyCode::Tab, KeyModifiers::NONE),
+            Action::Quit => (KeyCode::Esc, KeyModifiers::NONE),
+            Action::Pass => (KeyCode::Char('P'), KeyModifiers::CONTROL),
+            Action::Settings => (KeyCode::F(2), KeyModifiers::NONE),
+            Action::Suggestions => (KeyCode::F(3), KeyModifiers::NONE),
+            Action::Hint => (KeyCode::Char('!'), KeyModifiers::NONE),
+            Action::LetterRounds => (KeyCode::F(4), KeyModifiers::NONE),
+            Action::Reveal => (KeyCode::F(7), KeyModifiers::NONE),
+            Action::Solver => (KeyCode::F(8), KeyModifiers::NONE),
+            Action::HighContrast => (KeyCode::F(6), KeyModifiers::NONE),
+            Action::KeyboardLayout => (KeyCode::F(9), KeyModifiers::NONE),
+            Action::Remaining => (KeyCode::Char('#'), KeyModifiers::NONE),
+            Action::Help => (KeyCode::F(1), KeyModifiers::NONE),
+        };
+        KeyBinding { code, modifiers }
+    }
+
+    fn index(&self) -> usize {
+        Action::ALL
+            .iter()
+            .position(|action| action == self)
+            .unwrap_or(0)
+    }
+}
+
+/// A key with its modifiers, written like `Ctrl+Shift+A`, `F5` or
+/// `Shift+Tab` in `config.json` and on screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// The binding `key` would trigger. Letters are kept uppercase with
+    /// Shift spelled out, so Ctrl+A and Ctrl+Shift+A stay apart; Shift is
+    /// dropped from symbols and from Shift+Tab, which already imply it.
+    pub fn from_event(key: KeyEvent) -> Self {
+        if normalize(key).code == KeyCode::Enter {
+            return KeyBinding {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::NONE,
+            };
+        }
+        let mut modifiers =
+            key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match key.code {
+            KeyCode::Char(ch) if ch.is_ascii_alphabetic() => {
+                if ch.is_ascii_uppercase() {
+                    modifiers |= KeyModifiers::SHIFT;
+                }
+                KeyCode::Char(ch.to_ascii_uppercase())
+            }
+            code @ (KeyCode::Char(_) | KeyCode::BackTab) => {
+                modifiers -= KeyModifiers::SHIFT;
+                code
+            }
+            code => code,
+        };
+        KeyBinding { code, modifiers }
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let invalid = || format!("'{text}' is not a key");
+        // the key itself may be `+`, as in `Ctrl++`
+        let (prefix, key) = match text.strip_suffix("++") {
+            Some(prefix) => (Some(prefix), "+"),
+            None if text == "+" => (None, "+"),
+            None => match text.rsplit_once('+') {
+                Some((prefix, key)) => (Some(prefix), key),
+                None => (None, text),
+            },
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in prefix.into_iter().flat_map(|prefix| prefix.split('+')) {
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => bail!("unknown modifier '{modifier}' in '{text}'"),
+            };
+        }
+        let code = match key.to_ascii_lowercase().as_str() {
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers -= KeyModifiers::SHIFT;
+                KeyCode::BackTab
+            }
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "space" => KeyCode::Char(' '),
+            name if name.len() > 1 && name.starts_with('f') => {
+                let number: u8 = name[1..].parse().ok().with_context(invalid)?;
+                if !(1..=24).contains(&number) {
+                    bail!(invalid());
+                }
+                KeyCode::F(number)
+            }
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    // Shift is spelled out, `A` and `a` are the same key
+                    (Some(ch), None) if !ch.is_control() => KeyCode::Char(ch.to_ascii_lowercase()),
+                    _ => bail!(invalid()),
+                }
+            }
+        };
+        Ok(KeyBinding::from_event(KeyEvent::new(code, modifiers)))
+    }
+
+    /// Keys the board handles before any binding: plain letters type
+    /// guesses, Backspace deletes them, Delete, Ctrl+U and Ctrl+W clear the
+    /// row and `?` opens help. None of them can be taken by an action.
+    fn reserved_for(&self) -> Option<&'static str> {
+        let bare = self.modifiers - KeyModifiers::SHIFT == KeyModifiers::NONE;
+        match self.code {
+            KeyCode::Char(ch) if bare && ch.is_ascii_alphabetic() => Some("typing guesses"),
+            KeyCode::Backspace => Some("deleting letters"),
+            KeyCode::Delete => Some("clearing the guess"),
+            KeyCode::Char('U' | 'W') if self.modifiers == KeyModifiers::CONTROL => {
+                Some("clearing the guess")
+            }
+            KeyCode::Char('?') => Some("help"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Enter => f.write_str("Enter"),
+            KeyCode::Esc => f.write_str("Esc"),
+            KeyCode::Tab => f.write_str("Tab"),
+            KeyCode::BackTab => f.write_str("Shift+Tab"),
+            KeyCode::Backspace => f.write_str("Backspace"),
+            KeyCode::Delete => f.write_str("Delete"),
+            KeyCode::Insert => f.write_str("Insert"),
+            KeyCode::Home => f.write_str("Home"),
+            KeyCode::End => f.write_str("End"),
+            KeyCode::PageUp => f.write_str("PageUp"),
+            KeyCode::PageDown => f.write_str("PageDown"),
+            KeyCode::Up => f.write_str("Up"),
+            KeyCode::Down => f.write_str("Down"),
+            KeyCode::Left => f.write_str("Left"),
+            KeyCode::Right => f.write_str("Right"),
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(ch) => write!(f, "{ch}"),
+            KeyCode::F(number) => write!(f, "F{number}"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+/// The key of every action, with its label as shown in legends.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<KeyBinding>,
+    /// e.g. `<Ctrl+P>`, kept so legends don't format them every frame
+    labels: Vec<String>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings: Vec<KeyBinding> = Action::ALL
+            .iter()
+            .map(|action| action.default_binding())
+            .collect();
+        let labels = bindings
+            .iter()
+            .map(|binding| format!("<{binding}>"))
+            .collect();
+        Keymap { bindings, labels }
+    }
+}
+
+impl Keymap {
+    /// The defaults with the `keys` of `config.json` applied. Entries that
+    /// don't parse or clash are skipped and reported.
+    pub fn from_config(keys: &BTreeMap<String, String>) -> (Self, Vec<String>) {
+        let mut keymap = Keymap::default();
+        let mut errors = Vec::new();
+        for (name, text) in keys {
+            let Some(action) = Action::ALL.iter().find(|action| action.name() == name) else {
+                errors.push(format!("unknown action '{name}' in keys"));
+                continue;
+            };
+            if let Err(err) =
+                KeyBinding::parse(text).and_then(|binding| keymap.rebind(*action, binding))
+            {
+                errors.push(format!("{err:#}, keeping {}", keymap.binding(*action)));
+            }
+        }
+        (keymap, errors)
+    }
+
+    pub fn binding(&self, action: Action) -> KeyBinding {
+        self.bindings[action.index()]
+    }
+
+    pub fn label(&self, action: Action) -> &str {
+        &self.labels[action.index()]
+    }
+
+    /// The action bound to `binding`, if any.
+    pub fn action(&self, binding: KeyBinding) -> Option<Action> {
+        Action::ALL
+            .iter()
+            .copied()
+            .find(|action| self.binding(*action) == binding)
+    }
+
+    /// Binds `action` to `binding` unless another action has it or it's
+    /// needed for typing.
+    pub fn rebind(&mut self, action: Action, binding: KeyBinding) -> Result<()> {
+        if let Some(use_) = binding.reserved_for() {
+            bail!("{binding} is for {use_}");
+        }
+        if let Some(other) = self.action(binding)
+            && other != action
+        {
+            bail!("{binding} is already bound to {}", other.label());
+        }
+        self.bindings[action.index()] = binding;
+        self.labels[action.index()] = format!("<{binding}>");
+        Ok(())
+    }
+}
+
+/// Cursor of the key bindings page, and whether the next key press is
+/// being captured as the selected action's new binding.
+#[derive(Debug, Default)]
+pub struct KeysPage {
+    pub cursor: usize,
+    pub capturing: bool,
+}
+
+impl Wordle {
+    /// Runs the action bound to `binding`; `None` when nothing is bound to
+    /// it or the action doesn't apply right now.
+    pub fn on_action(&mut self, binding: KeyBinding) -> Option<InputState> {
+        match self.keymap.action(binding)? {
+            Action::Quit if self.config.quit_requires_modifier && binding.modifiers.is_empty() => {
+                self.show_status(String::from("press Ctrl+Q to quit"));
+            }
+            Action::Quit => return Some(InputState::Quit),
+            Action::Pass if self.is_accepting_guesses() => return Some(InputState::Pass),
+            Action::NewGame if self.timed.is_some() => self.next_timed_word(),
+            Action::NewGame if self.hot_seat.is_some() => self.next_hot_seat_word(),
+            Action::NewGame if self.race.is_some() => self.next_race_word(),
+            Action::NewGame if self.restart_needs_confirmation() => self.confirm_restart = true,
+            Action::NewGame => self.game_restart(),
+            Action::Settings => self.open_settings(),
+            Action::Suggestions if self.is_accepting_guesses() => self.open_suggestions(),
+            Action::Hint if self.is_accepting_guesses() => self.position_hint(),
+            Action::Reveal if self.is_accepting_guesses() => self.reveal_letter(),
+            Action::Solver => self.toggle_solver(),
+            Action::LetterRounds => self.show_letter_rounds = !self.show_letter_rounds,
+            Action::HighContrast => self.toggle_high_contrast(),
+            Action::KeyboardLayout => self.next_keyboard_layout(),
+            Action::Remaining => self.toggle_remaining(),
+            Action::Help => self.show_help = true,
+            Action::Submit if self.is_accepting_guesses() => return Some(InputState::Submit),
+            _ => return None,
+        }
+        Some(InputState::None)
+    }
+
+    /// Keys on the bindings page: Enter starts capturing a new key for the
+    /// selected action.
+    pub fn on_keys_key(&mut self, key: KeyEvent) -> InputState {
+        let Some(page) = &mut self.keys_page else {
+            return InputState::None;
+        };
+        match key.code {
+            KeyCode::Esc => self.keys_page = None,
+            KeyCode::Up => page.cursor = page.cursor.saturating_sub(1),
+            KeyCode::Down => page.cursor = (page.cursor + 1).min(Action::ALL.len() - 1),
+            KeyCode::Enter => {
+                page.capturing = true;
+                self.messages.clear(Level::Error);
+            }
+            _ => {}
+        }
+        InputState::None
+    }
+
+    /// Takes the one key press after Enter on the bindings page as the new
+    /// binding and saves it. A bare Esc cancels and keeps the old binding.
+    pub fn capture_key(&mut self, key: KeyEvent) -> InputState {
+        let Some(page) = &mut self.keys_page else {
+            return InputState::None;
+        };
+        page.capturing = false;
+        let action = Action::ALL[page.cursor];
+        if key.code == KeyCode::Esc && key.modifiers == KeyModifiers::NONE {
+            self.messages.clear(Level::Error);
+            self.show_status(format!(
+                "{}: kept {}",
+                action.label(),
+                self.keymap.binding(action)
+            ));
+            return InputState::None;
+        }
+        let binding = KeyBinding::from_event(key);
+        if let Err(err) = self.keymap.rebind(action, binding) {
+            self.show_error(format!("{err:#}"));
+            return InputState::None;
+        }
+        self.config
+            .keys
+            .insert(action.name().to_string(), binding.to_string());
+        if let Err(err) = self.config.save(&self.storage) {
+            self.show_error(format!("failed to save config: {err}"));
+            return InputState::None;
+        }
+        self.messages.clear(Level::Error);
+        self.show_status(format!("{}: {binding}", action.label()));
+        InputState::None
+    }
+
+    pub fn render_keys_page(&self, page: &KeysPage, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let mut lines = vec![Line::from("Key bindings").bold(), Line::from("")];
+        for (index, action) in Action::ALL.iter().enumerate() {
+            let cursor = if index == page.cursor { "> " } else { "  " };
+            let mut line = Line::from(vec![
+                Span::raw(cursor),
+                Span::raw(format!("{:<16}", action.label())),
+                Span::raw(self.keymap.label(*action)).blue().bold(),
+            ]);
+            if index == page.cursor && page.capturing {
+                line.push_span(Span::raw("  press the new key, Esc to cancel").fg(Color::Yellow));
+            }
+            lines.push(line);
+        }
+        if let Some(error) = self.messages.error() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(error).fg(Color::Red));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::raw("Rebind "),
+            "<Enter>".blue().bold(),
+            Span::raw(" Back "),
+            "<Esc>".blue().bold(),
+        ]));
+
+        Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .title("Keys")
+                    .border_type(BorderType::Rounded),
+            )
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::storage::Storage;
+    use crate::utils::testing::temp_dir;
+    use std::fs;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn round_trip_test() {
+        for text in [
+            "Enter",
+            "Esc",
+            "F5",
+            "F12",
+            "Ctrl+Shift+A",
+            "Ctrl+A",
+            "Alt+Shift+Tab",
+            "Shift+Tab",
+            "Ctrl++",
+            "+",
+            "Space",
+            "!",
+            "Ctrl+Alt+PageDown",
+        ] {
+            assert_eq!(KeyBinding::parse(text).unwrap().to_string(), text);
+        }
+        // names are case insensitive, output is canonical
+        assert_eq!(
+            KeyBinding::parse("ctrl+shift+f1").unwrap().to_string(),
+            "Ctrl+Shift+F1"
+        );
+        assert_eq!(KeyBinding::parse("ctrl+a").unwrap().to_string(), "Ctrl+A");
+        for bad in ["", "F0", "F25", "Hyper+A", "Ctrl+", "Enterr"] {
+            assert!(KeyBinding::parse(bad).is_err(), "{bad}");
+        }
+
+        // as terminals report them
+        let shown = |code, modifiers| KeyBinding::from_event(key(code, modifiers)).to_string();
+        assert_eq!(shown(KeyCode::BackTab, KeyModifiers::SHIFT), "Shift+Tab");
+        assert_eq!(
+            shown(
+                KeyCode::Char('A'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            ),
+            "Ctrl+Shift+A"
+        );
+        assert_eq!(shown(KeyCode::Char('a'), KeyModifiers::CONTROL), "Ctrl+A");
+        assert_eq!(shown(KeyCode::Char('!'), KeyModifiers::SHIFT), "!");
+        assert_eq!(shown(KeyCode::F(7), KeyModifiers::NONE), "F7");
+        assert_eq!(shown(KeyCode::Char('\r'), KeyModifiers::NONE), "Enter");
+    }
+
+    #[test]
+    fn conflicts_test() {
+        let mut keymap = Keymap::default();
+        let parse = |text| KeyBinding::parse(text).unwrap();
+        let err = keymap.rebind(Action::NewGame, parse("Ctrl+P")).unwrap_err();
+        assert_eq!(err.to_string(), "Ctrl+P is already bound to Pass");
+        assert!(keymap.rebind(Action::NewGame, parse("X")).is_err());
+        assert!(keymap.rebind(Action::NewGame, parse("Shift+X")).is_err());
+        assert!(keymap.rebind(Action::NewGame, parse("Backspace")).is_err());
+        assert!(
+            keymap
+                .rebind(Action::NewGame, parse("Ctrl+Backspace"))
+                .is_err()
+        );
+        assert!(keymap.rebind(Action::NewGame, parse("Delete")).is_err());
+        assert!(keymap.rebind(Action::NewGame, parse("?")).is_err());
+        let err = keymap.rebind(Action::NewGame, parse("Ctrl+U")).unwrap_err();
+        assert_eq!(err.to_string(), "Ctrl+U is for clearing the guess");
+        assert!(keymap.rebind(Action::NewGame, parse("Ctrl+W")).is_err());
+        assert_eq!(keymap.binding(Action::NewGame), parse("Tab"));
+
+        // rebinding to its own key is fine, and frees the old one
+        keymap.rebind(Action::NewGame, parse("Tab")).unwrap();
+        keymap
+            .rebind(Action::NewGame, parse("Ctrl+Shift+N"))
+            .unwrap();
+        assert_eq!(keymap.label(Action::NewGame), "<Ctrl+Shift+N>");
+        keymap.rebind(Action::Pass, parse("Tab")).unwrap();
+
+        let keys = BTreeMap::from([
+            (String::from("new_game"), String::from("F5")),
+            (String::from("pass"), String::from("F5")),
+            (String::from("fly"), String::from("F6")),
+        ]);
+        let (keymap, errors) = Keymap::from_config(&keys);
+        assert_eq!(keymap.binding(Action::NewGame), parse("F5"));
+        assert_eq!(keymap.binding(Action::Pass), parse("Ctrl+P"));
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn capture_test() {
+        let dir = temp_dir("keys_capture");
+        let mut game = Wordle::new();
+        game.storage = Storage::open(dir.clone());
+        let press = |game: &mut Wordle, code, modifiers| game.on_key(key(code, modifiers));
+
+        press(&mut game, KeyCode::F(2), KeyModifiers::NONE);
+        press(&mut game, KeyCode::Char('b'), KeyModifiers::NONE);
+        press(&mut game, KeyCode::Down, KeyModifiers::NONE);
+        press(&mut game, KeyCode::Enter, KeyModifiers::NONE);
+        // taken, whatever it is, even a key that would otherwise close the page
+        press(&mut game, KeyCode::F(5), KeyModifiers::NONE);
+        assert_eq!(game.keymap.label(Action::NewGame), "<F5>");
+        assert_eq!(game.messages.status(), Some("New game: F5"));
+        assert!(game.keys_page.is_some());
+
+        // a clash is refused and the page stays open
+        press(&mut game, KeyCode::Enter, KeyModifiers::NONE);
+        press(&mut game, KeyCode::Char('p'), KeyModifiers::CONTROL);
+        assert_eq!(
+            game.messages.error(),
+            Some("Ctrl+P is already bound to Pass")
+        );
+        assert_eq!(game.keymap.label(Action::NewGame), "<F5>");
+        assert!(game.keys_page.is_some());
+
+        // Esc cancels the capture instead of being taken
+        press(&mut game, KeyCode::Enter, KeyModifiers::NONE);
+        press(&mut game, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(game.messages.error(), None);
+        assert_eq!(game.messages.status(), Some("New game: kept F5"));
+        assert_eq!(game.keymap.label(Action::NewGame), "<F5>");
+        assert!(game.keys_page.is_some());
+        press(&mut game, KeyCode::Esc, KeyModifiers::NONE);
+        press(&mut game, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!game.show_settings);
+
+        game.answer = String::from("EPOCH");
+        press(&mut game, KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(game.answer, "EPOCH");
+        press(&mut game, KeyCode::F(5), KeyModifiers::NONE);
+        assert_ne!(game.answer, "EPOCH");
+
+        let mut relaunched = Wordle::new();
+        relaunched.storage = Storage::open(dir.clone());
+        relaunched.config = crate::utils::config::Config::load(&relaunched.storage).unwrap();
+        assert_eq!(relaunched.config.keys["new_game"], "F5");
+        let (keymap, errors) = Keymap::from_config(&relaunched.config.keys);
+        assert!(errors.is_empty());
+        assert_eq!(keymap.binding(Action::NewGame).code, KeyCode::F(5));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::{BTreeSet, HashMap};
+use wordle::engine::{TileState, Word};
+
+use super::explain::{copies, ordinal};
+use super::wordle::Wordle;
+
+/// What the guesses so far reveal about one letter of the answer.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LetterKnowledge {
+    /// the answer has at least this many copies
+    pub min_count: usize,
+    /// positions known to hold the letter
+    pub placed: BTreeSet<usize>,
+}
+
+impl LetterKnowledge {
+    /// Some copies are placed, but the answer is known to have more.
+    pub fn has_unplaced_copies(&self) -> bool {
+        !self.placed.is_empty() && self.min_count > self.placed.len()
+    }
+}
+
+/// Per-letter knowledge from scored guesses.
+///
+/// Under the duplicate-letter rules a guess only marks as many copies
+/// Correct or Present as the answer holds, so each guess proves at least
+/// that many copies exist; the minimum count is the largest such number
+/// seen in a single guess. Copies from different guesses can't be added
+/// up, since they may be the same copy. Passed rows are skipped.
+pub fn letter_knowledge(history: &[Word]) -> HashMap<char, LetterKnowledge> {
+    let mut knowledge: HashMap<char, LetterKnowledge> = HashMap::new();
+    for guess in history.iter().filter(|guess| !guess.is_pass()) {
+        let mut found: HashMap<char, usize> = HashMap::new();
+        for (position, tile) in guess.letters.iter().enumerate() {
+            let entry = knowledge.entry(tile.letter).or_default();
+            match tile.state {
+                TileState::Correct => {
+                    entry.placed.insert(position);
+                    *found.entry(tile.letter).or_insert(0) += 1;
+                }
+                TileState::Present => *found.entry(tile.letter).or_insert(0) += 1,
+                TileState::Absent | TileState::Unused => {}
+            }
+        }
+        for (letter, count) in found {
+            let entry = knowledge.entry(letter).or_default();
+            entry.min_count = entry.min_count.max(count);
+        }
+    }
+    knowledge
+}
+
+/// Every hard-mode rule `guess` breaks: letters found Correct must stay in
+/// place, and letters found at all must be used again, as many copies as
+/// are known to exist. Placement rules come first, in board order.
+pub fn hard_mode_violations(guess: &Word, history: &[Word]) -> Vec<String> {
+    let knowledge = letter_knowledge(history);
+    let mut letters: Vec<(&char, &LetterKnowledge)> = knowledge.iter().collect();
+    letters.sort_unstable_by_key(|(letter, _)| **letter);
+
+    let mut placed: Vec<(usize, char)> = letters
+        .iter()
+        .flat_map(|(letter, known)| known.placed.iter().map(|position| (*position, **letter)))
+        .collect();
+    placed.sort_unstable();
+    let mut violations: Vec<String> = placed
+        .into_iter()
+        .filter(|(position, letter)| {
+            guess.letters.get(*position).map(|tile| tile.letter) != Some(*letter)
+        })
+        .map(|(position, letter)| format!("{} letter must be {letter}", ordinal(position + 1)))
+        .collect();
+
+    for (letter, known) in letters {
+        let used = guess
+            .letters
+            .iter()
+            .filter(|tile| tile.letter == *letter)
+            .count();
+        // when every known copy is placed, the rules above already say so
+        if used < known.min_count && known.min_count > known.placed.len() {
+            violations.push(format!(
+                "guess must contain {}",
+                copies(known.min_count, *letter)
+            ));
+        }
+    }
+    violations
+}
+
+/// How much of the alphabet the guesses have covered.
+#[derive(Debug, PartialEq)]
+pub struct AlphabetProgress {
+    /// letters played, whatever color they came back
+    pub known: usize,
+    /// letters in the alphabet being played
+    pub total: usize,
+    /// every letter of the answer has been found, though maybe not placed
+    pub answer_found: bool,
+}
+
+pub fn alphabet_progress(
+    used_chars: &HashMap<char, TileState>,
+    alphabet: &[char],
+    answer: &str,
+) -> AlphabetProgress {
+    let is_known = |letter: &char| {
+        used_chars
+            .get(letter)
+            .is_some_and(|state| *state != TileState::Unused)
+    };
+    AlphabetProgress {
+        known: alphabet.iter().filter(|letter| is_known(letter)).count(),
+        total: alphabet.len(),
+        answer_found: answer.chars().all(|letter| is_known(&letter)),
+    }
+}
+
+impl Wordle {
+    pub fn alphabet_progress(&self) -> AlphabetProgress {
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+        alphabet_progress(&self.used_chars, &alphabet, &self.answer)
+    }
+
+    /// Whether `letter` is green somewhere but still has copies to locate.
+    pub fn has_unplaced_copies(&self, letter: char) -> bool {
+        letter_knowledge(&self.guess_history)
+            .get(&letter)
+            .is_some_and(LetterKnowledge::has_unplaced_copies)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wordle::engine::score;
+
+    fn history(answer: &str, guesses: &[&str]) -> Vec<Word> {
+        guesses
+            .iter()
+            .map(|guess| {
+                let mut word = Word::from(guess).unwrap();
+                score(answer, &mut word).unwrap();
+                word
+            })
+            .collect()
+    }
+
+    fn e(answer: &str, guesses: &[&str]) -> LetterKnowledge {
+        letter_knowledge(&history(answer, guesses))
+            .remove(&'E')
+            .unwrap()
+    }
+
+    fn violations(answer: &str, guesses: &[&str], guess: &str) -> Vec<String> {
+        hard_mode_violations(&Word::from(guess).unwrap(), &history(answer, guesses))
+    }
+
+    #[test]
+    fn hard_mode_test() {
+        // CRANE against EPOCH: C and E are yellow
+        assert!(violations("EPOCH", &["CRANE"], "ECHOS").is_empty());
+        assert_eq!(
+            violations("EPOCH", &["CRANE"], "CHOSE").len(),
+            0,
+            "yellow letters may move anywhere"
+        );
+        assert_eq!(
+            violations("EPOCH", &["CRANE"], "SHOUT"),
+            vec!["guess must contain 1 C", "guess must contain 1 E"]
+        );
+
+        // several rules at once: SPOKE places P and O and finds E again
+        assert_eq!(
+            violations("EPOCH", &["CRANE", "SPOKE"], "ALOHA"),
+            vec![
+                "2nd letter must be P",
+                "guess must contain 1 C",
+                "guess must contain 1 E"
+            ]
+        );
+        assert_eq!(
+            violations("EPOCH", &["CRANE", "EPICS"], "ALOHA"),
+            vec![
+                "1st letter must be E",
+                "2nd letter must be P",
+                "4th letter must be C"
+            ]
+        );
+        assert!(violations("EPOCH", &["CRANE", "EPICS"], "EPOCH").is_empty());
+    }
+
+    #[test]
+    fn hard_mode_duplicates_test() {
+        // ELOPE against GEESE proves two Es, one of them in the 5th spot
+        assert_eq!(
+            violations("GEESE", &["ELOPE"], "SHAPE"),
+            vec!["guess must contain 2 Es"]
+        );
+        assert!(violations("GEESE", &["ELOPE"], "EERIE").is_empty());
+        assert_eq!(
+            violations("GEESE", &["ELOPE"], "EDGES"),
+            vec!["5th letter must be E"]
+        );
+    }
+
+    #[test]
+    fn alphabet_progress_test() {
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+        let mut used: HashMap<char, TileState> =
+            alphabet.iter().map(|ch| (*ch, TileState::Unused)).collect();
+        let progress = alphabet_progress(&used, &alphabet, "EPOCH");
+        assert_eq!((progress.known, progress.total), (0, 26));
+        assert!(!progress.answer_found);
+
+        for word in history("EPOCH", &["CRANE", "SHOUT"]) {
+            for tile in word.letters {
+                used.insert(tile.letter, tile.state);
+            }
+        }
+        let progress = alphabet_progress(&used, &alphabet, "EPOCH");
+        assert_eq!(progress.known, 10);
+        // P hasn't been played yet
+        assert!(!progress.answer_found);
+        used.insert('P', TileState::Present);
+        assert!(alphabet_progress(&used, &alphabet, "EPOCH").answer_found);
+
+        // a smaller alphabet changes the denominator only
+        let progress = alphabet_progress(&used, &['A', 'B', 'C'], "EPOCH");
+        assert_eq!((progress.known, progress.total), (2, 3));
+    }
+
+    #[test]
+    fn one_copy_guessed_test() {
+        // a single E can only prove one copy, even though GEESE has three
+        let known = e("GEESE", &["CRANE"]);
+        assert_eq!(known.min_count, 1);
+        assert_eq!(known.placed, BTreeSet::from([4]));
+        assert!(!known.has_unplaced_copies());
+    }
+
+    #[test]
+    fn two_copies_guessed_test() {
+        // E4 green, E0 yellow: a second copy exists somewhere else
+        let known = e("GEESE", &["ELOPE"]);
+        assert_eq!(known.min_count, 2);
+        assert_eq!(known.placed, BTreeSet::from([4]));
+        assert!(known.has_unplaced_copies());
+
+        // both copies green
+        assert!(!e("GEESE", &["THESE"]).has_unplaced_copies());
+    }
+
+    #[test]
+    fn three_copies_guessed_test() {
+        let known = e("GEESE", &["TEPEE"]);
+        assert_eq!(known.min_count, 3);
+        assert_eq!(known.placed, BTreeSet::from([1, 4]));
+        assert!(known.has_unplaced_copies());
+
+        // the third copy is located by a later guess
+        let known = e("GEESE", &["TEPEE", "GEESE"]);
+        assert_eq!(known.placed, BTreeSet::from([1, 2, 4]));
+        assert!(!known.has_unplaced_copies());
+    }
+
+    #[test]
+    fn counts_not_summed_across_guesses_test() {
+        // each guess shows one E; together they still only prove one copy
+        let known = e("EPOCH", &["CRANE", "EPOCH"]);
+        assert_eq!(known.min_count, 1);
+        assert!(!known.has_unplaced_copies());
+
+        // extra copies beyond the answer's come back absent
+        let known = e("CRANE", &["EERIE"]);
+        assert_eq!(known.min_count, 1);
+        assert!(!known.has_unplaced_copies());
+    }
+}
@@ -0,0 +1,205 @@
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use wordle::engine::Alphabet;
+use wordle::engine::internal::{parse_list_in, read_list};
+
+/// The words of a language pack, one per line like any word list.
+pub const PACK_WORDS: &str = "words.txt";
+/// The keyboard of a language pack.
+pub const PACK_LAYOUT: &str = "layout.toml";
+
+/// `layout.toml` as written, e.g.
+///
+/// ```toml
+/// name = "Español"
+/// rows = ["QWERTYUIOP", "ASDFGHJKLÑ", "ZXCVBNM"]
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LayoutFile {
+    name: String,
+    rows: Vec<String>,
+}
+
+/// A language to play in, from a directory with `words.txt` and
+/// `layout.toml`. The keyboard's letters are the language's alphabet, so
+/// every word has to be typeable on it.
+#[derive(Debug, Clone)]
+pub struct LanguagePack {
+    pub name: String,
+    pub alphabet: Alphabet,
+    /// the keyboard's letters, row by row from the top
+    pub rows: Vec<Vec<char>>,
+    pub words: HashSet<String>,
+}
+
+impl LanguagePack {
+    pub fn load(dir: &Path) -> Result<Self> {
+        let layout_path = dir.join(PACK_LAYOUT);
+        let layout = fs::read_to_string(&layout_path)
+            .with_context(|| format!("failed to read {}", layout_path.display()))?;
+        let mut pack = LanguagePack::from_layout(&layout)
+            .with_context(|| format!("invalid keyboard in {}", layout_path.display()))?;
+        let words_path = dir.join(PACK_WORDS);
+        pack.words = parse_list_in(
+            &read_list(&words_path)?,
+            &words_path.display().to_string(),
+            &pack.alphabet,
+        )?
+        .words;
+        Ok(pack)
+    }
+
+    /// The pack's keyboard from `layout.toml`'s contents, with no words yet.
+    pub fn from_layout(layout: &str) -> Result<Self> {
+        let layout: LayoutFile = toml::from_str(layout)?;
+        let rows: Vec<Vec<char>> = layout
+            .rows
+            .iter()
+            .map(|row| {
+                row.chars()
+                    .filter(|ch| !ch.is_whitespace())
+                    .map(Alphabet::upper)
+                    .collect()
+            })
+            .collect();
+        let keys = rows.iter().map(Vec::len).sum::<usize>();
+        if keys == 0 {
+            bail!("the keyboard has no keys");
+        }
+        let alphabet = Alphabet::new(rows.iter().flatten().copied())?;
+        if alphabet.letters().len() != keys {
+            bail!("a letter is on the keyboard more than once");
+        }
+        Ok(LanguagePack {
+            name: layout.name,
+            alphabet,
+            rows,
+            words: HashSet::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::storage::Storage;
+    use crate::utils::testing::temp_dir;
+    use crate::utils::wordle::{Setup, Wordle};
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use wordle::engine::TileState;
+
+    const SPANISH: &str = r#"
+name = "Español"
+rows = ["QWERTYUIOP", "ASDFGHJKLÑ", "ZXCVBNM"]
+"#;
+
+    #[test]
+    fn layout_test() {
+        let pack = LanguagePack::from_layout(SPANISH).unwrap();
+        assert_eq!(pack.name, "Español");
+        assert_eq!(pack.rows[1].last(), Some(&'Ñ'));
+        assert_eq!(pack.alphabet.letters().len(), 27);
+        // lowercase keys are fine, the same key twice isn't
+        assert!(LanguagePack::from_layout("name = \"x\"\nrows = [\"abc\"]").is_ok());
+        assert!(LanguagePack::from_layout("name = \"x\"\nrows = [\"ABA\"]").is_err());
+        assert!(LanguagePack::from_layout("name = \"x\"\nrows = [\"AB1\"]").is_err());
+        assert!(LanguagePack::from_layout("name = \"x\"\nrows = []").is_err());
+        assert!(LanguagePack::from_layout("rows = [\"ABC\"]").is_err());
+    }
+
+    #[test]
+    fn spanish_game_test() {
+        let dir = temp_dir("language_pack");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(PACK_LAYOUT), SPANISH).unwrap();
+        fs::write(dir.join(PACK_WORDS), "niños\naños\nñoños\nsueño\n").unwrap();
+        let setup = Setup {
+            language: Some(dir.clone()),
+            ..Setup::default()
+        };
+        let mut game = Wordle::with_config(Storage::in_memory("test"), setup).unwrap();
+        assert_eq!(game.word_len, 5);
+        assert!(game.used_chars.contains_key(&'Ñ'));
+        game.answer = String::from("NIÑOS");
+
+        for ch in "ñoños".chars() {
+            game.on_key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        assert_eq!(game.current_guess, "ÑOÑOS");
+        game.submit_guess();
+        assert_eq!(game.guess_history.len(), 1);
+        assert_eq!(game.used_chars[&'Ñ'], TileState::Correct);
+        assert_eq!(game.used_chars[&'O'], TileState::Correct);
+
+        // the keyboard has Ñ on it, where clicks find it too
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(60, 45)).unwrap();
+        terminal.draw(|frame| game.render_terminal(frame)).unwrap();
+        let keyboard = game.keyboard_area.get().unwrap();
+        let buf = terminal.backend().buffer();
+        assert!(
+            keyboard
+                .positions()
+                .any(|spot| buf[spot].symbol() == "Ñ" && buf[spot].bg == game.theme.correct.bg)
+        );
+
+        // a word the keyboard can't type is left out, and a list of mostly
+        // those rejects the pack
+        fs::write(dir.join(PACK_WORDS), "niños\nbühne\n").unwrap();
+        let pack = LanguagePack::load(&dir).unwrap();
+        assert_eq!(pack.words, HashSet::from([String::from("NIÑOS")]));
+        fs::write(dir.join(PACK_WORDS), "niños\nbühne\nmüsli\n").unwrap();
+        assert!(LanguagePack::load(&dir).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn long_pack_word_test() {
+        use crate::utils::bot::{SolverStrategy, play_headless};
+        use crate::utils::protocol::respond;
+        use crate::utils::solver::{feedback_pattern, row_pattern};
+        use wordle::engine::{Word, score};
+
+        let dir = temp_dir("long_pack_word");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(PACK_LAYOUT), SPANISH).unwrap();
+        let words = "enseñado\nmañanero\npequeños\nmontañas\ncampanas\n";
+        fs::write(dir.join(PACK_WORDS), words).unwrap();
+        let setup = Setup {
+            language: Some(dir.clone()),
+            length: Some(8),
+            ..Setup::default()
+        };
+        let mut game = Wordle::with_config(Storage::in_memory("test"), setup).unwrap();
+        assert_eq!(game.word_len, 8);
+
+        // Ñ is one tile to the solver as it is on the board
+        for (guess, answer) in [
+            ("MAÑANERO", "ENSEÑADO"),
+            ("MONTAÑAS", "CAMPANAS"),
+            ("PEQUEÑOS", "MONTAÑAS"),
+        ] {
+            let mut row = Word::from(guess).unwrap();
+            score(answer, &mut row).unwrap();
+            assert_eq!(feedback_pattern(guess, answer), row_pattern(&row));
+        }
+
+        let response = respond(&mut game, "{\"guess\": \"mañanero\"}".as_bytes(), false);
+        assert_eq!(response["type"], "result", "{response}");
+        game.reset_board();
+
+        let mut strategy = SolverStrategy::new(game.alphabet.clone());
+        let solved = play_headless(&mut game, "ENSEÑADO", &mut strategy);
+        assert!(solved.is_some());
+        // the game's record reads back, row for row
+        assert_eq!(game.record().rows().unwrap(), game.guess_history);
+
+        game.on_key(KeyEvent::new(KeyCode::Char('ñ'), KeyModifiers::NONE));
+        assert_eq!(game.key_counts.letter('Ñ'), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
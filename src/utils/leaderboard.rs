@@ -0,0 +1,313 @@
+use anyhow::{Result, bail};
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+use super::daily::DailySave;
+use super::record::{GameRecord, HISTORY_PREFIX};
+use super::stats::Stats;
+use super::storage::Storage;
+
+/// What the leaderboard is sorted by.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Metric {
+    WinRate,
+    AverageGuesses,
+    BestStreak,
+    DailyRate,
+}
+
+impl Metric {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "win-rate" => Ok(Metric::WinRate),
+            "avg-guesses" => Ok(Metric::AverageGuesses),
+            "streak" => Ok(Metric::BestStreak),
+            "daily" => Ok(Metric::DailyRate),
+            _ => bail!("unknown metric '{name}', expected win-rate, avg-guesses, streak or daily"),
+        }
+    }
+}
+
+/// One profile's standing. Rates are `None` for profiles that haven't played
+/// the kind of game they measure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileRow {
+    pub name: String,
+    pub games_played: u32,
+    pub win_rate: Option<f64>,
+    pub average_guesses: Option<f64>,
+    pub best_streak: u32,
+    pub daily_rate: Option<f64>,
+}
+
+impl ProfileRow {
+    /// Reads a profile's stats, history (including archived logs) and daily
+    /// saves without writing anything. Corrupt history lines are skipped.
+    pub fn load(name: &str, storage: &Storage) -> Result<Self> {
+        let stats = Stats::load(storage)?;
+
+        let mut solved = Vec::new();
+        for log in storage.list(HISTORY_PREFIX)? {
+            let history = storage.load(&log)?.unwrap_or_default();
+            solved.extend(
+                history
+                    .lines()
+                    .filter_map(|line| serde_json::from_str::<GameRecord>(line).ok())
+                    .filter(|record| record.solved)
+                    .map(|record| record.guesses.len()),
+            );
+        }
+
+        let mut dailies = 0;
+        let mut dailies_solved = 0;
+        for slot in storage.list("daily-")? {
+            let Some(contents) = storage.load(&slot)? else {
+                continue;
+            };
+            let Ok(save) = serde_json::from_str::<DailySave>(&contents) else {
+                continue;
+            };
+            dailies += 1;
+            if save.guesses.contains(&save.answer) {
+                dailies_solved += 1;
+            }
+        }
+
+        Ok(ProfileRow {
+            name: name.to_string(),
+            games_played: stats.games_played,
+            win_rate: ratio(stats.games_won as usize, stats.games_played as usize),
+            average_guesses: ratio(solved.iter().sum(), solved.len()),
+            best_streak: stats.max_streak,
+            daily_rate: ratio(dailies_solved, dailies),
+        })
+    }
+
+    fn value(&self, metric: Metric) -> Option<f64> {
+        match metric {
+            Metric::WinRate => self.win_rate,
+            Metric::AverageGuesses => self.average_guesses,
+            Metric::BestStreak => Some(self.best_streak as f64),
+            Metric::DailyRate => self.daily_rate,
+        }
+    }
+}
+
+fn ratio(part: usize, whole: usize) -> Option<f64> {
+    (whole > 0).then(|| part as f64 / whole as f64)
+}
+
+/// Best first by `metric` (fewest guesses for average guesses). Profiles
+/// without a value go last, then ties break on games played and name.
+pub fn sort_rows(rows: &mut [ProfileRow], metric: Metric) {
+    rows.sort_by(|a, b| {
+        let by_metric = match (a.value(metric), b.value(metric)) {
+            (Some(a), Some(b)) if metric == Metric::AverageGuesses => a.total_cmp(&b),
+            (Some(a), Some(b)) => b.total_cmp(&a),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        by_metric
+            .then(b.games_played.cmp(&a.games_played))
+            .then(a.name.cmp(&b.name))
+    });
+}
+
+struct Column {
+    title: &'static str,
+    width: usize,
+    metric: Option<Metric>,
+    cell: fn(&ProfileRow) -> String,
+}
+
+fn percent(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |value| format!("{:.0}%", value * 100.0))
+}
+
+/// Columns in the order they're shown. When space runs out they're dropped
+/// from the back, except the one being sorted by.
+const COLUMNS: [Column; 5] = [
+    Column {
+        title: "win",
+        width: 5,
+        metric: Some(Metric::WinRate),
+        cell: |row| percent(row.win_rate),
+    },
+    Column {
+        title: "avg",
+        width: 5,
+        metric: Some(Metric::AverageGuesses),
+        cell: |row| {
+            row.average_guesses
+                .map_or("-".to_string(), |avg| format!("{avg:.2}"))
+        },
+    },
+    Column {
+        title: "streak",
+        width: 6,
+        metric: Some(Metric::BestStreak),
+        cell: |row| row.best_streak.to_string(),
+    },
+    Column {
+        title: "daily",
+        width: 5,
+        metric: Some(Metric::DailyRate),
+        cell: |row| percent(row.daily_rate),
+    },
+    Column {
+        title: "games",
+        width: 5,
+        metric: None,
+        cell: |row| row.games_played.to_string(),
+    },
+];
+
+/// Text table of sorted `rows` fitting in `width` columns, with the
+/// `active` profile's row marked.
+pub fn render_table(rows: &[ProfileRow], metric: Metric, active: &str, width: usize) -> String {
+    let name_width = rows
+        .iter()
+        .map(|row| row.name.len())
+        .chain(std::iter::once("profile".len()))
+        .max()
+        .unwrap_or_default();
+
+    let mut columns: Vec<&Column> = COLUMNS.iter().collect();
+    let table_width = |columns: &[&Column]| {
+        2 + name_width + columns.iter().map(|column| column.width + 2).sum::<usize>()
+    };
+    while table_width(&columns) > width {
+        let Some(index) = columns
+            .iter()
+            .rposition(|column| column.metric != Some(metric))
+        else {
+            break;
+        };
+        columns.remove(index);
+    }
+
+    let mut out = String::new();
+    let _ = write!(out, "  {:<name_width$}", "profile");
+    for column in &columns {
+        let _ = write!(out, "  {:>width$}", column.title, width = column.width);
+    }
+    out.push('\n');
+    for row in rows {
+        let marker = if row.name == active { "> " } else { "  " };
+        let _ = write!(out, "{marker}{:<name_width$}", row.name);
+        for column in &columns {
+            let _ = write!(
+                out,
+                "  {:>width$}",
+                (column.cell)(row),
+                width = column.width
+            );
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::record::HISTORY_FILE;
+    use crate::utils::testing::temp_dir;
+    use std::fs;
+
+    fn row(name: &str, games_played: u32, win_rate: Option<f64>) -> ProfileRow {
+        ProfileRow {
+            name: name.to_string(),
+            games_played,
+            win_rate,
+            average_guesses: None,
+            best_streak: 0,
+            daily_rate: None,
+        }
+    }
+
+    fn names(rows: &[ProfileRow]) -> Vec<&str> {
+        rows.iter().map(|row| row.name.as_str()).collect()
+    }
+
+    #[test]
+    fn sort_test() {
+        let mut rows = vec![
+            row("new", 0, None),
+            row("bo", 4, Some(0.5)),
+            row("al", 4, Some(0.5)),
+            row("cy", 10, Some(0.5)),
+            row("di", 2, Some(1.0)),
+        ];
+        sort_rows(&mut rows, Metric::WinRate);
+        assert_eq!(names(&rows), vec!["di", "cy", "al", "bo", "new"]);
+
+        rows[0].average_guesses = Some(4.0);
+        rows[3].average_guesses = Some(3.5);
+        sort_rows(&mut rows, Metric::AverageGuesses);
+        assert_eq!(names(&rows), vec!["bo", "di", "cy", "al", "new"]);
+    }
+
+    #[test]
+    fn narrow_table_test() {
+        let rows = vec![row("default", 3, Some(2.0 / 3.0)), row("al", 0, None)];
+        let wide = render_table(&rows, Metric::DailyRate, "al", 80);
+        assert_eq!(
+            wide.lines().next().unwrap(),
+            "  profile    win    avg  streak  daily  games"
+        );
+        assert!(wide.contains("\n> al           -      -       0      -      0\n"));
+
+        let narrow = render_table(&rows, Metric::DailyRate, "al", 24);
+        assert_eq!(narrow.lines().next().unwrap(), "  profile    win  daily");
+        assert!(narrow.lines().all(|line| line.len() <= 24));
+    }
+
+    #[test]
+    fn load_profile_test() {
+        let dir = temp_dir("leaderboard_load");
+        let storage = Storage::open(dir.clone());
+        let empty = ProfileRow::load("empty", &storage).unwrap();
+        assert_eq!(empty, row("empty", 0, None));
+
+        let mut stats = Stats::default();
+        stats.record_game(true, 4);
+        stats.record_game(false, 6);
+        stats.save(&storage).unwrap();
+        storage
+            .append(
+                HISTORY_FILE,
+                r#"{"answer":"EPOCH","guesses":["CRANE","EPOCH"],"solved":true,"word_list_hash":""}"#,
+            )
+            .unwrap();
+        storage.append(HISTORY_FILE, "not json").unwrap();
+        storage
+            .append(
+                "history-2026-01-01.jsonl",
+                r#"{"answer":"EPOCH","guesses":["EPOCH"],"solved":true,"word_list_hash":""}"#,
+            )
+            .unwrap();
+        storage
+            .save(
+                "daily-2026-01-01.json",
+                r#"{"date":"2026-01-01","answer":"EPOCH","guesses":["EPOCH"]}"#,
+            )
+            .unwrap();
+        storage
+            .save(
+                "daily-2026-01-02.json",
+                r#"{"date":"2026-01-02","answer":"EPOCH","guesses":["CRANE"]}"#,
+            )
+            .unwrap();
+
+        let row = ProfileRow::load("me", &storage).unwrap();
+        assert_eq!(row.games_played, 2);
+        assert_eq!(row.win_rate, Some(0.5));
+        assert_eq!(row.average_guesses, Some(1.5));
+        assert_eq!(row.best_streak, 1);
+        assert_eq!(row.daily_rate, Some(0.5));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
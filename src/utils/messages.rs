@@ -0,0 +1,426 @@
+use anyhow::Result;
+use chrono::Local;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Widget, Wrap},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io;
+use std::time::{Duration, Instant};
+use wordle::engine::WordleError;
+
+use super::storage::Storage;
+use super::ui::InputState;
+use super::wordle::Wordle;
+
+/// Messages kept for the messages page and bug reports.
+pub const MESSAGE_LOG_LEN: usize = 50;
+/// Where the log is kept between runs, with the answer redacted.
+pub const MESSAGES_FILE: &str = "messages.json";
+/// Seconds an error stays in the message area unless `error_seconds` in
+/// `config.json` says otherwise.
+pub const ERROR_SECONDS: u64 = 3;
+/// Least time between writes of the saved log, so a burst of errors, like
+/// Enter held down on a word that isn't one, is written once.
+const SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Error,
+    Status,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Message {
+    /// local time, `%Y-%m-%d %H:%M:%S`
+    pub at: String,
+    pub level: Level,
+    pub text: String,
+    /// still showing in the message area
+    #[serde(skip)]
+    active: bool,
+    /// when it was first shown, for errors to go away on their own
+    #[serde(skip)]
+    shown: Option<Instant>,
+    /// the text as saved, masked for the answer it was shown with
+    #[serde(skip)]
+    masked: Option<String>,
+}
+
+/// Every message shown to the player, newest last. The message area shows
+/// the newest active error, or the newest active status if there is none.
+#[derive(Debug, Default)]
+pub struct MessageLog {
+    entries: VecDeque<Message>,
+    /// an error came in since the log was last written
+    unsaved: bool,
+    saved_at: Option<Instant>,
+}
+
+impl MessageLog {
+    /// Shows `text`, replacing the active message of the same level. It is
+    /// saved with every mention of `answer` masked.
+    pub fn push(&mut self, level: Level, text: String, answer: &str) {
+        self.clear(level);
+        if self.entries.len() == MESSAGE_LOG_LEN {
+            self.entries.pop_front();
+        }
+        self.unsaved |= level == Level::Error;
+        self.entries.push_back(Message {
+            at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            level,
+            masked: Some(redact(&text, answer)),
+            text,
+            active: true,
+            shown: Some(Instant::now()),
+        });
+    }
+
+    /// Takes errors shown for `timeout` or longer by `now` off the message
+    /// area, returning whether one was showing.
+    pub fn expire(&mut self, now: Instant, timeout: Duration) -> bool {
+        let mut expired = false;
+        for message in self.entries.iter_mut().filter(|m| m.level == Level::Error) {
+            if message.active
+                && message
+                    .shown
+                    .is_some_and(|shown| now.saturating_duration_since(shown) >= timeout)
+            {
+                message.active = false;
+                expired = true;
+            }
+        }
+        expired
+    }
+
+    /// Takes the active message of `level` off the message area. It stays in
+    /// the log.
+    pub fn clear(&mut self, level: Level) {
+        for message in self.entries.iter_mut().filter(|m| m.level == level) {
+            message.active = false;
+        }
+    }
+
+    fn active(&self, level: Level) -> Option<&Message> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|message| message.active && message.level == level)
+    }
+
+    /// The message the message area shows.
+    pub fn current(&self) -> Option<&Message> {
+        self.active(Level::Error)
+            .or_else(|| self.active(Level::Status))
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.active(Level::Error)
+            .map(|message| message.text.as_str())
+    }
+
+    #[cfg(test)]
+    pub fn status(&self) -> Option<&str> {
+        self.active(Level::Status)
+            .map(|message| message.text.as_str())
+    }
+
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &Message> {
+        self.entries.iter()
+    }
+
+    /// Writes the log, masked, if an error came in since the last write
+    /// and that was `SAVE_INTERVAL` or more before `now`, or at all with
+    /// `force`.
+    pub fn save_due(&mut self, storage: &Storage, now: Instant, force: bool) -> io::Result<()> {
+        let due = force
+            || self
+                .saved_at
+                .is_none_or(|at| now.saturating_duration_since(at) >= SAVE_INTERVAL);
+        if !self.unsaved || !due {
+            return Ok(());
+        }
+        let masked: Vec<Message> = self
+            .entries
+            .iter()
+            .map(|message| Message {
+                text: message
+                    .masked
+                    .clone()
+                    .unwrap_or_else(|| message.text.clone()),
+                ..message.clone()
+            })
+            .collect();
+        storage.save(MESSAGES_FILE, &serde_json::to_string_pretty(&masked)?)?;
+        self.unsaved = false;
+        self.saved_at = Some(now);
+        Ok(())
+    }
+
+    /// The log saved by the last run, for `doctor`.
+    pub fn load(storage: &Storage) -> Result<Vec<Message>> {
+        match storage.load(MESSAGES_FILE)? {
+            Some(contents) => Ok(serde_json::from_str(&contents)?),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+/// `text` with `answer` masked, ignoring case.
+pub fn redact(text: &str, answer: &str) -> String {
+    if answer.is_empty() {
+        return text.to_string();
+    }
+    // ASCII uppercasing keeps byte offsets, so matches line up with `text`
+    let upper = text.to_ascii_uppercase();
+    let answer = answer.to_ascii_uppercase();
+    let mut out = String::new();
+    let mut rest = 0;
+    for (start, _) in upper.match_indices(&answer) {
+        out.push_str(&text[rest..start]);
+        out.push_str(&"*".repeat(answer.chars().count()));
+        rest = start + answer.len();
+    }
+    out.push_str(&text[rest..]);
+    out
+}
+
+/// How an engine error reads in the message area.
+pub fn describe(err: &WordleError) -> String {
+    match err {
+        WordleError::InvalidWordLength { expected, .. } => {
+            format!("guesses have {expected} letters")
+        }
+        WordleError::NotLetters(_) => String::from("only letters A–Z are allowed"),
+        WordleError::WordNotInDictionary(_) => String::from("not in the word list"),
+        WordleError::HardModeViolation(rules) => rules.join(", "),
+        WordleError::GameOver => String::from("the game is over"),
+        WordleError::EmptyWordList => String::from("no words left to play"),
+        other => other.to_string(),
+    }
+}
+
+impl Wordle {
+    /// Shows an error and records it in the saved log.
+    pub fn show_error(&mut self, text: impl Into<String>) {
+        self.messages.push(Level::Error, text.into(), &self.answer);
+        self.save_messages(false);
+    }
+
+    pub fn show_status(&mut self, text: impl Into<String>) {
+        self.messages.push(Level::Status, text.into(), &self.answer);
+    }
+
+    /// Writes the log if errors are waiting for it and it wasn't written
+    /// moments ago, or whenever errors are waiting with `force`.
+    pub fn save_messages(&mut self, force: bool) {
+        // a failure to save the log can't usefully be reported through it
+        let _ = self.messages.save_due(&self.storage, Instant::now(), force);
+    }
+
+    /// How long errors stay up; `None` keeps them until the next guess.
+    pub fn error_timeout(&self) -> Option<Duration> {
+        match self.config.error_seconds.unwrap_or(ERROR_SECONDS) {
+            0 => None,
+            seconds => Some(Duration::from_secs(seconds)),
+        }
+    }
+
+    pub fn on_messages_key(&mut self, key: KeyEvent) -> InputState {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('M')) {
+            self.show_messages = false;
+        }
+        InputState::None
+    }
+
+    pub fn render_messages_page(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let mut lines = Vec::new();
+        for message in self.messages.entries().rev() {
+            let color = match message.level {
+                Level::Error => Color::Red,
+                Level::Status => Color::Cyan,
+            };
+            lines.push(Line::from(vec![
+                Span::raw(format!("{} ", message.at)).fg(Color::DarkGray),
+                Span::raw(message.text.as_str()).fg(color),
+            ]));
+        }
+        if lines.is_empty() {
+            lines.push(Line::from("No messages yet").fg(Color::DarkGray));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::raw("Back "), "<Esc>".blue().bold()]));
+
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::bordered()
+                    .title("Messages")
+                    .border_type(BorderType::Rounded),
+            )
+            .render(area, buf);
+    }
+}
+
+/// Errors still waiting to be written are saved when the game goes away,
+/// however it ended.
+impl Drop for Wordle {
+    fn drop(&mut self) {
+        self.save_messages(true);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::testing::temp_dir;
+    use std::fs;
+
+    #[test]
+    fn current_message_test() {
+        let mut log = MessageLog::default();
+        assert!(log.current().is_none());
+        log.push(Level::Status, String::from("try E in position 4"), "");
+        log.push(Level::Error, String::from("invalid word"), "");
+        assert_eq!(log.current().unwrap().text, "invalid word");
+
+        // errors win until cleared, then the status shows again
+        log.clear(Level::Error);
+        assert_eq!(log.current().unwrap().text, "try E in position 4");
+        log.push(Level::Status, String::from("press Ctrl+Q to quit"), "");
+        log.clear(Level::Status);
+        assert!(log.current().is_none());
+        assert_eq!(log.entries().count(), 3);
+    }
+
+    #[test]
+    fn submit_error_test() {
+        let mut game = Wordle::new();
+        for (guess, message) in [
+            ("CRAN", "guesses have 5 letters"),
+            ("CR4NE", "only letters A–Z are allowed"),
+            ("CAFÉS", "only letters A–Z are allowed"),
+            ("QQQQQ", "not in the word list"),
+        ] {
+            game.current_guess = String::from(guess);
+            game.submit_guess();
+            assert_eq!(game.messages.current().unwrap().text, message);
+        }
+        assert!(game.guess_history.is_empty());
+    }
+
+    #[test]
+    fn did_you_mean_test() {
+        let mut game = Wordle::with_words("DRAIN", &["GRAIN", "CRANE", "BRAIN", "TRAIN"]);
+        game.current_guess = String::from("CRAIN");
+        game.submit_guess();
+        // all one letter off; the answer, also one off, isn't offered
+        assert_eq!(
+            game.messages.error(),
+            Some("not in the word list, did you mean BRAIN, GRAIN, TRAIN?")
+        );
+        game.current_guess = String::from("CARNE");
+        game.submit_guess();
+        assert_eq!(
+            game.messages.error(),
+            Some("not in the word list, did you mean CRANE?")
+        );
+        assert!(game.guess_history.is_empty());
+    }
+
+    #[test]
+    fn error_expiry_test() {
+        let mut log = MessageLog::default();
+        let timeout = Duration::from_secs(3);
+        log.push(Level::Status, String::from("playing in Español"), "");
+        log.push(Level::Error, String::from("not in the word list"), "");
+        let shown = Instant::now();
+
+        assert!(!log.expire(shown, timeout));
+        assert_eq!(log.error(), Some("not in the word list"));
+        // gone after the timeout, and the status shows again
+        assert!(log.expire(shown + timeout, timeout));
+        assert!(!log.expire(shown + timeout, timeout));
+        assert!(log.error().is_none());
+        assert_eq!(log.current().unwrap().text, "playing in Español");
+        assert_eq!(log.entries().count(), 2);
+    }
+
+    #[test]
+    fn error_timeout_test() {
+        let mut game = Wordle::new();
+        assert_eq!(
+            game.error_timeout(),
+            Some(Duration::from_secs(ERROR_SECONDS))
+        );
+        game.config.error_seconds = Some(0);
+        assert_eq!(game.error_timeout(), None);
+
+        // typing clears it at once
+        game.config.error_seconds = Some(10);
+        game.current_guess = String::from("QQQQQ");
+        game.submit_guess();
+        assert!(game.messages.error().is_some());
+        game.delete_letter();
+        assert!(game.messages.error().is_some());
+        game.type_letter('Z');
+        assert!(game.messages.error().is_none());
+    }
+
+    #[test]
+    fn bounded_log_test() {
+        let mut log = MessageLog::default();
+        for index in 0..MESSAGE_LOG_LEN + 5 {
+            log.push(Level::Error, format!("error {index}"), "");
+        }
+        assert_eq!(log.entries().count(), MESSAGE_LOG_LEN);
+        assert_eq!(log.entries().next().unwrap().text, "error 5");
+        assert_eq!(log.error(), Some("error 54"));
+    }
+
+    #[test]
+    fn saved_log_is_redacted_test() {
+        assert_eq!(
+            redact("no definition for epoch (EPOCH)", "EPOCH"),
+            "no definition for ***** (*****)"
+        );
+
+        let dir = temp_dir("messages_saved");
+        let mut game = Wordle::new();
+        game.storage = Storage::open(dir.clone());
+        game.answer = "EPOCH".to_string();
+        game.show_error("failed to fetch definition of epoch");
+        let saved = MessageLog::load(&game.storage).unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].text, "failed to fetch definition of *****");
+        // the player still sees the message as it was
+        assert_eq!(
+            game.messages.error(),
+            Some("failed to fetch definition of epoch")
+        );
+
+        // errors right after are written together later, masked for the
+        // answer they were shown with
+        for _ in 0..3 {
+            game.show_error("epoch is the answer");
+        }
+        game.answer = "CRANE".to_string();
+        assert_eq!(MessageLog::load(&game.storage).unwrap().len(), 1);
+        game.save_messages(false);
+        assert_eq!(MessageLog::load(&game.storage).unwrap().len(), 1);
+        drop(game);
+        let saved = MessageLog::load(&Storage::open(dir.clone())).unwrap();
+        assert_eq!(saved.len(), 4);
+        assert_eq!(saved[3].text, "***** is the answer");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,133 @@
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// Turns the JSON of one format version into the next.
+pub type Migration = fn(Map<String, Value>) -> Result<Map<String, Value>>;
+
+/// The shape of one persisted JSON file over time.
+///
+/// Files carry a top-level `version`; one written before versioning has
+/// none and counts as version 0. `migrations[n]` upgrades version `n` to
+/// `n + 1`, so the current version is the number of migrations.
+pub struct Format {
+    /// file name, for error messages
+    pub name: &'static str,
+    pub migrations: &'static [Migration],
+}
+
+impl Format {
+    pub fn current(&self) -> u64 {
+        self.migrations.len() as u64
+    }
+
+    /// Parses `contents` written by this or any older version. The flag is
+    /// set when migrations ran, so the caller can write the file back.
+    pub fn parse<T: DeserializeOwned>(&self, contents: &str) -> Result<(T, bool)> {
+        let value: Value =
+            serde_json::from_str(contents).with_context(|| format!("{} is not JSON", self.name))?;
+        let Value::Object(mut object) = value else {
+            bail!("{} should hold a JSON object", self.name);
+        };
+        let version = match object.remove("version") {
+            None => 0,
+            Some(version) => version
+                .as_u64()
+                .with_context(|| format!("{} has an invalid version {version}", self.name))?,
+        };
+        if version > self.current() {
+            bail!(
+                "{} was created by a newer version of rust_wordle (format {version}, this one reads up to {})",
+                self.name,
+                self.current()
+            );
+        }
+
+        for (from, migration) in self.migrations.iter().enumerate().skip(version as usize) {
+            object = migration(object).with_context(|| {
+                format!(
+                    "failed to upgrade {} from format {from} to {}",
+                    self.name,
+                    from + 1
+                )
+            })?;
+        }
+        let parsed = serde_json::from_value(Value::Object(object))
+            .with_context(|| format!("failed to read {}", self.name))?;
+        Ok((parsed, version < self.current()))
+    }
+
+    /// `value` as pretty JSON stamped with the current version.
+    pub fn to_string<T: Serialize>(&self, value: &T) -> serde_json::Result<String> {
+        let mut json = serde_json::to_value(value)?;
+        if let Value::Object(object) = &mut json {
+            object.insert(String::from("version"), Value::from(self.current()));
+        }
+        serde_json::to_string_pretty(&json)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Counter {
+        count: u32,
+        label: String,
+    }
+
+    fn rename_total(mut object: Map<String, Value>) -> Result<Map<String, Value>> {
+        let total = object.remove("total").context("missing total")?;
+        object.insert(String::from("count"), total);
+        Ok(object)
+    }
+
+    fn add_label(mut object: Map<String, Value>) -> Result<Map<String, Value>> {
+        object.insert(String::from("label"), Value::from("none"));
+        Ok(object)
+    }
+
+    const COUNTER: Format = Format {
+        name: "counter.json",
+        migrations: &[rename_total, add_label],
+    };
+
+    #[test]
+    fn chained_migrations_test() {
+        let (counter, migrated): (Counter, bool) = COUNTER.parse("{\"total\": 4}").unwrap();
+        assert!(migrated);
+        assert_eq!(
+            counter,
+            Counter {
+                count: 4,
+                label: String::from("none")
+            }
+        );
+
+        let (_, migrated): (Counter, bool) =
+            COUNTER.parse("{\"version\": 1, \"count\": 4}").unwrap();
+        assert!(migrated);
+
+        let current = COUNTER.to_string(&counter).unwrap();
+        assert!(current.contains("\"version\": 2"));
+        let (reread, migrated): (Counter, bool) = COUNTER.parse(&current).unwrap();
+        assert!(!migrated);
+        assert_eq!(reread, counter);
+    }
+
+    #[test]
+    fn rejected_versions_test() {
+        let newer = COUNTER
+            .parse::<Counter>("{\"version\": 3, \"count\": 4}")
+            .unwrap_err();
+        assert!(format!("{newer:#}").contains("created by a newer version"));
+
+        let broken = COUNTER.parse::<Counter>("{\"count\": 4}").unwrap_err();
+        assert!(format!("{broken:#}").contains("from format 0 to 1"));
+        assert!(COUNTER.parse::<Counter>("[1]").is_err());
+        assert!(COUNTER.parse::<Counter>("{\"version\": \"2\"}").is_err());
+    }
+}
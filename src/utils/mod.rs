@@ -1,4 +1,59 @@
+mod adaptive;
+pub mod assets;
+mod background;
+mod bell;
+mod blitz;
+pub mod bot;
+mod candidates;
+pub mod challenge;
+pub mod commitment;
+pub mod compare;
+pub mod config;
+mod daily;
+pub mod debug;
+mod definition;
+pub mod difficulty;
+mod elapsed;
+pub mod events;
+mod explain;
+mod family;
+pub mod feedback;
+mod heatmap;
+mod hint;
+mod hotseat;
+pub mod import;
+mod input;
+pub mod integrations;
+mod keyboard;
+mod keys;
+mod knowledge;
+mod language;
+pub mod leaderboard;
+pub mod messages;
+mod migrate;
+mod mouse;
+mod played;
+pub mod protocol;
+pub mod race;
+pub mod record;
+pub mod replay;
+mod restart;
+pub mod session;
+mod settings;
+mod share;
+mod solver;
+pub mod stats;
+pub mod storage;
+mod suggest;
+mod switcher;
+#[cfg(test)]
+mod testing;
+mod theme;
 mod tile;
+mod timed;
+mod timing;
+mod transcript;
+pub mod transfer;
 mod ui;
-mod word;
 pub mod wordle;
+mod zoom;
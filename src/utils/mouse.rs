@@ -0,0 +1,164 @@
+use ratatui::{
+    crossterm::{
+        event::{DisableMouseCapture, EnableMouseCapture, MouseButton, MouseEvent, MouseEventKind},
+        execute,
+    },
+    layout::{Position, Rect},
+};
+use std::io;
+
+use super::ui::InputState;
+use super::wordle::Wordle;
+
+/// Submits the row when clicked.
+pub const ENTER_KEY: char = '⏎';
+/// Deletes the last letter when clicked.
+pub const BACKSPACE_KEY: char = '⌫';
+
+/// The on-screen keyboard for rows of letters, with Enter and Backspace
+/// either side of the last row. Each row is written like
+/// `"⏎ Z X C V B N M ⌫"`: a space is a one-column gap and every other
+/// character a key three columns wide.
+pub fn keyboard_rows(letters: &[Vec<char>]) -> Vec<String> {
+    let last = letters.len().saturating_sub(1);
+    letters
+        .iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let keys: Vec<String> = row.iter().map(char::to_string).collect();
+            match index == last {
+                true => format!("{ENTER_KEY} {} {BACKSPACE_KEY}", keys.join(" ")),
+                false => keys.join(" "),
+            }
+        })
+        .collect()
+}
+
+/// Turns the terminal's mouse reporting on or off, for `config.mouse`.
+pub fn capture_mouse(on: bool) -> io::Result<()> {
+    if on {
+        execute!(io::stdout(), EnableMouseCapture)
+    } else {
+        execute!(io::stdout(), DisableMouseCapture)
+    }
+}
+
+/// Every key of the keyboard drawn in `area` with the cells it covers,
+/// each row centered and followed by a blank line. Both drawing and
+/// clicking go through this, so they can't disagree about where a key is.
+pub fn key_boxes(area: Rect, rows: &[String]) -> impl Iterator<Item = (char, Rect)> {
+    rows.iter()
+        .zip((area.y..area.bottom()).step_by(2))
+        .flat_map(move |(row, y)| {
+            let mut x = area.x + area.width.saturating_sub(keys_width(row)) / 2;
+            row.chars().filter_map(move |ch| {
+                let spot = Rect::new(x, y, if ch == ' ' { 1 } else { 3 }, 1);
+                x += spot.width;
+                (ch != ' ').then_some((ch, spot.intersection(area)))
+            })
+        })
+        .filter(|(_, spot)| !spot.is_empty())
+}
+
+/// Width of a keyboard row: three cells a key, one a space.
+pub fn keys_width(row: &str) -> u16 {
+    row.chars().map(|ch| if ch == ' ' { 1 } else { 3 }).sum()
+}
+
+/// The key drawn at `position` in the keyboard of `rows` at `area`, if any.
+pub fn key_at(area: Rect, rows: &[String], position: Position) -> Option<char> {
+    key_boxes(area, rows)
+        .find(|(_, spot)| spot.contains(position))
+        .map(|(key, _)| key)
+}
+
+impl Wordle {
+    /// A left click on the keyboard types the key under it. Clicks
+    /// anywhere else, or while a popup covers the board, do nothing.
+    pub fn on_mouse(&mut self, mouse: MouseEvent) -> InputState {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) || !self.board_has_focus() {
+            return InputState::None;
+        }
+        let Some(area) = self.keyboard_area.get() else {
+            return InputState::None;
+        };
+        match key_at(area, &self.keyboard, Position::new(mouse.column, mouse.row)) {
+            Some(ENTER_KEY) if self.is_accepting_guesses() => InputState::Submit,
+            Some(BACKSPACE_KEY) => self.delete_letter(),
+            Some(letter) if letter.is_alphabetic() => self.type_letter(letter),
+            _ => InputState::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::KeyModifiers;
+
+    fn click(game: &mut Wordle, key: char) -> InputState {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(60, 45)).unwrap();
+        terminal.draw(|frame| game.render_terminal(frame)).unwrap();
+        let area = game.keyboard_area.get().unwrap();
+        let (_, spot) = key_boxes(area, &game.keyboard)
+            .find(|(ch, _)| *ch == key)
+            .unwrap();
+        // the key's label is where the player sees it
+        let label = &terminal.backend().buffer()[(spot.x + 1, spot.y)];
+        assert_eq!(label.symbol(), key.to_string());
+        game.on_mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: spot.right() - 1,
+            row: spot.y,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn click_keyboard_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        for letter in "CRANX".chars() {
+            assert!(matches!(click(&mut game, letter), InputState::EditingGuess));
+        }
+        click(&mut game, BACKSPACE_KEY);
+        click(&mut game, 'E');
+        assert_eq!(game.current_guess, "CRANE");
+        assert!(matches!(click(&mut game, ENTER_KEY), InputState::Submit));
+
+        // gaps between keys and the board above them aren't keys
+        let area = game.keyboard_area.get().unwrap();
+        let rows = &game.keyboard;
+        assert_eq!(key_at(area, rows, Position::new(area.x, area.y + 1)), None);
+        assert_eq!(key_at(area, rows, Position::new(area.x, area.y - 3)), None);
+
+        // nor is the keyboard while something covers it
+        game.celebration = Some(String::from("unlocked"));
+        assert!(matches!(click(&mut game, 'S'), InputState::None));
+        assert_eq!(game.current_guess, "CRANE");
+    }
+
+    #[test]
+    fn rows_centered_test() {
+        let area = Rect::new(3, 5, 42, 5);
+        let letters: Vec<Vec<char>> = ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"]
+            .iter()
+            .map(|row| row.chars().collect())
+            .collect();
+        let rows = keyboard_rows(&letters);
+        assert_eq!(rows[2], "⏎ Z X C V B N M ⌫");
+        for (row, y) in rows.iter().zip([5, 7, 9]) {
+            let keys: Vec<Rect> = key_boxes(area, &rows)
+                .filter(|(ch, _)| row.contains(*ch))
+                .map(|(_, spot)| spot)
+                .collect();
+            assert!(keys.iter().all(|spot| spot.y == y));
+            let left = keys[0].x - area.x;
+            let right = area.right() - keys[keys.len() - 1].right();
+            assert!(left.abs_diff(right) <= 1, "{row}: {left} and {right}");
+        }
+        // a keyboard too narrow for a row keeps the keys that fit
+        assert!(key_boxes(Rect::new(0, 0, 20, 5), &rows).all(|(_, spot)| spot.right() <= 20));
+    }
+}
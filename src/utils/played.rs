@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+use std::io;
+use wordle::engine::Alphabet;
+
+use super::storage::{SaveOutcome, Storage};
+use super::wordle::Wordle;
+
+/// Answers of every finished game, one per line, so later runs don't deal
+/// them again.
+pub const PLAYED_FILE: &str = "played.txt";
+
+/// The answers in the played file; none when there isn't one yet.
+pub fn load_played(storage: &Storage) -> io::Result<HashSet<String>> {
+    let contents = storage.load(PLAYED_FILE)?.unwrap_or_default();
+    Ok(contents
+        .lines()
+        .map(|line| Alphabet::upper_word(line.trim()))
+        .filter(|word| !word.is_empty())
+        .collect())
+}
+
+/// Writes `played` sorted, through a temporary file so a crash leaves the
+/// old list rather than half a new one.
+fn save_played(storage: &Storage, played: &HashSet<String>) -> io::Result<SaveOutcome> {
+    let mut words: Vec<&String> = played.iter().collect();
+    words.sort_unstable();
+    let mut contents = String::new();
+    for word in words {
+        contents.push_str(word);
+        contents.push('\n');
+    }
+    storage.save(PLAYED_FILE, &contents)
+}
+
+impl Wordle {
+    /// Adds the finished game's answer to the played file.
+    pub fn remember_answer(&mut self) {
+        if self.played_answers.insert(self.answer.clone()) {
+            self.save_played();
+        }
+    }
+
+    /// Forgets the played answers among `words`, once all of them have come
+    /// up.
+    pub fn forget_played(&mut self, words: &HashSet<String>) {
+        let before = self.played_answers.len();
+        self.played_answers.retain(|word| !words.contains(word));
+        if self.played_answers.len() != before {
+            self.save_played();
+        }
+    }
+
+    /// Empties the played file, as `--reset-history` asks.
+    pub fn reset_played(&mut self) {
+        self.played_answers.clear();
+        self.seen_answers.clear();
+        self.seen_answers.insert(self.answer.clone());
+        self.save_played();
+    }
+
+    fn save_played(&mut self) {
+        if let Err(err) = save_played(&self.storage, &self.played_answers) {
+            self.show_error(format!("failed to save played answers: {err}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::testing::temp_dir;
+    use crate::utils::wordle::Setup;
+    use std::fs;
+
+    #[test]
+    fn played_across_runs_test() {
+        let dir = temp_dir("played");
+        fs::create_dir_all(&dir).unwrap();
+        let list = dir.join("words.txt");
+        fs::write(&list, "CRANE\nEPOCH\nSLATE\n").unwrap();
+        let setup = Setup {
+            wordlist_paths: vec![list],
+            ..Setup::default()
+        };
+        let open = || Wordle::with_config(Storage::open(dir.clone()), setup.clone()).unwrap();
+
+        // every run deals a word the runs before it haven't finished
+        let mut played = HashSet::new();
+        for _ in 0..3 {
+            let mut game = open();
+            assert!(played.insert(game.answer.clone()), "{} again", game.answer);
+            game.remember_answer();
+        }
+        let stored = fs::read_to_string(dir.join(PLAYED_FILE)).unwrap();
+        assert_eq!(stored, "CRANE\nEPOCH\nSLATE\n");
+
+        // all played: start over, and the file follows
+        let mut game = open();
+        assert!(game.messages.status().unwrap().contains("every word"));
+        assert_eq!(load_played(&game.storage).unwrap().len(), 0);
+        game.remember_answer();
+
+        game.reset_played();
+        assert_eq!(fs::read_to_string(dir.join(PLAYED_FILE)).unwrap(), "");
+        assert!(!dir.join(format!("{PLAYED_FILE}.tmp")).exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,265 @@
+use serde_json::{Value, json};
+use std::io::{self, BufRead, Write};
+use wordle::engine::{Alphabet, TileState, WordleError};
+
+use super::wordle::Wordle;
+
+/// Bumped whenever a message changes shape so clients can detect it.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Machine-readable reasons a line was rejected.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ErrorCode {
+    InvalidUtf8,
+    MalformedJson,
+    UnknownField,
+    InvalidCharacters,
+    InvalidLength,
+    UnknownWord,
+    GameOver,
+    /// a word `--family-mode` keeps out
+    NotAllowed,
+    /// a hard mode guess that leaves out a revealed letter
+    HardMode,
+    /// refused for any other reason the game gives
+    Rejected,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::InvalidUtf8 => "invalid_utf8",
+            ErrorCode::MalformedJson => "malformed_json",
+            ErrorCode::UnknownField => "unknown_field",
+            ErrorCode::InvalidCharacters => "invalid_characters",
+            ErrorCode::InvalidLength => "invalid_length",
+            ErrorCode::UnknownWord => "unknown_word",
+            ErrorCode::GameOver => "game_over",
+            ErrorCode::NotAllowed => "not_allowed",
+            ErrorCode::HardMode => "hard_mode",
+            ErrorCode::Rejected => "rejected",
+        }
+    }
+
+    /// The code for a guess the game refused to play.
+    pub fn for_error(err: &WordleError) -> Self {
+        match err {
+            WordleError::InvalidWordLength { .. } => ErrorCode::InvalidLength,
+            WordleError::NotLetters(_) => ErrorCode::InvalidCharacters,
+            WordleError::WordNotInDictionary(_) => ErrorCode::UnknownWord,
+            WordleError::GameOver => ErrorCode::GameOver,
+            WordleError::HardModeViolation(_) => ErrorCode::HardMode,
+            WordleError::WordNotAllowed => ErrorCode::NotAllowed,
+            _ => ErrorCode::Rejected,
+        }
+    }
+}
+
+fn error(code: ErrorCode, message: impl Into<String>) -> Value {
+    json!({
+        "type": "error",
+        "code": code.as_str(),
+        "message": message.into(),
+    })
+}
+
+fn state_name(state: TileState) -> &'static str {
+    match state {
+        TileState::Correct => "correct",
+        TileState::Present => "present",
+        TileState::Absent => "absent",
+        TileState::Unused => "unused",
+    }
+}
+
+pub fn handshake(game: &Wordle) -> Value {
+    json!({
+        "type": "hello",
+        "protocol-version": PROTOCOL_VERSION,
+        "word_length": game.word_len,
+        "rounds": game.rounds,
+    })
+}
+
+/// Validates one request line and plays it. Every line gets exactly one
+/// response and rejected lines never consume a round.
+///
+/// Requests look like `{"guess": "CRANE"}`. Strict mode additionally rejects
+/// unknown fields and lowercase letters instead of tolerating them.
+pub fn respond(game: &mut Wordle, line: &[u8], strict: bool) -> Value {
+    let Ok(line) = std::str::from_utf8(line) else {
+        return error(ErrorCode::InvalidUtf8, "request is not valid UTF-8");
+    };
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(err) => return error(ErrorCode::MalformedJson, err.to_string()),
+    };
+    let Some(fields) = request.as_object() else {
+        return error(ErrorCode::MalformedJson, r#"expected {"guess": "WORD"}"#);
+    };
+    let Some(guess) = fields.get("guess").and_then(Value::as_str) else {
+        return error(ErrorCode::MalformedJson, r#"expected {"guess": "WORD"}"#);
+    };
+    if strict && let Some(field) = fields.keys().find(|key| *key != "guess") {
+        return error(ErrorCode::UnknownField, format!("unknown field '{field}'"));
+    }
+
+    let guess = if strict {
+        guess.to_string()
+    } else {
+        Alphabet::upper_word(guess.trim())
+    };
+    if !guess
+        .chars()
+        .all(|ch| game.alphabet.contains(ch) && Alphabet::is_upper(ch))
+    {
+        let letters = if game.alphabet.is_latin() {
+            String::from("A-Z")
+        } else {
+            game.alphabet.to_string()
+        };
+        return error(
+            ErrorCode::InvalidCharacters,
+            format!("only uppercase letters {letters} are allowed"),
+        );
+    }
+    game.current_guess = guess.clone();
+    let row = match game.play_guess() {
+        Ok(row) => row,
+        Err(err) => {
+            game.current_guess.clear();
+            return error(ErrorCode::for_error(&err), err.to_string());
+        }
+    };
+
+    let mut response = json!({
+        "type": "result",
+        "game_id": game.game_id,
+        "guess": guess,
+        "states": row.letters.iter().map(|tile| state_name(tile.state)).collect::<Vec<_>>(),
+        "round": game.guess_history.len(),
+        "guesses_remaining": game.guesses_remaining(),
+        "solved": game.solved,
+        "game_over": game.is_game_over,
+    });
+    if game.is_game_over {
+        response["answer"] = json!(game.answer);
+    }
+    response
+}
+
+/// Runs the line protocol until `input` is exhausted.
+pub fn run_protocol<R: BufRead, W: Write>(
+    game: &mut Wordle,
+    mut input: R,
+    mut output: W,
+    strict: bool,
+) -> io::Result<()> {
+    writeln!(output, "{}", handshake(game))?;
+    output.flush()?;
+
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            return Ok(());
+        }
+        while line.last().is_some_and(|byte| byte.is_ascii_whitespace()) {
+            line.pop();
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        writeln!(output, "{}", respond(game, &line, strict))?;
+        output.flush()?;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn game() -> Wordle {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        game
+    }
+
+    fn code(response: &Value) -> &str {
+        response["code"].as_str().unwrap_or("")
+    }
+
+    #[test]
+    fn strict_rejections_test() {
+        let mut game = game();
+        let cases: [(&[u8], &str); 8] = [
+            (b"not json", "malformed_json"),
+            (b"[\"CRANE\"]", "malformed_json"),
+            (b"{\"guess\": 5}", "malformed_json"),
+            (b"{\"guess\": \"CRANE\", \"x\": 1}", "unknown_field"),
+            (b"{\"guess\": \"crane\"}", "invalid_characters"),
+            (b"{\"guess\": \"CRANES\"}", "invalid_length"),
+            (b"{\"guess\": \"XXXXX\"}", "unknown_word"),
+            (b"{\"guess\": \"\xff\xfe\"}", "invalid_utf8"),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(code(&respond(&mut game, line, true)), expected);
+        }
+        assert_eq!(game.round, 1);
+        assert!(game.guess_history.is_empty());
+    }
+
+    #[test]
+    fn lenient_mode_test() {
+        let mut game = game();
+        let response = respond(&mut game, br#"{"guess": " crane ", "x": 1}"#, false);
+        assert_eq!(response["type"], "result");
+        assert_eq!(response["guess"], "CRANE");
+        assert_eq!(response["round"], 1);
+    }
+
+    #[test]
+    fn game_over_test() {
+        let mut game = game();
+        let response = respond(&mut game, br#"{"guess": "EPOCH"}"#, true);
+        assert_eq!(response["solved"], true);
+        assert_eq!(response["answer"], "EPOCH");
+        let response = respond(&mut game, br#"{"guess": "CRANE"}"#, true);
+        assert_eq!(code(&response), "game_over");
+        assert_eq!(game.guess_history.len(), 1);
+    }
+
+    #[test]
+    fn hard_mode_test() {
+        let mut game = game();
+        game.config.hard_mode = true;
+        respond(&mut game, br#"{"guess": "CRANE"}"#, true);
+        let response = respond(&mut game, br#"{"guess": "SLATE"}"#, true);
+        assert_eq!(code(&response), "hard_mode");
+        assert!(response["message"].as_str().unwrap().contains('C'));
+        assert_eq!(game.guess_history.len(), 1);
+    }
+
+    #[test]
+    fn run_protocol_test() {
+        let mut game = game();
+        let input = b"{\"guess\":\"CRANE\"}\n\n garbage \n{\"guess\":\"EPOCH\"}\n";
+        let mut output = Vec::new();
+        run_protocol(&mut game, &input[..], &mut output, true).unwrap();
+
+        let lines: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0]["protocol-version"], PROTOCOL_VERSION);
+        assert_eq!(
+            lines[1]["states"],
+            json!(["present", "absent", "absent", "absent", "present"])
+        );
+        assert_eq!(code(&lines[2]), "malformed_json");
+        assert_eq!(lines[3]["solved"], true);
+    }
+}
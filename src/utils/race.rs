@@ -0,0 +1,374 @@
+use anyhow::{Context, Result, bail};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+use wordle::engine::{Alphabet, TileState, Word};
+
+use super::share::{pass_symbol, tile_symbol};
+use super::wordle::Wordle;
+
+/// How long joining waits for the host to deal the first word.
+const JOIN_TIMEOUT: Duration = Duration::from_secs(10);
+/// Width of the opponent panel, borders included.
+pub const RACE_WIDTH: u16 = 16;
+
+/// One line of the race protocol, as JSON with a `type` field, e.g.
+/// `{"type":"guess_result","row":"BYBGB"}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RaceMessage {
+    /// from the host: a new game with this answer
+    Start { answer: String },
+    /// a row the sender played, as `G`/`Y`/`B` per tile, `-` for a pass
+    GuessResult { row: String },
+    /// the sender's game ended after `rows` rows
+    GameOver { solved: bool, rows: usize },
+}
+
+/// What the socket thread hands the main loop.
+#[derive(Debug)]
+enum RaceEvent {
+    Message(RaceMessage),
+    Disconnected(String),
+}
+
+/// A race against one opponent over TCP. A thread reads the opponent's
+/// messages into a channel, which the main loop drains every frame.
+pub struct Race {
+    writer: TcpStream,
+    events: Receiver<RaceEvent>,
+    /// this side deals the words
+    pub host: bool,
+    /// the opponent's rows of the current game, colors only
+    pub opponent: Vec<String>,
+    /// how the opponent's game ended: solved, and in how many rows
+    pub opponent_result: Option<(bool, usize)>,
+    pub disconnected: bool,
+}
+
+impl Race {
+    /// Waits for one player to connect to `listener` and hosts the race.
+    pub fn accept(listener: &TcpListener) -> Result<Self> {
+        let (stream, _) = listener.accept().context("failed to accept a player")?;
+        Race::new(stream, true)
+    }
+
+    /// Joins the race hosted at `addr`, returning it with the first answer.
+    pub fn join(addr: &str) -> Result<(Self, String)> {
+        let stream = TcpStream::connect(addr).with_context(|| format!("failed to join {addr}"))?;
+        let race = Race::new(stream, false)?;
+        match race.events.recv_timeout(JOIN_TIMEOUT) {
+            Ok(RaceEvent::Message(RaceMessage::Start { answer })) => Ok((race, answer)),
+            Ok(RaceEvent::Message(message)) => bail!("the host sent {message:?} before a word"),
+            Ok(RaceEvent::Disconnected(reason)) => bail!("the host hung up: {reason}"),
+            Err(RecvTimeoutError::Timeout) => bail!("the host didn't deal a word in time"),
+            Err(RecvTimeoutError::Disconnected) => bail!("the host hung up"),
+        }
+    }
+
+    fn new(stream: TcpStream, host: bool) -> Result<Self> {
+        let reader = BufReader::new(stream.try_clone()?);
+        let (sender, events) = mpsc::channel();
+        thread::spawn(move || {
+            for line in reader.lines() {
+                let event = match line {
+                    Ok(line) if line.trim().is_empty() => continue,
+                    Ok(line) => match serde_json::from_str(&line) {
+                        Ok(message) => RaceEvent::Message(message),
+                        Err(err) => RaceEvent::Disconnected(format!("bad message: {err}")),
+                    },
+                    Err(err) => RaceEvent::Disconnected(err.to_string()),
+                };
+                let done = matches!(event, RaceEvent::Disconnected(_));
+                if sender.send(event).is_err() || done {
+                    return;
+                }
+            }
+            let _ = sender.send(RaceEvent::Disconnected(String::from("connection closed")));
+        });
+        Ok(Race {
+            writer: stream,
+            events,
+            host,
+            opponent: Vec::new(),
+            opponent_result: None,
+            disconnected: false,
+        })
+    }
+
+    /// Sends `message` as one line.
+    fn send(&mut self, message: &RaceMessage) -> Result<()> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl Drop for Race {
+    /// Hangs up, which also ends the reading thread and tells the opponent.
+    fn drop(&mut self) {
+        let _ = self.writer.shutdown(Shutdown::Both);
+    }
+}
+
+/// A played row as the protocol sends it.
+pub fn row_colors(row: &Word) -> String {
+    row.letters
+        .iter()
+        .map(|tile| {
+            if row.is_pass() {
+                pass_symbol(false)
+            } else {
+                tile_symbol(tile.state, false)
+            }
+        })
+        .collect()
+}
+
+impl Wordle {
+    /// Plays the race's first game: the host deals its own answer, a player
+    /// who joined takes the host's.
+    pub fn start_race(&mut self, race: Race, answer: Option<String>) -> Result<()> {
+        if let Some(answer) = answer {
+            self.take_race_answer(answer)?;
+        }
+        self.race = Some(race);
+        if self.race.as_ref().is_some_and(|race| race.host) {
+            self.send_race(RaceMessage::Start {
+                answer: self.answer.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Starts a new game with the host's answer, which is guessable for this
+    /// game even if this side's list doesn't have it.
+    fn take_race_answer(&mut self, answer: String) -> Result<()> {
+        let len = answer.chars().count();
+        let spelled = answer
+            .chars()
+            .all(|ch| self.alphabet.contains(ch) && Alphabet::is_upper(ch));
+        if len != self.word_len || !spelled {
+            bail!("the host plays {len}-letter words, join with --length {len}");
+        }
+        self.challenge = None;
+        self.answer = answer;
+        self.reset_board();
+        Ok(())
+    }
+
+    /// Sends `message` to the opponent; a failure ends the race with a
+    /// message instead of stopping the game.
+    pub fn send_race(&mut self, message: RaceMessage) {
+        let Some(race) = self.race.as_mut().filter(|race| !race.disconnected) else {
+            return;
+        };
+        if let Err(err) = race.send(&message) {
+            race.disconnected = true;
+            self.show_error(format!("lost the connection to your opponent: {err}"));
+        }
+    }
+
+    /// Takes in everything the opponent sent since the last frame.
+    pub fn poll_race(&mut self) {
+        let Some(race) = &mut self.race else {
+            return;
+        };
+        let events: Vec<RaceEvent> = race.events.try_iter().collect();
+        for event in events {
+            match event {
+                RaceEvent::Message(RaceMessage::Start { answer }) => {
+                    if let Err(err) = self.take_race_answer(answer) {
+                        self.show_error(format!("{err:#}"));
+                        continue;
+                    }
+                    if let Some(race) = &mut self.race {
+                        race.opponent.clear();
+                        race.opponent_result = None;
+                    }
+                    self.show_status(String::from("the host dealt a new word"));
+                }
+                RaceEvent::Message(RaceMessage::GuessResult { row }) => {
+                    if let Some(race) = &mut self.race {
+                        race.opponent.push(row);
+                    }
+                }
+                RaceEvent::Message(RaceMessage::GameOver { solved, rows }) => {
+                    if let Some(race) = &mut self.race {
+                        race.opponent_result = Some((solved, rows));
+                    }
+                    self.show_status(if solved {
+                        format!("your opponent solved it in {rows}")
+                    } else {
+                        String::from("your opponent ran out of rows")
+                    });
+                }
+                RaceEvent::Disconnected(reason) => {
+                    if let Some(race) = &mut self.race {
+                        race.disconnected = true;
+                    }
+                    self.show_error(format!("your opponent disconnected ({reason})"));
+                }
+            }
+        }
+    }
+
+    /// The new game key in a race: the host deals the next word to both
+    /// sides, the other player waits for it.
+    pub fn next_race_word(&mut self) {
+        let Some(race) = &mut self.race else {
+            return;
+        };
+        if !race.host && !race.disconnected {
+            self.show_status(String::from("the host deals the next word"));
+            return;
+        }
+        race.opponent.clear();
+        race.opponent_result = None;
+        self.game_restart();
+        self.send_race(RaceMessage::Start {
+            answer: self.answer.clone(),
+        });
+    }
+
+    /// The opponent's rows, colors without letters, `RACE_WIDTH` columns
+    /// wide at the top of `area`.
+    pub fn render_race(&self, race: &Race, area: Rect, buf: &mut Buffer) {
+        let panel = Rect {
+            width: RACE_WIDTH.min(area.width),
+            height: (self.rounds as u16 + 2).min(area.height),
+            ..area
+        };
+        // the latest rows when there are more than fit
+        let skip = race
+            .opponent
+            .len()
+            .saturating_sub(panel.height.saturating_sub(2) as usize);
+        let lines: Vec<Line> = race
+            .opponent
+            .iter()
+            .skip(skip)
+            .map(|row| {
+                let tiles: Vec<Span> = row
+                    .chars()
+                    .map(|symbol| {
+                        let state = match symbol {
+                            'G' => TileState::Correct,
+                            'Y' => TileState::Present,
+                            'B' => TileState::Absent,
+                            _ => TileState::Unused,
+                        };
+                        Span::raw("  ").bg(self.theme.tile(state).bg)
+                    })
+                    .collect();
+                Line::from(tiles).centered()
+            })
+            .collect();
+        let status = match race.opponent_result {
+            _ if race.disconnected => Line::from(" gone ").fg(Color::Red),
+            Some((true, rows)) => Line::from(format!(" solved in {rows} ")).fg(Color::Green),
+            Some((false, _)) => Line::from(" out of rows ").fg(Color::LightYellow),
+            None => Line::from(" playing ").fg(Color::DarkGray),
+        };
+        Clear.render(panel, buf);
+        Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .title(" Opponent ")
+                    .title_bottom(status.right_aligned()),
+            )
+            .render(panel, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn wait(game: &mut Wordle, until: impl Fn(&Wordle) -> bool) {
+        for _ in 0..200 {
+            game.poll_race();
+            if until(game) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("the opponent's message never arrived");
+    }
+
+    #[test]
+    fn message_format_test() {
+        let message = RaceMessage::GameOver {
+            solved: true,
+            rows: 4,
+        };
+        let json = serde_json::to_string(&message).unwrap();
+        assert_eq!(json, r#"{"type":"game_over","solved":true,"rows":4}"#);
+        assert_eq!(serde_json::from_str::<RaceMessage>(&json).unwrap(), message);
+        assert_eq!(row_colors(&Word::pass(5)), "-----");
+    }
+
+    #[test]
+    fn race_test() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let joining = thread::spawn(move || Race::join(&addr).unwrap());
+        let mut host = Wordle::new();
+        host.start_race(Race::accept(&listener).unwrap(), None)
+            .unwrap();
+        let (race, answer) = joining.join().unwrap();
+        assert_eq!(answer, host.answer);
+        let mut guest = Wordle::new();
+        guest.start_race(race, Some(answer.clone())).unwrap();
+        assert_eq!(guest.answer, host.answer);
+
+        // the host's word is only lent for the game it's played in
+        let mut dealt = Wordle::new();
+        dealt.take_race_answer(String::from("QZXVK")).unwrap();
+        assert!(dealt.parse_input("qzxvk").is_ok());
+        assert!(!dealt.accepted_words.contains("QZXVK"));
+        dealt.answer = String::from("EPOCH");
+        assert!(dealt.parse_input("qzxvk").is_err());
+
+        // rows and results show up on the other side, without letters
+        guest.current_guess = String::from(if answer == "CRANE" { "SLATE" } else { "CRANE" });
+        guest.submit_guess();
+        guest.current_guess = answer.clone();
+        guest.submit_guess();
+        wait(&mut host, |host| {
+            host.race.as_ref().unwrap().opponent_result.is_some()
+        });
+        let race = host.race.as_ref().unwrap();
+        assert_eq!(race.opponent.len(), 2);
+        assert_eq!(race.opponent[1], "GGGGG");
+        assert_eq!(race.opponent_result, Some((true, 2)));
+
+        // only the host deals
+        guest.next_race_word();
+        assert_eq!(guest.answer, answer);
+        host.next_race_word();
+        wait(&mut guest, |guest| guest.guess_history.is_empty());
+        assert_eq!(guest.answer, host.answer);
+
+        // a player leaving is a message, not a crash
+        drop(host);
+        wait(&mut guest, |guest| {
+            guest.race.as_ref().unwrap().disconnected
+        });
+        guest.current_guess = String::from("CRANE");
+        guest.submit_guess();
+        assert!(guest.messages.current().is_some());
+    }
+}
@@ -0,0 +1,263 @@
+use anyhow::{Context, Result, bail};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use wordle::engine::internal::WORD_LENGTHS;
+use wordle::engine::{Alphabet, Word, score};
+
+use super::daily::DailyRules;
+use super::storage::Storage;
+use super::timing::RowTiming;
+use super::wordle::Wordle;
+
+pub const HISTORY_FILE: &str = "history.jsonl";
+/// Matches the history log and its dated archives.
+pub const HISTORY_PREFIX: &str = "history";
+/// Size past which the history log is moved to a dated archive.
+pub const HISTORY_ARCHIVE_BYTES: u64 = 1 << 20;
+
+/// A finished game as written to the history log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    /// ties this record to the game's other artifacts; empty in records
+    /// written before ids existed
+    #[serde(default)]
+    pub game_id: String,
+    /// the commitment's secret, for checking it; written once the game is
+    /// over, so a recording made as it's played doesn't give it away
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub nonce: String,
+    pub answer: String,
+    pub guesses: Vec<String>,
+    pub solved: bool,
+    pub word_list_hash: String,
+    /// thinking/typing split per row, `null` for rows not typed from the
+    /// keyboard; empty when none were
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timings: Vec<Option<RowTiming>>,
+    /// seconds each guess was allowed, only for blitz games
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blitz_seconds: Option<u64>,
+    /// columns whose letter was revealed as a hint
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub revealed: Vec<usize>,
+    /// played without a row limit
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub zen: bool,
+    /// rules the daily was played under, only for dailies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_rules: Option<DailyRules>,
+}
+
+impl GameRecord {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        GameRecord::parse(&contents)
+            .with_context(|| format!("invalid game record {}", path.display()))
+    }
+
+    /// Accepts a single JSON object, or a history log in which case its last
+    /// entry is used.
+    pub fn parse(contents: &str) -> Result<Self> {
+        if let Ok(record) = serde_json::from_str(contents) {
+            return Ok(record);
+        }
+        let last = contents
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .context("empty game record")?;
+        Ok(serde_json::from_str(last)?)
+    }
+
+    /// Rebuilds the scored board rows from the recorded guesses. A passed
+    /// turn is recorded as a blank guess.
+    pub fn rows(&self) -> Result<Vec<Word>> {
+        let len = self.answer.chars().count();
+        // any alphabet's letters, as a language pack game records them
+        let is_valid =
+            |word: &str| word.chars().count() == len && word.chars().all(Alphabet::is_upper);
+        if !WORD_LENGTHS.contains(&len) || !is_valid(&self.answer) {
+            bail!("invalid answer '{}'", self.answer);
+        }
+
+        let mut rows = Vec::new();
+        for guess in &self.guesses {
+            if guess.chars().count() == len && guess.chars().all(|ch| ch == ' ') {
+                rows.push(Word::pass(len));
+                continue;
+            }
+            if !is_valid(guess) {
+                bail!("invalid guess '{}'", guess);
+            }
+            let mut word = Word::from(guess)?;
+            score(&self.answer, &mut word)?;
+            rows.push(word);
+        }
+        Ok(rows)
+    }
+}
+
+/// Searches the history log and its archives for the game with `game_id`.
+pub fn find_game(storage: &Storage, game_id: &str) -> Result<Option<GameRecord>> {
+    for log in storage.list(HISTORY_PREFIX)? {
+        let history = storage.load(&log)?.unwrap_or_default();
+        let found = history
+            .lines()
+            .filter_map(|line| serde_json::from_str::<GameRecord>(line).ok())
+            .find(|record| record.game_id == game_id);
+        if found.is_some() {
+            return Ok(found);
+        }
+    }
+    Ok(None)
+}
+
+/// Short fingerprint of a word list, independent of its order.
+///
+/// FNV-1a over the sorted words, so it stays stable across builds and
+/// platforms unlike `DefaultHasher`.
+pub fn word_list_hash(words: &HashSet<String>) -> String {
+    let mut sorted: Vec<&String> = words.iter().collect();
+    sorted.sort_unstable();
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for word in sorted {
+        for byte in word.bytes().chain(std::iter::once(b'\n')) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    format!("{hash:016x}")
+}
+
+impl Wordle {
+    pub fn record(&self) -> GameRecord {
+        GameRecord {
+            game_id: self.game_id.clone(),
+            nonce: self.revealed_nonce().unwrap_or_default().to_string(),
+            answer: self.answer.clone(),
+            guesses: self
+                .guess_history
+                .iter()
+                .map(|word| word.letters.iter().map(|tile| tile.letter).collect())
+                .collect(),
+            solved: self.solved,
+            word_list_hash: word_list_hash(&self.accepted_words),
+            timings: if self.row_timings.iter().any(Option::is_some) {
+                self.row_timings.clone()
+            } else {
+                Vec::new()
+            },
+            blitz_seconds: self.blitz.map(|blitz| blitz.budget.as_secs()),
+            revealed: self.revealed.iter().map(|reveal| reveal.column).collect(),
+            zen: self.zen,
+            daily_rules: self.daily_rules,
+        }
+    }
+
+    /// Appends the finished game to the history log, archiving the log once
+    /// it gets large.
+    pub fn log_game(&mut self) {
+        let line = match serde_json::to_string(&self.record()) {
+            Ok(line) => line,
+            Err(err) => {
+                self.show_error(format!("failed to record game: {err}"));
+                return;
+            }
+        };
+        if let Err(err) = self.storage.append(HISTORY_FILE, &line) {
+            self.show_error(format!("failed to record game: {err}"));
+            return;
+        }
+        let stamp = Local::now().format("%Y-%m-%d").to_string();
+        if let Err(err) =
+            self.storage
+                .archive_if_larger(HISTORY_FILE, HISTORY_ARCHIVE_BYTES, &stamp)
+        {
+            self.show_error(format!("failed to archive history: {err}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wordle::engine::TileState;
+
+    fn record(answer: &str, guesses: &[&str]) -> GameRecord {
+        GameRecord {
+            game_id: String::new(),
+            nonce: String::new(),
+            answer: answer.to_string(),
+            guesses: guesses.iter().map(|guess| guess.to_string()).collect(),
+            solved: guesses.last() == Some(&answer),
+            word_list_hash: String::from("0123456789abcdef"),
+            timings: Vec::new(),
+            blitz_seconds: None,
+            revealed: Vec::new(),
+            zen: false,
+            daily_rules: None,
+        }
+    }
+
+    #[test]
+    fn parse_object_and_log_test() {
+        let first = record("CRATE", &["SLATE", "CRATE"]);
+        let second = record("EPOCH", &["BAGEL"]);
+        let json = serde_json::to_string(&first).unwrap();
+        assert_eq!(GameRecord::parse(&json).unwrap(), first);
+
+        let log = format!("{}\n{}\n\n", json, serde_json::to_string(&second).unwrap());
+        assert_eq!(GameRecord::parse(&log).unwrap(), second);
+        assert!(GameRecord::parse("").is_err());
+        assert!(GameRecord::parse("not json").is_err());
+    }
+
+    #[test]
+    fn rows_test() {
+        let rows = record("CRATE", &["CATER"]).rows().unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].letters[0].state, TileState::Correct);
+        assert_eq!(rows[0].letters[1].state, TileState::Present);
+
+        assert!(record("CRATE", &["CAT"]).rows().is_err());
+        assert!(record("CRATE", &["cater"]).rows().is_err());
+        // other lengths are playable, but a game never mixes them
+        assert_eq!(record("CRATES", &["CRATER"]).rows().unwrap().len(), 1);
+        assert!(record("CRATES", &["CRATE"]).rows().is_err());
+        assert!(record("CRATESSSS", &[]).rows().is_err());
+    }
+
+    #[test]
+    fn word_list_hash_test() {
+        let a = HashSet::from(["CRATE".to_string(), "SLATE".to_string()]);
+        let b = HashSet::from(["SLATE".to_string(), "CRATE".to_string()]);
+        let c = HashSet::from(["SLATE".to_string()]);
+        assert_eq!(word_list_hash(&a), word_list_hash(&b));
+        assert_ne!(word_list_hash(&a), word_list_hash(&c));
+    }
+
+    #[test]
+    fn record_round_trip_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        for word in ["BAGEL", "EPOCH"] {
+            game.current_guess = word.to_string();
+            game.submit_guess();
+        }
+        let record = game.record();
+        assert_eq!(record.guesses, vec!["BAGEL", "EPOCH"]);
+        assert!(record.solved);
+        let rows = record.rows().unwrap();
+        assert_eq!(rows.len(), game.guess_history.len());
+        for (row, word) in rows.iter().zip(game.guess_history.iter()) {
+            for (a, b) in row.letters.iter().zip(word.letters.iter()) {
+                assert_eq!(a.state, b.state);
+            }
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use anyhow::{Context, Result, bail};
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use wordle::engine::Word;
+use wordle::engine::internal::WORD_LENGTHS;
+
+use super::keys::{Action, KeyBinding};
+use super::ui::InputState;
+use super::wordle::{MAX_ROUNDS, Wordle};
+
+/// A game as `--record` writes it: enough to play it back row by row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub answer: String,
+    pub seed: u64,
+    pub word_len: usize,
+    pub rounds: u8,
+    pub steps: Vec<ReplayStep>,
+}
+
+/// A submitted row and when, counted from the start of the game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayStep {
+    pub at_ms: u64,
+    pub row: Word,
+}
+
+impl Replay {
+    /// Reads a replay, refusing one whose rows don't fit its own board.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let replay: Replay = serde_json::from_str(&contents)
+            .with_context(|| format!("invalid replay {}", path.display()))?;
+        replay
+            .check()
+            .with_context(|| format!("invalid replay {}", path.display()))?;
+        Ok(replay)
+    }
+
+    fn check(&self) -> Result<()> {
+        if !WORD_LENGTHS.contains(&self.word_len) || self.answer.chars().count() != self.word_len {
+            bail!(
+                "'{}' isn't a word this game can be played with",
+                self.answer
+            );
+        }
+        if !(1..=MAX_ROUNDS).contains(&self.rounds) || self.steps.len() > self.rounds as usize {
+            bail!("{} rows on a board of {}", self.steps.len(), self.rounds);
+        }
+        if let Some(step) = self
+            .steps
+            .iter()
+            .find(|step| step.row.letters.len() != self.word_len)
+        {
+            bail!("a row of {} letters", step.row.letters.len());
+        }
+        Ok(())
+    }
+}
+
+/// Writes the game being played to a file as rows are submitted, so it
+/// survives quitting halfway.
+pub struct ReplayRecorder {
+    pub path: PathBuf,
+    pub replay: Replay,
+    started: Instant,
+}
+
+/// Steps through a replay in place of live input.
+pub struct ReplayPlayer {
+    pub steps: Vec<ReplayStep>,
+    /// index of the next row to play
+    pub next: usize,
+    /// when automatic playback would have started to reach the next row
+    /// on time, `None` while stepping by hand
+    pub playing_since: Option<Instant>,
+}
+
+impl ReplayPlayer {
+    /// How far into the game the next row was played.
+    fn next_at(&self) -> Option<Duration> {
+        self.steps
+            .get(self.next)
+            .map(|step| Duration::from_millis(step.at_ms))
+    }
+}
+
+impl Wordle {
+    /// Records every game from now on to `path`, the file holding the
+    /// latest one.
+    pub fn start_recording(&mut self, path: PathBuf) {
+        self.recorder = Some(ReplayRecorder {
+            path,
+            replay: self.empty_replay(),
+            started: Instant::now(),
+        });
+    }
+
+    fn empty_replay(&self) -> Replay {
+        Replay {
+            answer: self.answer.clone(),
+            seed: self.seed,
+            word_len: self.word_len,
+            rounds: self.rounds,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Starts a new recording for the board just dealt.
+    pub fn restart_recording(&mut self) {
+        let replay = self.empty_replay();
+        if let Some(recorder) = &mut self.recorder {
+            recorder.replay = replay;
+            recorder.started = Instant::now();
+        }
+    }
+
+    /// Adds the row just played to the recording and writes it out.
+    pub fn record_step(&mut self, row: &Word) {
+        let Some(recorder) = &mut self.recorder else {
+            return;
+        };
+        recorder.replay.steps.push(ReplayStep {
+            at_ms: recorder.started.elapsed().as_millis() as u64,
+            row: row.clone(),
+        });
+        let written = serde_json::to_string_pretty(&recorder.replay)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| Ok(fs::write(&recorder.path, json)?));
+        if let Err(err) = written {
+            let path = recorder.path.display().to_string();
+            self.show_error(format!("failed to write replay {path}: {err}"));
+        }
+    }
+
+    /// Sets the board up to play `replay` back, one row per `<Space>`.
+    pub fn start_replay(&mut self, replay: Replay) -> Result<()> {
+        if replay.word_len != self.word_len || replay.rounds != self.rounds {
+            bail!(
+                "the replay is of {}-letter words on {} rows, this board has {} and {}",
+                replay.word_len,
+                replay.rounds,
+                self.word_len,
+                self.rounds
+            );
+        }
+        self.challenge = None;
+        self.answer = replay.answer;
+        self.seed = replay.seed;
+        self.reset_board();
+        self.replay = Some(ReplayPlayer {
+            steps: replay.steps,
+            next: 0,
+            playing_since: None,
+        });
+        self.show_replay_status();
+        Ok(())
+    }
+
+    /// Plays the next recorded row, scored again against the answer.
+    pub fn replay_step(&mut self) {
+        let Some(player) = &mut self.replay else {
+            return;
+        };
+        let Some(step) = player.steps.get(player.next) else {
+            player.playing_since = None;
+            return;
+        };
+        let mut row = step.row.clone();
+        player.next += 1;
+        if !self.is_accepting_guesses() {
+            return;
+        }
+        if !row.is_pass()
+            && let Err(err) = self.check_guess(&mut row)
+        {
+            self.show_error(err.to_string());
+            return;
+        }
+        self.update_status(&row);
+        self.show_replay_status();
+    }
+
+    /// Plays rows as their recorded time comes up during automatic
+    /// playback.
+    pub fn tick_replay(&mut self, now: Instant) {
+        let Some(player) = &self.replay else {
+            return;
+        };
+        let due = match (player.playing_since, player.next_at()) {
+            (Some(since), Some(at)) => since + at <= now,
+            // stops playback at the end
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+        if due {
+            self.replay_step();
+        }
+    }
+
+    /// Whether rows are being played on their own.
+    pub fn is_replaying(&self) -> bool {
+        self.replay
+            .as_ref()
+            .is_some_and(|player| player.playing_since.is_some())
+    }
+
+    /// Keys during playback: `<Space>` or `→` plays a row, `p` starts or
+    /// stops automatic playback with the recorded timing, and quitting
+    /// works as bound. Everything else is ignored.
+    pub fn on_replay_key(&mut self, key: KeyEvent, binding: KeyBinding) -> InputState {
+        if self.keymap.action(binding) == Some(Action::Quit) {
+            return self.on_action(binding).unwrap_or(InputState::None);
+        }
+        match key.code {
+            KeyCode::Char(' ') | KeyCode::Right => self.replay_step(),
+            KeyCode::Char('p' | 'P') => {
+                let Some(player) = &mut self.replay else {
+                    return InputState::None;
+                };
+                player.playing_since = match player.playing_since {
+                    Some(_) => None,
+                    // carry on from the row on the board
+                    None => {
+                        let played = match player.next.checked_sub(1) {
+                            Some(last) => Duration::from_millis(player.steps[last].at_ms),
+                            None => Duration::ZERO,
+                        };
+                        // a recording longer than the clock goes back starts
+                        // its timing over from now
+                        let now = Instant::now();
+                        Some(now.checked_sub(played).unwrap_or(now))
+                    }
+                };
+                self.show_replay_status();
+            }
+            _ => {}
+        }
+        InputState::None
+    }
+
+    fn show_replay_status(&mut self) {
+        let Some(player) = &self.replay else {
+            return;
+        };
+        let mode = if player.playing_since.is_some() {
+            "<p> pause"
+        } else {
+            "<Space> next, <p> play"
+        };
+        let status = format!(
+            "replay: row {} of {}, {mode}",
+            player.next,
+            player.steps.len()
+        );
+        self.show_status(status);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::testing::temp_dir;
+    use ratatui::crossterm::event::KeyModifiers;
+
+    fn press(game: &mut Wordle, code: KeyCode) {
+        game.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn record_and_replay_test() {
+        let dir = temp_dir("replay");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("replay.json");
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        game.start_recording(path.clone());
+        game.restart_recording();
+        for guess in ["CRANE", "EPOCH"] {
+            game.current_guess = String::from(guess);
+            game.submit_guess();
+        }
+        let replay = Replay::load(&path).unwrap();
+        assert_eq!(replay.answer, "EPOCH");
+        assert_eq!(replay.seed, game.seed);
+        assert_eq!(replay.steps.len(), 2);
+        assert!(replay.steps[0].at_ms <= replay.steps[1].at_ms);
+
+        let mut player = Wordle::new();
+        player.start_replay(replay).unwrap();
+        // typing does nothing, space plays the next row
+        press(&mut player, KeyCode::Char('x'));
+        press(&mut player, KeyCode::Enter);
+        assert!(player.guess_history.is_empty());
+        assert!(player.current_guess.is_empty());
+        press(&mut player, KeyCode::Char(' '));
+        assert_eq!(player.guess_history.len(), 1);
+        assert!(!player.is_game_over);
+
+        // automatic playback keeps to the recorded timing
+        press(&mut player, KeyCode::Char('p'));
+        assert!(player.is_replaying());
+        player.tick_replay(Instant::now() + Duration::from_secs(60));
+        assert!(player.solved && player.is_game_over);
+        player.tick_replay(Instant::now() + Duration::from_secs(60));
+        assert!(!player.is_replaying());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn long_recording_test() {
+        let mut game = Wordle::new();
+        let replay = Replay {
+            answer: game.answer.clone(),
+            seed: 0,
+            word_len: 5,
+            rounds: 6,
+            steps: vec![
+                ReplayStep {
+                    at_ms: u64::MAX,
+                    row: Word::from("CRANE").unwrap(),
+                };
+                2
+            ],
+        };
+        game.start_replay(replay).unwrap();
+        press(&mut game, KeyCode::Char(' '));
+        press(&mut game, KeyCode::Char('p'));
+        assert!(game.is_replaying());
+    }
+
+    #[test]
+    fn mismatched_replay_test() {
+        let replay = Replay {
+            answer: String::from("EPOCHS"),
+            seed: 0,
+            word_len: 6,
+            rounds: 6,
+            steps: Vec::new(),
+        };
+        let mut game = Wordle::new();
+        let err = game.start_replay(replay.clone()).unwrap_err();
+        assert!(err.to_string().contains("6-letter words"));
+
+        let too_long = Replay {
+            steps: vec![
+                ReplayStep {
+                    at_ms: 0,
+                    row: Word::from("CRANES").unwrap(),
+                };
+                7
+            ],
+            ..replay
+        };
+        assert!(too_long.check().is_err());
+    }
+}
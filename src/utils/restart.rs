@@ -0,0 +1,160 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Rect},
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+use std::io;
+
+use super::ui::InputState;
+use super::wordle::Wordle;
+
+impl Wordle {
+    /// Whether starting a new game would throw away rows already played,
+    /// so the player is asked first. A finished game restarts straight
+    /// away, and a daily never restarts.
+    pub fn restart_needs_confirmation(&self) -> bool {
+        !self.guess_history.is_empty() && !self.is_game_over && self.daily.is_none()
+    }
+
+    /// Keys while the restart prompt is open: Enter or `y` abandons the
+    /// game for a new one, Esc or `n` goes back to it.
+    pub fn on_restart_key(&mut self, key: KeyEvent) -> InputState {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('Y') => {
+                self.confirm_restart = false;
+                self.abandon_game();
+                self.game_restart();
+            }
+            KeyCode::Esc | KeyCode::Char('N') => self.confirm_restart = false,
+            _ => {}
+        }
+        InputState::None
+    }
+
+    /// Counts the game in progress as a loss when `abandon_counts_as_loss`
+    /// is set in `config.json`; otherwise, and always in hot seat, it is
+    /// left out of the stats.
+    fn abandon_game(&mut self) {
+        if !self.config.abandon_counts_as_loss || self.hot_seat.is_some() {
+            return;
+        }
+        let (_, saved) = self.stats.record_shared(
+            &self.storage,
+            false,
+            self.guess_history.len(),
+            &self.row_timings,
+            None,
+            self.blitz.is_some(),
+            self.zen,
+            self.revealed.len(),
+        );
+        match saved {
+            Err(err) if err.kind() == io::ErrorKind::TimedOut => {
+                self.show_error("stats are in use by another instance; the forfeit wasn't saved")
+            }
+            Err(err) => self.show_error(format!("failed to save stats: {err}")),
+            Ok(_) => {}
+        }
+    }
+
+    pub fn render_restart_prompt(&self, area: Rect, buf: &mut Buffer) {
+        let lines = vec![
+            Line::from("Abandon this puzzle?").bold(),
+            Line::from(""),
+            Line::from(vec![
+                Span::raw("Yes "),
+                "<Enter>".blue().bold(),
+                Span::raw(" No "),
+                "<Esc>".blue().bold(),
+            ]),
+        ];
+        let popup = area.centered(Constraint::Length(30), Constraint::Length(5));
+        Clear.render(popup, buf);
+        Paragraph::new(lines)
+            .centered()
+            .fg(Color::White)
+            .block(
+                Block::bordered()
+                    .title("New game")
+                    .border_type(BorderType::Rounded),
+            )
+            .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::storage::Storage;
+    use crate::utils::testing::temp_dir;
+    use ratatui::crossterm::event::KeyModifiers;
+    use std::fs;
+
+    fn press(game: &mut Wordle, code: KeyCode) {
+        game.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn guess(game: &mut Wordle, word: &str) {
+        game.current_guess = String::from(word);
+        game.submit_guess();
+    }
+
+    #[test]
+    fn restart_prompt_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        // nothing to lose yet
+        press(&mut game, KeyCode::Tab);
+        assert!(!game.confirm_restart);
+        assert_ne!(game.answer, "EPOCH");
+
+        game.answer = String::from("EPOCH");
+        guess(&mut game, "CRANE");
+        press(&mut game, KeyCode::Tab);
+        assert!(game.confirm_restart);
+        // the board doesn't see keys meanwhile
+        press(&mut game, KeyCode::Char('s'));
+        assert!(game.confirm_restart && game.current_guess.is_empty());
+        press(&mut game, KeyCode::Esc);
+        assert!(!game.confirm_restart);
+        assert_eq!(game.answer, "EPOCH");
+        assert_eq!(game.guess_history.len(), 1);
+
+        press(&mut game, KeyCode::Tab);
+        press(&mut game, KeyCode::Char('n'));
+        assert_eq!(game.guess_history.len(), 1);
+        press(&mut game, KeyCode::Tab);
+        press(&mut game, KeyCode::Char('y'));
+        assert!(!game.confirm_restart);
+        assert!(game.guess_history.is_empty());
+        assert_ne!(game.answer, "EPOCH");
+        // left out of the stats by default
+        assert_eq!(game.stats.games_played, 0);
+
+        // a finished game restarts straight away
+        game.answer = String::from("EPOCH");
+        guess(&mut game, "EPOCH");
+        press(&mut game, KeyCode::Tab);
+        assert!(!game.confirm_restart && game.guess_history.is_empty());
+    }
+
+    #[test]
+    fn forfeit_test() {
+        let dir = temp_dir("forfeit");
+        let mut game = Wordle::new();
+        game.storage = Storage::open(dir.clone());
+        game.config.abandon_counts_as_loss = true;
+        game.stats.record_game(true, 3);
+        game.answer = String::from("EPOCH");
+        guess(&mut game, "CRANE");
+        press(&mut game, KeyCode::Tab);
+        press(&mut game, KeyCode::Enter);
+        assert_eq!(game.stats.games_played, 2);
+        assert_eq!(game.stats.games_won, 1);
+        assert_eq!(game.stats.current_streak, 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
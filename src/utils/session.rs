@@ -0,0 +1,209 @@
+use chrono::Local;
+use ratatui::crossterm::terminal;
+use std::fs::{self, File, TryLockError};
+use std::io::{self, Write};
+use std::panic;
+use std::path::PathBuf;
+use std::process;
+use std::sync::Once;
+
+use super::daily::slot_name;
+use super::events::bracketed_paste;
+use super::mouse::capture_mouse;
+use super::storage::Storage;
+use super::wordle::Wordle;
+
+/// Marks a running TUI session; left behind when the process dies without
+/// restoring the terminal.
+pub const SESSION_FILE: &str = ".session";
+/// Set once the panic hook is in place, so it's only chained once.
+static PANIC_HOOK: Once = Once::new();
+
+/// Full terminal reset (RIS), clearing raw-mode leftovers such as
+/// half-finished escape sequences.
+const RESET: &[u8] = b"\x1bc";
+
+/// The marker of this session, locked for as long as the session runs so
+/// other instances can tell a live marker from a stale one.
+pub struct Session {
+    /// `None` when there's no data directory or another instance owns the
+    /// marker
+    marker: Option<(File, PathBuf)>,
+}
+
+impl Session {
+    /// Writes the marker, returning whether a previous session left one
+    /// behind. A marker still locked by a running instance isn't stale.
+    pub fn begin(storage: &Storage) -> io::Result<(Session, bool)> {
+        let Some(path) = storage.path(SESSION_FILE) else {
+            return Ok((Session { marker: None }, false));
+        };
+        let existed = path.exists();
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => return Ok((Session { marker: None }, false)),
+            Err(TryLockError::Error(err)) => return Err(err),
+        }
+        file.set_len(0)?;
+        writeln!(
+            file,
+            "pid {} started {}",
+            process::id(),
+            Local::now().format("%Y-%m-%d %H:%M:%S")
+        )?;
+        Ok((
+            Session {
+                marker: Some((file, path)),
+            },
+            existed,
+        ))
+    }
+
+    /// Removes the marker after the terminal has been restored.
+    pub fn end(self) -> io::Result<()> {
+        match self.marker {
+            Some((file, path)) => {
+                drop(file);
+                fs::remove_file(path)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Lets go of the marker without removing it, as a killed process would.
+    #[cfg(test)]
+    fn abandon(self) {}
+}
+
+/// Puts a terminal left in raw mode by a killed session back to normal.
+pub fn reset_terminal() -> io::Result<()> {
+    let _ = terminal::disable_raw_mode();
+    let mut stdout = io::stdout();
+    stdout.write_all(RESET)?;
+    stdout.flush()
+}
+
+/// Makes a panic, even one inside a draw, put the terminal back before
+/// the panic is printed, so the message is readable and the shell isn't
+/// left in raw mode on the alternate screen. Installed when a screen is
+/// taken over, so `--json` and `--bot` runs keep the default hook.
+pub fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let _ = capture_mouse(false);
+            let _ = bracketed_paste(false);
+            ratatui::restore();
+            previous(info);
+        }));
+    });
+}
+
+/// Whether `install_panic_hook` has run.
+#[cfg(test)]
+pub fn panic_hook_installed() -> bool {
+    PANIC_HOOK.is_completed()
+}
+
+impl Wordle {
+    /// Notice for the start of a session after one that exited uncleanly,
+    /// pointing at today's daily save if the new session isn't resuming it.
+    pub fn unclean_exit_notice(&self) -> String {
+        let mut notice =
+            String::from("the last session didn't exit cleanly, so the terminal was reset");
+        let today = Local::now().date_naive();
+        if self.daily.is_none()
+            && self
+                .storage
+                .path(&slot_name(today))
+                .is_some_and(|path| path.exists())
+        {
+            notice.push_str("; run with --daily to resume today's puzzle");
+        }
+        notice
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::events::AppEvent;
+    use crate::utils::testing::temp_dir;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn marker_lifecycle_test() {
+        let dir = temp_dir("session_marker");
+        let storage = Storage::open(dir.clone());
+
+        let (session, stale) = Session::begin(&storage).unwrap();
+        assert!(!stale);
+        assert!(dir.join(SESSION_FILE).exists());
+        // a second instance running alongside isn't mistaken for a crash
+        let (other, stale) = Session::begin(&storage).unwrap();
+        assert!(!stale);
+        other.end().unwrap();
+        assert!(dir.join(SESSION_FILE).exists());
+
+        // killed: the lock goes away with the process, the marker stays
+        session.abandon();
+        let (session, stale) = Session::begin(&storage).unwrap();
+        assert!(stale);
+        session.end().unwrap();
+        assert!(!dir.join(SESSION_FILE).exists());
+
+        let (session, stale) = Session::begin(&storage).unwrap();
+        assert!(!stale);
+        session.end().unwrap();
+        assert!(storage.list("").unwrap().is_empty());
+
+        let (session, stale) = Session::begin(&Storage::in_memory("test")).unwrap();
+        assert!(!stale);
+        session.end().unwrap();
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn notice_points_at_daily_test() {
+        let dir = temp_dir("session_notice");
+        let mut game = Wordle::new();
+        game.storage = Storage::open(dir.clone());
+        assert!(!game.unclean_exit_notice().contains("--daily"));
+
+        game.storage
+            .save(&slot_name(Local::now().date_naive()), "{}")
+            .unwrap();
+        assert!(game.unclean_exit_notice().contains("--daily"));
+        game.daily = Some(Local::now().date_naive());
+        assert!(!game.unclean_exit_notice().contains("--daily"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn interrupt_quits_test() {
+        install_panic_hook();
+        // a second call doesn't chain the hook again
+        install_panic_hook();
+        assert!(panic_hook_installed());
+
+        // Ctrl+C quits even from a page that keeps other keys to itself
+        let mut game = Wordle::new();
+        game.open_settings();
+        let ctrl_c = AppEvent::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        let mut events = VecDeque::from([ctrl_c, AppEvent::Char('b')]);
+        let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+        assert!(game.event_loop(&mut terminal, &mut events).is_ok());
+        assert_eq!(events, [AppEvent::Char('b')]);
+        assert!(game.show_settings);
+    }
+}
@@ -0,0 +1,279 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Rect},
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+
+use super::config::Config;
+use super::keys::{Action, KeyBinding, KeysPage};
+use super::messages::Level;
+use super::theme::{HIGH_CONTRAST, THEMES};
+use super::ui::InputState;
+use super::wordle::Wordle;
+
+impl Wordle {
+    pub fn open_settings(&mut self) {
+        self.show_settings = true;
+        self.settings_cursor = THEMES
+            .iter()
+            .position(|entry| entry.id == self.theme_id)
+            .unwrap_or(0);
+    }
+
+    /// Keys while the settings screen is open; nothing reaches the board.
+    pub fn on_settings_key(&mut self, key: KeyEvent, binding: KeyBinding) -> InputState {
+        if key.code == KeyCode::Esc || self.keymap.action(binding) == Some(Action::Settings) {
+            self.show_settings = false;
+            return InputState::None;
+        }
+        match key.code {
+            KeyCode::Up => self.settings_cursor = self.settings_cursor.saturating_sub(1),
+            KeyCode::Down => {
+                self.settings_cursor = (self.settings_cursor + 1).min(THEMES.len() - 1);
+            }
+            KeyCode::Enter => self.select_theme(self.settings_cursor),
+            KeyCode::Char('W') => self.open_word_lists(),
+            KeyCode::Char('M') => self.show_messages = true,
+            KeyCode::Char('B') => self.keys_page = Some(KeysPage::default()),
+            KeyCode::Char('Z') => self.set_layout(self.config.layout.next()),
+            KeyCode::Char('K') if self.config.no_key_stats => {
+                self.show_error("key presses aren't counted (no_key_stats in config.json)");
+            }
+            KeyCode::Char('K') => self.show_heatmap = !self.show_heatmap,
+            KeyCode::Char('H')
+                if !self.rules_locked() && !self.guess_history.is_empty() && !self.is_game_over =>
+            {
+                self.show_error("hard mode can only be changed before the first guess");
+            }
+            KeyCode::Char('H') => self.toggle_rule("hard mode", |config| &mut config.hard_mode),
+            KeyCode::Char('A') => self.toggle_rule("assists", |config| &mut config.no_assists),
+            _ => {}
+        }
+        InputState::None
+    }
+
+    /// Flips a setting that changes the rules, unless a daily in progress
+    /// locked them.
+    fn toggle_rule(&mut self, name: &str, setting: fn(&mut Config) -> &mut bool) {
+        if self.rules_locked() {
+            self.show_error(format!(
+                "{name} is locked until {} is finished",
+                self.rules_locked_by()
+            ));
+            return;
+        }
+        let value = setting(&mut self.config);
+        *value = !*value;
+        self.messages.clear(Level::Error);
+        if let Err(err) = self.config.save(&self.storage) {
+            self.show_error(format!("failed to save config: {err}"));
+        }
+    }
+
+    /// Switches to the high contrast theme, or back to the one it replaced.
+    pub fn toggle_high_contrast(&mut self) {
+        let target = if self.theme_id == HIGH_CONTRAST {
+            self.previous_theme
+        } else {
+            self.previous_theme = self.theme_id;
+            HIGH_CONTRAST
+        };
+        if let Some(index) = THEMES.iter().position(|entry| entry.id == target) {
+            self.select_theme(index);
+        }
+    }
+
+    /// Applies the theme at `index` unless it is still locked.
+    pub fn select_theme(&mut self, index: usize) {
+        let Some(entry) = THEMES.get(index) else {
+            return;
+        };
+        if entry.is_locked(&self.stats.unlocked_themes) {
+            self.show_error(format!(
+                "{} unlocks at a {}-game win streak",
+                entry.name,
+                entry.unlock_streak.unwrap_or_default()
+            ));
+            return;
+        }
+        self.messages.clear(Level::Error);
+        self.theme = entry.theme(self.background, &self.palettes);
+        self.theme_id = entry.id;
+    }
+
+    pub fn render_settings_page(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let mut lines = vec![Line::from("Theme").bold(), Line::from("")];
+        for (index, entry) in THEMES.iter().enumerate() {
+            let cursor = if index == self.settings_cursor {
+                "> "
+            } else {
+                "  "
+            };
+            let line = if entry.is_locked(&self.stats.unlocked_themes) {
+                Line::from(vec![
+                    Span::raw(cursor),
+                    Span::raw(entry.name),
+                    Span::raw(format!(
+                        "  locked: reach a {}-game streak",
+                        entry.unlock_streak.unwrap_or_default()
+                    )),
+                ])
+                .fg(Color::DarkGray)
+            } else if entry.id == self.theme_id {
+                Line::from(vec![
+                    Span::raw(cursor),
+                    Span::raw(entry.name).bold(),
+                    Span::raw("  (active)").fg(Color::Green),
+                ])
+            } else {
+                Line::from(vec![Span::raw(cursor), Span::raw(entry.name)])
+            };
+            lines.push(line);
+        }
+        lines.push(Line::from(""));
+        let on_off = |on: bool| if on { "on" } else { "off" };
+        let lock = if self.rules_locked() {
+            format!("  (locked for {})", self.rules_locked_by())
+        } else {
+            String::new()
+        };
+        lines.push(Line::from(format!(
+            "Hard mode {}  Assists {}{lock}",
+            on_off(self.hard_mode()),
+            on_off(self.assists_allowed())
+        )));
+        lines.push(Line::from(format!(
+            "Layout {}  Keyboard {}  Key heatmap {}",
+            self.config.layout.name(),
+            self.keyboard_name(),
+            on_off(self.show_heatmap)
+        )));
+        if let Some(error) = self.messages.error() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(error).fg(Color::Red));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::raw("Select "),
+            "<Enter>".blue().bold(),
+            Span::raw(" Word lists "),
+            "<w>".blue().bold(),
+            Span::raw(" Messages "),
+            "<m>".blue().bold(),
+            Span::raw(" Hard mode "),
+            "<h>".blue().bold(),
+            Span::raw(" Assists "),
+            "<a>".blue().bold(),
+            Span::raw(" Layout "),
+            "<z>".blue().bold(),
+            Span::raw(" Heatmap "),
+            "<k>".blue().bold(),
+            Span::raw(" Keys "),
+            "<b>".blue().bold(),
+            Span::raw(" Back "),
+            "<Esc>".blue().bold(),
+        ]));
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!("game {}", self.game_id)).fg(Color::DarkGray));
+        if self.is_game_over {
+            // the game is over, so the commitment's inputs can be revealed
+            lines.push(
+                Line::from(format!(
+                    "commitment {} = sha256({} + nonce)",
+                    self.commitment(),
+                    self.answer
+                ))
+                .fg(Color::DarkGray),
+            );
+            lines.push(Line::from(format!("nonce {}", self.nonce)).fg(Color::DarkGray));
+            if let Some(code) = self.challenge_code() {
+                lines.push(Line::from(format!("challenge a friend: {code}")).fg(Color::DarkGray));
+            }
+        } else {
+            lines.push(Line::from(format!("commitment {}", self.commitment())).fg(Color::DarkGray));
+        }
+
+        Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .title("Settings")
+                    .border_type(BorderType::Rounded),
+            )
+            .render(area, buf);
+    }
+
+    /// Popup announcing themes unlocked by the game that just ended.
+    pub fn render_celebration(&self, message: &str, area: Rect, buf: &mut Buffer) {
+        let popup = area.centered(
+            Constraint::Length(message.chars().count() as u16 + 4),
+            Constraint::Length(3),
+        );
+        Clear.render(popup, buf);
+        Paragraph::new(message)
+            .bold()
+            .fg(Color::Yellow)
+            .centered()
+            .block(Block::bordered().border_type(BorderType::Rounded))
+            .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::theme::{Background, Theme};
+    use ratatui::crossterm::event::KeyModifiers;
+    use wordle::engine::TileState;
+
+    fn press(game: &mut Wordle, code: KeyCode) {
+        game.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn locked_theme_not_selectable_test() {
+        let mut game = Wordle::new();
+        game.stats.unlocked_themes.clear();
+        press(&mut game, KeyCode::F(2));
+        assert!(game.show_settings);
+
+        press(&mut game, KeyCode::Down);
+        press(&mut game, KeyCode::Enter);
+        assert_eq!(game.theme_id, "classic");
+        assert!(game.messages.error().is_some());
+
+        game.stats.unlocked_themes.insert("ocean".to_string());
+        press(&mut game, KeyCode::Enter);
+        assert_eq!(game.theme_id, "ocean");
+        assert_eq!(game.theme, THEMES[1].theme(game.background, &game.palettes));
+
+        // Esc leaves settings instead of quitting
+        press(&mut game, KeyCode::Esc);
+        assert!(!game.show_settings);
+    }
+
+    #[test]
+    fn high_contrast_toggle_test() {
+        let mut game = Wordle::new();
+        game.stats.unlocked_themes.insert("ocean".to_string());
+        game.select_theme(1);
+        press(&mut game, KeyCode::F(6));
+        assert_eq!(game.theme_id, HIGH_CONTRAST);
+        assert_eq!(game.theme.correct.bg, Color::Rgb(245, 121, 58));
+        assert_eq!(
+            game.theme.key(TileState::Present).bg,
+            Color::Rgb(133, 192, 249)
+        );
+        press(&mut game, KeyCode::F(6));
+        assert_eq!(game.theme_id, "ocean");
+
+        // and has a light variant
+        press(&mut game, KeyCode::F(6));
+        game.set_background(Background::Light);
+        assert_eq!(game.theme, Theme::high_contrast_light());
+    }
+}
@@ -0,0 +1,150 @@
+use wordle::engine::TileState;
+
+use super::elapsed::stopwatch;
+use super::integrations::{Effect, Outcome};
+use super::wordle::Wordle;
+
+/// A tile in a shared grid: emoji squares, or `G`/`Y`/`B` for places that
+/// can't show them.
+pub fn tile_symbol(state: TileState, emoji: bool) -> &'static str {
+    match (state, emoji) {
+        (TileState::Correct, true) => "🟩",
+        (TileState::Present, true) => "🟨",
+        (_, true) => "⬛",
+        (TileState::Correct, false) => "G",
+        (TileState::Present, false) => "Y",
+        (_, false) => "B",
+    }
+}
+
+/// A tile of a passed row, which has no colors to give away.
+pub fn pass_symbol(emoji: bool) -> &'static str {
+    if emoji { "⬜" } else { "-" }
+}
+
+impl Wordle {
+    /// The result to paste to friends: a title such as `Wordle #1945 4/6*`
+    /// and one line of colors per row, never the letters. Emoji unless
+    /// `share_ascii` is set in `config.json`.
+    pub fn share_text(&self) -> String {
+        let emoji = !self.config.share_ascii;
+        let guesses = if self.solved {
+            self.guess_history.len().to_string()
+        } else {
+            String::from("X")
+        };
+        let marker = if self.hard_mode() { "*" } else { "" };
+        let title = self.daily_title().unwrap_or_else(|| String::from("Wordle"));
+        let rounds = if self.zen {
+            String::from("∞")
+        } else {
+            self.rounds.to_string()
+        };
+        let mut text = format!("{title} {guesses}/{rounds}{marker}");
+        match self.revealed.len() {
+            0 => {}
+            1 => text.push_str(" (1 letter revealed)"),
+            count => text.push_str(&format!(" ({count} letters revealed)")),
+        }
+        if let Some(took) = self.game_clock.finished.filter(|_| self.config.share_time) {
+            text.push_str(&format!(" in {}", stopwatch(took)));
+        }
+        text.push('\n');
+        for row in &self.guess_history {
+            text.push('\n');
+            for tile in &row.letters {
+                text.push_str(if row.is_pass() {
+                    pass_symbol(emoji)
+                } else {
+                    tile_symbol(tile.state, emoji)
+                });
+            }
+        }
+        text
+    }
+
+    /// Copies the share text to the clipboard, through the integrations
+    /// executor so a dry run shows it instead.
+    pub fn share(&mut self) {
+        let effect = Effect::Clipboard {
+            integration: "share",
+            text: self.share_text(),
+        };
+        match self.integrations.execute(&effect) {
+            Ok(Outcome::Executed) => self.show_status(String::from("copied!")),
+            Ok(Outcome::Captured) => {
+                self.integration_preview =
+                    Some(vec![effect.describe(&self.answer, self.config.privacy)]);
+            }
+            Err(err) => self.show_error(format!("{:#}", err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::integrations::DryRunExecutor;
+    use chrono::NaiveDate;
+    use std::time::Duration;
+
+    fn play(game: &mut Wordle, guesses: &[&str]) {
+        for guess in guesses {
+            game.current_guess = guess.to_string();
+            game.submit_guess();
+        }
+    }
+
+    #[test]
+    fn share_text_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        play(&mut game, &["CRANE", "CHEAP"]);
+        game.pass_turn();
+        play(&mut game, &["EPOCH"]);
+        assert_eq!(
+            game.share_text(),
+            "Wordle 4/6\n\n🟨⬛⬛⬛🟨\n🟨🟨🟨⬛🟨\n⬜⬜⬜⬜⬜\n🟩🟩🟩🟩🟩"
+        );
+        assert!(!game.share_text().contains('C'));
+
+        game.config.share_ascii = true;
+        assert_eq!(
+            game.share_text(),
+            "Wordle 4/6\n\nYBBBY\nYYYBY\n-----\nGGGGG"
+        );
+
+        // the time only when asked for
+        game.game_clock.finished = Some(Duration::from_secs(83));
+        assert!(game.share_text().starts_with("Wordle 4/6\n"));
+        game.config.share_time = true;
+        assert!(game.share_text().starts_with("Wordle 4/6 in 1:23\n"));
+    }
+
+    #[test]
+    fn daily_share_test() {
+        let mut game = Wordle::new();
+        game.config.hard_mode = true;
+        game.start_daily(NaiveDate::from_ymd_opt(2026, 10, 16).unwrap());
+        let answer = game.answer.clone();
+        let wrong = if answer == "CRANE" { "SLATE" } else { "CRANE" };
+        play(&mut game, &[wrong; 6]);
+        let text = game.share_text();
+        assert!(text.starts_with("Wordle #1945 X/6*\n\n"), "{text}");
+        assert_eq!(text.lines().count(), 8);
+    }
+
+    #[test]
+    fn copy_goes_through_executor_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        play(&mut game, &["EPOCH"]);
+        game.integrations = Box::new(DryRunExecutor::default());
+        game.share();
+        let preview = game.integration_preview.clone().unwrap();
+        assert_eq!(
+            preview,
+            vec!["[share] copy to clipboard: Wordle 1/6 🟩🟩🟩🟩🟩"]
+        );
+    }
+}
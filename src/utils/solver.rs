@@ -0,0 +1,398 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+use std::collections::HashMap;
+use wordle::engine::internal::WORD_LENGTHS;
+use wordle::engine::{Alphabet, TileState, Word};
+
+use super::wordle::Wordle;
+
+/// Guesses listed in the solver panel.
+pub const SOLVER_COUNT: usize = 5;
+/// Width of the solver panel, borders included.
+pub const SOLVER_WIDTH: u16 = 20;
+/// Past this many guess and candidate pairs, scoring every guess against
+/// every candidate is too slow to do on a key press, so the ranking
+/// estimates from letter counts instead.
+const EXACT_LIMIT: usize = 200 * 200;
+
+/// Guesses with the bits of information each is expected to give.
+pub type RankedGuesses = Vec<(String, f64)>;
+
+/// Expected bits of information from guessing `word` when the answer is one
+/// of `candidates`: the entropy of the feedback patterns it would get.
+pub fn expected_information(word: &str, candidates: &[&str]) -> f64 {
+    let mut patterns: HashMap<u32, usize> = HashMap::new();
+    for candidate in candidates {
+        *patterns
+            .entry(feedback_pattern(word, candidate))
+            .or_default() += 1;
+    }
+    entropy(patterns.into_values(), candidates.len())
+}
+
+/// The colors `guess` gets against `answer` by the rules of `score`, as one
+/// base-3 digit per tile: 2 for green, 1 for yellow, 0 for gray. Goes by
+/// letters rather than bytes, so Ñ is one tile, and doesn't allocate, as
+/// the solver compares every candidate with every other.
+pub fn feedback_pattern(guess: &str, answer: &str) -> u32 {
+    const LONGEST: usize = *WORD_LENGTHS.end();
+    let mut digits = [0u8; LONGEST];
+    // answer letters not matched in place, with how many of each are left
+    let mut unmatched = [('\0', 0u8); LONGEST];
+    let mut kinds = 0;
+    let mut len = 0;
+    for (column, (guessed, letter)) in guess.chars().zip(answer.chars()).take(LONGEST).enumerate() {
+        len = column + 1;
+        if guessed == letter {
+            digits[column] = 2;
+        } else if let Some(entry) = unmatched[..kinds]
+            .iter_mut()
+            .find(|(known, _)| *known == letter)
+        {
+            entry.1 += 1;
+        } else {
+            unmatched[kinds] = (letter, 1);
+            kinds += 1;
+        }
+    }
+    for (column, guessed) in guess.chars().take(len).enumerate() {
+        if digits[column] == 0
+            && let Some(entry) = unmatched[..kinds]
+                .iter_mut()
+                .find(|(known, left)| *known == guessed && *left > 0)
+        {
+            digits[column] = 1;
+            entry.1 -= 1;
+        }
+    }
+    digits[..len]
+        .iter()
+        .fold(0, |pattern, &digit| pattern * 3 + digit as u32)
+}
+
+/// The colors of a scored row, numbered like `feedback_pattern`.
+pub fn row_pattern(row: &Word) -> u32 {
+    row.letters.iter().fold(0, |pattern, tile| {
+        pattern * 3
+            + match tile.state {
+                TileState::Correct => 2,
+                TileState::Present => 1,
+                _ => 0,
+            }
+    })
+}
+
+/// Bits of information from learning which of `counts` an outcome fell in,
+/// out of `total`.
+fn entropy(counts: impl Iterator<Item = usize>, total: usize) -> f64 {
+    counts
+        .filter(|&count| count > 0)
+        .map(|count| {
+            let p = count as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// A quick stand-in for `expected_information`: each distinct letter of
+/// `word` counted as a yes/no question about the candidates, as if letters
+/// were independent. `containing` counts the candidates with each letter
+/// of `alphabet`.
+fn letter_information(word: &str, alphabet: &Alphabet, containing: &[usize], total: usize) -> f64 {
+    let mut seen = vec![false; containing.len()];
+    word.chars()
+        .filter_map(|letter| alphabet.index(letter))
+        .filter(|&index| !std::mem::replace(&mut seen[index], true))
+        .map(|index| {
+            let count = containing[index];
+            entropy([count, total - count].into_iter(), total)
+        })
+        .sum()
+}
+
+/// How many of `words` have each letter of `alphabet`, in the alphabet's
+/// order.
+pub fn letter_counts(words: &[&str], alphabet: &Alphabet) -> Vec<usize> {
+    let mut containing = vec![0usize; alphabet.letters().len()];
+    for word in words {
+        let mut seen = vec![false; containing.len()];
+        for index in word.chars().filter_map(|letter| alphabet.index(letter)) {
+            seen[index] = true;
+        }
+        for (count, seen) in containing.iter_mut().zip(seen) {
+            *count += seen as usize;
+        }
+    }
+    containing
+}
+
+/// The best `limit` of `words`, spelled with `alphabet`, to guess next when
+/// the answer is one of `candidates`, with their expected information in
+/// bits, best first; ties go to the alphabetically first. The solver and
+/// the bot rank the candidates themselves, the suggestion panel any word.
+pub fn rank_guesses(
+    words: &[&str],
+    candidates: &[&str],
+    alphabet: &Alphabet,
+    limit: usize,
+) -> RankedGuesses {
+    let containing = letter_counts(candidates, alphabet);
+    let exact = words.len().saturating_mul(candidates.len()) <= EXACT_LIMIT;
+    let mut ranked: RankedGuesses = words
+        .iter()
+        .map(|word| {
+            let bits = if exact {
+                expected_information(word, candidates)
+            } else {
+                letter_information(word, alphabet, &containing, candidates.len())
+            };
+            (word.to_string(), bits)
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked.truncate(limit);
+    ranked
+}
+
+impl Wordle {
+    /// Shows or hides the solver panel; showing it counts as an assist.
+    pub fn toggle_solver(&mut self) {
+        if self.show_solver {
+            self.show_solver = false;
+            return;
+        }
+        if !self.assists_allowed() {
+            self.show_error("assists are turned off");
+            return;
+        }
+        self.assists_used += 1;
+        self.show_solver = true;
+    }
+
+    /// The next guesses worth making given everything the board shows, with
+    /// their expected information in bits. Worked out once per row and
+    /// kept until the next one is played.
+    pub fn suggest_guesses(&self) -> RankedGuesses {
+        let rows = self.guess_history.len();
+        let mut cache = self.solver_cache.borrow_mut();
+        if let Some((cached_rows, guesses)) = cache.as_ref()
+            && *cached_rows == rows
+        {
+            return guesses.clone();
+        }
+        let candidates = self.candidates();
+        let guesses = rank_guesses(&candidates, &candidates, &self.alphabet, SOLVER_COUNT);
+        *cache = Some((rows, guesses.clone()));
+        guesses
+    }
+
+    /// How many answers still fit the board. Counted once per row, like the
+    /// solver's guesses, as it is drawn on every frame.
+    pub fn remaining_candidates(&self) -> usize {
+        let rows = self.guess_history.len();
+        if let Some((cached_rows, count)) = self.remaining_cache.get()
+            && cached_rows == rows
+        {
+            return count;
+        }
+        let count = self.candidates().len();
+        self.remaining_cache.set(Some((rows, count)));
+        count
+    }
+
+    /// Shows or hides the count of answers left, which some consider a
+    /// spoiler, and remembers the choice in `config.json`.
+    pub fn toggle_remaining(&mut self) {
+        if !self.config.show_remaining && !self.assists_allowed() {
+            self.show_error("assists are turned off");
+            return;
+        }
+        self.config.show_remaining = !self.config.show_remaining;
+        if let Err(err) = self.config.save(&self.storage) {
+            self.show_error(format!("failed to save config: {err}"));
+        }
+    }
+
+    /// Whether the count of answers left takes a line under the messages.
+    pub fn shows_remaining(&self) -> bool {
+        self.config.show_remaining && self.assists_allowed()
+    }
+
+    pub fn render_remaining(&self, area: Rect, buf: &mut Buffer) {
+        Line::from(format!(
+            "possible answers remaining: {}",
+            self.remaining_candidates()
+        ))
+        .fg(Color::DarkGray)
+        .render(area, buf);
+    }
+
+    /// The solver panel, `SOLVER_WIDTH` columns wide at the top of `area`.
+    pub fn render_solver(&self, area: Rect, buf: &mut Buffer) {
+        let guesses = self.suggest_guesses();
+        let panel = Rect {
+            width: SOLVER_WIDTH.min(area.width),
+            height: (SOLVER_COUNT as u16 + 2).min(area.height),
+            ..area
+        };
+        let mut lines: Vec<Line> = guesses
+            .iter()
+            .map(|(word, bits)| Line::from(format!("{word:<8} {bits:>5.2}")))
+            .collect();
+        if lines.is_empty() {
+            lines.push(Line::from("no words fit").fg(Color::DarkGray));
+        }
+        Clear.render(panel, buf);
+        Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .title(" Solver ")
+                    .title_bottom(Line::from(" bits ").fg(Color::DarkGray).right_aligned()),
+            )
+            .render(panel, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratatui::{Terminal, backend::TestBackend};
+    use wordle::engine::is_consistent;
+
+    #[test]
+    fn information_test() {
+        // CRATE tells all four apart, CRANE leaves GRATE and GRAPE together
+        let candidates = ["CRANE", "CRATE", "GRATE", "GRAPE"];
+        assert_eq!(expected_information("CRANE", &candidates), 1.5);
+        assert_eq!(expected_information("GRAPE", &candidates), 1.5);
+        assert_eq!(expected_information("CRATE", &candidates), 2.0);
+        assert_eq!(expected_information("CRANE", &["CRANE"]), 0.0);
+
+        let ranked = rank_guesses(&candidates, &candidates, &Alphabet::latin(), 2);
+        assert_eq!(ranked[0], (String::from("CRATE"), 2.0));
+        assert_eq!(ranked[1], (String::from("GRATE"), 2.0));
+
+        // Ñ counts as a letter of its own, not as the bytes it's made of
+        let spanish = Alphabet::new("ABCDEFGHIJKLMNÑOPQRSTUVWXYZ".chars()).unwrap();
+        let containing = letter_counts(&["AÑEJO", "NIÑOS"], &spanish);
+        assert_eq!(containing[spanish.index('Ñ').unwrap()], 2);
+        assert_eq!(letter_information("AÑEJO", &spanish, &containing, 2), 3.0);
+    }
+
+    #[test]
+    fn feedback_pattern_test() {
+        use wordle::engine::score;
+
+        for (guess, answer) in [
+            ("CRANE", "EPOCH"),
+            ("SPEED", "ABIDE"),
+            ("EERIE", "THEME"),
+            ("LLAMA", "HELLO"),
+            ("EPOCH", "EPOCH"),
+        ] {
+            let mut row = Word::from(guess).unwrap();
+            score(answer, &mut row).unwrap();
+            assert_eq!(
+                feedback_pattern(guess, answer),
+                row_pattern(&row),
+                "{guess} against {answer}"
+            );
+        }
+        assert_eq!(feedback_pattern("EPOCH", "EPOCH"), 3u32.pow(5) - 1);
+    }
+
+    #[test]
+    fn suggestions_fit_the_board_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        for guess in ["CRANE", "SLOTH"] {
+            game.current_guess = String::from(guess);
+            game.submit_guess();
+            let guesses = game.suggest_guesses();
+            assert!(!guesses.is_empty());
+            for (word, bits) in &guesses {
+                assert!(is_consistent(word, &game.guess_history), "{word}");
+                assert!(*bits >= 0.0);
+            }
+            assert!(guesses.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        }
+
+        // kept for the row, worked out again after the next
+        game.solver_cache.borrow_mut().as_mut().unwrap().1.clear();
+        assert!(game.suggest_guesses().is_empty());
+        game.current_guess = String::from("EPOCH");
+        game.submit_guess();
+        assert_eq!(game.suggest_guesses()[0].0, "EPOCH");
+    }
+
+    #[test]
+    fn remaining_candidates_test() {
+        let words = [
+            "NACRE", "CANER", "RANCE", "SLATE", "CRATE", "GRATE", "GRAPE",
+        ];
+        let mut game = Wordle::with_words("CRANE", &words);
+        game.answer_words = game.accepted_words.clone();
+        assert_eq!(game.remaining_candidates(), 8);
+
+        // an anagram colors every letter, and the answer still fits
+        game.current_guess = String::from("NACRE");
+        game.submit_guess();
+        assert!(game.candidates().contains(&"CRANE"));
+        assert_eq!(game.remaining_candidates(), game.candidates().len());
+
+        // CRATE has the answer's letters but T, and leaves only it
+        game.current_guess = String::from("CRATE");
+        game.submit_guess();
+        assert_eq!(game.remaining_candidates(), 1);
+        assert_eq!(game.candidates(), ["CRANE"]);
+
+        // cached for the row
+        game.remaining_cache.set(Some((2, 42)));
+        assert_eq!(game.remaining_candidates(), 42);
+    }
+
+    #[test]
+    fn remaining_toggle_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        game.current_guess = String::from("CRANE");
+        game.submit_guess();
+        let screen = |game: &Wordle| {
+            let mut terminal = Terminal::new(TestBackend::new(80, 45)).unwrap();
+            terminal.draw(|frame| game.render_terminal(frame)).unwrap();
+            format!("{:?}", terminal.backend().buffer())
+        };
+        let line = format!("possible answers remaining: {}", game.candidates().len());
+        assert!(!screen(&game).contains(&line));
+
+        game.toggle_remaining();
+        assert!(game.config.show_remaining);
+        assert!(screen(&game).contains(&line));
+
+        // hidden while assists are off, and can't be turned on
+        game.config.no_assists = true;
+        assert!(!screen(&game).contains(&line));
+        game.toggle_remaining();
+        assert!(!game.config.show_remaining);
+        game.toggle_remaining();
+        assert!(!game.config.show_remaining);
+    }
+
+    #[test]
+    fn toggle_counts_assist_test() {
+        let mut game = Wordle::new();
+        game.toggle_solver();
+        game.toggle_solver();
+        assert!(!game.show_solver);
+        assert_eq!(game.assists_used, 1);
+        game.config.no_assists = true;
+        game.toggle_solver();
+        assert!(!game.show_solver);
+    }
+}
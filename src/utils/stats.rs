@@ -0,0 +1,381 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::io;
+use std::time::Duration;
+
+use super::migrate::Format;
+use super::storage::{LOCK_TIMEOUT, SaveOutcome, Storage};
+use super::theme::{THEMES, ThemeEntry};
+use super::timing::RowTiming;
+
+pub const STATS_FILE: &str = "stats.json";
+
+/// Version 1 added the `version` field and changed nothing else.
+pub const STATS_FORMAT: Format = Format {
+    name: STATS_FILE,
+    migrations: &[Ok],
+};
+
+/// Results across games, persisted in the data directory.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    pub games_played: u32,
+    pub games_won: u32,
+    pub current_streak: u32,
+    pub max_streak: u32,
+    /// wins by the number of rows they took, `[0]` being wins on the first
+    pub guess_distribution: Vec<u32>,
+    /// games played against the clock with `--blitz`, and how many were won
+    pub blitz_played: u32,
+    pub blitz_won: u32,
+    /// games played with `--zen`, kept out of the counts above, and the
+    /// rows they took altogether
+    pub zen_played: u32,
+    pub zen_rows: u64,
+    /// ids of themes unlocked by streak milestones; never shrinks
+    pub unlocked_themes: BTreeSet<String>,
+    /// rows with a recorded thinking/typing split, and their summed times
+    pub timed_rows: u32,
+    pub think_ms: u64,
+    pub typing_ms: u64,
+    /// wins with a solve time, their summed time and the quickest
+    pub solve_times: u32,
+    pub solve_ms: u64,
+    pub best_solve_ms: Option<u64>,
+    /// games in which letters were revealed as hints, and how many letters
+    /// they revealed altogether
+    pub hinted_games: u32,
+    pub letters_revealed: u32,
+}
+
+impl Stats {
+    /// Loads the stats file, starting from zero if there isn't one yet.
+    /// A file from an older version is upgraded and written back, keeping the
+    /// original as a backup.
+    pub fn load(storage: &Storage) -> Result<Self> {
+        let Some(contents) = storage.load(STATS_FILE)? else {
+            return Ok(Stats::default());
+        };
+        let (stats, migrated): (Stats, bool) = STATS_FORMAT.parse(&contents)?;
+        if migrated {
+            stats.save(storage)?;
+        }
+        Ok(stats)
+    }
+
+    pub fn save(&self, storage: &Storage) -> io::Result<SaveOutcome> {
+        storage.save_with_backup(STATS_FILE, &STATS_FORMAT.to_string(self)?)
+    }
+
+    /// Counts a finished game and saves it, returning the themes it
+    /// unlocked. The stats file is re-read under a lock first so games that
+    /// other running instances finished in the meantime are kept; `self`
+    /// becomes the merged stats.
+    ///
+    /// If the file can't be locked or read the game is still counted here,
+    /// and the error is returned for a warning.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_shared(
+        &mut self,
+        storage: &Storage,
+        solved: bool,
+        rows: usize,
+        timings: &[Option<RowTiming>],
+        solve_time: Option<Duration>,
+        blitz: bool,
+        zen: bool,
+        revealed: usize,
+    ) -> (Vec<&'static ThemeEntry>, io::Result<SaveOutcome>) {
+        let mut unlocked = None;
+        let result = storage.update(STATS_FILE, LOCK_TIMEOUT, |contents| {
+            let mut merged = match contents {
+                Some(contents) => {
+                    STATS_FORMAT
+                        .parse(contents)
+                        .map_err(|err| io::Error::other(format!("{err:#}")))?
+                        .0
+                }
+                None => self.clone(),
+            };
+            unlocked =
+                Some(merged.record_all(solved, rows, timings, solve_time, blitz, zen, revealed));
+            *self = merged;
+            Ok(STATS_FORMAT.to_string(self)?)
+        });
+        let unlocked = unlocked.unwrap_or_else(|| {
+            self.record_all(solved, rows, timings, solve_time, blitz, zen, revealed)
+        });
+        (unlocked, result)
+    }
+
+    /// Everything `record_shared` counts for one game. Zen games only go
+    /// into their own counts, so unlimited rows can't keep a streak alive
+    /// or set a solve time.
+    #[allow(clippy::too_many_arguments)]
+    fn record_all(
+        &mut self,
+        solved: bool,
+        rows: usize,
+        timings: &[Option<RowTiming>],
+        solve_time: Option<Duration>,
+        blitz: bool,
+        zen: bool,
+        revealed: usize,
+    ) -> Vec<&'static ThemeEntry> {
+        self.record_timings(timings);
+        self.record_reveals(revealed);
+        if blitz {
+            self.record_blitz(solved);
+        }
+        if zen {
+            self.record_zen(rows);
+            return Vec::new();
+        }
+        if let Some(took) = solve_time.filter(|_| solved) {
+            self.record_solve_time(took);
+        }
+        self.record_game(solved, rows)
+    }
+
+    /// Counts a finished game that took `rows` rows and returns the themes
+    /// it unlocked.
+    pub fn record_game(&mut self, solved: bool, rows: usize) -> Vec<&'static ThemeEntry> {
+        self.games_played += 1;
+        if solved {
+            self.games_won += 1;
+            if self.guess_distribution.len() < rows {
+                self.guess_distribution.resize(rows, 0);
+            }
+            self.guess_distribution[rows.max(1) - 1] += 1;
+            self.current_streak += 1;
+            self.max_streak = self.max_streak.max(self.current_streak);
+        } else {
+            self.current_streak = 0;
+        }
+        self.unlock_milestones()
+    }
+
+    /// Tags a game counted by `record_game` as played in blitz mode.
+    pub fn record_blitz(&mut self, solved: bool) {
+        self.blitz_played += 1;
+        if solved {
+            self.blitz_won += 1;
+        }
+    }
+
+    /// Counts a zen game that took `rows` rows.
+    pub fn record_zen(&mut self, rows: usize) {
+        self.zen_played += 1;
+        self.zen_rows += rows as u64;
+    }
+
+    pub fn record_timings(&mut self, timings: &[Option<RowTiming>]) {
+        for timing in timings.iter().flatten() {
+            self.timed_rows += 1;
+            self.think_ms += timing.think_ms;
+            self.typing_ms += timing.typing_ms;
+        }
+    }
+
+    /// Counts the letters a game revealed as hints, if it revealed any.
+    pub fn record_reveals(&mut self, revealed: usize) {
+        if revealed > 0 {
+            self.hinted_games += 1;
+            self.letters_revealed += revealed as u32;
+        }
+    }
+
+    /// Counts how long a win took, from the first letter to the last row.
+    pub fn record_solve_time(&mut self, took: Duration) {
+        let ms = took.as_millis() as u64;
+        self.solve_times += 1;
+        self.solve_ms += ms;
+        self.best_solve_ms = Some(self.best_solve_ms.map_or(ms, |best| best.min(ms)));
+    }
+
+    /// The quickest win and the average one, once a win has been timed.
+    pub fn solve_time_summary(&self) -> Option<(Duration, Duration)> {
+        let best = self.best_solve_ms?;
+        let average = self.solve_ms.checked_div(self.solve_times as u64)?;
+        Some((Duration::from_millis(best), Duration::from_millis(average)))
+    }
+
+    /// Average split of a row across every timed game.
+    pub fn average_timing(&self) -> Option<RowTiming> {
+        let rows = self.timed_rows as u64;
+        (rows > 0).then(|| RowTiming {
+            think_ms: self.think_ms / rows,
+            typing_ms: self.typing_ms / rows,
+        })
+    }
+
+    /// Unlocks every theme whose streak milestone has been reached. Each one
+    /// fires once: losing the streak later doesn't lock it again, and
+    /// reaching the milestone again doesn't announce it twice.
+    fn unlock_milestones(&mut self) -> Vec<&'static ThemeEntry> {
+        let mut unlocked = Vec::new();
+        for entry in THEMES.iter() {
+            let Some(streak) = entry.unlock_streak else {
+                continue;
+            };
+            if self.current_streak >= streak && self.unlocked_themes.insert(entry.id.to_string()) {
+                unlocked.push(entry);
+            }
+        }
+        unlocked
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::testing::temp_dir;
+    use std::fs;
+
+    fn ids(entries: Vec<&'static ThemeEntry>) -> Vec<&'static str> {
+        entries.into_iter().map(|entry| entry.id).collect()
+    }
+
+    #[test]
+    fn solve_time_test() {
+        let mut stats = Stats::default();
+        assert_eq!(stats.solve_time_summary(), None);
+        let secs = Duration::from_secs;
+        stats.record_all(true, 3, &[], Some(secs(90)), false, false, 0);
+        stats.record_all(true, 4, &[], Some(secs(30)), false, false, 0);
+        // losses and untimed games don't count
+        stats.record_all(false, 6, &[], Some(secs(10)), false, false, 0);
+        stats.record_all(true, 2, &[], None, false, false, 0);
+        // nor do zen games, with rows to spare
+        stats.record_all(true, 20, &[], Some(secs(5)), false, true, 0);
+        assert_eq!(stats.solve_time_summary(), Some((secs(30), secs(60))));
+        assert_eq!(stats.games_won, 3);
+    }
+
+    #[test]
+    fn streak_test() {
+        let mut stats = Stats::default();
+        stats.record_game(true, 4);
+        stats.record_game(true, 4);
+        stats.record_game(false, 6);
+        stats.record_game(true, 4);
+        assert_eq!(stats.games_played, 4);
+        assert_eq!(stats.games_won, 3);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.max_streak, 2);
+    }
+
+    #[test]
+    fn distribution_test() {
+        let mut stats = Stats::default();
+        stats.record_game(true, 3);
+        stats.record_game(false, 6);
+        stats.record_game(true, 3);
+        stats.record_game(true, 1);
+        assert_eq!(stats.guess_distribution, vec![1, 0, 2]);
+        // games with more rows than any before grow it
+        stats.record_game(true, 9);
+        assert_eq!(stats.guess_distribution, vec![1, 0, 2, 0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn milestone_fires_once_test() {
+        let mut stats = Stats::default();
+        for _ in 0..4 {
+            assert!(stats.record_game(true, 4).is_empty());
+        }
+        assert_eq!(ids(stats.record_game(true, 4)), vec!["ocean"]);
+        assert!(stats.record_game(true, 4).is_empty());
+
+        // losing the streak keeps the theme, regaining it doesn't re-announce
+        stats.record_game(false, 6);
+        assert!(stats.unlocked_themes.contains("ocean"));
+        for _ in 0..5 {
+            assert!(stats.record_game(true, 4).is_empty());
+        }
+    }
+
+    #[test]
+    fn save_load_test() {
+        let dir = temp_dir("stats_save_load");
+        let storage = Storage::open(dir.clone());
+        assert_eq!(Stats::load(&storage).unwrap(), Stats::default());
+
+        let mut stats = Stats::default();
+        for _ in 0..10 {
+            stats.record_game(true, 4);
+        }
+        stats.save(&storage).unwrap();
+        let loaded = Stats::load(&storage).unwrap();
+        assert_eq!(loaded, stats);
+        assert_eq!(
+            loaded.unlocked_themes,
+            BTreeSet::from(["ocean".to_string(), "sunset".to_string()])
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn concurrent_instances_test() {
+        let dir = temp_dir("stats_concurrent");
+        let workers: Vec<_> = (0..2)
+            .map(|_| {
+                let dir = dir.clone();
+                std::thread::spawn(move || {
+                    // each instance loaded its stats before the other's games
+                    let storage = Storage::open(dir);
+                    let mut stats = Stats::default();
+                    for _ in 0..25 {
+                        let (_, saved) =
+                            stats.record_shared(&storage, true, 3, &[], None, false, false, 0);
+                        saved.unwrap();
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        let stats = Stats::load(&Storage::open(dir.clone())).unwrap();
+        assert_eq!(stats.games_played, 50);
+        assert_eq!(stats.games_won, 50);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn migrate_unversioned_test() {
+        let dir = temp_dir("stats_migrate");
+        let storage = Storage::open(dir.clone());
+        let old = include_str!("../../tests/fixtures/stats_v0.json");
+        storage.save(STATS_FILE, old).unwrap();
+
+        let stats = Stats::load(&storage).unwrap();
+        assert_eq!(stats.games_played, 12);
+        assert_eq!(stats.max_streak, 5);
+        assert!(stats.unlocked_themes.contains("ocean"));
+        assert_eq!(stats.timed_rows, 0);
+
+        // written back once, with the original kept as a backup
+        let saved = storage.load(STATS_FILE).unwrap().unwrap();
+        assert!(saved.contains("\"version\": 1"));
+        assert_eq!(
+            storage.load(&format!("{STATS_FILE}.1")).unwrap().as_deref(),
+            Some(old)
+        );
+        assert_eq!(Stats::load(&storage).unwrap(), stats);
+        assert_eq!(storage.list(STATS_FILE).unwrap().len(), 2);
+
+        storage
+            .save(STATS_FILE, "{\"version\": 9, \"games_played\": 1}")
+            .unwrap();
+        let err = Stats::load(&storage).unwrap_err();
+        assert!(format!("{err:#}").contains("created by a newer version"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,604 @@
+use std::fs::{self, File, TryLockError};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+const APP_DIR: &str = "rust_wordle";
+const PROFILES_DIR: &str = "profiles";
+const PROBE_FILE: &str = ".doctor";
+
+/// Previous versions `save_with_backup` keeps unless configured otherwise.
+pub const DEFAULT_BACKUPS: usize = 3;
+
+/// How long to wait for another instance to finish writing a shared file.
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The profile stored directly in the data directory, used without `--profile`.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// A rotated copy of a persisted file, `stats.json.1` being the newest.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Backup {
+    pub name: String,
+    pub original: String,
+    pub index: usize,
+    pub modified: Option<SystemTime>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SaveOutcome {
+    Saved,
+    /// storage is in-memory only, nothing was written
+    Skipped,
+}
+
+/// Where persisted files (stats, config, saves) live.
+///
+/// If the data directory cannot be created the storage falls back to an
+/// in-memory-only mode: saves become no-ops and `warning` carries a single
+/// banner explaining why.
+#[derive(Debug)]
+pub struct Storage {
+    dir: Option<PathBuf>,
+    pub warning: Option<String>,
+    /// how many previous versions `save_with_backup` keeps, 0 disables it
+    pub backups: usize,
+}
+
+impl Storage {
+    /// `$XDG_DATA_HOME/rust_wordle`, falling back to `~/.local/share/rust_wordle`.
+    pub fn default_dir() -> Option<PathBuf> {
+        if let Some(dir) = std::env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+            return Some(PathBuf::from(dir).join(APP_DIR));
+        }
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share").join(APP_DIR))
+    }
+
+    /// Opens the data directory of profile `name`. Named profiles live in
+    /// `profiles/<name>` below the default one.
+    pub fn open_profile(name: &str) -> Self {
+        match Storage::default_dir() {
+            Some(root) => Storage::open(profile_dir(root, name)),
+            None => Storage::in_memory("no home directory"),
+        }
+    }
+
+    /// Every profile with a data directory, the default one first and the
+    /// rest by name.
+    pub fn profiles() -> io::Result<Vec<(String, PathBuf)>> {
+        let Some(root) = Storage::default_dir() else {
+            return Ok(Vec::new());
+        };
+        list_profiles(root)
+    }
+
+    /// Wraps an existing directory without creating it, for reading another
+    /// profile's files.
+    pub fn existing(dir: PathBuf) -> Self {
+        Storage {
+            dir: Some(dir),
+            warning: None,
+            backups: DEFAULT_BACKUPS,
+        }
+    }
+
+    pub fn open(dir: PathBuf) -> Self {
+        match fs::create_dir_all(&dir) {
+            Ok(()) => Storage {
+                dir: Some(dir),
+                warning: None,
+                backups: DEFAULT_BACKUPS,
+            },
+            Err(err) => Storage::in_memory(&describe(&err)),
+        }
+    }
+
+    pub fn in_memory(reason: &str) -> Self {
+        Storage {
+            dir: None,
+            warning: Some(format!("stats won't be saved: {reason}")),
+            backups: 0,
+        }
+    }
+
+    /// Writes `contents` to `name` in the data directory via a temp file and
+    /// rename so a crash never leaves a half-written file behind.
+    pub fn save(&self, name: &str, contents: &str) -> io::Result<SaveOutcome> {
+        let Some(dir) = &self.dir else {
+            return Ok(SaveOutcome::Skipped);
+        };
+        let path = dir.join(name);
+        let tmp = dir.join(format!("{name}.tmp"));
+        fs::write(&tmp, contents)?;
+        fs::rename(&tmp, &path)?;
+        Ok(SaveOutcome::Saved)
+    }
+
+    /// Like `save`, but first keeps the current contents of `name` as
+    /// `name.1`, shifting older copies up to `name.<backups>`. Copies past
+    /// that, left over from a higher limit, are removed.
+    pub fn save_with_backup(&self, name: &str, contents: &str) -> io::Result<SaveOutcome> {
+        if let Some(dir) = &self.dir
+            && dir.join(name).exists()
+        {
+            for index in (1..self.backups).rev() {
+                let from = dir.join(backup_name(name, index));
+                if from.exists() {
+                    fs::rename(&from, dir.join(backup_name(name, index + 1)))?;
+                }
+            }
+            if self.backups > 0 {
+                fs::copy(dir.join(name), dir.join(backup_name(name, 1)))?;
+            }
+            for backup in self.backups()? {
+                if backup.original == name && backup.index > self.backups {
+                    fs::remove_file(dir.join(&backup.name))?;
+                }
+            }
+        }
+        self.save(name, contents)
+    }
+
+    /// Every rotated backup in the data directory, by file and then newest
+    /// first.
+    pub fn backups(&self) -> io::Result<Vec<Backup>> {
+        let Some(dir) = &self.dir else {
+            return Ok(Vec::new());
+        };
+        let mut backups = Vec::new();
+        for name in self.list("")? {
+            let Some((original, index)) = name.rsplit_once('.') else {
+                continue;
+            };
+            let Ok(index) = index.parse() else {
+                continue;
+            };
+            backups.push(Backup {
+                original: original.to_string(),
+                index,
+                modified: fs::metadata(dir.join(&name))?.modified().ok(),
+                name,
+            });
+        }
+        backups.sort_by(|a, b| a.original.cmp(&b.original).then(a.index.cmp(&b.index)));
+        Ok(backups)
+    }
+
+    /// Puts `backup` back in place. The version it replaces is rotated into
+    /// the backups itself, so a restore can be undone.
+    pub fn restore(&self, backup: &Backup) -> io::Result<SaveOutcome> {
+        let Some(contents) = self.load(&backup.name)? else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("backup {} no longer exists", backup.name),
+            ));
+        };
+        self.save_with_backup(&backup.original, &contents)
+    }
+
+    /// Moves `name` to a dated archive (`history-<stamp>.jsonl`) once it grows
+    /// past `max_bytes`, so append-only logs stay small. Returns the archive's
+    /// name if one was made.
+    pub fn archive_if_larger(
+        &self,
+        name: &str,
+        max_bytes: u64,
+        stamp: &str,
+    ) -> io::Result<Option<String>> {
+        let Some(dir) = &self.dir else {
+            return Ok(None);
+        };
+        match fs::metadata(dir.join(name)) {
+            Ok(metadata) if metadata.len() > max_bytes => {}
+            Ok(_) => return Ok(None),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        }
+
+        let (stem, ext) = name.rsplit_once('.').unwrap_or((name, ""));
+        let archive_name = |suffix: String| match ext {
+            "" => format!("{stem}-{stamp}{suffix}"),
+            ext => format!("{stem}-{stamp}{suffix}.{ext}"),
+        };
+        let mut archive = archive_name(String::new());
+        let mut copy = 2;
+        while dir.join(&archive).exists() {
+            archive = archive_name(format!("-{copy}"));
+            copy += 1;
+        }
+        fs::rename(dir.join(name), dir.join(&archive))?;
+        Ok(Some(archive))
+    }
+
+    /// Appends `line` plus a newline to `name`, creating the file if needed.
+    pub fn append(&self, name: &str, line: &str) -> io::Result<SaveOutcome> {
+        let Some(dir) = &self.dir else {
+            return Ok(SaveOutcome::Skipped);
+        };
+        let _lock = self.lock(name, LOCK_TIMEOUT)?;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(name))?;
+        writeln!(file, "{line}")?;
+        Ok(SaveOutcome::Saved)
+    }
+
+    /// Read-modify-write of `name` that other instances can't interleave
+    /// with: `change` gets the current contents, read under the lock, and
+    /// returns what to save (with a backup). If the lock isn't free within
+    /// `timeout` this fails with `TimedOut` without calling `change`.
+    pub fn update(
+        &self,
+        name: &str,
+        timeout: Duration,
+        change: impl FnOnce(Option<&str>) -> io::Result<String>,
+    ) -> io::Result<SaveOutcome> {
+        let _lock = self.lock(name, timeout)?;
+        let contents = change(self.load(name)?.as_deref())?;
+        self.save_with_backup(name, &contents)
+    }
+
+    /// Holds an advisory lock for `name` until the returned file is dropped.
+    /// The lock lives on a separate hidden file because saves replace `name`
+    /// itself. In-memory storage has nothing to lock.
+    fn lock(&self, name: &str, timeout: Duration) -> io::Result<Option<File>> {
+        let Some(dir) = &self.dir else {
+            return Ok(None);
+        };
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(dir.join(format!(".{name}.lock")))?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(Some(file)),
+                Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                    thread::sleep(Duration::from_millis(10));
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        format!("{name} is locked by another instance"),
+                    ));
+                }
+                Err(TryLockError::Error(err)) => return Err(err),
+            }
+        }
+    }
+
+    /// Reads `name` from the data directory, `None` if it doesn't exist yet.
+    pub fn load(&self, name: &str) -> io::Result<Option<String>> {
+        let Some(dir) = &self.dir else {
+            return Ok(None);
+        };
+        match fs::read_to_string(dir.join(name)) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Where `name` lives in the data directory, `None` in in-memory mode.
+    pub fn path(&self, name: &str) -> Option<PathBuf> {
+        self.dir.as_ref().map(|dir| dir.join(name))
+    }
+
+    /// Names of the files in the data directory starting with `prefix`,
+    /// sorted. Hidden files such as locks are left out.
+    pub fn list(&self, prefix: &str) -> io::Result<Vec<String>> {
+        let Some(dir) = &self.dir else {
+            return Ok(Vec::new());
+        };
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        let mut names = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            if let Some(name) = entry.file_name().to_str()
+                && name.starts_with(prefix)
+                && !name.starts_with('.')
+                && entry.file_type()?.is_file()
+            {
+                names.push(name.to_string());
+            }
+        }
+        names.sort_unstable();
+        Ok(names)
+    }
+
+    /// Human-readable explanation of the storage state for `rust_wordle doctor`.
+    ///
+    /// Also probes the directory with a small write, since a directory that
+    /// already exists can still be read-only.
+    pub fn doctor(&self) -> String {
+        let Some(dir) = &self.dir else {
+            return format!(
+                "data directory: unavailable\n{}\n\
+                 the game still works, but stats and settings only last until you quit",
+                self.warning.as_deref().unwrap_or("stats won't be saved")
+            );
+        };
+
+        let probe = self
+            .save(PROBE_FILE, "ok")
+            .and_then(|_| self.load(PROBE_FILE));
+        let _ = fs::remove_file(dir.join(PROBE_FILE));
+        match probe {
+            Ok(Some(_)) => format!("data directory: {} (writable)", dir.display()),
+            Ok(None) => format!("data directory: {} (write probe vanished)", dir.display()),
+            Err(err) => format!(
+                "data directory: {} (not writable: {})",
+                dir.display(),
+                describe(&err)
+            ),
+        }
+    }
+}
+
+/// Profile names become directory names, so only plain ASCII words are allowed.
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 32
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn backup_name(name: &str, index: usize) -> String {
+    format!("{name}.{index}")
+}
+
+fn profile_dir(root: PathBuf, name: &str) -> PathBuf {
+    if name == DEFAULT_PROFILE {
+        root
+    } else {
+        root.join(PROFILES_DIR).join(name)
+    }
+}
+
+fn list_profiles(root: PathBuf) -> io::Result<Vec<(String, PathBuf)>> {
+    let mut named = Vec::new();
+    match fs::read_dir(root.join(PROFILES_DIR)) {
+        Ok(entries) => {
+            for entry in entries {
+                let entry = entry?;
+                if let Some(name) = entry.file_name().to_str()
+                    && is_valid_profile_name(name)
+                    && name != DEFAULT_PROFILE
+                    && entry.file_type()?.is_dir()
+                {
+                    named.push((name.to_string(), entry.path()));
+                }
+            }
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+    named.sort_unstable();
+
+    let mut profiles = vec![(DEFAULT_PROFILE.to_string(), root)];
+    profiles.extend(named);
+    Ok(profiles)
+}
+
+fn describe(err: &io::Error) -> String {
+    err.kind().to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::testing::temp_dir;
+
+    #[test]
+    fn unwritable_dir_test() {
+        // a directory can't be created underneath a regular file
+        let base = temp_dir("storage_unwritable");
+        fs::create_dir_all(&base).unwrap();
+        let file = base.join("file");
+        fs::write(&file, "").unwrap();
+
+        let storage = Storage::open(file.join("data"));
+        assert!(
+            storage
+                .warning
+                .as_deref()
+                .unwrap()
+                .starts_with("stats won't be saved: ")
+        );
+        assert_eq!(
+            storage.save("stats.json", "{}").unwrap(),
+            SaveOutcome::Skipped
+        );
+        assert_eq!(storage.load("stats.json").unwrap(), None);
+        assert_eq!(
+            storage.append("history.jsonl", "{}").unwrap(),
+            SaveOutcome::Skipped
+        );
+        assert!(storage.doctor().contains("unavailable"));
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn save_load_test() {
+        let dir = temp_dir("storage_save_load");
+        let storage = Storage::open(dir.clone());
+        assert!(storage.warning.is_none());
+        assert_eq!(storage.load("stats.json").unwrap(), None);
+        assert_eq!(
+            storage.save("stats.json", "{}").unwrap(),
+            SaveOutcome::Saved
+        );
+        assert_eq!(storage.load("stats.json").unwrap().as_deref(), Some("{}"));
+        assert!(!dir.join("stats.json.tmp").exists());
+
+        storage.append("history.jsonl", "a").unwrap();
+        storage.append("history.jsonl", "b").unwrap();
+        assert_eq!(
+            storage.load("history.jsonl").unwrap().as_deref(),
+            Some("a\nb\n")
+        );
+        assert!(storage.doctor().ends_with("(writable)"));
+        assert_eq!(storage.list("stats").unwrap(), vec!["stats.json"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn contents(storage: &Storage, name: &str) -> Option<String> {
+        storage.load(name).unwrap()
+    }
+
+    #[test]
+    fn backup_rotation_test() {
+        let dir = temp_dir("storage_backup_rotation");
+        let mut storage = Storage::open(dir.clone());
+        storage.backups = 2;
+        for version in ["v1", "v2", "v3", "v4"] {
+            storage.save_with_backup("stats.json", version).unwrap();
+        }
+        assert_eq!(contents(&storage, "stats.json").as_deref(), Some("v4"));
+        assert_eq!(contents(&storage, "stats.json.1").as_deref(), Some("v3"));
+        assert_eq!(contents(&storage, "stats.json.2").as_deref(), Some("v2"));
+        assert_eq!(contents(&storage, "stats.json.3"), None);
+
+        let backups = storage.backups().unwrap();
+        let names: Vec<&str> = backups.iter().map(|backup| backup.name.as_str()).collect();
+        assert_eq!(names, vec!["stats.json.1", "stats.json.2"]);
+        assert!(backups.iter().all(|backup| backup.original == "stats.json"));
+
+        // restoring rotates the replaced version in as the newest backup
+        storage.restore(&backups[1]).unwrap();
+        assert_eq!(contents(&storage, "stats.json").as_deref(), Some("v2"));
+        assert_eq!(contents(&storage, "stats.json.1").as_deref(), Some("v4"));
+        assert_eq!(contents(&storage, "stats.json.2").as_deref(), Some("v3"));
+
+        // lowering the limit drops the copies past it on the next save
+        storage.backups = 1;
+        storage.save_with_backup("stats.json", "v5").unwrap();
+        assert_eq!(contents(&storage, "stats.json.1").as_deref(), Some("v2"));
+        assert_eq!(contents(&storage, "stats.json.2"), None);
+        let backups = storage.backups().unwrap();
+        let names: Vec<&str> = backups.iter().map(|backup| backup.name.as_str()).collect();
+        assert_eq!(names, vec!["stats.json.1"]);
+
+        storage.backups = 0;
+        storage.save_with_backup("config.toml", "a").unwrap();
+        storage.save_with_backup("config.toml", "b").unwrap();
+        assert_eq!(contents(&storage, "config.toml.1"), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn archive_test() {
+        let dir = temp_dir("storage_archive");
+        let storage = Storage::open(dir.clone());
+        storage.append("history.jsonl", "0123456789").unwrap();
+        assert_eq!(
+            storage
+                .archive_if_larger("history.jsonl", 64, "2026-01-01")
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            storage
+                .archive_if_larger("history.jsonl", 8, "2026-01-01")
+                .unwrap()
+                .as_deref(),
+            Some("history-2026-01-01.jsonl")
+        );
+        assert_eq!(contents(&storage, "history.jsonl"), None);
+
+        storage.append("history.jsonl", "0123456789").unwrap();
+        assert_eq!(
+            storage
+                .archive_if_larger("history.jsonl", 8, "2026-01-01")
+                .unwrap()
+                .as_deref(),
+            Some("history-2026-01-01-2.jsonl")
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn profiles_test() {
+        let root = temp_dir("storage_profiles");
+        fs::create_dir_all(root.join("profiles/zoe")).unwrap();
+        fs::create_dir_all(root.join("profiles/al")).unwrap();
+        fs::create_dir_all(root.join("profiles/bad name")).unwrap();
+        fs::write(root.join("profiles/notes.txt"), "").unwrap();
+
+        let names: Vec<String> = list_profiles(root.clone())
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, vec!["default", "al", "zoe"]);
+        assert_eq!(profile_dir(root.clone(), "default"), root);
+        assert!(!is_valid_profile_name("../etc"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn concurrent_updates_test() {
+        let dir = temp_dir("storage_concurrent");
+        let workers: Vec<_> = (0..2)
+            .map(|worker| {
+                let dir = dir.clone();
+                thread::spawn(move || {
+                    let storage = Storage::open(dir);
+                    for round in 0..50 {
+                        storage
+                            .update("count", LOCK_TIMEOUT, |contents| {
+                                let count: u32 = contents.map_or(Ok(0), str::parse).unwrap();
+                                Ok((count + 1).to_string())
+                            })
+                            .unwrap();
+                        storage.append("log", &format!("{worker}-{round}")).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        let storage = Storage::open(dir.clone());
+        assert_eq!(storage.load("count").unwrap().as_deref(), Some("100"));
+        assert_eq!(storage.load("log").unwrap().unwrap().lines().count(), 100);
+        assert!(
+            !storage
+                .list("")
+                .unwrap()
+                .iter()
+                .any(|name| name.contains("lock"))
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn lock_timeout_test() {
+        let dir = temp_dir("storage_lock_timeout");
+        let storage = Storage::open(dir.clone());
+        let _held = storage.lock("stats.json", LOCK_TIMEOUT).unwrap();
+        let err = storage
+            .update("stats.json", Duration::from_millis(30), |_| {
+                unreachable!("the lock is held")
+            })
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,202 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Rect},
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+use std::collections::HashSet;
+
+use super::solver::rank_guesses;
+use super::ui::InputState;
+use super::wordle::Wordle;
+
+/// Suggestions listed in the panel.
+pub const SUGGESTION_COUNT: usize = 10;
+
+/// Which words the suggestion panel offers.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum SuggestionFilter {
+    #[default]
+    All,
+    /// words that could still be the answer
+    Answers,
+    /// words that can't be the answer, played only to learn letters
+    Probes,
+}
+
+impl SuggestionFilter {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SuggestionFilter::All => "All words",
+            SuggestionFilter::Answers => "Possible answers only",
+            SuggestionFilter::Probes => "Probe words only",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            SuggestionFilter::All => SuggestionFilter::Answers,
+            SuggestionFilter::Answers => SuggestionFilter::Probes,
+            SuggestionFilter::Probes => SuggestionFilter::All,
+        }
+    }
+}
+
+/// The open suggestion panel. The ranking is computed when the panel opens or
+/// the filter changes, not every frame.
+pub struct SuggestionPanel {
+    pub filter: SuggestionFilter,
+    pub words: Vec<String>,
+}
+
+/// The words of `words` that `filter` lets the panel offer.
+pub fn filter_words<'a>(
+    words: &[&'a str],
+    candidates: &[&str],
+    filter: SuggestionFilter,
+) -> Vec<&'a str> {
+    let is_candidate: HashSet<&str> = candidates.iter().copied().collect();
+    words
+        .iter()
+        .copied()
+        .filter(|word| match filter {
+            SuggestionFilter::All => true,
+            SuggestionFilter::Answers => is_candidate.contains(word),
+            SuggestionFilter::Probes => !is_candidate.contains(word),
+        })
+        .collect()
+}
+
+impl Wordle {
+    /// Opens the suggestion panel, which counts as an assist.
+    pub fn open_suggestions(&mut self) {
+        if !self.assists_allowed() {
+            self.show_error("assists are turned off");
+            return;
+        }
+        self.assists_used += 1;
+        self.suggestions = Some(self.suggestion_panel(SuggestionFilter::default()));
+    }
+
+    fn suggestion_panel(&self, filter: SuggestionFilter) -> SuggestionPanel {
+        let mut words: Vec<&str> = self.accepted_words.iter().map(String::as_str).collect();
+        words.sort_unstable();
+        let candidates = self.candidates();
+        let words = filter_words(&words, &candidates, filter);
+        SuggestionPanel {
+            filter,
+            words: rank_guesses(&words, &candidates, &self.alphabet, SUGGESTION_COUNT)
+                .into_iter()
+                .map(|(word, _)| word)
+                .collect(),
+        }
+    }
+
+    pub fn on_suggestion_key(&mut self, key: KeyEvent) -> InputState {
+        let Some(panel) = &self.suggestions else {
+            return InputState::None;
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::F(3) => self.suggestions = None,
+            KeyCode::Char('F') => {
+                self.suggestions = Some(self.suggestion_panel(panel.filter.next()));
+            }
+            _ => {}
+        }
+        InputState::None
+    }
+
+    pub fn render_suggestions(&self, panel: &SuggestionPanel, area: Rect, buf: &mut Buffer) {
+        let popup = area.centered(
+            Constraint::Length(area.width.saturating_sub(2)),
+            Constraint::Length(area.height.saturating_sub(4)),
+        );
+        Clear.render(popup, buf);
+
+        let mut lines: Vec<Line> = panel
+            .words
+            .iter()
+            .enumerate()
+            .map(|(index, word)| Line::from(format!("{:>2}. {word}", index + 1)))
+            .collect();
+        if lines.is_empty() {
+            lines.push(Line::from("No words match").fg(Color::DarkGray));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::raw("Filter "),
+            "<f>".blue().bold(),
+            Span::raw(" Back "),
+            "<Esc>".blue().bold(),
+        ]));
+
+        Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .title(format!("Suggestions: {}", panel.filter.name()))
+                    .border_type(BorderType::Rounded),
+            )
+            .fg(Color::White)
+            .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratatui::crossterm::event::KeyModifiers;
+    use wordle::engine::Alphabet;
+
+    const WORDS: [&str; 5] = ["CRANE", "EPOCH", "MOUTH", "SLOTH", "TOOTH"];
+
+    #[test]
+    fn filters_test() {
+        let candidates = ["MOUTH", "SLOTH", "TOOTH"];
+        let ranked = |filter, limit| -> Vec<String> {
+            let words = filter_words(&WORDS, &candidates, filter);
+            rank_guesses(&words, &candidates, &Alphabet::latin(), limit)
+                .into_iter()
+                .map(|(word, _)| word)
+                .collect()
+        };
+        // each candidate tells all three apart, EPOCH only MOUTH from the
+        // rest and CRANE nothing
+        assert_eq!(
+            ranked(SuggestionFilter::All, 5),
+            vec!["MOUTH", "SLOTH", "TOOTH", "EPOCH", "CRANE"]
+        );
+        assert_eq!(
+            ranked(SuggestionFilter::Answers, 5),
+            vec!["MOUTH", "SLOTH", "TOOTH"]
+        );
+        assert_eq!(ranked(SuggestionFilter::Probes, 1), vec!["EPOCH"]);
+    }
+
+    #[test]
+    fn cycle_filter_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        game.current_guess = "CRANE".to_string();
+        game.submit_guess();
+        let press = |game: &mut Wordle, code| game.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+
+        press(&mut game, KeyCode::F(3));
+        assert_eq!(game.assists_used, 1);
+        press(&mut game, KeyCode::Char('f'));
+        let panel = game.suggestions.as_ref().unwrap();
+        assert_eq!(panel.filter, SuggestionFilter::Answers);
+        assert!(panel.words.iter().all(|word| game.is_candidate(word)));
+
+        press(&mut game, KeyCode::Char('f'));
+        let panel = game.suggestions.as_ref().unwrap();
+        assert_eq!(panel.filter, SuggestionFilter::Probes);
+        assert!(panel.words.iter().all(|word| !game.is_candidate(word)));
+        // typing goes nowhere while the panel is open
+        assert!(game.current_guess.is_empty());
+
+        press(&mut game, KeyCode::Esc);
+        assert!(game.suggestions.is_none());
+    }
+}
@@ -0,0 +1,340 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+use std::fs::{self, ReadDir};
+use std::path::{Path, PathBuf};
+use wordle::engine::internal::pick_length;
+use wordle::engine::wordlist::load_file;
+
+use super::record::word_list_hash;
+use super::ui::InputState;
+use super::wordle::Wordle;
+
+/// Directory entries looked at per frame, so huge directories don't freeze
+/// the UI while they're scanned.
+pub const SCAN_BATCH: usize = 16;
+/// Default list directory inside the data directory.
+pub const WORD_LIST_DIR: &str = "wordlists";
+
+/// A word list the switcher can load. `path` is `None` for the built-in list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListInfo {
+    pub path: Option<PathBuf>,
+    pub name: String,
+    pub count: usize,
+    pub hash: String,
+}
+
+/// State of the word list overlay. The directory is read a batch at a time;
+/// files that can't be read as a word list are left out.
+pub struct WordListSwitcher {
+    pub dir: Option<PathBuf>,
+    pending: Option<ReadDir>,
+    pub lists: Vec<ListInfo>,
+    pub cursor: usize,
+    /// asking whether to abandon the game in progress
+    pub confirming: bool,
+    pub error: Option<String>,
+}
+
+impl WordListSwitcher {
+    pub fn open(dir: Option<PathBuf>) -> Self {
+        let builtin = Wordle::load_words().unwrap_or_default();
+        let mut switcher = WordListSwitcher {
+            dir: dir.clone(),
+            pending: None,
+            lists: vec![ListInfo {
+                path: None,
+                name: String::from("built-in"),
+                count: builtin.len(),
+                hash: word_list_hash(&builtin),
+            }],
+            cursor: 0,
+            confirming: false,
+            error: None,
+        };
+        match dir.map(fs::read_dir) {
+            Some(Ok(entries)) => switcher.pending = Some(entries),
+            Some(Err(err)) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Some(Err(err)) => switcher.error = Some(format!("can't read list directory: {err}")),
+            None => switcher.error = Some(String::from("no list directory")),
+        }
+        switcher
+    }
+
+    pub fn is_scanning(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Looks at up to `budget` more directory entries.
+    pub fn scan(&mut self, budget: usize) {
+        let Some(entries) = &mut self.pending else {
+            return;
+        };
+        for _ in 0..budget {
+            let Some(entry) = entries.next() else {
+                self.pending = None;
+                return;
+            };
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "txt") {
+                continue;
+            }
+            if let Some(info) = list_info(&path) {
+                // keep the built-in list first and the rest by name
+                let index = self.lists[1..].partition_point(|list| list.name < info.name) + 1;
+                self.lists.insert(index, info);
+            }
+        }
+    }
+}
+
+fn list_info(path: &Path) -> Option<ListInfo> {
+    let words = load_file(path).ok()?;
+    Some(ListInfo {
+        path: Some(path.to_path_buf()),
+        name: path.file_name()?.to_string_lossy().into_owned(),
+        count: words.len(),
+        hash: word_list_hash(&words),
+    })
+}
+
+impl Wordle {
+    pub fn open_word_lists(&mut self) {
+        let dir = self
+            .config
+            .word_list_dir
+            .clone()
+            .or_else(|| self.storage.path(WORD_LIST_DIR));
+        self.word_lists = Some(WordListSwitcher::open(dir));
+    }
+
+    pub fn on_word_list_key(&mut self, key: KeyEvent) -> InputState {
+        let Some(switcher) = &mut self.word_lists else {
+            return InputState::None;
+        };
+        let cursor = switcher.cursor;
+        if switcher.confirming {
+            switcher.confirming = false;
+            if key.code == KeyCode::Char('Y') {
+                self.switch_word_list(cursor);
+            }
+            return InputState::None;
+        }
+
+        match key.code {
+            KeyCode::Esc => self.word_lists = None,
+            KeyCode::Up => switcher.cursor = switcher.cursor.saturating_sub(1),
+            KeyCode::Down => {
+                switcher.cursor = (switcher.cursor + 1).min(switcher.lists.len() - 1);
+            }
+            KeyCode::Enter if self.daily.is_some() => {
+                switcher.error = Some(String::from("can't switch lists during the daily puzzle"));
+            }
+            KeyCode::Enter if !self.guess_history.is_empty() && !self.is_game_over => {
+                switcher.confirming = true;
+            }
+            KeyCode::Enter => self.switch_word_list(cursor),
+            _ => {}
+        }
+        InputState::None
+    }
+
+    /// Loads the list at `index` and starts a new game with it. If it can't
+    /// be loaded the current list stays active.
+    pub fn switch_word_list(&mut self, index: usize) {
+        let Some(switcher) = &mut self.word_lists else {
+            return;
+        };
+        let Some(info) = switcher.lists.get(index) else {
+            return;
+        };
+        let words = match &info.path {
+            Some(path) => load_file(path),
+            None => Wordle::load_words(),
+        };
+        let words = words.and_then(|mut words| {
+            let len = pick_length(&words, None)?;
+            words.retain(|word| word.chars().count() == len);
+            Ok((words, len))
+        });
+        let (words, len) = match words {
+            Ok(words) => words,
+            Err(err) => {
+                switcher.error = Some(format!("{:#}", err));
+                return;
+            }
+        };
+
+        self.config.word_list = info.path.clone();
+        // a separate answer list belongs to the list it was set up with
+        self.config.answer_list = None;
+        self.answer_words = words.clone();
+        self.answer_words
+            .retain(|word| !self.excluded.contains(word));
+        self.accepted_words = words;
+        self.word_len = len;
+        self.word_lists = None;
+        self.show_settings = false;
+        self.game_restart();
+        if let Err(err) = self.config.save(&self.storage) {
+            self.show_error(format!("failed to save config: {err}"));
+        }
+    }
+
+    pub fn render_word_list_page(&self, switcher: &WordListSwitcher, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let active = self.config.word_list.as_ref();
+        let mut lines = vec![Line::from("Word lists").bold()];
+        if let Some(dir) = &switcher.dir {
+            lines.push(Line::from(dir.display().to_string()).fg(Color::DarkGray));
+        }
+        lines.push(Line::from(""));
+        for (index, list) in switcher.lists.iter().enumerate() {
+            let cursor = if index == switcher.cursor { "> " } else { "  " };
+            let mut spans = vec![
+                Span::raw(cursor),
+                Span::raw(format!("{:<14}", list.name)),
+                Span::raw(format!("{:>6} words  ", list.count)),
+                Span::raw(list.hash[..8].to_string()).fg(Color::DarkGray),
+            ];
+            if list.path.as_ref() == active {
+                spans.push(Span::raw("  (active)").fg(Color::Green));
+            }
+            lines.push(Line::from(spans));
+        }
+        if switcher.is_scanning() {
+            lines.push(Line::from("scanning…").fg(Color::DarkGray));
+        }
+        if let Some(error) = &switcher.error {
+            lines.push(Line::from(""));
+            lines.push(Line::from(error.as_str()).fg(Color::Red));
+        }
+        lines.push(Line::from(""));
+        if switcher.confirming {
+            lines.push(Line::from(vec![
+                Span::raw("Abandon the current game? "),
+                "<y>".blue().bold(),
+            ]));
+        } else {
+            lines.push(Line::from(vec![
+                Span::raw("Switch "),
+                "<Enter>".blue().bold(),
+                Span::raw(" Back "),
+                "<Esc>".blue().bold(),
+            ]));
+        }
+
+        Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .title("Settings")
+                    .border_type(BorderType::Rounded),
+            )
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::testing::temp_dir;
+    use ratatui::crossterm::event::KeyModifiers;
+
+    fn press(game: &mut Wordle, code: KeyCode) {
+        game.on_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn list_dir(name: &str) -> PathBuf {
+        let dir = temp_dir(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("themed.txt"), "CRANE\nEPOCH\n").unwrap();
+        fs::write(dir.join("another.txt"), "SLATE\n").unwrap();
+        fs::write(dir.join("notes.txt"), "not a word list\n").unwrap();
+        fs::write(dir.join("image.png"), [0x89, b'P', b'N', b'G']).unwrap();
+        dir
+    }
+
+    #[test]
+    fn scan_test() {
+        let dir = list_dir("switcher_scan");
+        let mut switcher = WordListSwitcher::open(Some(dir.clone()));
+        assert!(switcher.is_scanning());
+        switcher.scan(1);
+        while switcher.is_scanning() {
+            switcher.scan(1);
+        }
+        let names: Vec<&str> = switcher
+            .lists
+            .iter()
+            .map(|list| list.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["built-in", "another.txt", "themed.txt"]);
+        assert_eq!(switcher.lists[2].count, 2);
+        assert!(switcher.error.is_none());
+
+        let missing = WordListSwitcher::open(Some(dir.join("missing")));
+        assert!(!missing.is_scanning());
+        assert_eq!(missing.lists.len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn switch_with_confirmation_test() {
+        let dir = list_dir("switcher_switch");
+        let mut game = Wordle::new();
+        game.storage = crate::utils::storage::Storage::open(dir.join("data"));
+        game.config.word_list_dir = Some(dir.clone());
+        game.answer = "EPOCH".to_string();
+        game.current_guess = "BAGEL".to_string();
+        game.submit_guess();
+
+        press(&mut game, KeyCode::F(2));
+        press(&mut game, KeyCode::Char('w'));
+        game.word_lists.as_mut().unwrap().scan(SCAN_BATCH);
+        press(&mut game, KeyCode::Down);
+        press(&mut game, KeyCode::Down);
+        press(&mut game, KeyCode::Enter);
+        assert!(game.word_lists.as_ref().unwrap().confirming);
+        press(&mut game, KeyCode::Char('n'));
+        assert_eq!(game.guess_history.len(), 1);
+
+        press(&mut game, KeyCode::Enter);
+        press(&mut game, KeyCode::Char('y'));
+        assert!(game.word_lists.is_none());
+        assert!(game.guess_history.is_empty());
+        assert_eq!(game.accepted_words.len(), 2);
+        assert!(game.answer_words.contains(&game.answer));
+        assert_eq!(game.config.word_list, Some(dir.join("themed.txt")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failed_switch_keeps_list_test() {
+        let dir = list_dir("switcher_failed");
+        let mut game = Wordle::new();
+        game.config.word_list_dir = Some(dir.clone());
+        let before = game.accepted_words.len();
+        game.open_word_lists();
+        game.word_lists.as_mut().unwrap().scan(SCAN_BATCH);
+
+        // the file turns into garbage between the scan and the switch
+        fs::write(dir.join("another.txt"), "oops, not words\n").unwrap();
+        game.switch_word_list(1);
+        assert_eq!(game.accepted_words.len(), before);
+        assert!(game.word_lists.as_ref().unwrap().error.is_some());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,380 @@
+use anyhow::{Context, Result, bail};
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use wordle::engine::TileState;
+
+use super::assets::THEME_COLORS;
+
+/// Background and foreground of a tile or keyboard key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileStyle {
+    pub bg: Color,
+    pub fg: Color,
+}
+
+impl TileStyle {
+    /// Picks whichever of black or white text reads better on `bg`.
+    pub fn on(bg: Color) -> Self {
+        TileStyle {
+            bg,
+            fg: readable_fg(bg),
+        }
+    }
+}
+
+/// Colors for every tile state. Letters always get an explicit foreground so
+/// they never inherit the terminal default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub correct: TileStyle,
+    pub present: TileStyle,
+    pub absent: TileStyle,
+    pub unused: TileStyle,
+    /// keyboard keys that haven't been guessed yet
+    pub key_unused: TileStyle,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            correct: TileStyle::on(Color::Green),
+            present: TileStyle::on(Color::Yellow),
+            absent: TileStyle::on(Color::DarkGray),
+            unused: TileStyle::on(Color::Rgb(65, 65, 65)), // very dark gray
+            key_unused: TileStyle::on(Color::Black),
+        }
+    }
+}
+
+/// Whether the terminal draws on a light or a dark background.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Background {
+    Light,
+    #[default]
+    Dark,
+}
+
+/// A selectable theme. Themes with an `unlock_streak` stay locked until the
+/// player reaches that win streak once.
+pub struct ThemeEntry {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub unlock_streak: Option<u32>,
+    /// colors when `themes.toml` gives none, `None` for themes that are
+    /// only in `themes.toml`
+    pub build: Option<fn() -> Theme>,
+    /// variant for light terminals, when `build` is tuned for dark ones only
+    pub build_light: Option<fn() -> Theme>,
+}
+
+impl ThemeEntry {
+    /// The theme for `background`, in the colors `palettes` has for it if
+    /// any. A light variant is always the built-in one.
+    pub fn theme(&self, background: Background, palettes: &Palettes) -> Theme {
+        if let (Background::Light, Some(build_light)) = (background, self.build_light) {
+            return build_light();
+        }
+        match (palettes.get(self.id), self.build) {
+            (Some(palette), _) => palette.theme(),
+            (None, Some(build)) => build(),
+            (None, None) => Theme::default(),
+        }
+    }
+
+    pub fn is_locked(&self, unlocked: &BTreeSet<String>) -> bool {
+        self.unlock_streak.is_some() && !unlocked.contains(self.id)
+    }
+}
+
+/// Id of the orange/blue theme for players who can't tell green from
+/// yellow.
+pub const HIGH_CONTRAST: &str = "high_contrast";
+
+pub const THEMES: [ThemeEntry; 6] = [
+    ThemeEntry {
+        id: "classic",
+        name: "Classic",
+        unlock_streak: None,
+        build: Some(Theme::default),
+        build_light: Some(Theme::light),
+    },
+    ThemeEntry {
+        id: "ocean",
+        name: "Ocean",
+        unlock_streak: Some(5),
+        build: None,
+        build_light: None,
+    },
+    ThemeEntry {
+        id: "sunset",
+        name: "Sunset",
+        unlock_streak: Some(10),
+        build: None,
+        build_light: None,
+    },
+    ThemeEntry {
+        id: "forest",
+        name: "Forest",
+        unlock_streak: Some(25),
+        build: None,
+        build_light: None,
+    },
+    ThemeEntry {
+        id: "neon",
+        name: "Neon",
+        unlock_streak: Some(50),
+        build: None,
+        build_light: None,
+    },
+    ThemeEntry {
+        id: HIGH_CONTRAST,
+        name: "High contrast",
+        unlock_streak: None,
+        build: Some(Theme::high_contrast),
+        build_light: Some(Theme::high_contrast_light),
+    },
+];
+
+impl Theme {
+    /// The classic colors for light terminals, where dark gray and black
+    /// keys would read as holes in the page.
+    pub fn light() -> Self {
+        Theme {
+            correct: TileStyle::on(Color::Rgb(106, 170, 100)),
+            present: TileStyle::on(Color::Rgb(201, 180, 88)),
+            absent: TileStyle::on(Color::Rgb(120, 124, 126)),
+            unused: TileStyle::on(Color::Rgb(222, 224, 228)),
+            key_unused: TileStyle::on(Color::Rgb(211, 214, 218)),
+        }
+    }
+
+    /// Orange for correct and blue for present, as in the official
+    /// colorblind mode.
+    pub fn high_contrast() -> Self {
+        Theme {
+            key_unused: TileStyle::on(Color::Black),
+            ..Palette {
+                correct: [245, 121, 58],
+                present: [133, 192, 249],
+                absent: [58, 58, 60],
+                unused: [65, 65, 65],
+            }
+            .theme()
+        }
+    }
+
+    pub fn high_contrast_light() -> Self {
+        Theme {
+            correct: TileStyle::on(Color::Rgb(245, 121, 58)),
+            present: TileStyle::on(Color::Rgb(133, 192, 249)),
+            ..Theme::light()
+        }
+    }
+
+    pub fn tile(&self, state: TileState) -> TileStyle {
+        match state {
+            TileState::Correct => self.correct,
+            TileState::Present => self.present,
+            TileState::Absent => self.absent,
+            TileState::Unused => self.unused,
+        }
+    }
+
+    pub fn key(&self, state: TileState) -> TileStyle {
+        match state {
+            TileState::Unused => self.key_unused,
+            state => self.tile(state),
+        }
+    }
+}
+
+/// A theme's colors as `themes.toml` writes them: RGB backgrounds for
+/// correct, present, absent and unused tiles.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Palette {
+    pub correct: [u8; 3],
+    pub present: [u8; 3],
+    pub absent: [u8; 3],
+    pub unused: [u8; 3],
+}
+
+impl Palette {
+    /// The theme of these backgrounds; letters get contrast-aware
+    /// foregrounds.
+    pub fn theme(&self) -> Theme {
+        let rgb = |[r, g, b]: [u8; 3]| TileStyle::on(Color::Rgb(r, g, b));
+        Theme {
+            correct: rgb(self.correct),
+            present: rgb(self.present),
+            absent: rgb(self.absent),
+            unused: rgb(self.unused),
+            key_unused: rgb(self.unused),
+        }
+    }
+}
+
+/// Theme colors by theme id.
+pub type Palettes = BTreeMap<String, Palette>;
+
+/// The built-in colors with those in `contents` over them, so a file can
+/// change one theme and leave the rest. Ids of themes that don't exist are
+/// refused.
+pub fn parse_palettes(contents: &str, origin: &str) -> Result<Palettes> {
+    let mut palettes = builtin_palettes();
+    palettes.extend(read_palettes(contents, origin)?);
+    Ok(palettes)
+}
+
+/// The colors of the built-in `themes.toml`.
+pub fn builtin_palettes() -> Palettes {
+    read_palettes(THEME_COLORS.embedded, THEME_COLORS.builtin)
+        .expect("the built-in theme colors parse")
+}
+
+fn read_palettes(contents: &str, origin: &str) -> Result<Palettes> {
+    let palettes: Palettes =
+        toml::from_str(contents).with_context(|| format!("invalid theme colors in {origin}"))?;
+    if let Some(id) = palettes
+        .keys()
+        .find(|id| !THEMES.iter().any(|entry| entry.id == *id))
+    {
+        bail!("{origin} has colors for {id}, which isn't a theme");
+    }
+    Ok(palettes)
+}
+
+/// Ramp from a dim blue through orange to bright yellow, for `fraction`
+/// between 0 (cold) and 1 (hottest).
+pub fn heat_color(fraction: f32) -> Color {
+    const STOPS: [(f32, f32, f32); 3] = [
+        (40.0, 44.0, 70.0),
+        (230.0, 110.0, 30.0),
+        (255.0, 236.0, 110.0),
+    ];
+    let scaled = fraction.clamp(0.0, 1.0) * (STOPS.len() - 1) as f32;
+    let index = (scaled as usize).min(STOPS.len() - 2);
+    let t = scaled - index as f32;
+    let (from, to) = (STOPS[index], STOPS[index + 1]);
+    let mix = |a: f32, b: f32| (a + (b - a) * t).round() as u8;
+    Color::Rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+/// Black on light backgrounds, white on dark ones, using the usual luma
+/// weights over an approximation of the terminal's named colors.
+pub fn readable_fg(bg: Color) -> Color {
+    let (r, g, b) = match bg {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Black => (0, 0, 0),
+        Color::Red => (205, 0, 0),
+        Color::Green => (0, 205, 0),
+        Color::Yellow => (205, 205, 0),
+        Color::Blue => (0, 0, 238),
+        Color::Magenta => (205, 0, 205),
+        Color::Cyan => (0, 205, 205),
+        Color::Gray => (229, 229, 229),
+        Color::DarkGray => (127, 127, 127),
+        Color::LightRed => (255, 85, 85),
+        Color::LightGreen => (85, 255, 85),
+        Color::LightYellow => (255, 255, 85),
+        Color::LightBlue => (92, 92, 255),
+        Color::LightMagenta => (255, 85, 255),
+        Color::LightCyan => (85, 255, 255),
+        Color::White => (255, 255, 255),
+        Color::Reset | Color::Indexed(_) => return Color::White,
+    };
+    let luma = 0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32;
+    if luma > 140.0 {
+        Color::Black
+    } else {
+        Color::White
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default_contrast_test() {
+        let theme = Theme::default();
+        assert_eq!(theme.correct.fg, Color::Black);
+        assert_eq!(theme.present.fg, Color::Black);
+        assert_eq!(theme.absent.fg, Color::White);
+        assert_eq!(theme.unused.fg, Color::White);
+        assert_eq!(theme.key_unused.fg, Color::White);
+    }
+
+    #[test]
+    fn override_test() {
+        let theme = Theme {
+            present: TileStyle {
+                bg: Color::Yellow,
+                fg: Color::Blue,
+            },
+            ..Theme::default()
+        };
+        assert_eq!(theme.tile(TileState::Present).fg, Color::Blue);
+        assert_eq!(theme.key(TileState::Present).fg, Color::Blue);
+    }
+
+    #[test]
+    fn heat_ramp_test() {
+        assert_eq!(heat_color(0.0), Color::Rgb(40, 44, 70));
+        assert_eq!(heat_color(0.5), Color::Rgb(230, 110, 30));
+        assert_eq!(heat_color(1.0), Color::Rgb(255, 236, 110));
+        assert_eq!(heat_color(2.0), heat_color(1.0));
+        assert_eq!(readable_fg(heat_color(0.0)), Color::White);
+        assert_eq!(readable_fg(heat_color(1.0)), Color::Black);
+    }
+
+    #[test]
+    fn lock_predicate_test() {
+        let unlocked = BTreeSet::from(["ocean".to_string()]);
+        let locked: Vec<&str> = THEMES
+            .iter()
+            .filter(|entry| entry.is_locked(&unlocked))
+            .map(|entry| entry.id)
+            .collect();
+        assert_eq!(locked, vec!["sunset", "forest", "neon"]);
+        assert!(!THEMES[0].is_locked(&BTreeSet::new()));
+    }
+
+    #[test]
+    fn palettes_test() {
+        let builtin = builtin_palettes();
+        let ocean = THEMES.iter().find(|entry| entry.id == "ocean").unwrap();
+        assert_eq!(
+            ocean.theme(Background::Dark, &builtin).correct.bg,
+            Color::Rgb(0, 150, 136)
+        );
+
+        // one theme changed, the others as they were
+        let own = parse_palettes(
+            "[ocean]\ncorrect = [1, 2, 3]\npresent = [4, 5, 6]\nabsent = [7, 8, 9]\nunused = [0, 0, 0]\n",
+            "themes.toml",
+        )
+        .unwrap();
+        assert_eq!(
+            ocean.theme(Background::Dark, &own).correct.bg,
+            Color::Rgb(1, 2, 3)
+        );
+        assert_eq!(own["sunset"], builtin["sunset"]);
+        // classic keeps its light variant whatever the file says
+        let classic = parse_palettes(
+            "[classic]\ncorrect = [1, 2, 3]\npresent = [4, 5, 6]\nabsent = [7, 8, 9]\nunused = [0, 0, 0]\n",
+            "themes.toml",
+        )
+        .unwrap();
+        assert_eq!(THEMES[0].theme(Background::Light, &classic), Theme::light());
+        assert_ne!(
+            THEMES[0].theme(Background::Dark, &classic),
+            Theme::default()
+        );
+
+        let plaid = "[plaid]\ncorrect = [1, 2, 3]\npresent = [4, 5, 6]\nabsent = [7, 8, 9]\nunused = [0, 0, 0]\n";
+        assert!(parse_palettes(plaid, "themes.toml").is_err());
+        assert!(parse_palettes("[ocean]\ncorrect = [1, 2]\n", "themes.toml").is_err());
+    }
+}
@@ -1,41 +1,50 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Rect},
-    style::{Color, Stylize},
-    widgets::{Block, Paragraph, Widget},
+    style::{Style, Stylize},
+    widgets::{Block, Widget},
 };
+use wordle::engine::Tile;
 
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
-pub enum TileState {
-    Correct,
-    Present,
-    Absent,
-    Unused,
-}
-
-#[derive(Debug, Clone, Copy)]
-pub struct Tile {
-    pub letter: char,
-    pub state: TileState,
-}
+use super::theme::Theme;
 
-impl Tile {
-    pub fn get_color(&self) -> Color {
-        match self.state {
-            TileState::Correct => Color::Green,
-            TileState::Present => Color::Yellow,
-            TileState::Absent => Color::DarkGray,
-            TileState::Unused => Color::Rgb(65, 65, 65), // very dark gray
-        }
+/// Draws `tile` as a block of its color with the letter in the middle.
+pub fn render_tile(tile: Tile, theme: &Theme, area: Rect, buf: &mut Buffer) {
+    let style = theme.tile(tile.state);
+    Block::new().bg(style.bg).render(area, buf);
+    // set in place: a Paragraph would allocate for every tile, every frame
+    let center = area.centered(Constraint::Length(1), Constraint::Length(1));
+    if !center.is_empty()
+        && let Some(cell) = buf.cell_mut(center.as_position())
+    {
+        cell.set_char(tile.letter)
+            .set_style(Style::new().fg(style.fg).bold());
     }
 }
 
-impl Widget for Tile {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        Block::new().bg(self.get_color()).render(area, buf);
-        Paragraph::new(format!("{}", self.letter)).bold().render(
-            area.centered(Constraint::Length(1), Constraint::Length(1)),
-            buf,
-        );
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratatui::style::Color;
+    use wordle::engine::TileState;
+
+    #[test]
+    fn explicit_foreground_test() {
+        let theme = Theme::default();
+        let area = Rect::new(0, 0, 5, 3);
+        for state in [
+            TileState::Correct,
+            TileState::Present,
+            TileState::Absent,
+            TileState::Unused,
+        ] {
+            let mut buf = Buffer::empty(area);
+            render_tile(Tile { letter: 'A', state }, &theme, area, &mut buf);
+            let cell = &buf[(2, 1)];
+            assert_eq!(cell.symbol(), "A");
+            assert_ne!(cell.fg, Color::Reset);
+            assert_eq!(cell.fg, theme.tile(state).fg);
+            assert_eq!(cell.bg, theme.tile(state).bg);
+        }
     }
 }
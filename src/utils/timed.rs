@@ -0,0 +1,234 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+use std::time::{Duration, Instant};
+
+use super::elapsed::PausableClock;
+use super::wordle::Wordle;
+
+/// Seconds on the clock when `timed_seconds` isn't set in `config.json`.
+pub const DEFAULT_TIMED_SECS: u64 = 180;
+
+/// The one clock of a `--timed` run, shared by every word dealt in it, and
+/// how the run has gone so far. Time spent paused doesn't count.
+#[derive(Debug, Clone)]
+pub struct TimedRun {
+    pub limit: Duration,
+    pub clock: PausableClock,
+    /// run time when the word on the board was dealt
+    word_started: Duration,
+    pub solved: usize,
+    /// words moved on from without solving them
+    pub skipped: usize,
+    pub fastest: Option<Duration>,
+    /// the clock ran out; no more guesses until a new run
+    pub over: bool,
+    /// the summary popup, shown from the clock running out until a key
+    pub show_summary: bool,
+}
+
+impl TimedRun {
+    pub fn new(limit: Duration, now: Instant) -> Self {
+        TimedRun {
+            limit,
+            clock: PausableClock::new(now),
+            word_started: Duration::ZERO,
+            solved: 0,
+            skipped: 0,
+            fastest: None,
+            over: false,
+            show_summary: false,
+        }
+    }
+
+    /// Run time used up at `now`, pauses left out.
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        self.clock.elapsed(now)
+    }
+
+    pub fn remaining(&self, now: Instant) -> Duration {
+        self.limit.saturating_sub(self.elapsed(now))
+    }
+
+    /// Counts the word on the board as solved and starts timing the next.
+    pub fn solve(&mut self, now: Instant) {
+        let took = self.elapsed(now).saturating_sub(self.word_started);
+        self.solved += 1;
+        self.fastest = Some(self.fastest.map_or(took, |fastest| fastest.min(took)));
+        self.word_started = self.elapsed(now);
+    }
+
+    /// Counts the word on the board as skipped and starts timing the next.
+    pub fn skip(&mut self, now: Instant) {
+        self.skipped += 1;
+        self.word_started = self.elapsed(now);
+    }
+}
+
+/// `m:ss`, rounded up so the clock shows `0:00` only once time is up.
+pub fn clock(duration: Duration) -> String {
+    let secs = duration.as_secs_f64().ceil() as u64;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+impl Wordle {
+    /// Plays against one clock for the whole run, `timed_seconds` from the
+    /// config or `DEFAULT_TIMED_SECS` long.
+    pub fn start_timed(&mut self) {
+        let secs = self
+            .config
+            .timed_seconds
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_TIMED_SECS);
+        self.timed = Some(TimedRun::new(Duration::from_secs(secs), Instant::now()));
+    }
+
+    /// Keeps the run's clock in step at `now`: stopped while the definition
+    /// page is open, and the run ended once it reaches zero.
+    pub fn tick_timed(&mut self, now: Instant) {
+        let Some(run) = &mut self.timed else {
+            return;
+        };
+        if self.show_word_def {
+            run.clock.pause(now);
+        } else {
+            run.clock.resume(now);
+        }
+        if !run.over && run.remaining(now).is_zero() {
+            run.over = true;
+            run.show_summary = true;
+            self.current_guess.clear();
+        }
+    }
+
+    /// A finished game of a timed run: a solved word is counted and the
+    /// next one dealt straight away. A lost one stays on the board until
+    /// the player moves on, which counts it as skipped.
+    pub fn timed_game_over(&mut self, now: Instant) {
+        let Some(run) = &mut self.timed else {
+            return;
+        };
+        if !self.solved || run.over {
+            return;
+        }
+        let took = clock(run.elapsed(now).saturating_sub(run.word_started));
+        run.solve(now);
+        self.game_restart();
+        self.show_status(format!("solved in {took}, here's the next word"));
+    }
+
+    /// The new game key in a timed run: skips the word on the board, or
+    /// starts another run once the clock has run out.
+    pub fn next_timed_word(&mut self) {
+        let now = Instant::now();
+        let Some(run) = &mut self.timed else {
+            return;
+        };
+        if run.over {
+            self.start_timed();
+        } else if !self.solved {
+            run.skip(now);
+        }
+        self.game_restart();
+    }
+
+    /// Remaining time for the top edge of the border, red for the last
+    /// half minute.
+    pub fn timed_title(&self, run: &TimedRun) -> Line<'static> {
+        let remaining = run.remaining(Instant::now());
+        let title = Line::from(format!(" {} ", clock(remaining))).bold();
+        if remaining <= Duration::from_secs(30) {
+            title.fg(Color::Red)
+        } else {
+            title
+        }
+    }
+
+    /// Popup once the clock runs out: words solved and skipped, and the
+    /// quickest solve.
+    pub fn render_timed_summary(&self, run: &TimedRun, area: Rect, buf: &mut Buffer) {
+        let fastest = run.fastest.map_or(String::from("-"), clock);
+        let lines = vec![
+            Line::from("Time's up!").bold().fg(Color::Yellow).centered(),
+            Line::from(""),
+            Line::from(format!("words solved   {:>5}", run.solved)),
+            Line::from(format!("words skipped  {:>5}", run.skipped)),
+            Line::from(format!("fastest solve  {fastest:>5}")),
+        ];
+        let popup = area.centered(Constraint::Length(26), Constraint::Length(7));
+        Clear.render(popup, buf);
+        Paragraph::new(lines)
+            .block(Block::bordered().border_type(BorderType::Rounded))
+            .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn run_counts_test() {
+        let start = Instant::now();
+        let secs = Duration::from_secs;
+        let mut run = TimedRun::new(secs(180), start);
+        run.clock.pause(start + secs(10));
+        run.clock.resume(start + secs(40));
+        assert_eq!(run.remaining(start + secs(50)), secs(160));
+
+        run.solve(start + secs(50));
+        run.skip(start + secs(60));
+        run.solve(start + secs(65));
+        assert_eq!((run.solved, run.skipped), (2, 1));
+        assert_eq!(run.fastest, Some(secs(5)));
+        assert_eq!(clock(run.remaining(start + secs(400))), "0:00");
+        assert_eq!(clock(Duration::from_millis(61_200)), "1:02");
+    }
+
+    #[test]
+    fn timed_run_test() {
+        let mut game = Wordle::new();
+        game.start_timed();
+        let start = Instant::now();
+
+        // solving deals the next word at once
+        let first = game.answer.clone();
+        game.current_guess = first.clone();
+        game.submit_guess();
+        game.timed_game_over(start);
+        assert_ne!(game.answer, first);
+        assert!(game.is_accepting_guesses());
+        assert_eq!(game.timed.as_ref().unwrap().solved, 1);
+
+        // moving on from a word counts it as skipped
+        game.next_timed_word();
+        assert_eq!(game.timed.as_ref().unwrap().skipped, 1);
+
+        // the definition page stops the clock
+        game.show_word_def = true;
+        let limit = game.timed.as_ref().unwrap().limit;
+        game.tick_timed(start + limit / 2);
+        game.tick_timed(start + limit * 2);
+        assert!(!game.timed.as_ref().unwrap().over);
+        game.show_word_def = false;
+        game.tick_timed(start + limit * 2);
+        assert!(!game.timed.as_ref().unwrap().over);
+
+        // and running out ends the run mid-guess
+        game.current_guess = String::from("CRA");
+        game.tick_timed(start + limit * 4);
+        let run = game.timed.as_ref().unwrap();
+        assert!(run.over && run.show_summary);
+        assert!(!game.is_accepting_guesses());
+        assert!(game.current_guess.is_empty());
+
+        // until a new run starts
+        game.next_timed_word();
+        assert!(!game.timed.as_ref().unwrap().over);
+        assert_eq!(game.timed.as_ref().unwrap().solved, 0);
+    }
+}
@@ -0,0 +1,245 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, Widget},
+};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+use super::ui::InputState;
+use super::wordle::Wordle;
+
+/// Narrower bars than this are unreadable, so the analysis shows totals.
+const MIN_BAR_WIDTH: usize = 10;
+const THINK_COLOR: Color = Color::Blue;
+const TYPING_COLOR: Color = Color::Magenta;
+
+/// How long a submitted row took: from the row becoming active to the first
+/// letter, and from that letter to Enter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RowTiming {
+    pub think_ms: u64,
+    pub typing_ms: u64,
+}
+
+impl RowTiming {
+    pub fn total_ms(&self) -> u64 {
+        self.think_ms + self.typing_ms
+    }
+}
+
+/// Timestamps of the row being typed.
+#[derive(Debug, Clone, Copy)]
+pub struct RowClock {
+    started: Instant,
+    first_key: Option<Instant>,
+}
+
+impl RowClock {
+    pub fn new(now: Instant) -> Self {
+        RowClock {
+            started: now,
+            first_key: None,
+        }
+    }
+
+    /// Called after every edit of the guess. Clearing the row means the
+    /// player is thinking again, so the first key is forgotten.
+    pub fn edited(&mut self, is_empty: bool, now: Instant) {
+        if is_empty {
+            self.first_key = None;
+        } else if self.first_key.is_none() {
+            self.first_key = Some(now);
+        }
+    }
+
+    /// Times the row submitted at `now` and starts timing the next one.
+    pub fn finish(&mut self, now: Instant) -> RowTiming {
+        let first_key = self.first_key.unwrap_or(now);
+        let timing = RowTiming {
+            think_ms: first_key.duration_since(self.started).as_millis() as u64,
+            typing_ms: now.duration_since(first_key).as_millis() as u64,
+        };
+        *self = RowClock::new(now);
+        timing
+    }
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{:.1}s", ms as f64 / 1000.0)
+}
+
+/// One two-tone bar per row scaled to the slowest row, or just the totals
+/// when `width` can't fit readable bars. `timings` has a place per row of
+/// `guesses`, empty for rows that weren't timed.
+pub fn analysis_lines(
+    guesses: &[String],
+    timings: &[Option<RowTiming>],
+    width: usize,
+) -> Vec<Line<'static>> {
+    let think: u64 = timings.iter().flatten().map(|timing| timing.think_ms).sum();
+    let typing: u64 = timings
+        .iter()
+        .flatten()
+        .map(|timing| timing.typing_ms)
+        .sum();
+    let totals = Line::from(vec![
+        Span::raw("Thinking ").fg(THINK_COLOR),
+        Span::raw(seconds(think)),
+        Span::raw("  Typing ").fg(TYPING_COLOR),
+        Span::raw(seconds(typing)),
+    ]);
+
+    // "1 CRANE " before the bar, " 12.3s+4.1s" after it
+    let bar_width = width.saturating_sub(8 + 12);
+    if bar_width < MIN_BAR_WIDTH {
+        return vec![totals];
+    }
+
+    let slowest = timings
+        .iter()
+        .flatten()
+        .map(RowTiming::total_ms)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let cells = |ms: u64| (ms * bar_width as u64 / slowest) as usize;
+    let mut lines = Vec::new();
+    for (index, (guess, timing)) in guesses.iter().zip(timings).enumerate() {
+        let Some(timing) = timing else {
+            lines.push(Line::from(vec![
+                Span::raw(format!("{} {guess} ", index + 1)),
+                Span::raw("not timed").fg(Color::DarkGray),
+            ]));
+            continue;
+        };
+        let think_cells = cells(timing.think_ms);
+        let typing_cells = cells(timing.total_ms()) - think_cells;
+        lines.push(Line::from(vec![
+            Span::raw(format!("{} {guess} ", index + 1)),
+            Span::raw("█".repeat(think_cells)).fg(THINK_COLOR),
+            Span::raw("█".repeat(typing_cells)).fg(TYPING_COLOR),
+            Span::raw(format!(
+                " {}+{}",
+                seconds(timing.think_ms),
+                seconds(timing.typing_ms)
+            ))
+            .fg(Color::DarkGray),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(totals);
+    lines
+}
+
+impl Wordle {
+    pub fn on_analysis_key(&mut self, key: KeyEvent) -> InputState {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('A')) {
+            self.show_analysis = false;
+        }
+        InputState::None
+    }
+
+    pub fn render_analysis(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let guesses = self.record().guesses;
+        let mut lines = analysis_lines(
+            &guesses,
+            &self.row_timings,
+            (area.width as usize).saturating_sub(2),
+        );
+        if let Some(average) = self.stats.average_timing() {
+            lines.push(Line::from(
+                format!(
+                    "Per row on average: {} + {}",
+                    seconds(average.think_ms),
+                    seconds(average.typing_ms)
+                )
+                .fg(Color::DarkGray),
+            ));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::raw("Back "), "<Esc>".blue().bold()]));
+
+        Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .title("Thinking vs typing")
+                    .border_type(BorderType::Rounded),
+            )
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    fn bar(line: &Line, color: Color) -> usize {
+        line.spans
+            .iter()
+            .filter(|span| span.style.fg == Some(color) && span.content.contains('█'))
+            .map(|span| span.content.chars().count())
+            .sum()
+    }
+
+    #[test]
+    fn clock_reset_on_empty_test() {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let mut clock = RowClock::new(start);
+        clock.edited(false, at(1000));
+        clock.edited(false, at(1200));
+        // typed, deleted everything and thought again
+        clock.edited(true, at(1500));
+        clock.edited(false, at(4000));
+        assert_eq!(
+            clock.finish(at(5000)),
+            RowTiming {
+                think_ms: 4000,
+                typing_ms: 1000
+            }
+        );
+
+        // the next row starts at the previous Enter
+        clock.edited(false, at(6000));
+        assert_eq!(clock.finish(at(6500)).think_ms, 1000);
+    }
+
+    #[test]
+    fn analysis_collapses_test() {
+        let guesses = vec!["CRANE".to_string(), "EPOCH".to_string()];
+        let timings = [
+            Some(RowTiming {
+                think_ms: 3000,
+                typing_ms: 1000,
+            }),
+            Some(RowTiming {
+                think_ms: 1000,
+                typing_ms: 1000,
+            }),
+        ];
+        let wide = analysis_lines(&guesses, &timings, 60);
+        assert_eq!(wide.len(), 4);
+        assert_eq!(bar(&wide[0], THINK_COLOR), 30);
+        assert_eq!(bar(&wide[0], TYPING_COLOR), 10);
+        assert_eq!(bar(&wide[1], THINK_COLOR), 10);
+        assert_eq!(bar(&wide[1], TYPING_COLOR), 10);
+
+        let narrow = analysis_lines(&guesses, &timings, 24);
+        assert_eq!(narrow.len(), 1);
+        assert_eq!(narrow[0].to_string(), "Thinking 4.0s  Typing 2.0s");
+
+        // an untimed row, e.g. resumed, keeps the others on their own guess
+        let timings = [None, timings[1]];
+        let wide = analysis_lines(&guesses, &timings, 60);
+        assert_eq!(wide[0].to_string(), "1 CRANE not timed");
+        assert!(wide[1].to_string().starts_with("2 EPOCH "));
+        assert_eq!(bar(&wide[1], THINK_COLOR), 20);
+    }
+}
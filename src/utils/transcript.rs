@@ -0,0 +1,262 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use wordle::engine::TileState;
+
+use super::wordle::Wordle;
+
+/// What `e` writes after a game, set with `transcript_format` in
+/// `config.json`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    /// letters with `[G]`/`[Y]`/`[.]` markers, for reading
+    #[default]
+    Txt,
+    Json,
+}
+
+impl TranscriptFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            TranscriptFormat::Txt => "txt",
+            TranscriptFormat::Json => "json",
+        }
+    }
+}
+
+/// A finished game written out for keeping.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameTranscript {
+    pub answer: String,
+    pub rounds: u8,
+    pub rows: Vec<TranscriptRow>,
+    pub solved: bool,
+    /// time spent on the rows played from the keyboard
+    pub duration_ms: u64,
+}
+
+/// A guess and the color of each of its letters; a pass has no letters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptRow {
+    pub guess: String,
+    pub states: Vec<TileState>,
+}
+
+impl GameTranscript {
+    /// The text form: a heading with the outcome, then one row per line.
+    pub fn to_text(&self) -> String {
+        let secs = self.duration_ms / 1000;
+        let outcome = if self.solved {
+            format!("solved in {}/{}", self.rows.len(), self.rounds)
+        } else {
+            String::from("not solved")
+        };
+        let mut text = format!(
+            "Wordle {}, {outcome}, {}:{:02}\n\n",
+            self.answer,
+            secs / 60,
+            secs % 60
+        );
+        for row in &self.rows {
+            if row.guess.is_empty() {
+                text.push_str("(passed)\n");
+                continue;
+            }
+            let tiles: Vec<String> = row
+                .guess
+                .chars()
+                .zip(&row.states)
+                .map(|(letter, state)| {
+                    let marker = match state {
+                        TileState::Correct => 'G',
+                        TileState::Present => 'Y',
+                        _ => '.',
+                    };
+                    format!("{letter}[{marker}]")
+                })
+                .collect();
+            text.push_str(&tiles.join(" "));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl Wordle {
+    pub fn to_transcript(&self) -> GameTranscript {
+        GameTranscript {
+            answer: self.answer.clone(),
+            rounds: self.rounds,
+            rows: self
+                .guess_history
+                .iter()
+                .map(|row| TranscriptRow {
+                    guess: if row.is_pass() {
+                        String::new()
+                    } else {
+                        row.text()
+                    },
+                    states: row.letters.iter().map(|tile| tile.state).collect(),
+                })
+                .collect(),
+            solved: self.solved,
+            duration_ms: self
+                .row_timings
+                .iter()
+                .flatten()
+                .map(|timing| timing.total_ms())
+                .sum(),
+        }
+    }
+
+    /// Writes the finished game's transcript to the working directory and
+    /// shows where.
+    pub fn export_transcript(&mut self) {
+        let written = self.write_transcript(&self.transcript_dir);
+        match written {
+            Ok(path) => self.show_status(format!("wrote {}", path.display())),
+            Err(err)
+                if err.downcast_ref::<io::Error>().map(io::Error::kind)
+                    == Some(io::ErrorKind::AlreadyExists) =>
+            {
+                self.show_error(format!(
+                    "{err}; set overwrite_transcripts in config.json to replace it"
+                ))
+            }
+            Err(err) => self.show_error(format!("failed to write transcript: {err:#}")),
+        }
+    }
+
+    /// Writes the transcript in the configured format to `dir`, named after
+    /// the answer. An existing file is only replaced when
+    /// `overwrite_transcripts` is set.
+    pub fn write_transcript(&self, dir: &Path) -> Result<PathBuf> {
+        let format = self.config.transcript_format;
+        let transcript = self.to_transcript();
+        let contents = match format {
+            TranscriptFormat::Txt => transcript.to_text(),
+            TranscriptFormat::Json => serde_json::to_string_pretty(&transcript)? + "\n",
+        };
+        let path = dir.join(format!(
+            "wordle-{}.{}",
+            self.answer.to_lowercase(),
+            format.extension()
+        ));
+        if self.config.overwrite_transcripts {
+            fs::write(&path, contents)?;
+        } else {
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+                .map_err(|err| match err.kind() {
+                    io::ErrorKind::AlreadyExists => {
+                        io::Error::new(err.kind(), format!("{} exists", path.display()))
+                    }
+                    _ => err,
+                })?;
+            file.write_all(contents.as_bytes())?;
+        }
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::testing::temp_dir;
+    use crate::utils::timing::RowTiming;
+
+    fn scripted_game() -> Wordle {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        for guess in ["CRANE", "PORCH", "EPOCH"] {
+            game.current_guess = String::from(guess);
+            game.submit_guess();
+        }
+        game.row_timings = vec![
+            Some(RowTiming {
+                think_ms: 4000,
+                typing_ms: 1500,
+            }),
+            Some(RowTiming {
+                think_ms: 61000,
+                typing_ms: 900,
+            }),
+            None,
+        ];
+        game
+    }
+
+    #[test]
+    fn golden_json_test() {
+        let json = serde_json::to_string_pretty(&scripted_game().to_transcript()).unwrap();
+        let golden = r#"{
+  "answer": "EPOCH",
+  "rounds": 6,
+  "rows": [
+    {
+      "guess": "CRANE",
+      "states": [
+        "present",
+        "absent",
+        "absent",
+        "absent",
+        "present"
+      ]
+    },
+    {
+      "guess": "PORCH",
+      "states": [
+        "present",
+        "present",
+        "absent",
+        "correct",
+        "correct"
+      ]
+    },
+    {
+      "guess": "EPOCH",
+      "states": [
+        "correct",
+        "correct",
+        "correct",
+        "correct",
+        "correct"
+      ]
+    }
+  ],
+  "solved": true,
+  "duration_ms": 67400
+}"#;
+        assert_eq!(json, golden);
+    }
+
+    #[test]
+    fn text_and_clobber_test() {
+        let dir = temp_dir("transcript");
+        fs::create_dir_all(&dir).unwrap();
+        let mut game = scripted_game();
+        let path = game.write_transcript(&dir).unwrap();
+        assert_eq!(path, dir.join("wordle-epoch.txt"));
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("Wordle EPOCH, solved in 3/6, 1:07\n"));
+        assert!(text.contains("\nP[Y] O[Y] R[.] C[G] H[G]\n"));
+
+        // kept unless overwriting is allowed
+        fs::write(&path, "mine").unwrap();
+        let err = game.write_transcript(&dir).unwrap_err();
+        assert!(err.to_string().contains("exists"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "mine");
+        game.config.overwrite_transcripts = true;
+        game.config.transcript_format = TranscriptFormat::Json;
+        game.write_transcript(&dir).unwrap();
+        game.write_transcript(&dir).unwrap();
+        assert!(dir.join("wordle-epoch.json").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
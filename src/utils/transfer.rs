@@ -0,0 +1,344 @@
+use anyhow::{Context, Result, bail};
+use serde_json::{Map, Value};
+use std::fmt::Write;
+
+use super::stats::{STATS_FORMAT, Stats};
+
+const CSV_HEADER: &str = "field,value";
+/// Separates the items of a list within one CSV cell.
+const CSV_LIST_SEPARATOR: char = ';';
+
+/// Shapes `stats export` writes and `stats import` reads.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StatsFormat {
+    /// the stats file itself, version and all
+    Json,
+    /// a `field,value` row per stat, lists joined with `;`
+    Csv,
+}
+
+impl StatsFormat {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "json" => Ok(StatsFormat::Json),
+            "csv" => Ok(StatsFormat::Csv),
+            _ => bail!("unknown format '{name}', expected json or csv"),
+        }
+    }
+
+    /// JSON if it looks like an object, CSV otherwise.
+    pub fn sniff(contents: &str) -> Self {
+        if contents.trim_start().starts_with('{') {
+            StatsFormat::Json
+        } else {
+            StatsFormat::Csv
+        }
+    }
+}
+
+/// One CSV cell: numbers and strings as they are, nothing for `null`.
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(csv_cell)
+            .collect::<Vec<_>>()
+            .join(&CSV_LIST_SEPARATOR.to_string()),
+        other => other.to_string(),
+    }
+}
+
+/// Reads back a cell written by `csv_cell`, shaped like `template`, the
+/// field's value in empty stats.
+fn csv_value(field: &str, cell: &str, template: &Value) -> Result<Value> {
+    match template {
+        Value::Array(_) if cell.is_empty() => Ok(Value::Array(Vec::new())),
+        Value::Array(_) => Ok(cell
+            .split(CSV_LIST_SEPARATOR)
+            .map(|item| {
+                item.parse::<i64>()
+                    .map_or_else(|_| Value::from(item), Value::from)
+            })
+            .collect()),
+        _ if cell.is_empty() => Ok(Value::Null),
+        _ => cell
+            .parse::<i64>()
+            .map(Value::from)
+            .with_context(|| format!("{field} should be a number, not '{cell}'")),
+    }
+}
+
+fn from_csv(contents: &str) -> Result<Value> {
+    let template = serde_json::to_value(Stats::default())?;
+    let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+    if lines.next().map(str::trim) != Some(CSV_HEADER) {
+        bail!("stats CSV should start with '{CSV_HEADER}'");
+    }
+    let mut object = Map::new();
+    for line in lines {
+        let Some((field, cell)) = line.split_once(',') else {
+            bail!("expected 'field,value', found '{line}'");
+        };
+        let (field, cell) = (field.trim(), cell.trim());
+        let value = match template.get(field) {
+            Some(template) => csv_value(field, cell, template)?,
+            None if field == "version" => csv_value(field, cell, &Value::Null)?,
+            None => bail!("unknown field '{field}'"),
+        };
+        if object.insert(field.to_string(), value).is_some() {
+            bail!("{field} is given twice");
+        }
+    }
+    Ok(Value::Object(object))
+}
+
+/// Fails on the first negative number, which the stats can't hold but an
+/// edited file might.
+fn check_counts(field: &str, value: &Value) -> Result<()> {
+    match value {
+        Value::Number(number) if number.as_f64().is_some_and(|number| number < 0.0) => {
+            bail!("{field} can't be negative ({number})")
+        }
+        Value::Array(items) => items.iter().try_for_each(|item| check_counts(field, item)),
+        _ => Ok(()),
+    }
+}
+
+impl Stats {
+    /// The stats as `format`, for `stats export`.
+    pub fn export(&self, format: StatsFormat) -> Result<String> {
+        if format == StatsFormat::Json {
+            return Ok(STATS_FORMAT.to_string(self)? + "\n");
+        }
+        let Value::Object(object) = serde_json::to_value(self)? else {
+            unreachable!("stats serialize as an object");
+        };
+        let mut csv = format!("{CSV_HEADER}\nversion,{}\n", STATS_FORMAT.current());
+        for (field, value) in &object {
+            writeln!(csv, "{field},{}", csv_cell(value))?;
+        }
+        Ok(csv)
+    }
+
+    /// Reads stats written by `export` in either format, refusing any that
+    /// don't add up.
+    pub fn import(contents: &str) -> Result<Self> {
+        let value = match StatsFormat::sniff(contents) {
+            StatsFormat::Json => {
+                serde_json::from_str(contents).context("stats export is not valid JSON")?
+            }
+            StatsFormat::Csv => from_csv(contents)?,
+        };
+        let Value::Object(object) = &value else {
+            bail!("stats export should hold a JSON object");
+        };
+        for (field, value) in object {
+            check_counts(field, value)?;
+        }
+        let (stats, _): (Stats, bool) = STATS_FORMAT.parse(&value.to_string())?;
+        stats.validate()?;
+        Ok(stats)
+    }
+
+    /// Checks the counts agree with each other. An empty guess distribution
+    /// is let through, since stats from before it was kept have none.
+    pub fn validate(&self) -> Result<()> {
+        if self.games_won > self.games_played {
+            bail!(
+                "games_won ({}) is more than games_played ({})",
+                self.games_won,
+                self.games_played
+            );
+        }
+        let distributed: u64 = self
+            .guess_distribution
+            .iter()
+            .map(|&wins| wins as u64)
+            .sum();
+        if !self.guess_distribution.is_empty() && distributed != self.games_won as u64 {
+            bail!(
+                "guess_distribution adds up to {distributed} wins, but games_won is {}",
+                self.games_won
+            );
+        }
+        if self.current_streak > self.max_streak || self.max_streak > self.games_won {
+            bail!(
+                "streaks don't fit: current_streak {}, max_streak {}, games_won {}",
+                self.current_streak,
+                self.max_streak,
+                self.games_won
+            );
+        }
+        if self.blitz_won > self.blitz_played {
+            bail!(
+                "blitz_won ({}) is more than blitz_played ({})",
+                self.blitz_won,
+                self.blitz_played
+            );
+        }
+        if self.hinted_games > self.letters_revealed {
+            bail!(
+                "hinted_games ({}) is more than letters_revealed ({})",
+                self.hinted_games,
+                self.letters_revealed
+            );
+        }
+        if self.solve_times > self.games_won {
+            bail!(
+                "solve_times ({}) is more than games_won ({})",
+                self.solve_times,
+                self.games_won
+            );
+        }
+        Ok(())
+    }
+
+    /// Adds the games counted in `other`, e.g. another machine's stats.
+    ///
+    /// Streaks can't be joined without knowing which games came last, so
+    /// the current streak stays this one's and the best streak is the
+    /// longer of the two. Unlocked themes stay unlocked.
+    pub fn merge(&mut self, other: &Stats) {
+        let Stats {
+            games_played,
+            games_won,
+            current_streak: _,
+            max_streak,
+            guess_distribution,
+            blitz_played,
+            blitz_won,
+            zen_played,
+            zen_rows,
+            unlocked_themes,
+            timed_rows,
+            think_ms,
+            typing_ms,
+            solve_times,
+            solve_ms,
+            best_solve_ms,
+            hinted_games,
+            letters_revealed,
+        } = other;
+        self.games_played += games_played;
+        self.games_won += games_won;
+        self.max_streak = self.max_streak.max(*max_streak);
+        if self.guess_distribution.len() < guess_distribution.len() {
+            self.guess_distribution.resize(guess_distribution.len(), 0);
+        }
+        for (mine, theirs) in self.guess_distribution.iter_mut().zip(guess_distribution) {
+            *mine += theirs;
+        }
+        self.blitz_played += blitz_played;
+        self.blitz_won += blitz_won;
+        self.zen_played += zen_played;
+        self.zen_rows += zen_rows;
+        self.unlocked_themes.extend(unlocked_themes.iter().cloned());
+        self.timed_rows += timed_rows;
+        self.think_ms += think_ms;
+        self.typing_ms += typing_ms;
+        self.solve_times += solve_times;
+        self.solve_ms += solve_ms;
+        self.best_solve_ms = match (self.best_solve_ms, *best_solve_ms) {
+            (Some(mine), Some(theirs)) => Some(mine.min(theirs)),
+            (mine, theirs) => mine.or(theirs),
+        };
+        self.hinted_games += hinted_games;
+        self.letters_revealed += letters_revealed;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::storage::Storage;
+    use std::time::Duration;
+
+    fn played() -> Stats {
+        let mut stats = Stats::default();
+        for rows in [3, 4, 4, 6, 2, 3, 3, 3, 3] {
+            stats.record_game(rows != 6, rows);
+        }
+        stats.record_blitz(true);
+        stats.record_zen(9);
+        stats.record_solve_time(Duration::from_secs(75));
+        stats
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let stats = played();
+        for format in [StatsFormat::Json, StatsFormat::Csv] {
+            let exported = stats.export(format).unwrap();
+            assert_eq!(StatsFormat::sniff(&exported), format);
+            assert_eq!(Stats::import(&exported).unwrap(), stats, "{exported}");
+        }
+        let csv = stats.export(StatsFormat::Csv).unwrap();
+        assert!(csv.contains("\nguess_distribution,0;1;5;2\n"));
+        assert!(csv.contains("\nunlocked_themes,ocean\n"));
+        assert_eq!(
+            Stats::import(&Stats::default().export(StatsFormat::Csv).unwrap()).unwrap(),
+            Stats::default()
+        );
+    }
+
+    #[test]
+    fn zen_and_blitz_round_trip_test() {
+        let storage = Storage::in_memory("test");
+        let mut stats = Stats::default();
+        let secs = Duration::from_secs;
+        // a timed zen win before any other, then blitz games won with two
+        // letters revealed and lost
+        let _ = stats.record_shared(&storage, true, 14, &[], Some(secs(300)), false, true, 0);
+        let _ = stats.record_shared(&storage, true, 3, &[], Some(secs(40)), true, false, 2);
+        let _ = stats.record_shared(&storage, false, 6, &[], None, true, false, 0);
+        assert_eq!((stats.hinted_games, stats.letters_revealed), (1, 2));
+        stats.validate().unwrap();
+        for format in [StatsFormat::Json, StatsFormat::Csv] {
+            let exported = stats.export(format).unwrap();
+            assert_eq!(Stats::import(&exported).unwrap(), stats, "{exported}");
+        }
+    }
+
+    #[test]
+    fn import_validation_test() {
+        let csv = played().export(StatsFormat::Csv).unwrap();
+        let error = |contents: &str| format!("{:#}", Stats::import(contents).unwrap_err());
+
+        assert!(error(&csv.replace("games_played,9", "games_played,-1")).contains("negative"));
+        assert!(error(&csv.replace("0;1;5;2", "0;1;5;-2")).contains("negative"));
+        assert!(error(&csv.replace("0;1;5;2", "0;1;5;3")).contains("adds up to 9"));
+        assert!(error(&csv.replace("max_streak,5", "max_streak,1")).contains("streaks"));
+        assert!(error(&csv.replace("games_won,8", "games_won,x")).contains("not 'x'"));
+        assert!(error(&format!("{csv}wins,3\n")).contains("unknown field"));
+        assert!(error("games_played,1\n").contains("should start with"));
+        assert!(error("{\"games_played\": -3}").contains("negative"));
+        assert!(error("{\"games_played\": 1, \"games_won\": 2}").contains("more than"));
+    }
+
+    #[test]
+    fn merge_test() {
+        let mut mine = played();
+        let mut theirs = Stats::default();
+        for rows in [1, 1, 1, 1, 1, 1, 1] {
+            theirs.record_game(true, rows);
+        }
+        theirs.record_solve_time(Duration::from_secs(20));
+        theirs.unlocked_themes.insert(String::from("sunset"));
+        mine.merge(&theirs);
+
+        assert_eq!(mine.games_played, 16);
+        assert_eq!(mine.games_won, 15);
+        assert_eq!(mine.guess_distribution, vec![7, 1, 5, 2]);
+        // the current streak is this machine's, the best is either's
+        assert_eq!(mine.current_streak, 5);
+        assert_eq!(mine.max_streak, 7);
+        assert!(mine.unlocked_themes.contains("sunset"));
+        assert_eq!(
+            mine.solve_time_summary(),
+            Some((Duration::from_secs(20), Duration::from_millis(47_500)))
+        );
+        mine.validate().unwrap();
+    }
+}
@@ -1,254 +1,2025 @@
 use ratatui::{
     Frame,
     buffer::Buffer,
-    crossterm::event::{self, Event, KeyCode},
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, BorderType, Clear, Paragraph, Widget, Wrap},
+    widgets::{
+        Block, BorderType, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        StatefulWidget, Widget, Wrap,
+    },
 };
 
-use super::tile::{Tile, TileState};
-use super::word::WORD_LEN;
-use super::wordle::{ROUND, Wordle};
+use std::borrow::Cow;
+use std::time::{Duration, Instant};
+use wordle::engine::{Alphabet, Tile, TileState, Word};
+
+use super::definition::DefinitionView;
+use super::elapsed::stopwatch;
+use super::events::{AppEvent, EventSource, TICK_RATE};
+use super::hint::Reveal;
+use super::input::{ESC_WINDOW, is_interrupt, normalize};
+use super::keys::{Action, KeyBinding};
+use super::messages::Level;
+use super::mouse::{BACKSPACE_KEY, ENTER_KEY, key_boxes, keys_width};
+use super::race::RACE_WIDTH;
+use super::solver::SOLVER_WIDTH;
+use super::theme::{Theme, TileStyle, heat_color};
+use super::tile::render_tile;
+use super::wordle::Wordle;
+use super::zoom::BoardLayout;
+
+/// Cells around the game on each axis: the screen margin, the border and
+/// the padding inside it, on both sides.
+const CHROME: u16 = 6;
+/// Lines of the system message above the board.
+const MESSAGE_HEIGHT: u16 = 2;
+
+/// Frames of the spinner shown while a definition is loading, a tenth of a
+/// second each.
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Keyboard key labels, A to Z, so drawing the keyboard doesn't format 26
+/// strings every frame.
+const KEY_LABELS: [&str; 26] = [
+    " A", " B", " C", " D", " E", " F", " G", " H", " I", " J", " K", " L", " M", " N", " O", " P",
+    " Q", " R", " S", " T", " U", " V", " W", " X", " Y", " Z",
+];
 
 pub enum InputState {
     EditingGuess,
     Submit,
+    /// use up the round without guessing
+    Pass,
     Quit,
     None,
 }
 
 impl Wordle {
-    pub fn handle_input(&mut self) -> InputState {
-        if let Ok(Event::Key(key)) = event::read() {
-            match key.code {
-                KeyCode::Esc => return InputState::Quit,
-                KeyCode::Tab => {
-                    self.game_restart();
-                }
-                KeyCode::Char('?') if self.is_game_over && !self.show_word_def => {
-                    self.show_word_def = true;
-                }
-                KeyCode::Char(ch) if !self.is_game_over => {
-                    if self.current_guess.len() < WORD_LEN {
-                        self.current_guess.push(ch.to_ascii_uppercase());
-                    }
-                    return InputState::EditingGuess;
-                }
-                KeyCode::Backspace if !self.is_game_over => {
-                    if !self.current_guess.is_empty() {
-                        self.current_guess.pop();
+    /// Waits for the next event from `events` and acts on it. Running
+    /// out of events quits.
+    pub fn handle_input(&mut self, events: &mut impl EventSource) -> InputState {
+        // keep redrawing while definitions are still arriving or lists are
+        // being scanned, and come back to release a held Esc
+        let timeout = if self.escape_filter.is_holding() {
+            ESC_WINDOW
+        } else {
+            self.tick_rate()
+        };
+        let Some(event) = events.next_event(Some(timeout)) else {
+            return InputState::Quit;
+        };
+        // a tick changes nothing by itself; anything else may have
+        if event != AppEvent::Tick {
+            self.redraw = true;
+        }
+        if let Some(debug) = &mut self.debug {
+            debug.record_event(&event, Instant::now());
+        }
+        let key = match event {
+            AppEvent::Tick => {
+                return match self.escape_filter.flush(Instant::now()) {
+                    Some(esc) => {
+                        self.redraw = true;
+                        self.on_key(esc)
                     }
-                    return InputState::EditingGuess;
-                }
-                KeyCode::Enter if !self.is_game_over => {
-                    return InputState::Submit;
-                }
-                _ => {}
+                    None => InputState::None,
+                };
+            }
+            AppEvent::Mouse(mouse) => return self.on_mouse(mouse),
+            AppEvent::Paste(text) => return self.paste(&text),
+            key => match key.key_event() {
+                Some(key) => key,
+                None => return InputState::None,
+            },
+        };
+        // Windows reports releases and repeats too; only presses count, or
+        // every letter would be typed twice
+        if key.kind != KeyEventKind::Press {
+            return InputState::None;
+        }
+        // raw mode turns Ctrl+C into a key; it quits whatever is open
+        if is_interrupt(key) {
+            return InputState::Quit;
+        }
+
+        let mut state = InputState::None;
+        for key in self.escape_filter.feed(key, Instant::now()) {
+            match self.on_key(key) {
+                InputState::None => {}
+                InputState::EditingGuess => state = InputState::EditingGuess,
+                decisive => return decisive,
             }
         }
+        state
+    }
+
+    /// How often the game loop wakes up without input.
+    pub fn tick_rate(&self) -> Duration {
+        self.config
+            .tick_ms
+            .map_or(TICK_RATE, |ms| Duration::from_millis(ms.max(1)))
+    }
+
+    /// Whether background work is still running, a blitz or timed clock
+    /// is counting down, an opponent may send a row, a replay is playing
+    /// or the bell has yet to finish, so the screen has to be redrawn
+    /// without waiting for input.
+    pub fn is_busy(&self) -> bool {
+        (self.blitz.is_some() && self.is_accepting_guesses())
+            || self.timed.as_ref().is_some_and(|run| !run.over)
+            || self.race.is_some()
+            || self.is_replaying()
+            || self.definitions.is_busy()
+            || self.bell.is_busy()
+            || self
+                .word_lists
+                .as_ref()
+                .is_some_and(|switcher| switcher.is_scanning())
+    }
+
+    pub fn on_key(&mut self, key: KeyEvent) -> InputState {
+        if !self.config.no_key_stats {
+            self.key_counts.record(&key);
+        }
+        if self.keys_page.as_ref().is_some_and(|page| page.capturing) {
+            return self.capture_key(key);
+        }
+        let binding = KeyBinding::from_event(key);
+        let key = normalize(key);
+        if self.confirm_challenge {
+            return self.on_challenge_key(key);
+        }
+        if self.confirm_restart {
+            return self.on_restart_key(key);
+        }
+        if self.is_entering_word() {
+            return self.on_word_entry_key(key, binding);
+        }
+        if self.replay.is_some() {
+            return self.on_replay_key(key, binding);
+        }
+        let summary = self
+            .timed
+            .as_mut()
+            .is_some_and(|run| std::mem::take(&mut run.show_summary));
+        if summary
+            || self.celebration.take().is_some()
+            || self.integration_preview.take().is_some()
+            || std::mem::take(&mut self.show_help)
+        {
+            return InputState::None;
+        }
+        if self.word_lists.is_some() {
+            return self.on_word_list_key(key);
+        }
+        if self.show_messages {
+            return self.on_messages_key(key);
+        }
+        if self.keys_page.is_some() {
+            return self.on_keys_key(key);
+        }
+        if self.show_settings {
+            return self.on_settings_key(key, binding);
+        }
+        if self.review_row.is_some() {
+            return self.on_review_key(key);
+        }
+        if self.show_analysis {
+            return self.on_analysis_key(key);
+        }
+        if self.show_stats {
+            return self.on_stats_key(key);
+        }
+        if self.candidate_page.is_some() {
+            return self.on_candidates_key(key);
+        }
+        if self.suggestions.is_some() {
+            return self.on_suggestion_key(key);
+        }
+        if let Some(state) = self.on_action(binding) {
+            return state;
+        }
+        if self.show_word_def && self.definition_view.scroll(key.code) {
+            return InputState::None;
+        }
+
+        match key.code {
+            KeyCode::Char('Q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return InputState::Quit;
+            }
+            KeyCode::Char('+' | '=') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.set_layout(self.config.layout.bigger());
+            }
+            KeyCode::Char('-') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.set_layout(self.config.layout.smaller());
+            }
+            KeyCode::Up if !self.guess_history.is_empty() && !self.show_word_def => {
+                self.review_row = Some(self.guess_history.len() - 1);
+            }
+            KeyCode::Char('?') if self.is_game_over && !self.show_word_def => {
+                self.definitions.request(&self.answer);
+                self.show_word_def = true;
+            }
+            KeyCode::Char('?') if !self.show_word_def => self.show_help = true,
+            KeyCode::Char('A') if self.is_game_over && !self.show_word_def => {
+                self.show_analysis = true;
+            }
+            KeyCode::Char('S') if self.is_game_over && !self.show_word_def => {
+                self.show_stats = true;
+            }
+            KeyCode::Char('C') if self.is_game_over && !self.show_word_def => {
+                self.share();
+            }
+            KeyCode::Char('E') if self.is_game_over && !self.show_word_def => {
+                self.export_transcript();
+            }
+            KeyCode::Char('L') if self.is_game_over && !self.show_word_def => {
+                self.candidate_page = Some(DefinitionView::default());
+            }
+            KeyCode::Char(ch)
+                if self.alphabet.contains(ch) && key.modifiers == KeyModifiers::NONE =>
+            {
+                return self.type_letter(ch);
+            }
+            KeyCode::Backspace => return self.delete_letter(),
+            // a row is one word, so deleting a word clears it too
+            KeyCode::Char('U' | 'W') if key.modifiers == KeyModifiers::CONTROL => {
+                return self.clear_guess();
+            }
+            KeyCode::Delete => return self.clear_guess(),
+            _ => {}
+        }
         InputState::None
     }
 
+    /// Adds `letter` to the active row, if it has room. Only letters of
+    /// the game's alphabet, A–Z unless a language pack says otherwise, are
+    /// typed, in uppercase; anything else is ignored.
+    pub fn type_letter(&mut self, letter: char) -> InputState {
+        if !self.is_accepting_guesses() || !self.alphabet.contains(letter) {
+            return InputState::None;
+        }
+        // the player is fixing whatever the error was about
+        self.messages.clear(Level::Error);
+        self.messages.clear(Level::Status);
+        if self.current_guess.chars().count() < self.word_len {
+            self.current_guess.push(Alphabet::upper(letter));
+        }
+        self.row_clock.edited(false, Instant::now());
+        self.game_clock.start(Instant::now());
+        InputState::EditingGuess
+    }
+
+    /// Types the letters of pasted `text` into the active row, skipping
+    /// anything else. What doesn't fit is dropped, with a note saying so.
+    pub fn paste(&mut self, text: &str) -> InputState {
+        if !self.is_accepting_guesses() || !self.board_has_focus() {
+            return InputState::None;
+        }
+        let letters: Vec<char> = text
+            .chars()
+            .filter(|&ch| self.alphabet.contains(ch))
+            .map(Alphabet::upper)
+            .collect();
+        let room = self
+            .word_len
+            .saturating_sub(self.current_guess.chars().count());
+        self.messages.clear(Level::Status);
+        self.current_guess.extend(letters.iter().take(room));
+        if letters.len() > room {
+            self.show_status(format!(
+                "pasted {} letters, the row had room for {room}",
+                letters.len()
+            ));
+        }
+        self.row_clock
+            .edited(self.current_guess.is_empty(), Instant::now());
+        InputState::EditingGuess
+    }
+
+    /// Whether the board is in front, with no page or popup over it to
+    /// take typing and clicks.
+    pub fn board_has_focus(&self) -> bool {
+        !(self.show_settings
+            || self.show_stats
+            || self.show_analysis
+            || self.show_messages
+            || self.show_help
+            || self.keys_page.is_some()
+            || self.word_lists.is_some()
+            || self.suggestions.is_some()
+            || self.review_row.is_some()
+            || self.integration_preview.is_some()
+            || self.celebration.is_some()
+            || self.timed.as_ref().is_some_and(|run| run.show_summary)
+            || self.candidate_page.is_some()
+            || self.confirm_challenge
+            || self.confirm_restart
+            || self.replay.is_some())
+    }
+
+    /// Removes the last letter of the active row.
+    pub fn delete_letter(&mut self) -> InputState {
+        if !self.is_accepting_guesses() {
+            return InputState::None;
+        }
+        self.messages.clear(Level::Status);
+        self.current_guess.pop();
+        self.row_clock
+            .edited(self.current_guess.is_empty(), Instant::now());
+        InputState::EditingGuess
+    }
+
+    /// Empties the active row. An error stays until the next letter.
+    pub fn clear_guess(&mut self) -> InputState {
+        if !self.is_accepting_guesses() {
+            return InputState::None;
+        }
+        self.messages.clear(Level::Status);
+        self.current_guess.clear();
+        self.row_clock.edited(true, Instant::now());
+        InputState::EditingGuess
+    }
+
+    /// Height of the keyboard: a row of keys and the gap under it, plus
+    /// the border.
+    fn keyboard_height(&self) -> u16 {
+        2 * self.keyboard.len() as u16 + 1
+    }
+
+    /// Smallest terminal the game is drawn in: the widest keyboard row and
+    /// a single slim board row, with everything `render_terminal` puts
+    /// around them.
+    pub fn min_size(&self) -> (u16, u16) {
+        let keys = self
+            .keyboard
+            .iter()
+            .map(|row| keys_width(row))
+            .max()
+            .unwrap_or(0);
+        // the board's border and its top gap
+        let board = TileSize::SLIM.rows_height(1) + 3;
+        let height = CHROME
+            + MESSAGE_HEIGHT
+            + u16::from(self.shows_remaining())
+            + board
+            + self.keyboard_height();
+        // the keyboard's border
+        (CHROME + keys + 2, height)
+    }
+
     pub fn render_terminal(&self, frame: &mut Frame) {
+        self.keyboard_area.set(None);
+        let (min_width, min_height) = self.min_size();
+        if frame.area().width < min_width || frame.area().height < min_height {
+            render_too_small(frame.area(), (min_width, min_height), frame.buffer_mut());
+            return;
+        }
+        // the board's border, its top gap and a spare line around the rows
+        let board_height = self.config.layout.tiles().rows_height(self.rounds as usize) + 5;
+        let keyboard_height = self.keyboard_height();
+        let remaining_height = u16::from(self.shows_remaining());
         let [outer_area] = Layout::default()
             .direction(Direction::Vertical)
             .constraints(vec![Constraint::Length(50)])
             .margin(1)
-            .areas(
-                frame
-                    .area()
-                    .centered(Constraint::Length(50), Constraint::Length(42)),
-            );
+            .areas(frame.area().centered(
+                Constraint::Length(50),
+                Constraint::Length(
+                    board_height + keyboard_height + remaining_height + CHROME + MESSAGE_HEIGHT,
+                ),
+            ));
 
         let [inner_area] = Layout::vertical([Constraint::Fill(1)])
             .margin(1)
             .areas(outer_area);
 
-        let [msg_area, top_area, bottom_area] = Layout::default()
+        let [msg_area, remaining_area, top_area, bottom_area] = Layout::default()
             .direction(Direction::Vertical)
             .constraints(vec![
-                Constraint::Length(2),
-                Constraint::Length(28),
-                Constraint::Length(7),
+                Constraint::Length(MESSAGE_HEIGHT),
+                Constraint::Length(remaining_height),
+                // gives way first on short terminals, so the keyboard stays
+                Constraint::Max(board_height),
+                Constraint::Length(keyboard_height),
             ])
             .margin(1)
             .areas(inner_area);
 
         self.render_border(outer_area, frame.buffer_mut());
-        if self.show_word_def {
+        if let Some(seat) = self.hot_seat.as_ref().filter(|seat| seat.entry.is_some()) {
+            self.render_word_entry(seat, inner_area, frame.buffer_mut());
+        } else if let Some(switcher) = &self.word_lists {
+            self.render_word_list_page(switcher, inner_area, frame.buffer_mut());
+        } else if self.show_messages {
+            self.render_messages_page(inner_area, frame.buffer_mut());
+        } else if let Some(page) = &self.keys_page {
+            self.render_keys_page(page, inner_area, frame.buffer_mut());
+        } else if self.show_settings {
+            self.render_settings_page(inner_area, frame.buffer_mut());
+        } else if self.show_analysis {
+            self.render_analysis(inner_area, frame.buffer_mut());
+        } else if self.show_stats {
+            self.render_stats_page(inner_area, frame.buffer_mut());
+        } else if let Some(view) = &self.candidate_page {
+            self.render_candidates_page(view, inner_area, frame.buffer_mut());
+        } else if self.show_word_def {
             self.render_definition_page(inner_area, frame.buffer_mut());
+        } else if self.config.layout == BoardLayout::Zoom {
+            self.render_system_message(msg_area, frame.buffer_mut());
+            if self.shows_remaining() {
+                self.render_remaining(remaining_area, frame.buffer_mut());
+            }
+            let zoom_area = Rect {
+                y: remaining_area.bottom(),
+                height: bottom_area.bottom().saturating_sub(remaining_area.bottom()),
+                ..msg_area
+            };
+            self.render_zoom(zoom_area, frame.buffer_mut());
+            if self.is_game_over {
+                self.render_game_over(zoom_area, frame.buffer_mut());
+            }
         } else {
             self.render_system_message(msg_area, frame.buffer_mut());
+            if self.shows_remaining() {
+                self.render_remaining(remaining_area, frame.buffer_mut());
+            }
             self.render_game_board(top_area, frame.buffer_mut());
             self.render_keyboard(bottom_area, frame.buffer_mut());
+            if self.is_game_over {
+                let below_board = Rect {
+                    y: top_area.y,
+                    height: bottom_area.bottom().saturating_sub(top_area.y),
+                    ..bottom_area
+                };
+                self.render_game_over(below_board, frame.buffer_mut());
+            }
+            if let Some(seat) = self.hot_seat.as_ref().filter(|_| self.is_game_over) {
+                self.render_swap_prompt(seat, bottom_area, frame.buffer_mut());
+            }
+            if let Some(race) = &self.race {
+                // left of the board when there's room, over its corner if not
+                let beside = Rect {
+                    x: outer_area.x.saturating_sub(RACE_WIDTH),
+                    width: outer_area.x.min(RACE_WIDTH),
+                    ..outer_area
+                };
+                let spot = if beside.width >= RACE_WIDTH {
+                    beside
+                } else {
+                    inner_area
+                };
+                self.render_race(race, spot, frame.buffer_mut());
+            }
+            if self.show_solver {
+                // beside the board when there's room, over its corner if not
+                let beside = Rect {
+                    x: outer_area.right(),
+                    width: frame.area().right().saturating_sub(outer_area.right()),
+                    ..outer_area
+                };
+                let spot = if beside.width >= SOLVER_WIDTH {
+                    beside
+                } else {
+                    Rect {
+                        x: inner_area.right().saturating_sub(SOLVER_WIDTH),
+                        ..inner_area
+                    }
+                };
+                self.render_solver(spot, frame.buffer_mut());
+            }
+        }
+        if let Some(panel) = &self.suggestions {
+            self.render_suggestions(panel, inner_area, frame.buffer_mut());
+        }
+        if let Some(row) = self.review_row {
+            self.render_explanation(row, inner_area, frame.buffer_mut());
+        }
+        if let Some(lines) = &self.integration_preview {
+            self.render_integration_preview(lines, inner_area, frame.buffer_mut());
+        }
+        if let Some(message) = &self.celebration {
+            self.render_celebration(message, inner_area, frame.buffer_mut());
+        }
+        if let Some(run) = self.timed.as_ref().filter(|run| run.show_summary) {
+            self.render_timed_summary(run, inner_area, frame.buffer_mut());
+        }
+        if self.confirm_challenge {
+            self.render_challenge_prompt(inner_area, frame.buffer_mut());
+        }
+        if self.confirm_restart {
+            self.render_restart_prompt(inner_area, frame.buffer_mut());
+        }
+        if self.show_help {
+            self.render_help_popup(frame.area(), frame.buffer_mut());
+        }
+        if let Some(debug) = &self.debug {
+            self.render_debug(debug, frame.area(), frame.buffer_mut());
         }
     }
 
     fn render_border(&self, area: Rect, buf: &mut Buffer) {
-        let instructions = Line::from(vec![
-            " Submit ".into(),
-            "<Enter>".blue().bold(),
-            " New game ".into(),
-            "<Tab>".blue().bold(),
-            " Quit ".into(),
-            "<Esc>".blue().bold(),
+        let title = match self.daily_title() {
+            Some(title) => Line::from(title),
+            None => Line::from(
+                self.difficulty_title()
+                    .unwrap_or_else(|| String::from("Wordle")),
+            ),
+        };
+        let mut block = Block::bordered()
+            .title(title)
+            .title_bottom(Line::from(format!(" #{} ", self.commitment())).fg(Color::DarkGray))
+            .title_bottom(self.instructions())
+            .border_type(BorderType::Rounded);
+        if self.bell.flash_until.is_some() {
+            block = block.border_style(Style::new().reversed());
+        }
+        if let Some(adaptive) = &self.adaptive {
+            // level badge followed by the last ten games' levels
+            let chart = adaptive.chart();
+            let skip = chart.chars().count().saturating_sub(10);
+            let badge = Line::from(vec![
+                format!(" {} ", adaptive.level.name()).bold(),
+                chart.chars().skip(skip).collect::<String>().fg(Color::Cyan),
+                " ".into(),
+            ]);
+            block = block.title(badge.right_aligned());
+        }
+        if let Some(run) = &self.timed {
+            block = block.title(self.timed_title(run).right_aligned());
+        } else if let Some(elapsed) = self.game_clock.elapsed(Instant::now()) {
+            block = block.title(Line::from(format!(" {} ", stopwatch(elapsed))).right_aligned());
+        }
+        block.render(area, buf);
+    }
+
+    /// Key legend on the bottom edge of the border, with the keys as bound.
+    fn instructions(&self) -> Line<'_> {
+        let mut spans = Vec::with_capacity(9);
+        for action in [Action::Submit, Action::NewGame, Action::Quit] {
+            spans.push(Span::raw(" "));
+            spans.push(Span::raw(action.label()));
+            spans.push(Span::raw(" "));
+            spans.push(Span::raw(self.shown_key(action)).blue().bold());
+        }
+        Line::from(spans).right_aligned()
+    }
+
+    /// The key shown for `action`: as bound, except that Quit shows Ctrl+Q
+    /// when a key without modifiers isn't allowed to quit.
+    fn shown_key(&self, action: Action) -> &str {
+        if action == Action::Quit
+            && self.config.quit_requires_modifier
+            && self.keymap.binding(action).modifiers.is_empty()
+        {
+            return "<Ctrl+Q>";
+        }
+        self.keymap.label(action)
+    }
+
+    /// The letters typed on the board, e.g. `<A-Z>`, with any that fall
+    /// outside the range from the first to the last listed after it.
+    fn letter_keys(&self) -> String {
+        let letters = self.alphabet.letters();
+        let (Some(&first), Some(&last)) = (letters.first(), letters.last()) else {
+            return String::new();
+        };
+        let mut keys = format!("<{first}-{last}");
+        for letter in letters
+            .iter()
+            .filter(|&&letter| !(first..=last).contains(&letter))
+        {
+            keys.push_str(&format!(", {letter}"));
+        }
+        keys.push('>');
+        keys
+    }
+
+    /// Every key the board answers to, as bound, with what it does.
+    fn help_entries(&self) -> Vec<(String, &'static str)> {
+        let mut entries: Vec<(String, &'static str)> = vec![
+            (self.letter_keys(), "Type a letter"),
+            (String::from("<Backspace>"), "Delete a letter"),
+            (String::from("<Ctrl+U>"), "Clear the row"),
+            (String::from("<Up>"), "Explain a row"),
+        ];
+        entries.extend(
+            Action::ALL
+                .iter()
+                .map(|action| (self.shown_key(*action).to_string(), action.label())),
+        );
+        entries.extend([
+            (String::from("<Ctrl+C>"), "Quit from anywhere"),
+            (String::from("<Ctrl++>"), "Bigger board"),
+            (String::from("<Ctrl+->"), "Smaller board"),
+            (String::from("<?>"), "Help; definition after a game"),
+            (String::from("<a>"), "Analysis after a game"),
+            (String::from("<s>"), "Stats after a game"),
+            (String::from("<c>"), "Share after a game"),
+            (String::from("<e>"), "Transcript after a game"),
+            (String::from("<l>"), "Words left after a game"),
         ]);
+        entries
+    }
 
+    /// The key list in two columns, centered on `area` and only as large as
+    /// it needs to be; it fits an 80x24 terminal.
+    fn render_help_popup(&self, area: Rect, buf: &mut Buffer) {
+        let entries = self.help_entries();
+        let key_width = entries
+            .iter()
+            .map(|(key, _)| key.chars().count())
+            .max()
+            .unwrap_or(0);
+        let cells: Vec<Line> = entries
+            .iter()
+            .map(|(key, label)| {
+                Line::from(vec![
+                    Span::raw(format!("{key:<key_width$} ")).blue().bold(),
+                    Span::raw(*label),
+                ])
+            })
+            .collect();
+        let (left, right) = cells.split_at(cells.len().div_ceil(2));
+        let width = |column: &[Line]| column.iter().map(Line::width).max().unwrap_or(0) as u16;
+        // the border, with a space inside it and between the columns
+        let popup = area.centered(
+            Constraint::Length(width(left) + width(right) + 5),
+            Constraint::Length(left.len() as u16 + 2),
+        );
+        Clear.render(popup, buf);
         Block::bordered()
-            .title("Wordle")
-            .title_bottom(instructions.right_aligned())
+            .title("Keys")
+            .title_bottom(Line::from(" any key closes ").right_aligned())
             .border_type(BorderType::Rounded)
-            .render(area, buf);
+            .render(popup, buf);
+        let inner = popup.inner(Margin::new(1, 1));
+        let [left_area, right_area] =
+            Layout::horizontal([Constraint::Length(width(left) + 2), Constraint::Fill(1)])
+                .areas(inner);
+        Paragraph::new(left.to_vec()).render(left_area.inner(Margin::new(1, 0)), buf);
+        Paragraph::new(right.to_vec()).render(right_area, buf);
     }
 
     fn render_system_message(&self, area: Rect, buf: &mut Buffer) {
-        if !self.err_msg.is_empty() {
-            Span::styled(self.err_msg.clone(), Style::default().fg(Color::Red)).render(area, buf);
-        }
-        if self.is_game_over {
-            let mut game_result = if self.solved {
-                vec![Line::from(vec![
-                    Span::raw("You won! The answer is: ").fg(Color::Green),
-                    Span::raw(&self.answer).bold().fg(Color::White),
-                ])]
-            } else {
-                vec![Line::from(vec![
-                    Span::raw("You lost! The answer is: ").fg(Color::LightYellow),
-                    Span::raw(&self.answer).bold().fg(Color::White),
-                ])]
-            };
+        // errors win; a status message waits until the error is cleared
+        let (message, color) = match self.messages.current() {
+            Some(message) if message.level == Level::Error => (message.text.as_str(), Color::Red),
+            Some(message) => (message.text.as_str(), Color::Cyan),
+            None => ("", Color::Cyan),
+        };
+        let lines: Vec<Line> = wrap_message(message, area.width as usize, area.height as usize)
+            .into_iter()
+            .map(Line::from)
+            .collect();
+        Paragraph::new(lines)
+            .style(Style::default().fg(color))
+            .render(area, buf);
+    }
 
-            game_result.push(Line::from(vec![
-                Span::raw("Show word definition? "),
-                Span::raw("<?>").blue().bold(),
-            ]));
-            Paragraph::new(game_result).render(area, buf);
+    /// The result over the bottom of `area`, where the keyboard was, with
+    /// the guess distribution so far. It grows to fit the distribution and
+    /// leaves the rest of the board in view.
+    fn render_game_over(&self, area: Rect, buf: &mut Buffer) {
+        let distribution = &self.stats.guess_distribution;
+        let rows = (self.rounds as usize).max(distribution.len());
+        let has_wins = distribution.iter().any(|&count| count > 0);
+        let chart_height = if has_wins { rows as u16 } else { 1 };
+        // the result, the chart, two lines of keys and the border
+        let height = (chart_height + 5).min(area.height);
+        let popup = Rect {
+            y: area.bottom() - height,
+            height,
+            ..area
+        };
+        Clear.render(popup, buf);
+        Block::bordered()
+            .title("Your wins")
+            .border_type(BorderType::Rounded)
+            .render(popup, buf);
+        let [result_area, chart_area, keys_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(2),
+        ])
+        .margin(1)
+        .areas(popup);
+
+        let mut result = if self.solved {
+            Line::from(vec![
+                Span::raw("You won! The answer is: ").fg(Color::Green),
+                Span::raw(&self.answer).bold().fg(Color::White),
+            ])
+        } else {
+            Line::from(vec![
+                Span::raw("You lost! The answer is: ").fg(Color::LightYellow),
+                Span::raw(&self.answer).bold().fg(Color::White),
+            ])
+        };
+        if let (Some(title), Some(score)) = (self.daily_title(), self.daily_score()) {
+            result.push_span(Span::raw(format!("  {title} {score}")).fg(Color::DarkGray));
+        }
+        if let Some(took) = self.game_clock.finished {
+            result.push_span(Span::raw(format!("  in {}", stopwatch(took))).fg(Color::DarkGray));
+        }
+        if let Some((seed, difficulty)) = self.replay_seed() {
+            let seed = match difficulty {
+                Some(difficulty) => format!("  seed {seed} on {}", difficulty.name()),
+                None => format!("  seed {seed}"),
+            };
+            result.push_span(Span::raw(seed).fg(Color::DarkGray));
         }
+        Paragraph::new(result).render(result_area, buf);
+        let chart = if has_wins {
+            // a zen game isn't in the distribution
+            let finished = (self.solved && !self.zen).then_some(self.guess_history.len());
+            distribution_lines(distribution, rows, finished, chart_area.width as usize)
+        } else {
+            vec![Line::from("No wins yet").fg(Color::DarkGray)]
+        };
+        Paragraph::new(chart).render(chart_area, buf);
+        Paragraph::new(vec![
+            Line::from(vec![
+                Span::raw("Define "),
+                Span::raw("<?>").blue().bold(),
+                Span::raw(" Timing "),
+                Span::raw("<a>").blue().bold(),
+                Span::raw(" Stats "),
+                Span::raw("<s>").blue().bold(),
+            ]),
+            Line::from(vec![
+                Span::raw("Share "),
+                Span::raw("<c>").blue().bold(),
+                Span::raw(" Export "),
+                Span::raw("<e>").blue().bold(),
+                Span::raw(" Words left "),
+                Span::raw("<l>").blue().bold(),
+            ]),
+        ])
+        .render(keys_area, buf);
     }
 
     fn render_game_board(&self, area: Rect, buf: &mut Buffer) {
+        let current_guess = if self.is_accepting_guesses() {
+            Some(self.current_guess.as_str())
+        } else {
+            None
+        };
+        let last_tile = render_board(
+            &self.theme,
+            &self.guess_history,
+            self.guesses_remaining() as usize,
+            self.word_len,
+            current_guess,
+            &self.revealed,
+            self.config.layout.tiles(),
+            area,
+            buf,
+        );
+        if let (Some(blitz), Some(last_tile)) = (&self.blitz, last_tile)
+            && current_guess.is_some()
+        {
+            self.render_countdown(blitz, last_tile, area, buf);
+        }
+    }
+
+    /// "17/26 letters known" for the bottom edge of the keyboard, where it
+    /// can't push the keys off center. Green once every letter of the answer
+    /// has been found.
+    pub fn progress_title(&self) -> Line<'static> {
+        let progress = self.alphabet_progress();
+        let title = Line::from(format!(
+            " {}/{} letters known ",
+            progress.known, progress.total
+        ))
+        .right_aligned();
+        if progress.answer_found {
+            title.fg(Color::Green).bold()
+        } else {
+            title.fg(Color::DarkGray)
+        }
+    }
+
+    fn render_keyboard(&self, area: Rect, buf: &mut Buffer) {
         Block::bordered()
             .border_type(BorderType::Rounded)
+            .title_bottom(self.progress_title())
             .render(area, buf);
-        let [game_board_area] = Layout::vertical([Constraint::Fill(1)])
+        let [keyboard_area] = Layout::vertical([Constraint::Fill(1)])
             .margin(1)
             .areas(area);
+        self.keyboard_area.set(Some(keyboard_area));
+        for (ch, spot) in key_boxes(keyboard_area, &self.keyboard) {
+            let (label, style, corner) = match ch {
+                ENTER_KEY => (Cow::Borrowed(" ⏎"), self.theme.key_unused, None),
+                BACKSPACE_KEY => (Cow::Borrowed(" ⌫"), self.theme.key_unused, None),
+                ch => {
+                    let state = self.used_chars[&ch];
+                    let style = if self.show_heatmap {
+                        TileStyle::on(heat_color(self.key_counts.heat(ch)))
+                    } else {
+                        self.theme.key(state)
+                    };
+                    let label = match self.letter_rounds.get(&ch) {
+                        Some(&round) if self.show_letter_rounds => {
+                            Cow::Owned(format!("{round:>2}"))
+                        }
+                        _ if ch.is_ascii_uppercase() => {
+                            Cow::Borrowed(KEY_LABELS[(ch as u8 - b'A') as usize])
+                        }
+                        // a language pack's own letters
+                        _ => Cow::Owned(format!(" {ch}")),
+                    };
+                    // green with a yellow corner: placed, but more copies exist
+                    let corner = (state == TileState::Correct && self.has_unplaced_copies(ch))
+                        .then(|| self.theme.key(TileState::Present).bg);
+                    (label, style, corner)
+                }
+            };
+            Span::raw(label)
+                .bg(style.bg)
+                .fg(style.fg)
+                .bold()
+                .render(spot, buf);
+            let corner_spot = Rect {
+                x: spot.x + 2,
+                width: 1,
+                ..spot
+            };
+            Span::raw(" ")
+                .bg(corner.unwrap_or(style.bg))
+                .render(corner_spot.intersection(spot), buf);
+        }
+    }
+
+    fn on_stats_key(&mut self, key: KeyEvent) -> InputState {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('S')) {
+            self.show_stats = false;
+        }
+        InputState::None
+    }
 
-        let center_x = (game_board_area.left() + game_board_area.right()) / 2;
-        let base_y = game_board_area.y + 1;
-        // past guesses
-        for (row, word) in self.guess_history.iter().enumerate() {
-            for (col, tile) in word.letters.iter().enumerate() {
-                let area = self.tile_area(center_x, base_y, row, col);
-                tile.render(area, buf);
+    pub fn render_stats_page(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let stats = &self.stats;
+        let win_rate = (stats.games_won * 100)
+            .checked_div(stats.games_played)
+            .unwrap_or(0);
+        let mut lines = vec![
+            Line::from(format!("Played {}  Win % {win_rate}", stats.games_played)),
+            Line::from(format!(
+                "Current streak {}  Max streak {}",
+                stats.current_streak, stats.max_streak
+            )),
+        ];
+        if let Some((best, average)) = stats.solve_time_summary() {
+            lines.push(Line::from(format!(
+                "Best time {}  Average {}",
+                stopwatch(best),
+                stopwatch(average)
+            )));
+        }
+        if stats.blitz_played > 0 {
+            lines.push(Line::from(format!(
+                "Blitz played {}  won {}",
+                stats.blitz_played, stats.blitz_won
+            )));
+        }
+        if stats.hinted_games > 0 {
+            lines.push(Line::from(format!(
+                "Hints in {} games  {} letters revealed",
+                stats.hinted_games, stats.letters_revealed
+            )));
+        }
+        if stats.zen_played > 0 {
+            lines.push(Line::from(format!(
+                "Zen played {}  average {:.1} rows",
+                stats.zen_played,
+                stats.zen_rows as f64 / stats.zen_played as f64
+            )));
+        }
+        if let Some(adaptive) = self
+            .adaptive
+            .as_ref()
+            .filter(|state| !state.levels.is_empty())
+        {
+            // "Normal " before each row
+            const LABEL_WIDTH: usize = 7;
+            lines.push(Line::from(""));
+            lines.push(Line::from("Adaptive level by game").bold());
+            let width = (area.width as usize).saturating_sub(2 + LABEL_WIDTH);
+            for (difficulty, row) in adaptive.level_rows(width) {
+                lines.push(Line::from(vec![
+                    Span::raw(format!("{:<LABEL_WIDTH$}", difficulty.name())),
+                    Span::raw(row).fg(Color::Cyan),
+                ]));
             }
         }
+        lines.push(Line::from(""));
+        lines.push(Line::from("Guess distribution").bold());
+        // a zen game isn't in the distribution
+        let finished = (self.solved && !self.zen).then_some(self.guess_history.len());
+        lines.extend(distribution_lines(
+            &stats.guess_distribution,
+            self.rounds as usize,
+            finished,
+            (area.width as usize).saturating_sub(2),
+        ));
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::raw("Back "), "<Esc>".blue().bold()]));
 
-        // remaining spots
-        for row in (self.round - 1)..ROUND {
-            for col in 0..WORD_LEN {
-                let area = self.tile_area(center_x, base_y, row as usize, col);
-                Tile {
-                    letter: ' ',
-                    state: TileState::Unused,
-                }
-                .render(area, buf);
+        Paragraph::new(lines)
+            .block(
+                Block::bordered()
+                    .title("Statistics")
+                    .border_type(BorderType::Rounded),
+            )
+            .render(area, buf);
+    }
+
+    fn render_definition_page(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let [text_area] = Layout::vertical([Constraint::Fill(1)])
+            .margin(1)
+            .areas(area);
+        // a column spare for the scrollbar, which sits on the border
+        let width = text_area.width.saturating_sub(1) as usize;
+        let lines: Vec<Line> = match self.definitions.get(&self.answer) {
+            Some(Ok(definition)) if definition.meanings.is_empty() => {
+                vec![Line::from("Definition not found")]
             }
+            Some(Ok(definition)) => definition.lines(width),
+            Some(Err(err)) => wrap_message(&err.to_string(), width, usize::MAX)
+                .into_iter()
+                .map(Line::from)
+                .collect(),
+            None => {
+                let waited = self.definitions.waiting(&self.answer).unwrap_or_default();
+                let frame = (waited.as_millis() / 100) as usize % SPINNER.len();
+                vec![Line::from(format!(
+                    "{} Loading definition…",
+                    SPINNER[frame]
+                ))]
+            }
+        };
+
+        let view = &self.definition_view;
+        let page = text_area.height as usize;
+        let overflow = lines.len().saturating_sub(page);
+        view.page.set(page);
+        view.overflow.set(overflow);
+        let offset = view.offset.min(overflow);
+
+        Block::bordered().render(area, buf);
+        let shown: Vec<Line> = lines.into_iter().skip(offset).take(page).collect();
+        Paragraph::new(shown).render(text_area, buf);
+        if overflow > 0 {
+            let mut state = ScrollbarState::new(overflow + 1)
+                .position(offset)
+                .viewport_content_length(page);
+            Scrollbar::new(ScrollbarOrientation::VerticalRight).render(
+                area.inner(Margin::new(0, 1)),
+                buf,
+                &mut state,
+            );
+        }
+    }
+}
+
+/// One bar per number of rows a win took, from 1 to at least `rounds`,
+/// scaled so the most common fits `width` columns with its label and count.
+/// The bar of the game just won (`finished` rows) is green.
+fn distribution_lines(
+    distribution: &[u32],
+    rounds: usize,
+    finished: Option<usize>,
+    width: usize,
+) -> Vec<Line<'static>> {
+    let widest = distribution.iter().copied().max().unwrap_or(0).max(1);
+    let label_width = rounds.max(distribution.len()).to_string().len();
+    // the label, its gap and room for the count after the bar
+    let bar_width = width.saturating_sub(label_width + widest.to_string().len() + 2);
+    (1..=rounds.max(distribution.len()))
+        .map(|rows| {
+            let count = distribution.get(rows - 1).copied().unwrap_or(0);
+            let cells = (count as usize * bar_width / widest as usize).max(1);
+            let color = if finished == Some(rows) {
+                Color::Green
+            } else {
+                Color::DarkGray
+            };
+            Line::from(vec![
+                Span::raw(format!("{rows:>label_width$} ")),
+                Span::raw("█".repeat(cells)).fg(color),
+                Span::raw(format!(" {count}")),
+            ])
+        })
+        .collect()
+}
+
+/// Word-wraps `text` into at most `height` lines of `width` columns. Words
+/// longer than a line are split, and text that doesn't fit ends in an
+/// ellipsis.
+pub fn wrap_message(text: &str, width: usize, height: usize) -> Vec<String> {
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        let line_len = line.chars().count();
+        if line_len > 0 && line_len + 1 + word.len() <= width {
+            line.push(' ');
+            line.extend(word);
+            continue;
+        }
+        if line_len > 0 {
+            lines.push(std::mem::take(&mut line));
+        }
+        while word.len() > width {
+            lines.push(word.drain(..width).collect());
         }
+        line.extend(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    if lines.len() > height {
+        lines.truncate(height);
+        let last = &mut lines[height - 1];
+        while last.chars().count() >= width {
+            last.pop();
+        }
+        last.push('…');
+    }
+    lines
+}
+
+/// Dimensions of board tiles and the gaps between them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileSize {
+    pub width: u16,
+    pub height: u16,
+    pub gap_x: u16,
+    pub gap_y: u16,
+}
 
-        // current_guess guess
-        let active_row = (self.round - 1) as usize;
-        for (col, ch) in self.current_guess.chars().enumerate() {
-            let area = self.tile_area(center_x, base_y, active_row, col);
-            Tile {
-                letter: ch,
-                state: TileState::Absent,
+impl TileSize {
+    pub const NORMAL: TileSize = TileSize {
+        width: 5,
+        height: 3,
+        gap_x: 2,
+        gap_y: 1,
+    };
+    pub const LARGE: TileSize = TileSize {
+        width: 7,
+        height: 3,
+        gap_x: 1,
+        gap_y: 1,
+    };
+    /// a single row filling the board's width
+    pub const ZOOM: TileSize = TileSize {
+        width: 7,
+        height: 5,
+        gap_x: 1,
+        gap_y: 0,
+    };
+    /// for long words that don't fit at the chosen size
+    pub const COMPACT: TileSize = TileSize {
+        width: 3,
+        height: 3,
+        gap_x: 1,
+        gap_y: 1,
+    };
+    /// one line per row, for boards with more rows than the terminal has
+    /// room for
+    pub const SLIM: TileSize = TileSize {
+        width: 3,
+        height: 1,
+        gap_x: 1,
+        gap_y: 0,
+    };
+
+    /// Width of a row of `word_len` tiles, gaps included.
+    pub fn row_width(&self, word_len: usize) -> u16 {
+        let word_len = word_len as u16;
+        word_len * self.width + word_len.saturating_sub(1) * self.gap_x
+    }
+
+    /// Height of `rows` rows, gaps included.
+    pub fn rows_height(&self, rows: usize) -> u16 {
+        let rows = rows as u16;
+        rows * self.height + rows.saturating_sub(1) * self.gap_y
+    }
+
+    /// How many rows fit in `height`.
+    pub fn visible_rows(&self, height: u16) -> usize {
+        ((height + self.gap_y) / (self.height + self.gap_y)) as usize
+    }
+
+    /// The first of this size, `COMPACT` and `SLIM` that fits `rows` rows of
+    /// `word_len` tiles in a board's `area`, below its one-line top gap.
+    /// `SLIM` if none does.
+    pub fn fitting(self, word_len: usize, rows: usize, area: Rect) -> TileSize {
+        [self, TileSize::COMPACT, TileSize::SLIM]
+            .into_iter()
+            .find(|size| {
+                size.row_width(word_len) <= area.width && size.rows_height(rows) < area.height
+            })
+            .unwrap_or(TileSize::SLIM)
+    }
+}
+
+/// Draws a bordered board of past guesses, `rows_remaining` empty rows of
+/// `word_len` tiles and the guess being typed, if any, over the green
+/// `revealed` letters it hasn't reached yet. Tiles shrink when the
+/// board doesn't fit at `size`, and if even the smallest tiles don't, the
+/// board scrolls to keep the row being played in view.
+#[allow(clippy::too_many_arguments)]
+pub fn render_board(
+    theme: &Theme,
+    history: &[Word],
+    rows_remaining: usize,
+    word_len: usize,
+    current_guess: Option<&str>,
+    revealed: &[Reveal],
+    size: TileSize,
+    area: Rect,
+    buf: &mut Buffer,
+) -> Option<Rect> {
+    Block::bordered()
+        .border_type(BorderType::Rounded)
+        .render(area, buf);
+    let [game_board_area] = Layout::vertical([Constraint::Fill(1)])
+        .margin(1)
+        .areas(area);
+
+    let rows = history.len() + rows_remaining;
+    let size = size.fitting(word_len, rows, game_board_area);
+    let visible = size.visible_rows(game_board_area.height.saturating_sub(1));
+    let focus = history.len().min(rows.saturating_sub(1));
+    let first_row = (focus + 1).saturating_sub(visible);
+
+    let center_x = (game_board_area.left() + game_board_area.right()) / 2;
+    let base_y = game_board_area.y + 1;
+    let mut active_tile = None;
+    for row in first_row..rows.min(first_row + visible) {
+        for col in 0..word_len {
+            let active = current_guess.filter(|_| row == history.len());
+            let typed = active.and_then(|guess| guess.chars().nth(col));
+            let hint = active
+                .and_then(|_| revealed.iter().find(|reveal| reveal.column == col))
+                .map(|reveal| reveal.letter);
+            let tile = match (history.get(row), typed, hint) {
+                (Some(word), ..) => word.letters[col],
+                (None, Some(letter), _) => Tile {
+                    letter,
+                    state: TileState::Absent,
+                },
+                (None, None, Some(letter)) => Tile {
+                    letter,
+                    state: TileState::Correct,
+                },
+                (None, None, None) => Tile {
+                    letter: ' ',
+                    state: TileState::Unused,
+                },
+            };
+            let area = tile_area(center_x, base_y, row - first_row, col, word_len, size);
+            render_tile(tile, theme, area.intersection(game_board_area), buf);
+            if row == history.len() {
+                active_tile = Some(area);
             }
-            .render(area, buf);
         }
     }
+    active_tile
+}
 
-    fn tile_area(&self, center_x: u16, base_y: u16, row: usize, col: usize) -> Rect {
-        const TILE_WIDTH: u16 = 5;
-        const TILE_HEIGHT: u16 = 3;
-        const TILE_GAP_X: u16 = 2;
-        const TILE_GAP_Y: u16 = 1;
+/// Asks for a bigger terminal in the middle of `area`, which is too small
+/// for the board and keyboard.
+fn render_too_small(area: Rect, (min_width, min_height): (u16, u16), buf: &mut Buffer) {
+    let message = format!(
+        "Please enlarge your terminal to at least {min_width}x{min_height} (now {}x{})",
+        area.width, area.height
+    );
+    // one spare line for words wrapped early
+    let lines = u16::try_from(message.len())
+        .unwrap_or(u16::MAX)
+        .div_ceil(area.width.max(1))
+        + 1;
+    Paragraph::new(message)
+        .wrap(Wrap { trim: true })
+        .centered()
+        .yellow()
+        .render(area.centered_vertically(Constraint::Length(lines)), buf);
+}
 
-        let x = center_x as i32 - (TILE_WIDTH as i32 / 2)
-            + (col as i32 - 2) * (TILE_WIDTH + TILE_GAP_X) as i32;
+/// Where tile `col` of `row` goes on a board whose rows of `word_len` tiles
+/// are centered on `center_x`.
+pub fn tile_area(
+    center_x: u16,
+    base_y: u16,
+    row: usize,
+    col: usize,
+    word_len: usize,
+    size: TileSize,
+) -> Rect {
+    // saturating, so a board wider or taller than the terminal is clipped
+    // rather than wrapped around
+    let col = u16::try_from(col).unwrap_or(u16::MAX);
+    let row = u16::try_from(row).unwrap_or(u16::MAX);
+    let x = center_x
+        .saturating_sub(size.row_width(word_len) / 2)
+        .saturating_add(col.saturating_mul(size.width + size.gap_x));
+    let y = base_y.saturating_add(row.saturating_mul(size.height + size.gap_y));
 
-        let y = base_y as i32 + row as i32 * (TILE_HEIGHT + TILE_GAP_Y) as i32;
+    Rect {
+        x,
+        y,
+        width: size.width,
+        height: size.height,
+    }
+}
 
-        Rect {
-            x: x as u16,
-            y: y as u16,
-            width: TILE_WIDTH,
-            height: TILE_HEIGHT,
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::storage::Storage;
+    use crate::utils::testing::count_allocations;
+    use crate::utils::wordle::{MAX_ROUNDS, Setup};
+    use ratatui::backend::TestBackend;
+    use ratatui::layout::Position;
+    use wordle::engine::ROUND;
+
+    #[test]
+    fn rows_centered_test() {
+        for word_len in 4..=8 {
+            let size = TileSize::NORMAL;
+            let first = tile_area(40, 0, 0, 0, word_len, size);
+            let last = tile_area(40, 0, 0, word_len - 1, word_len, size);
+            assert_eq!(last.right() - first.x, size.row_width(word_len));
+            // as much room on either side, give or take the odd column
+            assert!((40 - first.x).abs_diff(last.right() - 40) <= 1);
         }
+        // the classic board didn't move
+        assert_eq!(tile_area(40, 0, 0, 2, 5, TileSize::NORMAL).x, 38);
+        let board = Rect::new(0, 0, 44, 26);
+        assert_eq!(TileSize::NORMAL.fitting(8, 6, board), TileSize::COMPACT);
+        assert_eq!(TileSize::NORMAL.fitting(6, 6, board), TileSize::NORMAL);
+        assert_eq!(TileSize::NORMAL.fitting(5, 10, board), TileSize::SLIM);
     }
 
-    fn render_keyboard(&self, area: Rect, buf: &mut Buffer) {
-        let qwerty = [
-            "Q W E R T Y U I O P",
-            " A S D F G H J K L ",
-            "  Z X C V B N M    ",
-        ];
-        Block::bordered()
-            .border_type(BorderType::Rounded)
-            .render(area, buf);
-        let [keyboard_area] = Layout::vertical([Constraint::Fill(1)])
-            .margin(1)
-            .areas(area);
-        let mut lines = Vec::new();
-        for row in qwerty {
-            let spans = row
-                .chars()
-                .map(|ch| {
-                    if ch == ' ' {
-                        Span::raw(" ")
-                    } else {
-                        let color = match self.used_chars.get(&ch) {
-                            Some(TileState::Correct) => Color::Green,
-                            Some(TileState::Present) => Color::Yellow,
-                            Some(TileState::Absent) => Color::DarkGray,
-                            Some(TileState::Unused) => Color::Black,
-                            _ => unreachable!(),
-                        };
-                        Span::raw(format!(" {ch} ")).bg(color).bold()
-                    }
+    fn screen(game: &Wordle, width: u16, height: u16) -> String {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| game.render_terminal(frame)).unwrap();
+        let buf = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn draw_allocations_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        type_guess(&mut game, "crane");
+        game.show_error("not in word list");
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(60, 45)).unwrap();
+        terminal.draw(|frame| game.render_terminal(frame)).unwrap();
+
+        let allocations = count_allocations(|| {
+            for _ in 0..1000 {
+                terminal.draw(|frame| game.render_terminal(frame)).unwrap();
+            }
+        });
+        // about 480 per frame while tiles and keys formatted their labels;
+        // what's left is mostly ratatui's own layout and diffing
+        assert!(allocations / 1000 < 250, "{allocations} allocations");
+    }
+
+    #[test]
+    fn light_and_dark_palettes_test() {
+        use crate::utils::theme::{Background, TileStyle};
+        use ratatui::style::Color;
+
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        type_guess(&mut game, "crane");
+        // the colors behind each letter, board first, then the keyboard
+        let colors = |game: &Wordle| {
+            let mut terminal = ratatui::Terminal::new(TestBackend::new(60, 45)).unwrap();
+            terminal.draw(|frame| game.render_terminal(frame)).unwrap();
+            let buf = terminal.backend().buffer().clone();
+            let text = screen(game, 60, 45);
+            let cells: Vec<(String, Color, Color)> = buf
+                .content()
+                .iter()
+                .filter(|cell| {
+                    cell.symbol().len() == 1
+                        && cell.symbol().chars().all(|ch| ch.is_ascii_uppercase())
+                        && cell.bg != Color::Reset
                 })
+                .map(|cell| (cell.symbol().to_string(), cell.fg, cell.bg))
                 .collect();
-            lines.push(spans);
-            lines.push(Line::from(vec![]));
+            (text, cells)
+        };
+        let find = |cells: &[(String, Color, Color)], letter: &str| {
+            let (_, fg, bg) = cells.iter().find(|(symbol, ..)| symbol == letter).unwrap();
+            TileStyle { bg: *bg, fg: *fg }
+        };
+
+        let (dark_text, dark) = colors(&game);
+        assert_eq!(find(&dark, "C"), game.theme.present);
+        assert_eq!(find(&dark, "R"), game.theme.absent);
+        assert_eq!(find(&dark, "Q").bg, Color::Black);
+        assert_eq!(find(&dark, "Q").fg, Color::White);
+
+        game.set_background(Background::Light);
+        let (light_text, light) = colors(&game);
+        assert_eq!(light_text, dark_text);
+        assert_eq!(find(&light, "C").bg, Color::Rgb(201, 180, 88));
+        assert_eq!(find(&light, "R").bg, Color::Rgb(120, 124, 126));
+        // untouched keys stay visible on a white page, with dark letters
+        assert_eq!(find(&light, "Q").bg, Color::Rgb(211, 214, 218));
+        assert_eq!(find(&light, "Q").fg, Color::Black);
+    }
+
+    #[test]
+    fn distribution_bars_test() {
+        let bar = |line: &Line| line.spans[1].content.chars().count();
+        let lines = distribution_lines(&[1, 0, 8, 4], 6, Some(4), 14);
+        assert_eq!(lines.len(), 6);
+        // "3 " and " 8" leave ten columns for the widest bar
+        assert_eq!(bar(&lines[2]), 10);
+        assert_eq!(bar(&lines[3]), 5);
+        assert_eq!(bar(&lines[0]), 1);
+        assert_eq!(bar(&lines[5]), 1);
+        assert_eq!(lines[3].spans[1].style.fg, Some(Color::Green));
+        assert_eq!(lines[2].spans[1].style.fg, Some(Color::DarkGray));
+
+        // wins from a longer game than this one still get a bar
+        let lines = distribution_lines(&[0, 0, 0, 0, 0, 0, 0, 0, 1], 6, None, 20);
+        assert_eq!(lines.len(), 9);
+    }
+
+    #[test]
+    fn stats_page_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        type_guess(&mut game, "crane");
+        // a letter while playing, not the stats page
+        press(&mut game, KeyCode::Char('s'));
+        assert!(!game.show_stats);
+        assert_eq!(game.current_guess, "S");
+        game.current_guess.clear();
+        type_guess(&mut game, "epoch");
+        game.stats.record_game(true, 2);
+
+        press(&mut game, KeyCode::Char('s'));
+        assert!(game.show_stats);
+        let page = screen(&game, 60, 45);
+        assert!(page.contains("Played 1  Win % 100"));
+        assert!(page.contains("Guess distribution"));
+        assert!(!page.contains("Adaptive level"));
+
+        // adaptive games chart their level, a row per difficulty
+        let adaptive = crate::utils::adaptive::Adaptive::default();
+        game.adaptive = Some(adaptive.next(true, 2).next(true, 2).next(false, 6));
+        let page = screen(&game, 60, 45);
+        assert!(page.contains("Adaptive level by game"));
+        assert!(page.contains("Hard   ·█·"), "{page}");
+        assert!(page.contains("Normal █·█"), "{page}");
+        assert!(page.contains("Easy   ···"), "{page}");
+
+        // Esc goes back to the board instead of quitting
+        assert!(matches!(press(&mut game, KeyCode::Esc), InputState::None));
+        assert!(!game.show_stats);
+        press(&mut game, KeyCode::Char('s'));
+        press(&mut game, KeyCode::Char('s'));
+        assert!(!game.show_stats);
+        assert!(!screen(&game, 60, 45).contains("Guess distribution"));
+    }
+
+    #[test]
+    fn three_rounds_test() {
+        let setup = Setup {
+            rounds: Some(3),
+            ..Setup::default()
+        };
+        let mut game = Wordle::with_config(Storage::in_memory("test"), setup).unwrap();
+        game.answer = "EPOCH".to_string();
+        for word in ["bagel", "round"] {
+            type_guess(&mut game, word);
         }
-        Paragraph::new(lines)
-            .alignment(Alignment::Center)
-            .render(keyboard_area, buf);
+        assert!(!game.is_game_over);
+        assert!(!screen(&game, 60, 45).contains("You lost!"));
+
+        type_guess(&mut game, "mount");
+        assert!(game.is_game_over);
+        assert!(!game.solved);
+        let screen = screen(&game, 60, 45);
+        assert!(screen.contains("You lost! The answer is: EPOCH"));
+        assert!(screen.contains("M"));
     }
 
-    fn render_definition_page(&self, area: Rect, buf: &mut Buffer) {
-        Clear.render(area, buf);
+    #[test]
+    fn tall_board_keeps_keyboard_test() {
+        let setup = Setup {
+            rounds: Some(MAX_ROUNDS),
+            ..Setup::default()
+        };
+        let mut game = Wordle::with_config(Storage::in_memory("test"), setup).unwrap();
+        game.answer = "EPOCH".to_string();
+        for word in [
+            "bagel", "round", "mount", "crack", "solve", "crane", "slate",
+        ] {
+            type_guess(&mut game, word);
+        }
+        for ch in "pl".chars() {
+            press(&mut game, KeyCode::Char(ch));
+        }
 
-        let mut lines = Vec::new();
-        if let Some(word_defs) = self.get_word_def(&self.answer) {
-            if word_defs.is_empty() {
-                lines.push(Line::from("Definition not found"));
-            } else {
-                for def in word_defs {
-                    lines.push(Line::from(def));
+        let screen = screen(&game, 60, 24);
+        assert!(screen.contains("Q   W   E   R   T   Y   U   I   O   P"));
+        // scrolled down to the row being typed
+        let typed = screen
+            .lines()
+            .find(|line| line.contains(" P ") && line.contains(" L "));
+        assert!(typed.is_some());
+    }
+
+    #[test]
+    fn zen_board_grows_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        game.zen = true;
+        for word in [
+            "bagel", "round", "mount", "crack", "solve", "crane", "slate", "plumb", "dwarf",
+        ] {
+            type_guess(&mut game, word);
+        }
+        assert!(!game.is_game_over);
+        assert_eq!(game.guess_history.len(), 9);
+        for ch in "fi".chars() {
+            press(&mut game, KeyCode::Char(ch));
+        }
+        let board = screen(&game, 60, 24);
+        assert!(!board.contains("You lost!"));
+        // the oldest rows gave way to the latest and the one being typed
+        assert!(!board.contains("B   A   G   E   L"));
+        assert!(board.contains("D   W   A   R   F"));
+        assert!(board.contains(" F   I "));
+
+        game.current_guess.clear();
+        type_guess(&mut game, "epoch");
+        assert!(game.is_game_over && game.solved);
+        assert!(screen(&game, 60, 45).contains("You won!"));
+        assert!(game.share_text().starts_with("Wordle 10/∞"));
+    }
+
+    #[test]
+    fn small_terminal_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        type_guess(&mut game, "crane");
+
+        let tiny = screen(&game, 20, 10);
+        assert!(tiny.contains("enlarge"));
+        assert!(game.keyboard_area.get().is_none());
+        // just big enough: every key and the row being played
+        type_guess(&mut game, "sl");
+        assert_eq!(game.min_size(), (47, 19));
+        let (width, height) = game.min_size();
+        let smallest = screen(&game, width, height);
+        assert!(!smallest.contains("enlarge"));
+        assert!(smallest.contains("Q   W   E   R   T   Y   U   I   O   P"));
+        assert!(smallest.contains(" S   L "));
+
+        // rows centered left of the screen's edge start at it
+        assert_eq!(tile_area(3, 0, 0, 0, 5, TileSize::NORMAL).x, 0);
+        assert_eq!(
+            tile_area(u16::MAX, 0, 0, 4, 5, TileSize::NORMAL).x,
+            u16::MAX
+        );
+    }
+
+    fn press(game: &mut Wordle, code: KeyCode) -> InputState {
+        game.on_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn type_guess(game: &mut Wordle, word: &str) {
+        for ch in word.chars() {
+            press(game, KeyCode::Char(ch));
+        }
+        if let InputState::Submit = press(game, KeyCode::Enter) {
+            game.submit_guess();
+        }
+    }
+
+    #[test]
+    fn multibyte_keys_test() {
+        let mut game = Wordle::new();
+        for ch in ['É', 'é', 'ß', 'Ж', '字', '\u{0301}', '4'] {
+            press(&mut game, KeyCode::Char(ch));
+            game.type_letter(ch);
+        }
+        assert_eq!(game.current_guess, "");
+
+        // a full row of letters takes no more, whatever comes next
+        for ch in "cafesé".chars() {
+            press(&mut game, KeyCode::Char(ch));
+        }
+        game.type_letter('x');
+        assert_eq!(game.current_guess, "CAFES");
+        assert_eq!(game.current_guess.len(), game.word_len);
+    }
+
+    #[test]
+    fn win_on_final_guess_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        for word in ["bagel", "round", "mount", "crack", "solve"] {
+            type_guess(&mut game, word);
+        }
+        assert!(!game.is_game_over);
+
+        type_guess(&mut game, "epoch");
+        assert_eq!(game.guess_history.len(), ROUND as usize);
+        assert!(game.solved);
+        assert!(game.is_game_over);
+    }
+
+    #[test]
+    fn seventh_guess_ignored_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        for word in ["bagel", "round", "mount", "crack", "solve", "crane"] {
+            type_guess(&mut game, word);
+        }
+        assert!(game.is_game_over);
+        assert!(!game.solved);
+
+        for ch in "epoch".chars() {
+            assert!(matches!(
+                press(&mut game, KeyCode::Char(ch)),
+                InputState::None
+            ));
+        }
+        assert!(game.current_guess.is_empty());
+        assert!(matches!(press(&mut game, KeyCode::Enter), InputState::None));
+        assert_eq!(game.guess_history.len(), ROUND as usize);
+    }
+
+    #[test]
+    fn shifted_letters_and_keypad_enter_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        for (ch, modifiers) in [
+            ('b', KeyModifiers::NONE),
+            ('A', KeyModifiers::SHIFT),
+            ('g', KeyModifiers::SHIFT),
+            ('E', KeyModifiers::NONE),
+            ('l', KeyModifiers::NONE),
+        ] {
+            game.on_key(KeyEvent::new(KeyCode::Char(ch), modifiers));
+        }
+        assert_eq!(game.current_guess, "BAGEL");
+
+        // Ctrl+letter isn't typing
+        game.on_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL));
+        assert_eq!(game.current_guess, "BAGEL");
+
+        let keypad_enter = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL);
+        assert!(matches!(game.on_key(keypad_enter), InputState::Submit));
+    }
+
+    #[test]
+    fn clear_guess_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        type_guess(&mut game, "xyzzy");
+        assert!(game.messages.error().is_some());
+        // Ctrl+U used to type a U
+        let ctrl_u = KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert!(matches!(game.on_key(ctrl_u), InputState::EditingGuess));
+        assert!(game.current_guess.is_empty());
+        // the error stays until the row is typed again
+        assert!(game.messages.error().is_some());
+
+        for key in [
+            KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL),
+            KeyEvent::new(KeyCode::Delete, KeyModifiers::NONE),
+        ] {
+            for ch in "cra".chars() {
+                press(&mut game, KeyCode::Char(ch));
+            }
+            assert!(matches!(game.on_key(key), InputState::EditingGuess));
+            assert!(game.current_guess.is_empty());
+        }
+        // plain and shifted u are still letters
+        press(&mut game, KeyCode::Char('u'));
+        game.on_key(KeyEvent::new(KeyCode::Char('U'), KeyModifiers::SHIFT));
+        assert_eq!(game.current_guess, "UU");
+
+        press(&mut game, KeyCode::Delete);
+        type_guess(&mut game, "epoch");
+        assert!(game.is_game_over);
+        assert!(matches!(game.on_key(ctrl_u), InputState::None));
+    }
+
+    #[test]
+    fn stray_characters_ignored_test() {
+        let mut game = Wordle::new();
+        for ch in ['1', ';', '<', '~', 'c', 'é', 'r'] {
+            press(&mut game, KeyCode::Char(ch));
+        }
+        assert_eq!(game.current_guess, "CR");
+    }
+
+    #[test]
+    fn quit_requires_modifier_test() {
+        let mut game = Wordle::new();
+        game.config.quit_requires_modifier = true;
+        assert!(matches!(press(&mut game, KeyCode::Esc), InputState::None));
+        assert!(game.messages.status().is_some());
+        let ctrl_q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert!(matches!(game.on_key(ctrl_q), InputState::Quit));
+        assert!(game.current_guess.is_empty());
+    }
+
+    #[test]
+    fn keyboard_explicit_foreground_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        type_guess(&mut game, "bagel");
+
+        let area = Rect::new(0, 0, 48, 7);
+        let mut buf = Buffer::empty(area);
+        game.render_keyboard(area, &mut buf);
+
+        let mut seen = 0;
+        for y in 0..area.height {
+            for x in 0..area.width {
+                let cell = &buf[(x, y)];
+                let Some(ch) = cell.symbol().chars().next() else {
+                    continue;
+                };
+                if !ch.is_ascii_uppercase() {
+                    continue;
                 }
+                let style = game.theme.key(game.used_chars[&ch]);
+                assert_eq!(cell.fg, style.fg, "key {ch}");
+                assert_eq!(cell.bg, style.bg, "key {ch}");
+                assert_ne!(cell.fg, Color::Reset, "key {ch}");
+                seen += 1;
             }
-        } else {
-            lines.push(Line::from(
-                "Connect to the internet to get word definitions",
-            ));
         }
+        assert_eq!(seen, 26);
+    }
 
-        Paragraph::new(lines)
-            .block(Block::bordered())
-            .wrap(Wrap { trim: true })
-            .render(area, buf);
+    #[test]
+    fn letter_rounds_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        type_guess(&mut game, "crane");
+        type_guess(&mut game, "porch");
+        // C went from yellow to green in round 2; R stayed gray from round 1
+        assert_eq!(game.letter_rounds[&'C'], 2);
+        assert_eq!(game.letter_rounds[&'R'], 1);
+        assert_eq!(game.letter_rounds[&'E'], 1);
+        assert!(!game.letter_rounds.contains_key(&'Z'));
+
+        let keyboard = |game: &Wordle| {
+            let area = Rect::new(0, 0, 48, 7);
+            let mut buf = Buffer::empty(area);
+            game.render_keyboard(area, &mut buf);
+            (0..area.height)
+                .map(|y| {
+                    (0..area.width)
+                        .map(|x| buf[(x, y)].symbol())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        assert!(keyboard(&game).contains('R'));
+        press(&mut game, KeyCode::F(4));
+        let rounds = keyboard(&game);
+        assert!(!rounds.contains('R') && !rounds.contains('C'));
+        assert!(rounds.contains('Z') && rounds.contains(" 2"));
+        // long games number their keys past a single digit
+        game.letter_rounds.insert('Z', 11);
+        assert!(keyboard(&game).contains("11"));
+        // only the labels change
+        type_guess(&mut game, "epoch");
+        assert!(game.solved);
+        press(&mut game, KeyCode::F(4));
+        assert!(keyboard(&game).contains('R'));
+    }
+
+    #[test]
+    fn keyboard_unplaced_copy_corner_test() {
+        let mut game = Wordle::new();
+        game.answer = "GEESE".to_string();
+        let area = Rect::new(0, 0, 48, 7);
+        let corner_of = |game: &Wordle, letter: &str| {
+            let mut buf = Buffer::empty(area);
+            game.render_keyboard(area, &mut buf);
+            let (x, y) = (0..area.height)
+                .flat_map(|y| (0..area.width).map(move |x| (x, y)))
+                .find(|&(x, y)| buf[(x, y)].symbol() == letter)
+                .unwrap();
+            (buf[(x, y)].bg, buf[(x + 1, y)].bg)
+        };
+        let correct = game.theme.correct.bg;
+        let present = game.theme.present.bg;
+
+        type_guess(&mut game, "elope");
+        assert_eq!(corner_of(&game, "E"), (correct, present));
+
+        type_guess(&mut game, "these");
+        assert_eq!(corner_of(&game, "E"), (correct, correct));
+    }
+
+    fn message_snapshot(game: &Wordle, width: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, width, 2);
+        let mut buf = Buffer::empty(area);
+        game.render_system_message(area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn long_message_wraps_test() {
+        let mut game = Wordle::new();
+        game.show_status("press Ctrl+Q to quit");
+        game.show_error(
+            "hard mode: the 2nd letter must be R, the 5th letter must be E, and the guess must contain A and T",
+        );
+        assert_eq!(
+            message_snapshot(&game, 50),
+            vec![
+                "hard mode: the 2nd letter must be R, the 5th",
+                "letter must be E, and the guess must contain A an…",
+            ]
+        );
+        assert_eq!(
+            message_snapshot(&game, 80),
+            vec![
+                "hard mode: the 2nd letter must be R, the 5th letter must be E, and the guess",
+                "must contain A and T",
+            ]
+        );
+
+        // the status message shows once the error is gone
+        game.messages.clear(Level::Error);
+        assert_eq!(
+            message_snapshot(&game, 50),
+            vec!["press Ctrl+Q to quit", ""]
+        );
+        assert_eq!(wrap_message("abcdefgh", 3, 2), vec!["abc", "de…"]);
+    }
+
+    /// What `game` draws on a `width` by `height` terminal, colors and all.
+    fn draw(game: &Wordle, width: u16, height: u16) -> Buffer {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| game.render_terminal(frame)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    /// Where `text` first starts within `area` of `buf`.
+    fn locate(buf: &Buffer, area: Rect, text: &str) -> Option<Position> {
+        let symbols: Vec<String> = text.chars().map(String::from).collect();
+        area.positions().find(|start| {
+            symbols.iter().enumerate().all(|(offset, symbol)| {
+                let x = start.x + offset as u16;
+                x < area.right() && buf[(x, start.y)].symbol() == symbol
+            })
+        })
+    }
+
+    #[test]
+    fn board_tile_colors_test() {
+        let mut game = Wordle::with_words("EPOCH", &["COACH"]);
+        type_guess(&mut game, "coach");
+        let buf = draw(&game, 60, 45);
+        let keyboard = game.keyboard_area.get().unwrap();
+        let board = Rect::new(0, 0, 60, keyboard.y);
+
+        // the whole tile is colored, not just under its letter
+        let h = locate(&buf, board, "H").unwrap();
+        assert_eq!(buf[(h.x, h.y)].bg, Color::Green);
+        assert_eq!(buf[(h.x - 1, h.y)].bg, Color::Green);
+        assert_eq!(buf[(h.x, h.y - 1)].bg, Color::Green);
+        let o = locate(&buf, board, "O").unwrap();
+        assert_eq!(buf[(o.x, o.y)].bg, Color::Yellow);
+        // the first C of two, with only one in the answer
+        let c = locate(&buf, board, "C").unwrap();
+        assert_eq!(buf[(c.x, c.y)].bg, Color::DarkGray);
+        assert_eq!(buf[(c.x, c.y)].symbol(), "C");
+    }
+
+    #[test]
+    fn keyboard_colors_test() {
+        let mut game = Wordle::with_words("EPOCH", &["COACH"]);
+        type_guess(&mut game, "coach");
+        let buf = draw(&game, 60, 45);
+        let keyboard = game.keyboard_area.get().unwrap();
+
+        let key = |letter: &str| {
+            let spot = locate(&buf, keyboard, letter).unwrap();
+            buf[(spot.x, spot.y)].bg
+        };
+        assert_eq!(key("A"), Color::DarkGray);
+        assert_eq!(key("O"), Color::Yellow);
+        // absent in one place but placed in another shows placed
+        assert_eq!(key("C"), Color::Green);
+        assert_eq!(key("Q"), Color::Black);
+    }
+
+    #[test]
+    fn error_message_render_test() {
+        let mut game = Wordle::with_words("EPOCH", &["COACH"]);
+        type_guess(&mut game, "crane");
+        let buf = draw(&game, 60, 45);
+        let message = locate(&buf, buf.area, "not in the word list").unwrap();
+        assert_eq!(buf[(message.x, message.y)].fg, Color::Red);
+        // the refused word is still in the row to be fixed
+        assert!(game.guess_history.is_empty());
+        assert_eq!(game.current_guess, "CRANE");
+    }
+
+    #[test]
+    fn game_over_popup_render_test() {
+        let mut game = Wordle::with_words("EPOCH", &["COACH"]);
+        type_guess(&mut game, "coach");
+        type_guess(&mut game, "epoch");
+        game.stats.record_game(true, 1);
+        game.stats.record_game(true, 2);
+        game.stats.record_game(true, 2);
+        let buf = draw(&game, 60, 45);
+        let won = locate(&buf, buf.area, "You won! The answer is: EPOCH").unwrap();
+        assert_eq!(buf[(won.x, won.y)].fg, Color::Green);
+        let answer = locate(&buf, buf.area, "EPOCH").unwrap();
+        assert_eq!(buf[(answer.x, answer.y)].fg, Color::White);
+        assert!(
+            buf[(answer.x, answer.y)]
+                .modifier
+                .contains(ratatui::style::Modifier::BOLD)
+        );
+        // this game's bar is green and, as the most common, the widest
+        let bar = locate(&buf, buf.area, "2 █").unwrap();
+        let bar = Position::new(bar.x + 2, bar.y);
+        assert_eq!(buf[bar].fg, Color::Green);
+        let cells = (bar.x..60).take_while(|&x| buf[(x, bar.y)].symbol() == "█");
+        assert!(cells.count() > 30);
+        assert!(locate(&buf, buf.area, "1 █").is_some());
+        // the board stays in view above it
+        assert!(
+            locate(
+                &buf,
+                Rect::new(0, 0, 60, bar.y),
+                "C      O      A      C      H"
+            )
+            .is_some()
+        );
+
+        let mut game = Wordle::with_words("EPOCH", &["COACH"]);
+        game.rounds = 2;
+        type_guess(&mut game, "coach");
+        type_guess(&mut game, "coach");
+        assert!(game.is_game_over && !game.solved);
+        let buf = draw(&game, 60, 45);
+        let lost = locate(&buf, buf.area, "You lost! The answer is: EPOCH").unwrap();
+        assert_eq!(buf[(lost.x, lost.y)].fg, Color::LightYellow);
+        // a first game lost has no distribution to draw
+        assert!(locate(&buf, buf.area, "No wins yet").is_some());
+        assert!(locate(&buf, buf.area, "█").is_none());
+    }
+
+    #[test]
+    fn definition_page_render_test() {
+        use crate::utils::definition::{DefinitionQueue, Meaning, Sense, WordDefinition};
+
+        let mut game = Wordle::with_words("EPOCH", &["COACH"]);
+        game.definitions = DefinitionQueue::offline([WordDefinition {
+            word: String::from("epoch"),
+            phonetic: Some(String::from("/ˈiːpɒk/")),
+            meanings: vec![Meaning {
+                part_of_speech: String::from("noun"),
+                definitions: vec![Sense {
+                    definition: String::from("A particular period of history."),
+                    example: None,
+                }],
+            }],
+        }]);
+        type_guess(&mut game, "epoch");
+        press(&mut game, KeyCode::Char('?'));
+        assert!(game.show_word_def);
+
+        let buf = draw(&game, 60, 45);
+        let heading = locate(&buf, buf.area, "epoch  /ˈiːpɒk/").unwrap();
+        assert!(
+            buf[(heading.x, heading.y)]
+                .modifier
+                .contains(ratatui::style::Modifier::BOLD)
+        );
+        let noun = locate(&buf, buf.area, "noun").unwrap();
+        assert_eq!(buf[(noun.x, noun.y)].fg, Color::Cyan);
+        assert!(locate(&buf, buf.area, "1. A particular period of history.").is_some());
+        // the board is out of the way
+        assert!(locate(&buf, buf.area, "You won!").is_none());
+    }
+
+    #[test]
+    fn help_popup_test() {
+        let mut game = Wordle::new();
+        type_guess(&mut game, "cr");
+        press(&mut game, KeyCode::Char('?'));
+        assert!(game.show_help);
+        let buf = draw(&game, 80, 24);
+        for (key, label) in game.help_entries() {
+            let spot = locate(&buf, buf.area, &key).unwrap_or_else(|| panic!("{key} not shown"));
+            assert!(locate(&buf, Rect::new(0, spot.y, 80, 1), label).is_some());
+        }
+        assert!(locate(&buf, buf.area, "<F9>").is_some());
+        assert_eq!(game.help_entries()[0].0, "<A-Z>");
+        let quit_keys = |game: &Wordle| -> Vec<String> {
+            game.help_entries()
+                .into_iter()
+                .filter(|(_, label)| *label == "Quit")
+                .map(|(key, _)| key)
+                .collect()
+        };
+        assert_eq!(quit_keys(&game), ["<Esc>"]);
+        let mut spanish = Wordle::new();
+        spanish.alphabet = Alphabet::new("ABCDEFGHIJKLMNÑOPQRSTUVWXYZ".chars()).unwrap();
+        spanish.config.quit_requires_modifier = true;
+        assert_eq!(spanish.help_entries()[0].0, "<A-Z, Ñ>");
+        assert_eq!(quit_keys(&spanish), ["<Ctrl+Q>"]);
+
+        // any key closes it without reaching the board
+        press(&mut game, KeyCode::Char('a'));
+        assert!(!game.show_help);
+        assert_eq!(game.current_guess, "CR");
+        press(&mut game, KeyCode::F(1));
+        assert!(game.show_help);
+        press(&mut game, KeyCode::Enter);
+        assert!(!game.show_help && game.guess_history.is_empty());
+    }
+
+    #[test]
+    fn candidates_page_test() {
+        let mut game = Wordle::new();
+        game.answer = String::from("EPOCH");
+        // only a letter while playing
+        press(&mut game, KeyCode::Char('l'));
+        assert!(game.candidate_page.is_none());
+        assert_eq!(game.current_guess, "L");
+        press(&mut game, KeyCode::Delete);
+        type_guess(&mut game, "fuzzy");
+        type_guess(&mut game, "epoch");
+        press(&mut game, KeyCode::Char('l'));
+        assert!(game.candidate_page.is_some());
+
+        let candidates = game.final_candidates();
+        assert!(candidates.len() > 20 && candidates.contains(&"EPOCH"));
+        let buf = draw(&game, 60, 30);
+        let heading = format!("{} words fit before your last guess", candidates.len());
+        assert!(locate(&buf, buf.area, &heading).is_some());
+        // several to a line
+        let first = locate(&buf, buf.area, candidates[0]).unwrap();
+        let second = locate(&buf, buf.area, candidates[1]).unwrap();
+        assert_eq!(first.y, second.y);
+
+        // scrolls like the definition page, down to the answer picked out
+        let overflow = game.candidate_page.as_ref().unwrap().overflow.get();
+        assert!(overflow > 0);
+        let answer = (0..=overflow).find_map(|_| {
+            let buf = draw(&game, 60, 30);
+            press(&mut game, KeyCode::Down);
+            locate(&buf, buf.area, "EPOCH").map(|spot| buf[spot].fg)
+        });
+        assert_eq!(answer, Some(Color::Green));
+        for _ in 0..=overflow {
+            press(&mut game, KeyCode::PageDown);
+        }
+        assert_eq!(game.candidate_page.as_ref().unwrap().offset, overflow);
+        press(&mut game, KeyCode::Esc);
+        assert!(game.candidate_page.is_none() && game.is_game_over);
     }
 }
@@ -1,230 +1,1098 @@
-use anyhow::Result;
-use core::panic;
-use rand::seq::IteratorRandom;
-use regex::Regex;
-use reqwest::blocking;
+use anyhow::{Result, bail};
+use chrono::NaiveDate;
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use ratatui::layout::Rect;
+use ratatui::{Terminal, backend::Backend};
+use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::PathBuf;
+use std::time::Instant;
+use uuid::Uuid;
+use wordle::engine::internal::{list_files, parse_list_in, pick_length};
+use wordle::engine::wordlist::{closest_words, parse_list};
+use wordle::engine::{
+    Alphabet, ROUND, TileState, Word, WordleError, is_consistent, play_row, score,
+};
 
-use super::tile::TileState;
+use super::adaptive::Adaptive;
+use super::assets::{ANSWERS, LAYOUTS, Source, THEME_COLORS, WORDS, parse_answers};
+use super::bell::{Bell, GameEvent};
+use super::blitz::Blitz;
+use super::challenge::Challenge;
+use super::commitment::new_nonce;
+use super::config::Config;
+use super::daily::DailyRules;
+use super::debug::DebugOverlay;
+use super::definition::{DefinitionQueue, DefinitionView, PREFETCH_DELAY, fetch_definitions};
+use super::difficulty::{Difficulty, difficulty_pool};
+use super::elapsed::GameClock;
+use super::events::{EventSource, bracketed_paste};
+use super::family::{EXCLUDED_FILE, builtin_excluded, load_excluded};
+use super::heatmap::KeyCounts;
+use super::hint::Reveal;
+use super::hotseat::HotSeat;
+use super::input::EscapeFilter;
+use super::integrations::{Executor, SystemExecutor};
+use super::keyboard::{Layouts, parse_layouts};
+use super::keys::{Keymap, KeysPage};
+use super::knowledge::hard_mode_violations;
+use super::language::LanguagePack;
+use super::messages::{Level, MessageLog, describe};
+use super::mouse::capture_mouse;
+use super::played::load_played;
+use super::race::{Race, RaceMessage, row_colors};
+use super::replay::{ReplayPlayer, ReplayRecorder};
+use super::session::{Session, install_panic_hook, reset_terminal};
+use super::solver::RankedGuesses;
+use super::stats::Stats;
+use super::storage::Storage;
+use super::suggest::SuggestionPanel;
+use super::switcher::{SCAN_BATCH, WordListSwitcher};
+use super::theme::{Background, Palettes, THEMES, Theme, parse_palettes};
+use super::timed::TimedRun;
+use super::timing::{RowClock, RowTiming};
 use super::ui::InputState;
-use super::word::{WORD_LEN, Word};
 
-pub const ROUND: u8 = 6; // maximum 6 rounds
+/// Most rows a game can be played with.
+pub const MAX_ROUNDS: u8 = 12;
+
+/// Seeds drawn for random games stay below this, short enough to read off
+/// the end of a game and type back in after `--seed`.
+const SEEDS: u64 = 1_000_000;
+
+const WRAPPED_NOTICE: &str = "you've played every word, starting over";
+
+/// How a game is put together, separate from the saved `Config` so the
+/// command line and tests can build one without touching the data directory.
+#[derive(Debug, Clone, Default)]
+pub struct Setup {
+    /// word list files to play with instead of the configured one, merged;
+    /// a directory stands for every `.txt` file in it
+    pub wordlist_paths: Vec<PathBuf>,
+    /// board rows, the configured number or `ROUND` when unset
+    pub rounds: Option<u8>,
+    /// makes the answers drawn reproducible
+    pub seed: Option<u64>,
+    /// letters per word, the word list's most common length when unset
+    pub length: Option<usize>,
+    /// a friend's game to play instead of a random one, under its rules
+    pub challenge: Option<Challenge>,
+    /// list to draw answers from instead of the whole word list
+    pub answer_list: Option<PathBuf>,
+    /// language pack directory to play with instead of the configured one
+    pub language: Option<PathBuf>,
+    /// refuse excluded words as guesses too, not just as answers
+    pub family_mode: bool,
+    /// how common answers are, the config's when unset
+    pub difficulty: Option<Difficulty>,
+}
 
 pub struct Wordle {
     pub round: u8,
-    pub valid_words: HashSet<String>,
+    /// rows on the board
+    pub rounds: u8,
+    /// draws the seed of every game after the first, seeded by `--seed` if
+    /// given
+    pub rng: StdRng,
+    /// seed the random answer was drawn with; `--seed` with it plays the
+    /// same word again
+    pub seed: u64,
+    /// random answers dealt this session or finished in earlier ones, not
+    /// drawn again until every word has been
+    pub seen_answers: HashSet<String>,
+    /// answers of finished games, as kept in the played file
+    pub played_answers: HashSet<String>,
+    /// letters per word; the word lists only hold words of this length
+    pub word_len: usize,
+    /// words answers are drawn from
+    pub answer_words: HashSet<String>,
+    /// words accepted as guesses, every answer included
+    pub accepted_words: HashSet<String>,
+    /// words never drawn as answers, from the exclusion lists
+    pub excluded: HashSet<String>,
+    /// whether excluded words are refused as guesses as well
+    pub family_mode: bool,
+    /// how common random answers are, `None` for the whole list; only set
+    /// for the built-in list, see `answer_pool`
+    pub difficulty: Option<Difficulty>,
+    /// letters words are spelled with, A–Z unless a language pack is used
+    pub alphabet: Alphabet,
+    /// the language pack played in; its words are in the word lists
+    pub language: Option<LanguagePack>,
+    /// the on-screen keyboard's rows, as `keyboard_rows` writes them
+    pub keyboard: Vec<String>,
+    /// rows of each keyboard layout, from `layouts.toml`
+    pub layouts: Layouts,
     pub used_chars: HashMap<char, TileState>,
+    /// where the on-screen keyboard's keys were last drawn, `None` while
+    /// it's hidden; clicks are matched against it
+    pub keyboard_area: Cell<Option<Rect>>,
+    /// round in which each played letter's `used_chars` state was set
+    pub letter_rounds: HashMap<char, u8>,
     pub answer: String,
+    /// random id shared by every record of this game, kept when resuming
+    pub game_id: String,
+    /// secret salt of the answer's commitment, told once the game is over
+    pub nonce: String,
     pub current_guess: String,
     pub guess_history: Vec<Word>,
-    pub err_msg: String,
+    /// errors and statuses shown in the message area, kept for the
+    /// messages page
+    pub messages: MessageLog,
+    pub assists_used: u8,
+    /// letters of the answer shown on the active row as hints
+    pub revealed: Vec<Reveal>,
+    pub storage: Storage,
+    pub definitions: DefinitionQueue,
+    pub theme: Theme,
+    /// colors of each theme, from `themes.toml`
+    pub palettes: Palettes,
+    /// date of the daily puzzle being played, `None` for random games
+    pub daily: Option<NaiveDate>,
+    /// rules fixed when the daily started, `None` outside dailies
+    pub daily_rules: Option<DailyRules>,
+    /// adaptive difficulty state, `None` unless the mode is enabled
+    pub adaptive: Option<Adaptive>,
+    pub stats: Stats,
+    pub theme_id: &'static str,
+    /// theme to go back to when high contrast is toggled off
+    pub previous_theme: &'static str,
+    /// terminal background the theme was picked for
+    pub background: Background,
+    pub settings_cursor: usize,
+    /// key bindings page of the settings, open while `Some`
+    pub keys_page: Option<KeysPage>,
+    /// actions' keys, the defaults with `keys` from the config applied
+    pub keymap: Keymap,
+    pub config: Config,
+    /// word list overlay, open while `Some`
+    pub word_lists: Option<WordListSwitcher>,
+    /// popup shown after a game that unlocked something, dismissed by any key
+    pub celebration: Option<String>,
+    /// runs hooks and uploads, or captures them with `--dry-run-integrations`
+    pub integrations: Box<dyn Executor>,
+    /// effects a dry run would have performed, shown until a key is pressed
+    pub integration_preview: Option<Vec<String>>,
+    /// where `e` writes transcripts, the working directory
+    pub transcript_dir: PathBuf,
+    pub escape_filter: EscapeFilter,
+    /// past row whose feedback is being explained
+    pub review_row: Option<usize>,
+    /// suggestion panel, open while `Some`
+    pub suggestions: Option<SuggestionPanel>,
+    /// solver panel beside the board
+    pub show_solver: bool,
+    /// the solver's guesses and the number of rows they were worked out for
+    pub solver_cache: RefCell<Option<(usize, RankedGuesses)>>,
+    /// answers left and the number of rows they were counted for
+    pub remaining_cache: Cell<Option<(usize, usize)>>,
+    pub row_clock: RowClock,
+    /// time since the first letter of the game
+    pub game_clock: GameClock,
+    /// per-guess clock, `None` unless playing `--blitz`
+    pub blitz: Option<Blitz>,
+    /// one clock for a whole run of words, `None` unless playing `--timed`
+    pub timed: Option<TimedRun>,
+    /// no row limit, the game only ends once the word is found; `--zen`
+    pub zen: bool,
+    /// two players taking turns to pick the word, `None` unless `--hot-seat`
+    pub hot_seat: Option<HotSeat>,
+    /// the opponent of a `--host` or `--join` race
+    pub race: Option<Race>,
+    /// writes each game to a file for `--replay`, `None` unless `--record`
+    pub recorder: Option<ReplayRecorder>,
+    /// the recorded game being played back, which takes the place of input
+    pub replay: Option<ReplayPlayer>,
+    /// thinking/typing split of each played row, `None` for rows that
+    /// weren't typed here, such as a resumed daily's or the bot's
+    pub row_timings: Vec<Option<RowTiming>>,
+    /// internals overlay, shown with `--debug`
+    pub debug: Option<DebugOverlay>,
+    /// the friend's challenge being played; its rules win over the config
+    pub challenge: Option<Challenge>,
+    /// presses per key across every game of this session
+    pub key_counts: KeyCounts,
+    pub bell: Bell,
 
     /* control flow flags */
     pub solved: bool,
     pub show_word_def: bool,
+    pub definition_view: DefinitionView,
+    /// words that fit before the final guess, open after a game while `Some`
+    pub candidate_page: Option<DefinitionView>,
+    /// stats and guess distribution, after a game
+    pub show_stats: bool,
+    pub show_settings: bool,
+    /// challenge prompt, shown until the player accepts
+    pub confirm_challenge: bool,
+    /// asks before a new game throws away the one in progress
+    pub confirm_restart: bool,
+    /// keyboard colored by `key_counts` instead of feedback
+    pub show_heatmap: bool,
+    /// keyboard labelled with `letter_rounds` instead of letters
+    pub show_letter_rounds: bool,
+    pub show_analysis: bool,
+    pub show_messages: bool,
+    /// the key list popup, closed by the next key
+    pub show_help: bool,
+    /// something on screen may have changed since the last draw
+    pub redraw: bool,
     pub is_game_over: bool,
 }
 
 impl Wordle {
+    /// A game that saves nothing, so tests leave the real data directory
+    /// alone.
+    #[cfg(test)]
     pub fn new() -> Self {
-        let valid_words = Wordle::load_words().expect("failed to load words");
-        let answer = Wordle::draw_word(&valid_words).expect("failed to draw word");
+        let mut storage = Storage::in_memory("test");
+        storage.warning = None;
+        let mut game = Wordle::with_config(storage, Setup::default()).unwrap();
+        game.definitions = DefinitionQueue::offline([]);
+        game.transcript_dir = std::env::temp_dir();
+        game
+    }
+
+    /// A game of `answer` that accepts only `words`, for tests that
+    /// shouldn't depend on what the built-in list has in it.
+    #[cfg(test)]
+    pub fn with_words(answer: &str, words: &[&str]) -> Self {
+        let mut game = Wordle::new();
+        game.accepted_words = words.iter().map(|word| word.to_string()).collect();
+        game.accepted_words.insert(answer.to_string());
+        game.answer_words = HashSet::from([answer.to_string()]);
+        game.word_len = answer.chars().count();
+        game.answer = answer.to_string();
+        game
+    }
+
+    /// A game that keeps its stats and saves in `storage`, e.g. a profile,
+    /// set up as `setup` asks. A word list given there must load; one from
+    /// the config or the data directory falls back to the next place the
+    /// `assets` resolver looks, with a warning.
+    pub fn with_config(mut storage: Storage, setup: Setup) -> Result<Self> {
+        // every warning and note of loading is shown, not just the last
+        let mut startup_errors: Vec<String> = storage.warning.iter().cloned().collect();
+        let mut startup_status = Vec::new();
+        let stats = Stats::load(&storage).unwrap_or_else(|err| {
+            startup_errors.push(format!("failed to load stats: {err}"));
+            Stats::default()
+        });
+        let config = Config::load(&storage).unwrap_or_else(|err| {
+            startup_errors.push(format!("failed to load config: {err}"));
+            Config::default()
+        });
+        config.apply_backups(&mut storage);
+        let (keymap, key_errors) = Keymap::from_config(&config.keys);
+        startup_errors.extend(key_errors);
+
+        let mut language = match setup.language.as_deref().or(config.language.as_deref()) {
+            Some(dir) => Some(LanguagePack::load(dir)?),
+            None => None,
+        };
+        let alphabet = language
+            .as_ref()
+            .map(|pack| pack.alphabet.clone())
+            .unwrap_or_default();
+        // words per list and lines skipped from them, noted once the game
+        // is up
+        let mut list_notes = Vec::new();
+        let mut builtin_list = false;
+        let mut accepted_words = match &mut language {
+            // a list on the command line still wins over the pack's own
+            Some(pack) if setup.wordlist_paths.is_empty() => {
+                startup_status.push(format!("playing in {}", pack.name));
+                std::mem::take(&mut pack.words)
+            }
+            _ => {
+                // each list given on the command line through the resolver,
+                // or without any, whichever it falls back to
+                let mut files = Vec::new();
+                for path in &setup.wordlist_paths {
+                    files.extend(list_files(path)?.into_iter().map(Some));
+                }
+                if files.is_empty() {
+                    files.push(None);
+                }
+                let mut words = HashSet::new();
+                let mut counts = Vec::new();
+                for file in &files {
+                    let list = WORDS.resolve(
+                        file.as_deref(),
+                        config.word_list.as_deref(),
+                        &storage,
+                        |contents: &str, source: &str| parse_list_in(contents, source, &alphabet),
+                    )?;
+                    if !list.value.skipped.is_empty() {
+                        let source = match &list.source {
+                            Source::Embedded => WORDS.builtin.to_string(),
+                            source => source.to_string(),
+                        };
+                        list_notes.push(format!("{source}: {}", list.value.summary()));
+                    }
+                    startup_errors.extend(list.warnings);
+                    match &list.source {
+                        Source::DataDir(path) => {
+                            startup_status.push(format!("using words from {}", path.display()));
+                        }
+                        Source::Embedded => builtin_list = true,
+                        Source::Cli(_) | Source::Config(_) => {}
+                    }
+                    if let Some(path) = file {
+                        counts.push(format!(
+                            "{} from {}",
+                            list.value.words.len(),
+                            path.display()
+                        ));
+                    }
+                    words.extend(list.value.words);
+                }
+                if counts.len() > 1 {
+                    list_notes.insert(0, format!("words: {}", counts.join(", ")));
+                }
+                words
+            }
+        };
+        // answers default to the whole list
+        let answers = ANSWERS.resolve(
+            setup.answer_list.as_deref(),
+            config.answer_list.as_deref(),
+            &storage,
+            |contents: &str, source: &str| parse_answers(contents, source, &alphabet),
+        )?;
+        startup_errors.extend(answers.warnings);
+        let mut answer_words = match answers.value {
+            Some(list) => {
+                if !list.skipped.is_empty() {
+                    list_notes.push(format!("{}: {}", answers.source, list.summary()));
+                }
+                list.words
+            }
+            None => accepted_words.clone(),
+        };
+        let excluded = load_excluded(&storage).unwrap_or_else(|err| {
+            startup_errors.push(format!(
+                "failed to load {EXCLUDED_FILE}: {err}, using the built-in exclusion list"
+            ));
+            builtin_excluded()
+        });
+        answer_words.retain(|word| !excluded.contains(word));
+        startup_status.extend(list_notes);
+        accepted_words.extend(answer_words.iter().cloned());
+        let challenge_answer = match &setup.challenge {
+            Some(challenge) => Some(challenge.answer(&answer_words)?),
+            None => None,
+        };
+        let challenge_rules = setup
+            .challenge
+            .as_ref()
+            .and_then(|challenge| challenge.settings);
+        let length = match &setup.challenge {
+            Some(challenge) => Some(challenge.word_length()),
+            None => setup.length,
+        };
+        let word_len = pick_length(&answer_words, length)?;
+        answer_words.retain(|word| word.chars().count() == word_len);
+        accepted_words.retain(|word| word.chars().count() == word_len);
+        let rounds = challenge_rules
+            .map(|rules| rules.rounds)
+            .or(setup.rounds)
+            .or(config.rounds)
+            .unwrap_or(ROUND);
+        if !(1..=MAX_ROUNDS).contains(&rounds) {
+            bail!("rounds must be between 1 and {MAX_ROUNDS}");
+        }
+        let seed = setup
+            .seed
+            .unwrap_or_else(|| rand::rng().random_range(0..SEEDS));
+        let played_answers = load_played(&storage).unwrap_or_else(|err| {
+            startup_errors.push(format!("failed to load played answers: {err}"));
+            HashSet::new()
+        });
+        // the ranking is of the built-in list's words, so another list is
+        // drawn from whole
+        let difficulty = setup.difficulty.or(config.difficulty);
+        let builtin_answers = builtin_list && answers.source == Source::Embedded;
+        let difficulty = match difficulty {
+            Some(_) if !builtin_answers => {
+                startup_status.push(String::from(
+                    "the difficulty only applies to the built-in list, drawing from all of yours",
+                ));
+                None
+            }
+            difficulty => difficulty,
+        };
+        let drawn = challenge_answer.is_none();
+        let answer = match challenge_answer {
+            Some(answer) => answer,
+            None => {
+                let pool = match difficulty {
+                    Some(difficulty) => difficulty_pool(&answer_words, difficulty),
+                    None => answer_words.clone(),
+                };
+                let sorted = sorted_words(&pool);
+                Wordle::draw_word(&mut StdRng::seed_from_u64(seed), &sorted, word_len)?
+            }
+        };
+
+        let palettes = THEME_COLORS.resolve(None, None, &storage, parse_palettes)?;
+        let layouts = LAYOUTS.resolve(None, None, &storage, parse_layouts)?;
+        startup_errors.extend(palettes.warnings);
+        startup_errors.extend(layouts.warnings);
 
         let mut used_chars = HashMap::new();
-        for ch in 'A'..='Z' {
+        for &ch in alphabet.letters() {
             used_chars.entry(ch).or_insert(TileState::Unused);
         }
 
-        Wordle {
+        let mut game = Wordle {
             round: 1,
-            valid_words,
+            rounds,
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+            seen_answers: played_answers.clone(),
+            played_answers,
+            word_len,
+            answer_words,
+            accepted_words,
+            excluded,
+            family_mode: setup.family_mode,
+            difficulty,
+            alphabet,
+            language,
+            keyboard: Vec::new(),
+            layouts: layouts.value,
             used_chars,
+            keyboard_area: Cell::new(None),
+            letter_rounds: HashMap::new(),
             answer,
+            game_id: Uuid::new_v4().to_string(),
+            nonce: new_nonce(),
             current_guess: String::new(),
             guess_history: Vec::new(),
-            err_msg: String::new(),
+            messages: MessageLog::default(),
+            assists_used: 0,
+            revealed: Vec::new(),
+            storage,
+            definitions: DefinitionQueue::new(fetch_definitions, PREFETCH_DELAY),
+            theme: THEMES[0].theme(config.background.initial(), &palettes.value),
+            palettes: palettes.value,
+            daily: None,
+            daily_rules: None,
+            adaptive: None,
+            stats,
+            theme_id: THEMES[0].id,
+            previous_theme: THEMES[0].id,
+            background: config.background.initial(),
+            settings_cursor: 0,
+            keys_page: None,
+            keymap,
+            config,
+            word_lists: None,
+            celebration: None,
+            integrations: Box::new(SystemExecutor::default()),
+            integration_preview: None,
+            transcript_dir: PathBuf::from("."),
+            escape_filter: EscapeFilter::default(),
+            review_row: None,
+            suggestions: None,
+            show_solver: false,
+            solver_cache: RefCell::new(None),
+            remaining_cache: Cell::new(None),
+            row_clock: RowClock::new(Instant::now()),
+            game_clock: GameClock::default(),
+            blitz: None,
+            timed: None,
+            zen: false,
+            hot_seat: None,
+            race: None,
+            recorder: None,
+            replay: None,
+            row_timings: Vec::new(),
+            debug: None,
+            confirm_challenge: setup.challenge.is_some(),
+            confirm_restart: false,
+            challenge: setup.challenge,
+            key_counts: KeyCounts::default(),
+            bell: Bell::default(),
+            show_heatmap: false,
+            show_letter_rounds: false,
             solved: false,
             is_game_over: false,
             show_word_def: false,
+            definition_view: DefinitionView::default(),
+            candidate_page: None,
+            show_stats: false,
+            show_settings: false,
+            show_analysis: false,
+            show_messages: false,
+            show_help: false,
+            redraw: true,
+        };
+        game.keyboard = game.build_keyboard();
+        // a seed given to replay a game deals its word even if played
+        if drawn && setup.seed.is_none() && game.seen_answers.contains(&game.answer) {
+            let (answer, wrapped) = game.draw_unseen_word()?;
+            game.answer = answer;
+            if wrapped {
+                startup_status.push(String::from(WRAPPED_NOTICE));
+            }
+        } else if drawn {
+            game.seen_answers.insert(game.answer.clone());
+        }
+        if !startup_status.is_empty() {
+            game.show_status(startup_status.join("; "));
         }
+        if !startup_errors.is_empty() {
+            game.show_error(startup_errors.join("; "));
+        }
+        Ok(game)
     }
 
     pub fn game_restart(&mut self) {
+        if self.daily.is_some() {
+            self.show_status(String::from(
+                "there's one daily puzzle a day, come back tomorrow for a new word",
+            ));
+            return;
+        }
+        self.challenge = None;
+        let (answer, wrapped) = match self.draw_unseen_word() {
+            Ok(drawn) => drawn,
+            Err(err) => {
+                self.show_error(describe(&err));
+                return;
+            }
+        };
+        self.answer = answer;
+        self.reset_board();
+        if wrapped {
+            self.show_status(String::from(WRAPPED_NOTICE));
+        }
+    }
+
+    /// Turns on adaptive difficulty, picking up the saved level, and starts a
+    /// game at that level.
+    pub fn start_adaptive(&mut self) {
+        let adaptive = Adaptive::load(&self.storage).unwrap_or_else(|err| {
+            self.show_error(format!("failed to load adaptive level: {err}"));
+            Adaptive::default()
+        });
+        self.adaptive = Some(adaptive);
+        match self.draw_unseen_word() {
+            Ok((answer, _)) => self.answer = answer,
+            Err(err) => self.show_error(describe(&err)),
+        }
+        self.reset_board();
+    }
+
+    /// A random answer not played yet this session, at the adaptive level
+    /// when adaptive difficulty is on, and whether every word had been
+    /// played so they start over. Fails if there are no words to draw.
+    ///
+    /// Seeds whose word was played are skipped, rather than the word being
+    /// left out of the list, so `--seed` with the game's seed still draws it.
+    fn draw_unseen_word(&mut self) -> Result<(String, bool), WordleError> {
+        let pool: HashSet<String> = match &self.adaptive {
+            Some(adaptive) => difficulty_pool(&self.answer_words, adaptive.level),
+            None => self.answer_pool(),
+        };
+        // the words `draw_word` picks from, so a seed deals the same one here
+        let playable: Vec<&String> = sorted_words(&pool)
+            .into_iter()
+            .filter(|word| word.chars().count() == self.word_len)
+            .collect();
+        if playable.is_empty() {
+            return Err(WordleError::EmptyWordList);
+        }
+        let wrapped = playable
+            .iter()
+            .all(|word| self.seen_answers.contains(*word));
+        if wrapped {
+            self.seen_answers.retain(|word| !pool.contains(word));
+            self.forget_played(&pool);
+        }
+        // seeds in turn from a random one, so the draw ends within SEEDS
+        // tries even with one word left; should none deal it, the first
+        // seed's word is played again
+        let start = self.rng.random_range(0..SEEDS);
+        let deal = |seed: u64| playable.choose(&mut StdRng::seed_from_u64(seed)).copied();
+        let (seed, answer) = (0..SEEDS)
+            .map(|offset| (start + offset) % SEEDS)
+            .filter_map(|seed| Some((seed, deal(seed)?)))
+            .find(|(_, word)| !self.seen_answers.contains(*word))
+            .or_else(|| Some((start, deal(start)?)))
+            .ok_or(WordleError::EmptyWordList)?;
+        let answer = answer.clone();
+        self.seed = seed;
+        self.seen_answers.insert(answer.clone());
+        Ok((answer, wrapped))
+    }
+
+    /// The seed `--seed` needs to play this game's word again, for random
+    /// games, with the `--difficulty` it was drawn at.
+    pub fn replay_seed(&self) -> Option<(u64, Option<Difficulty>)> {
+        let drawn = self.daily.is_none()
+            && self.challenge.is_none()
+            && self.adaptive.is_none()
+            && self.hot_seat.is_none();
+        drawn.then_some((self.seed, self.difficulty))
+    }
+
+    /// Clears the board and per-game state, keeping the current answer. The
+    /// board counts as a new game and gets a fresh id.
+    pub fn reset_board(&mut self) {
+        self.game_id = Uuid::new_v4().to_string();
+        self.nonce = new_nonce();
         self.round = 1;
         for (_, state) in self.used_chars.iter_mut() {
             *state = TileState::Unused;
         }
-        self.answer = Wordle::draw_word(&self.valid_words).expect("failed to draw word");
+        self.letter_rounds.clear();
         self.current_guess.clear();
         self.guess_history = Vec::new();
-        self.err_msg.clear();
+        self.messages.clear(Level::Error);
+        self.messages.clear(Level::Status);
+        self.assists_used = 0;
+        self.revealed.clear();
         self.solved = false;
         self.is_game_over = false;
         self.show_word_def = false;
+        self.definition_view = DefinitionView::default();
+        self.candidate_page = None;
+        self.show_stats = false;
+        self.review_row = None;
+        self.suggestions = None;
+        self.solver_cache.take();
+        self.remaining_cache.take();
+        self.row_clock = RowClock::new(Instant::now());
+        self.game_clock = GameClock::default();
+        if let Some(blitz) = &mut self.blitz {
+            blitz.restart(Instant::now());
+        }
+        self.row_timings.clear();
+        self.show_analysis = false;
+        self.definitions.cancel();
+        self.restart_recording();
     }
 
-    fn load_words() -> Result<HashSet<String>> {
-        const WORDS: &str = include_str!("../../words.txt");
-        let words: Vec<&str> = WORDS.lines().collect();
+    /// The list built into the binary, checked like any list file.
+    pub fn load_words() -> Result<HashSet<String>, WordleError> {
+        parse_list(WORDS.embedded, WORDS.builtin)
+    }
 
-        let mut result = HashSet::new();
-        words.iter().for_each(|word| {
-            result.insert(word.to_ascii_uppercase());
-        });
-        Ok(result)
+    /// Picks one of the `len`-letter words from words sorted by
+    /// `sorted_words`, so the same seed always gives the same answer whatever
+    /// order the set iterates in.
+    fn draw_word(rng: &mut StdRng, sorted: &[&String], len: usize) -> Result<String, WordleError> {
+        let playable: Vec<&&String> = sorted
+            .iter()
+            .filter(|word| word.chars().count() == len)
+            .collect();
+        playable
+            .choose(rng)
+            .map(|word| word.to_string())
+            .ok_or(WordleError::EmptyWordList)
     }
 
-    fn draw_word(words: &HashSet<String>) -> Option<String> {
-        if words.is_empty() {
-            panic!("Error: empty word set");
+    /// The typed guess as a row, if it's a word from the list. The answer
+    /// always counts, so a hot-seat word or a race answer missing from the
+    /// list is guessable for that game only.
+    pub fn parse_input(&self, input: &str) -> Result<Word, WordleError> {
+        let guess = Word::parse_in(input, self.word_len, &self.alphabet)?;
+        let text = guess.text();
+        if self.is_refused(&text) {
+            return Err(WordleError::WordNotAllowed);
         }
+        if !self.accepted_words.contains(&text) && text != self.answer {
+            return Err(WordleError::WordNotInDictionary(text));
+        }
+        Ok(guess)
+    }
 
-        let mut rng = rand::rng();
-        words.iter().choose(&mut rng).cloned()
+    /// In hard mode, rejects guesses that ignore what earlier rows revealed.
+    pub fn validate_hard_mode(&self, guess: &Word) -> Result<(), WordleError> {
+        if !self.hard_mode() {
+            return Ok(());
+        }
+        let violations = hard_mode_violations(guess, &self.guess_history);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(WordleError::HardModeViolation(violations))
+        }
     }
 
-    fn parse_input(&self, input: &str) -> Result<Word, String> {
-        let input = input.trim();
+    pub fn check_guess(&self, user_input: &mut Word) -> Result<(), WordleError> {
+        score(&self.answer, user_input)
+    }
 
-        if !input.is_ascii() {
-            return Err(String::from("not ascii"));
-        }
+    /// Whether `word` could still be the answer given every guess so far.
+    pub fn is_candidate(&self, word: &str) -> bool {
+        is_consistent(word, &self.guess_history)
+            && self.revealed.iter().all(|reveal| reveal.fits(word))
+    }
+
+    /// All possible answers consistent with the guess history, sorted
+    /// alphabetically.
+    pub fn candidates(&self) -> Vec<&str> {
+        let mut candidates: Vec<&str> = self
+            .answer_words
+            .iter()
+            .map(String::as_str)
+            .filter(|word| self.is_candidate(word))
+            .collect();
+        candidates.sort_unstable();
+        candidates
+    }
 
-        if input.len() != WORD_LEN {
-            return Err(String::from("incorrect word length"));
+    pub fn update_status(&mut self, guess: &Word) {
+        debug_assert!(self.guesses_remaining() > 0, "row played on a full board");
+        debug_assert_eq!(guess.letters.len(), self.word_len);
+        debug_assert!(
+            guess.is_pass()
+                || guess.letters.iter().all(|tile| {
+                    self.alphabet.contains(tile.letter)
+                        && Alphabet::upper(tile.letter) == tile.letter
+                })
+        );
+        // the engine's rules play the row; untimed until the key loop says
+        // otherwise
+        let played = play_row(&mut self.guess_history, &mut self.used_chars, guess.clone());
+        self.row_timings.push(None);
+        for letter in played.marked {
+            self.letter_rounds.insert(letter, self.round);
         }
 
-        if !self.valid_words.contains(&input.to_ascii_uppercase()) {
-            return Err(String::from("invalid word"));
+        // update game status
+        self.solved = played.solved;
+        self.round = self.round.saturating_add(1);
+        self.current_guess.clear();
+        if self.solved || self.guesses_remaining() == 0 {
+            self.is_game_over = true;
         }
+    }
 
-        Ok(Word::from(&input.to_ascii_uppercase()))
+    /// Number of board rows still available for guesses.
+    ///
+    /// `round` starts at 1 and advances after every accepted guess, so this is
+    /// `rounds` before the first guess and 0 once the last guess is recorded.
+    ///
+    /// In zen mode there's always at least one more until the word is found.
+    pub fn guesses_remaining(&self) -> u8 {
+        let left = self.rounds.saturating_sub(self.round - 1);
+        if self.zen && !self.solved {
+            left.max(1)
+        } else {
+            left
+        }
     }
 
-    fn check_guess(&self, user_input: &mut Word) {
-        let mut answer_map = HashMap::new();
-        self.answer.chars().for_each(|c| {
-            *answer_map.entry(c).or_insert(0) += 1;
-        });
+    /// Whether the player may still type and submit a guess.
+    pub fn is_accepting_guesses(&self) -> bool {
+        !self.is_game_over
+            && self.guesses_remaining() > 0
+            && !self.timed.as_ref().is_some_and(|run| run.over)
+            && !self.is_entering_word()
+    }
 
-        // First pass: mark correct letters
-        let answer_vec: Vec<char> = self.answer.chars().collect();
-        for (i, tile) in user_input.letters.iter_mut().enumerate() {
-            if answer_vec[i] == tile.letter {
-                tile.state = TileState::Correct;
-                if let Some(val) = answer_map.get_mut(&tile.letter) {
-                    *val -= 1;
+    /// Validates, scores and records `current_guess`.
+    ///
+    /// Invalid guesses only show an error and do not consume a round. Nothing
+    /// happens once the game is over or the board is full.
+    pub fn submit_guess(&mut self) {
+        if !self.is_accepting_guesses() {
+            return;
+        }
+        match self.play_guess() {
+            Ok(_) => {}
+            Err(WordleError::WordNotInDictionary(word)) => {
+                // never the answer, which would give it away
+                let others = self
+                    .accepted_words
+                    .iter()
+                    .filter(|w| **w != self.answer && !self.is_refused(w));
+                let near = closest_words(&word, others, 2, 3);
+                if near.is_empty() {
+                    self.show_error(describe(&WordleError::WordNotInDictionary(word)));
+                } else {
+                    self.show_error(format!(
+                        "not in the word list, did you mean {}?",
+                        near.join(", ")
+                    ));
                 }
             }
+            Err(WordleError::NotLetters(_)) if !self.alphabet.is_latin() => {
+                self.show_error("only letters on the keyboard are allowed");
+            }
+            Err(err) => self.show_error(describe(&err)),
         }
+    }
 
-        // Second pass: mark present and absent letters
-        for tile in user_input.letters.iter_mut() {
-            if tile.state == TileState::Correct {
-                continue;
-            }
+    /// Scores `current_guess` and plays it as the next row, which is
+    /// returned. A guess that can't be played leaves the board as it was,
+    /// with the reason as the error.
+    pub fn play_guess(&mut self) -> Result<Word, WordleError> {
+        if !self.is_accepting_guesses() {
+            return Err(WordleError::GameOver);
+        }
+        let mut guess = self.parse_input(&self.current_guess)?;
+        self.validate_hard_mode(&guess)?;
+        self.messages.clear(Level::Error);
 
-            match answer_map.get_mut(&tile.letter) {
-                Some(val) if *val > 0 => {
-                    tile.state = TileState::Present;
-                    *val -= 1;
-                }
-                _ => tile.state = TileState::Absent,
+        // compare guess to answer
+        self.check_guess(&mut guess)?;
+
+        self.play_row(&guess);
+        Ok(guess)
+    }
+
+    /// Uses up a round without guessing, leaving a blank gray row. Losing
+    /// by running out of rows works as usual.
+    pub fn pass_turn(&mut self) {
+        if !self.is_accepting_guesses() {
+            return;
+        }
+        self.messages.clear(Level::Error);
+        self.play_row(&Word::pass(self.word_len));
+    }
+
+    /// Records a scored row or a pass; the one path every row takes.
+    fn play_row(&mut self, row: &Word) {
+        // update game status
+        self.update_status(row);
+        self.record_step(row);
+        if let Some(blitz) = &mut self.blitz {
+            blitz.restart(Instant::now());
+        }
+        if self.race.is_some() {
+            self.send_race(RaceMessage::GuessResult {
+                row: row_colors(row),
+            });
+            if self.is_game_over {
+                self.send_race(RaceMessage::GameOver {
+                    solved: self.solved,
+                    rows: self.guess_history.len(),
+                });
             }
         }
+
+        if self.daily.is_some() {
+            self.save_daily();
+        }
     }
 
-    fn update_status(&mut self, guess: &Word) {
-        // save guess into history
-        self.guess_history.push(guess.clone());
+    /// Bookkeeping once the last guess of a game has been submitted.
+    fn finish_game(&mut self) {
+        self.game_clock.stop(Instant::now());
+        self.log_game();
+        // a word one player made up for another says nothing about the
+        // profile, so it stays out of the stats, streak and level
+        if self.hot_seat.is_none() {
+            self.remember_answer();
+            self.count_game();
+        }
+        self.run_integrations();
 
-        // update used chars
-        let mut solved = true;
-        for tile in guess.letters.iter() {
-            if tile.state != TileState::Correct {
-                solved = false;
-            }
+        // answer first, then the guesses in the order they were played
+        let mut words = vec![self.answer.clone()];
+        words.extend(
+            self.record()
+                .guesses
+                .into_iter()
+                .filter(|guess| !guess.trim().is_empty()),
+        );
+        self.definitions.prefetch(&words);
+    }
 
-            let used_state = self
-                .used_chars
-                .entry(tile.letter)
-                .or_insert(TileState::Unused);
-            // states have priorities. The higher the priority, the smaller the value
-            if tile.state < *used_state {
-                *used_state = tile.state;
+    /// Adds the finished game to the stats and the adaptive level.
+    fn count_game(&mut self) {
+        let (unlocked, saved) = self.stats.record_shared(
+            &self.storage,
+            self.solved,
+            self.guess_history.len(),
+            &self.row_timings,
+            self.game_clock.finished,
+            self.blitz.is_some(),
+            self.zen,
+            self.revealed.len(),
+        );
+        if !unlocked.is_empty() {
+            let names: Vec<&str> = unlocked.iter().map(|entry| entry.name).collect();
+            self.celebration = Some(format!(
+                "{}-game streak! Unlocked theme: {}",
+                self.stats.current_streak,
+                names.join(", ")
+            ));
+        }
+        match saved {
+            Err(err) if err.kind() == io::ErrorKind::TimedOut => {
+                self.show_error("stats are in use by another instance; this game wasn't saved")
             }
+            Err(err) => self.show_error(format!("failed to save stats: {err}")),
+            Ok(_) => {}
         }
-
-        // update game status
-        self.solved = solved;
-        self.round += 1;
-        self.current_guess.clear();
-        if self.round > ROUND || self.solved {
-            self.is_game_over = true;
+        if let Some(adaptive) = &self.adaptive {
+            let next = adaptive.next(self.solved, self.guess_history.len());
+            if let Err(err) = next.save(&self.storage) {
+                self.show_error(format!("failed to save adaptive level: {err}"));
+            }
+            self.adaptive = Some(next);
         }
     }
 
-    pub fn get_word_def(&self, word: &String) -> Option<Vec<String>> {
-        let url = format!("https://api.dictionaryapi.dev/api/v2/entries/en/{}", word);
-        let content = blocking::get(url).ok()?.text().ok()?;
+    /// Plays in the terminal, taking input from `events`, until the player
+    /// quits.
+    pub fn run(&mut self, events: &mut impl EventSource) -> Result<()> {
+        let session = match Session::begin(&self.storage) {
+            Ok((session, stale)) => {
+                if stale {
+                    reset_terminal()?;
+                    self.show_status(self.unclean_exit_notice());
+                }
+                Some(session)
+            }
+            Err(err) => {
+                self.show_error(format!("failed to mark the session: {err}"));
+                None
+            }
+        };
+        install_panic_hook();
+        let mut terminal = ratatui::try_init()?;
+        if self.config.mouse {
+            capture_mouse(true)?;
+        }
+        bracketed_paste(true)?;
+        self.detect_background();
 
-        let re = Regex::new(r#""definition":"([^"]*)""#).ok()?;
-        let mut definitions = vec![format!("Definitions for '{}':", word)];
-        for cap in re.captures_iter(&content) {
-            definitions.push("- ".to_string() + &cap[1]);
+        let played = self.event_loop(&mut terminal, events);
+        let _ = bracketed_paste(false);
+        if self.config.mouse {
+            let _ = capture_mouse(false);
+        }
+        ratatui::restore();
+        // the terminal is back even if the loop failed, so that still ends
+        // the session cleanly
+        let ended = session.map_or(Ok(()), Session::end);
+        played?;
+        ended?;
+        if !self.config.no_key_stats {
+            println!("keys pressed this session: {}", self.key_counts.summary());
         }
-        Some(definitions)
-    }
 
-    pub fn run(&mut self) -> Result<()> {
-        let mut terminal = ratatui::init();
+        Ok(())
+    }
 
+    /// Draws to `terminal` and handles `events` until quitting or running
+    /// out of them.
+    pub fn event_loop<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        events: &mut impl EventSource,
+    ) -> Result<()>
+    where
+        B::Error: Send + Sync + 'static,
+    {
+        // a new terminal starts blank
+        self.redraw = true;
         loop {
-            // render terminal output
-            terminal.draw(|frame| {
-                self.render_terminal(frame);
-            })?;
-
-            match self.handle_input() {
-                InputState::Submit => {
-                    // parsing
-                    let mut guess = match self.parse_input(&self.current_guess) {
-                        Ok(val) => {
-                            self.err_msg.clear();
-                            val
-                        }
-                        Err(err) => {
-                            self.err_msg = err;
-                            continue;
-                        }
-                    };
+            // what finishes in the polls below, like the last definition
+            // arriving, still needs drawing once
+            let was_busy = self.is_busy();
+            self.definitions.poll();
+            self.poll_integrations();
+            self.tick_timed(Instant::now());
+            if self.tick_game_clock(Instant::now()) {
+                self.redraw = true;
+            }
+            self.poll_race();
+            self.tick_replay(Instant::now());
+            if let Some(timeout) = self.error_timeout()
+                && self.messages.expire(Instant::now(), timeout)
+            {
+                self.redraw = true;
+            }
+            if let Some(switcher) = &mut self.word_lists {
+                switcher.scan(SCAN_BATCH);
+            }
+            self.bell.tick(Instant::now());
+            self.save_messages(false);
 
-                    // compare guess to answer
-                    self.check_guess(&mut guess);
+            // timers and background work change the screen with time alone
+            if self.redraw || was_busy || self.is_busy() {
+                if let Some(debug) = &mut self.debug {
+                    debug.frames += 1;
+                }
+                terminal.draw(|frame| {
+                    self.render_terminal(frame);
+                })?;
+                self.redraw = false;
+            }
 
-                    // update game status
-                    self.update_status(&guess);
+            let state = match self.handle_input(events) {
+                InputState::EditingGuess | InputState::None => self
+                    .blitz_expired(Instant::now())
+                    .unwrap_or(InputState::None),
+                state => state,
+            };
+            match state {
+                state @ (InputState::Submit | InputState::Pass) => {
+                    let rows = self.guess_history.len();
+                    if matches!(state, InputState::Pass) {
+                        self.pass_turn();
+                    } else {
+                        self.submit_guess();
+                    }
+                    if self.guess_history.len() > rows {
+                        let timing = self.row_clock.finish(Instant::now());
+                        if let Some(last) = self.row_timings.last_mut() {
+                            *last = Some(timing);
+                        }
+                    } else if matches!(state, InputState::Submit) && self.messages.error().is_some()
+                    {
+                        self.notify(GameEvent::InvalidGuess);
+                    }
+                    if self.is_game_over {
+                        self.notify(if self.solved {
+                            GameEvent::Won
+                        } else {
+                            GameEvent::Lost
+                        });
+                        self.finish_game();
+                        self.timed_game_over(Instant::now());
+                    }
                 }
-                InputState::Quit => break,
+                InputState::Quit => return Ok(()),
                 InputState::EditingGuess | InputState::None => {}
             }
         }
-        ratatui::restore();
-
-        Ok(())
     }
 }
 
+/// The words in order, for drawing from reproducibly.
+fn sorted_words(words: &HashSet<String>) -> Vec<&String> {
+    let mut sorted: Vec<&String> = words.iter().collect();
+    sorted.sort_unstable();
+    sorted
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::utils::config::CONFIG_FILE;
+    use crate::utils::storage::DEFAULT_BACKUPS;
+    use crate::utils::testing::temp_dir;
+    use std::fs;
 
     #[test]
-    #[should_panic]
     fn draw_word_empty_test() {
-        let words = HashSet::new();
-        Wordle::draw_word(&words);
+        assert!(matches!(
+            Wordle::draw_word(&mut StdRng::seed_from_u64(0), &[], 5),
+            Err(WordleError::EmptyWordList)
+        ));
     }
 
     #[test]
@@ -235,8 +1103,79 @@ mod test {
             "a".to_string(),
             "test".to_string(),
         ]);
-        let result = Wordle::draw_word(&words).expect("random word expected");
-        assert!(words.contains(&result));
+        let sorted = sorted_words(&words);
+        for _ in 0..20 {
+            let result = Wordle::draw_word(&mut StdRng::from_os_rng(), &sorted, 4)
+                .expect("random word expected");
+            assert!(result == "this" || result == "test", "{result}");
+        }
+        // none of the right length
+        assert!(Wordle::draw_word(&mut StdRng::from_os_rng(), &sorted, 5).is_err());
+    }
+
+    #[test]
+    fn no_repeats_test() {
+        let words: HashSet<String> = ["CRANE", "EPOCH", "SLATE", "TRAIN", "GHOST"]
+            .map(String::from)
+            .into();
+        let mut game = Wordle::new();
+        game.answer_words = words.clone();
+        game.seen_answers.clear();
+        let mut played = HashSet::new();
+        for _ in 0..words.len() {
+            game.game_restart();
+            assert!(played.insert(game.answer.clone()), "{} again", game.answer);
+            assert_eq!(game.messages.status(), None);
+        }
+        assert_eq!(played, words);
+
+        // the sixth restart starts over, and says so
+        game.game_restart();
+        assert!(words.contains(&game.answer));
+        assert_eq!(game.seen_answers.len(), 1);
+        assert!(game.messages.status().unwrap().contains("every word"));
+    }
+
+    #[test]
+    fn unseen_draw_ends_test() {
+        let mut game = Wordle::new();
+        // the only unplayed word can't be dealt at this length, so it starts over
+        game.answer_words = ["CRANE", "PLANET"].map(String::from).into();
+        game.seen_answers = HashSet::from([String::from("CRANE")]);
+        let (answer, wrapped) = game.draw_unseen_word().unwrap();
+        assert_eq!((answer.as_str(), wrapped), ("CRANE", true));
+
+        // one word left of many is found, and its seed deals it again
+        let words: Vec<String> = sorted_words(&game.accepted_words)
+            .into_iter()
+            .cloned()
+            .collect();
+        game.answer_words = words.iter().cloned().collect();
+        game.seen_answers = game.answer_words.clone();
+        game.seen_answers.remove(&words[7]);
+        let (answer, wrapped) = game.draw_unseen_word().unwrap();
+        assert_eq!((&answer, wrapped), (&words[7], false));
+        let sorted = sorted_words(&game.answer_words);
+        let dealt = Wordle::draw_word(&mut StdRng::seed_from_u64(game.seed), &sorted, 5);
+        assert_eq!(dealt.unwrap(), answer);
+    }
+
+    #[test]
+    fn parse_multibyte_input_test() {
+        let game = Wordle::with_words("EPOCH", &["CAFES"]);
+        // five letters, but six bytes, and one of them not A–Z
+        for input in ["CAFÉS", "cafés", "ÉPOCH", "EPOC\u{0301}"] {
+            assert!(
+                matches!(game.parse_input(input), Err(WordleError::NotLetters(_))),
+                "{input}"
+            );
+        }
+        // refused as not letters before it's counted short
+        assert!(matches!(
+            game.parse_input("CAFÉ"),
+            Err(WordleError::NotLetters(_))
+        ));
+        assert_eq!(game.parse_input("cafes").unwrap().text(), "CAFES");
     }
 
     #[test]
@@ -251,260 +1190,575 @@ mod test {
     }
 
     #[test]
-    fn compare_test() {
+    fn update_status_round_test() {
+        // the engine's own tests cover the keyboard and the outcome; this is
+        // the round counter the wrapper keeps on top
         let mut game = Wordle::new();
-        game.answer = "CRATE".to_string();
-        let mut guess = Word::from("CATER");
-        game.check_guess(&mut guess);
-        assert_eq!(
-            guess
-                .letters
-                .into_iter()
-                .map(|tile| tile.state)
-                .collect::<Vec<TileState>>(),
-            vec![
-                TileState::Correct,
-                TileState::Present,
-                TileState::Present,
-                TileState::Present,
-                TileState::Present,
-            ]
-        );
+        game.answer = "DEALT".to_string();
+        for (round, word) in ["ASIDE", "DEATH", "DEALT"].into_iter().enumerate() {
+            assert_eq!(game.round as usize, round + 1);
+            let mut guess = Word::from(word).unwrap();
+            game.check_guess(&mut guess).unwrap();
+            game.update_status(&guess);
+            assert_eq!(game.guess_history.len(), round + 1);
+        }
+        assert!(game.solved && game.is_game_over);
+        assert_eq!(game.round, 4);
+    }
 
+    #[test]
+    fn plays_like_engine_test() {
         let mut game = Wordle::new();
-        game.answer = "HOUND".to_string();
-        let mut guess = Word::from("AMONG");
-        game.check_guess(&mut guess);
-        assert_eq!(
-            guess
-                .letters
-                .into_iter()
-                .map(|tile| tile.state)
-                .collect::<Vec<TileState>>(),
-            [
-                TileState::Absent,
-                TileState::Absent,
-                TileState::Present,
-                TileState::Correct,
-                TileState::Absent,
-            ]
-        );
+        game.answer = "EPOCH".to_string();
+        let mut engine =
+            wordle::engine::Game::new("EPOCH", game.rounds, game.accepted_words.clone());
+        for guess in ["CRANE", "PORCH", "EPOCH"] {
+            game.current_guess = guess.to_string();
+            game.submit_guess();
+            engine.guess(guess).unwrap();
+            assert_eq!(game.guess_history, engine.history());
+            for &letter in game.used_chars.keys() {
+                assert_eq!(game.used_chars[&letter], engine.letter_state(letter));
+            }
+            assert_eq!(game.solved, engine.is_solved());
+            assert_eq!(game.is_game_over, engine.is_over());
+        }
+    }
+
+    fn submit(game: &mut Wordle, word: &str) {
+        game.current_guess = word.to_string();
+        game.submit_guess();
+    }
 
+    #[test]
+    fn guesses_remaining_test() {
         let mut game = Wordle::new();
-        game.answer = "TRAIT".to_string();
-        let mut guess = Word::from("TXTXT");
-        game.check_guess(&mut guess);
-        assert_eq!(
-            guess
-                .letters
-                .into_iter()
-                .map(|tile| tile.state)
-                .collect::<Vec<TileState>>(),
-            [
-                TileState::Correct,
-                TileState::Absent,
-                TileState::Absent,
-                TileState::Absent,
-                TileState::Correct,
-            ]
-        );
+        game.answer = "EPOCH".to_string();
+        assert_eq!(game.guesses_remaining(), ROUND);
+
+        for (i, word) in ["BAGEL", "ROUND", "MOUNT"].iter().enumerate() {
+            submit(&mut game, word);
+            assert_eq!(game.guesses_remaining(), ROUND - 1 - i as u8);
+        }
+
+        // rejected guesses do not consume a round
+        submit(&mut game, "XXXXX");
+        assert_eq!(game.guesses_remaining(), ROUND - 3);
+        assert!(game.messages.error().is_some());
+    }
+
+    #[test]
+    fn win_on_final_guess_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        for word in ["BAGEL", "ROUND", "MOUNT", "CRACK", "SOLVE"] {
+            submit(&mut game, word);
+            assert!(game.is_accepting_guesses());
+        }
+        assert_eq!(game.guesses_remaining(), 1);
+
+        submit(&mut game, "EPOCH");
+        assert_eq!(game.guess_history.len(), 6);
+        assert_eq!(game.guesses_remaining(), 0);
+        assert!(game.solved);
+        assert!(game.is_game_over);
+    }
+
+    #[test]
+    fn lose_after_final_guess_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        for word in ["BAGEL", "ROUND", "MOUNT", "CRACK", "SOLVE"] {
+            submit(&mut game, word);
+        }
+        assert!(!game.is_game_over);
+
+        submit(&mut game, "CRANE");
+        assert_eq!(game.guess_history.len(), 6);
+        assert_eq!(game.guesses_remaining(), 0);
+        assert!(!game.solved);
+        assert!(game.is_game_over);
+    }
+
+    #[test]
+    fn seventh_guess_rejected_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        for word in ["BAGEL", "ROUND", "MOUNT", "CRACK", "SOLVE", "CRANE"] {
+            submit(&mut game, word);
+        }
+        assert!(game.is_game_over);
 
+        submit(&mut game, "EPOCH");
+        assert_eq!(game.guess_history.len(), 6);
+        assert_eq!(game.round, ROUND + 1);
+        assert!(!game.solved);
+        assert!(!game.is_accepting_guesses());
+    }
+
+    #[test]
+    fn adaptive_level_up_test() {
+        let dir = crate::utils::testing::temp_dir("wordle_adaptive");
         let mut game = Wordle::new();
-        game.answer = "TRAIT".to_string();
-        let mut guess = Word::from("TXTTX");
-        game.check_guess(&mut guess);
+        game.storage = Storage::open(dir.clone());
+        game.start_adaptive();
+        assert_eq!(game.adaptive.as_ref().unwrap().level, Difficulty::Normal);
+        let pool = difficulty_pool(&game.answer_words, Difficulty::Normal);
+        assert!(pool.contains(&game.answer));
+
+        for _ in 0..2 {
+            let answer = game.answer.clone();
+            submit(&mut game, &answer);
+            game.finish_game();
+            game.game_restart();
+        }
+        assert_eq!(game.adaptive.as_ref().unwrap().level, Difficulty::Hard);
+        assert!(game.answer_words.contains(&game.answer));
+
+        // the level survives a restart
+        let mut relaunched = Wordle::new();
+        relaunched.storage = Storage::open(dir.clone());
+        relaunched.start_adaptive();
         assert_eq!(
-            guess
-                .letters
-                .into_iter()
-                .map(|tile| tile.state)
-                .collect::<Vec<TileState>>(),
-            [
-                TileState::Correct,
-                TileState::Absent,
-                TileState::Present,
-                TileState::Absent,
-                TileState::Absent,
-            ]
+            relaunched.adaptive.as_ref().unwrap().level,
+            Difficulty::Hard
         );
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 
     #[test]
-    fn update_status_solved_test() {
+    fn stats_across_games_test() {
+        use crate::utils::stats::Stats;
+        use crate::utils::ui::InputState;
+        use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let dir = crate::utils::testing::temp_dir("wordle_stats");
         let mut game = Wordle::new();
-        game.answer = "DEALT".to_string();
-        assert_eq!(game.guess_history.len(), 0);
-        assert_eq!(game.solved, false);
-        assert_eq!(game.is_game_over, false);
-
-        /* round 1 */
-        assert_eq!(game.round, 1);
-        let mut guess = Word::from("ASIDE");
-        game.check_guess(&mut guess);
-        game.update_status(&guess);
-        for (&ch, &state) in &game.used_chars {
-            match ch {
-                'A' | 'D' | 'E' => assert_eq!(state, TileState::Present),
-                'I' | 'S' => assert_eq!(state, TileState::Absent),
-                _ => assert_eq!(state, TileState::Unused),
+        game.storage = Storage::open(dir.clone());
+        // as `run` does after every row
+        let play = |game: &mut Wordle, guesses: &[&str]| {
+            game.answer = String::from("EPOCH");
+            for guess in guesses {
+                submit(game, guess);
+                if game.is_game_over {
+                    game.finish_game();
+                }
             }
+        };
+
+        play(&mut game, &["CRANE", "EPOCH"]);
+        game.game_restart();
+        play(&mut game, &["CRANE"; 6]);
+        game.game_restart();
+        // quitting halfway through isn't a loss
+        play(&mut game, &["CRANE"]);
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(matches!(game.on_key(esc), InputState::Quit));
+        game.game_restart();
+        play(&mut game, &["CRANE", "SLATE", "EPOCH"]);
+        // zen games are counted on their own
+        game.zen = true;
+        game.game_restart();
+        let mut zen = vec!["CRANE"; 8];
+        zen.push("EPOCH");
+        play(&mut game, &zen);
+
+        let stats = Stats::load(&game.storage).unwrap();
+        assert_eq!((stats.zen_played, stats.zen_rows), (1, 9));
+        assert_eq!(stats.games_played, 3);
+        assert_eq!(stats.games_won, 2);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.max_streak, 1);
+        assert_eq!(stats.guess_distribution, vec![0, 1, 1]);
+        assert_eq!(game.stats, stats);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn game_id_artifacts_test() {
+        use crate::utils::integrations::{DryRunExecutor, Effect};
+        use crate::utils::record::{HISTORY_FILE, find_game};
+
+        let dir = crate::utils::testing::temp_dir("wordle_game_id");
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let mut game = Wordle::new();
+        game.storage = Storage::open(dir.clone());
+        game.config.on_game_end = Some(String::from("true"));
+        // the hook is only described, never run
+        game.integrations = Box::new(DryRunExecutor::default());
+        game.start_daily(date);
+        let id = game.game_id.clone();
+        assert_eq!(Uuid::parse_str(&id).unwrap().get_version_num(), 4);
+
+        let answer = game.answer.clone();
+        submit(&mut game, &answer);
+
+        // resuming keeps the id
+        let mut resumed = Wordle::new();
+        resumed.storage = Storage::open(dir.clone());
+        resumed.start_daily(date);
+        assert_eq!(resumed.game_id, id);
+
+        let mut executor = DryRunExecutor::default();
+        for effect in game.game_end_effects() {
+            executor.execute(&effect).unwrap();
         }
-        assert_eq!(game.guess_history.len(), 1);
-        assert_eq!(game.solved, false);
-        assert_eq!(game.is_game_over, false);
+        game.finish_game();
+        let Effect::Command { env, .. } = &executor.effects[0] else {
+            unreachable!("expected the hook");
+        };
+        assert!(env.contains(&("WORDLE_GAME_ID".to_string(), id.clone())));
+        let history = game.storage.load(HISTORY_FILE).unwrap().unwrap();
+        assert!(history.contains(&format!("\"game_id\":\"{id}\"")));
+        let daily = game
+            .storage
+            .load(&crate::utils::daily::slot_name(date))
+            .unwrap()
+            .unwrap();
+        assert!(daily.contains(&id));
+        assert_eq!(
+            find_game(&game.storage, &id).unwrap().unwrap().answer,
+            answer
+        );
 
-        /* round 2 */
+        // a restarted game is a new game
+        let mut random = Wordle::new();
+        let before = random.game_id.clone();
+        random.game_restart();
+        assert_ne!(random.game_id, before);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn hard_mode_rejects_test() {
+        let mut game = Wordle::new();
+        game.config.hard_mode = true;
+        game.answer = "EPOCH".to_string();
+        game.current_guess = "CRANE".to_string();
+        game.submit_guess();
+
+        game.current_guess = "SHOUT".to_string();
+        game.submit_guess();
         assert_eq!(game.round, 2);
-        let mut guess = Word::from("DEATH");
-        game.check_guess(&mut guess);
-        game.update_status(&guess);
-        for (&ch, &state) in &game.used_chars {
-            match ch {
-                'A' | 'D' | 'E' => assert_eq!(state, TileState::Correct),
-                'T' => assert_eq!(state, TileState::Present),
-                'H' | 'I' | 'S' => assert_eq!(state, TileState::Absent),
-                _ => assert_eq!(state, TileState::Unused),
-            }
-        }
-        assert_eq!(game.guess_history.len(), 2);
-        assert_eq!(game.solved, false);
-        assert_eq!(game.is_game_over, false);
+        assert_eq!(
+            game.messages.error(),
+            Some("guess must contain 1 C, guess must contain 1 E")
+        );
+        assert_eq!(game.current_guess, "SHOUT");
 
-        /* round 3 */
+        game.current_guess = "CHOSE".to_string();
+        game.submit_guess();
         assert_eq!(game.round, 3);
-        let mut guess = Word::from("DEALT");
-        game.check_guess(&mut guess);
-        game.update_status(&guess);
-        for (&ch, &state) in &game.used_chars {
-            match ch {
-                'A' | 'D' | 'E' | 'L' | 'T' => assert_eq!(state, TileState::Correct),
-                'H' | 'I' | 'S' => assert_eq!(state, TileState::Absent),
-                _ => assert_eq!(state, TileState::Unused),
-            }
-        }
-        assert_eq!(game.guess_history.len(), 3);
-        assert_eq!(game.solved, true);
-        assert_eq!(game.is_game_over, true);
+        assert!(game.messages.error().is_none());
+
+        // the same guess is fine without hard mode
+        game.config.hard_mode = false;
+        game.current_guess = "SHOUT".to_string();
+        game.submit_guess();
         assert_eq!(game.round, 4);
     }
 
     #[test]
-    fn update_status_unsolved_test() {
+    fn pass_turn_test() {
         let mut game = Wordle::new();
+        game.config.hard_mode = true;
         game.answer = "EPOCH".to_string();
-        assert_eq!(game.solved, false);
-        assert_eq!(game.is_game_over, false);
-        assert_eq!(game.guess_history.len(), 0);
-
-        /* round 1 */
-        assert_eq!(game.round, 1);
-        let mut guess = Word::from("BAGEL");
-        game.check_guess(&mut guess);
-        game.update_status(&guess);
-        for (&ch, &state) in &game.used_chars {
-            match ch {
-                'E' => assert_eq!(state, TileState::Present),
-                'A' | 'B' | 'G' | 'L' => assert_eq!(state, TileState::Absent),
-                _ => assert_eq!(state, TileState::Unused),
-            }
+        game.current_guess = "CRANE".to_string();
+        game.submit_guess();
+        game.current_guess = "SH".to_string();
+        game.pass_turn();
+
+        assert_eq!(game.round, 3);
+        assert!(game.guess_history[1].is_pass());
+        assert!(game.current_guess.is_empty());
+        assert_eq!(game.used_chars[&'S'], TileState::Unused);
+        assert!(!game.used_chars.contains_key(&' '));
+        assert_eq!(game.record().guesses[1], "     ");
+        // the blank row adds no hard-mode rules and rules out nothing
+        game.current_guess = "CHOSE".to_string();
+        game.submit_guess();
+        assert_eq!(game.round, 4);
+        assert!(game.is_candidate("EPOCH"));
+
+        for _ in 0..3 {
+            game.pass_turn();
         }
-        assert_eq!(game.guess_history.len(), 1);
-        assert_eq!(game.solved, false);
-        assert_eq!(game.is_game_over, false);
+        assert!(game.is_game_over);
+        assert!(!game.solved);
+        assert_eq!(game.guess_history.len(), 6);
+        game.pass_turn();
+        assert_eq!(game.guess_history.len(), 6);
+        assert!(game.record().rows().unwrap()[5].is_pass());
+    }
 
-        /* round 2 */
-        assert_eq!(game.round, 2);
-        let mut guess = Word::from("ROUND");
-        game.check_guess(&mut guess);
-        game.update_status(&guess);
-        for (&ch, &state) in &game.used_chars {
-            match ch {
-                'E' | 'O' => assert_eq!(state, TileState::Present),
-                'A' | 'B' | 'D' | 'G' | 'L' | 'N' | 'R' | 'U' => {
-                    assert_eq!(state, TileState::Absent)
-                }
-                _ => assert_eq!(state, TileState::Unused),
-            }
+    #[test]
+    fn with_config_test() {
+        let setup = |seed, rounds| Setup {
+            wordlist_paths: Vec::new(),
+            rounds,
+            seed: Some(seed),
+            ..Setup::default()
+        };
+        let build = |setup| Wordle::with_config(Storage::in_memory("test"), setup);
+        let mut first = build(setup(7, None)).unwrap();
+        let mut second = build(setup(7, None)).unwrap();
+        assert_eq!(first.answer, second.answer);
+        first.game_restart();
+        second.game_restart();
+        assert_eq!(first.answer, second.answer);
+
+        // the seed shown for any game plays its word first
+        let replay = build(setup(first.seed, None)).unwrap();
+        assert_eq!(replay.answer, first.answer);
+        assert!(first.seed < SEEDS);
+        assert_eq!(first.replay_seed(), Some((first.seed, None)));
+        first.daily = Some(NaiveDate::from_ymd_opt(2026, 10, 16).unwrap());
+        assert_eq!(first.replay_seed(), None);
+
+        let mut short = build(setup(7, Some(2))).unwrap();
+        assert_eq!(short.guesses_remaining(), 2);
+        short.answer = "EPOCH".to_string();
+        for guess in ["CRANE", "SHOUT"] {
+            short.current_guess = guess.to_string();
+            short.submit_guess();
         }
-        assert_eq!(game.guess_history.len(), 2);
-        assert_eq!(game.solved, false);
-        assert_eq!(game.is_game_over, false);
+        assert!(short.is_game_over);
+        assert!(!short.solved);
+        assert!(build(setup(7, Some(0))).is_err());
+        assert!(build(setup(7, Some(MAX_ROUNDS + 1))).is_err());
 
-        /* round 3 */
-        assert_eq!(game.round, 3);
-        let mut guess = Word::from("MOUNT");
-        game.check_guess(&mut guess);
-        game.update_status(&guess);
-        for (&ch, &state) in &game.used_chars {
-            match ch {
-                'E' | 'O' => assert_eq!(state, TileState::Present),
-                'A' | 'B' | 'D' | 'G' | 'L' | 'M' | 'N' | 'R' | 'T' | 'U' => {
-                    assert_eq!(state, TileState::Absent)
-                }
-                _ => assert_eq!(state, TileState::Unused),
-            }
+        let missing = Setup {
+            wordlist_paths: vec![PathBuf::from("/nonexistent/words.txt")],
+            ..Setup::default()
+        };
+        let err = build(missing).err().unwrap();
+        assert!(format!("{err:#}").contains("failed to read /nonexistent/words.txt"));
+        let too_long = Setup {
+            length: Some(6),
+            ..Setup::default()
+        };
+        let err = build(too_long).err().unwrap();
+        assert_eq!(err.to_string(), "the word list has no 6-letter words");
+    }
+
+    #[test]
+    fn configured_backups_test() {
+        let dir = temp_dir("wordle_configured_backups");
+        let storage = Storage::open(dir.clone());
+        storage.save(CONFIG_FILE, r#"{"backups": 1}"#).unwrap();
+        let game = Wordle::with_config(storage, Setup::default()).unwrap();
+        assert_eq!(game.storage.backups, 1);
+        for _ in 0..3 {
+            game.stats.save(&game.storage).unwrap();
         }
-        assert_eq!(game.guess_history.len(), 3);
-        assert_eq!(game.solved, false);
-        assert_eq!(game.is_game_over, false);
+        assert!(dir.join("stats.json.1").exists());
+        assert!(!dir.join("stats.json.2").exists());
 
-        /* round 4 */
-        assert_eq!(game.round, 4);
-        let mut guess = Word::from("CRACK");
-        game.check_guess(&mut guess);
-        game.update_status(&guess);
-        for (&ch, &state) in &game.used_chars {
-            match ch {
-                'C' => assert_eq!(state, TileState::Correct),
-                'E' | 'O' => assert_eq!(state, TileState::Present),
-                'A' | 'B' | 'D' | 'G' | 'K' | 'L' | 'M' | 'N' | 'R' | 'T' | 'U' => {
-                    assert_eq!(state, TileState::Absent)
-                }
-                _ => assert_eq!(state, TileState::Unused),
-            }
+        // unset keeps the default
+        fs::remove_file(dir.join(CONFIG_FILE)).unwrap();
+        let game = Wordle::with_config(Storage::open(dir.clone()), Setup::default()).unwrap();
+        assert_eq!(game.storage.backups, DEFAULT_BACKUPS);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn answers_only_from_answer_list_test() {
+        let dir = temp_dir("answer_list");
+        fs::create_dir_all(&dir).unwrap();
+        let guesses = dir.join("guesses.txt");
+        fs::write(&guesses, "crane\nslate\nxylyl\n").unwrap();
+        let answers = dir.join("answers.txt");
+        fs::write(&answers, "crane\nepoch\n").unwrap();
+        let setup = Setup {
+            wordlist_paths: vec![guesses],
+            answer_list: Some(answers),
+            ..Setup::default()
+        };
+        let mut game = Wordle::with_config(Storage::in_memory("test"), setup).unwrap();
+        assert!(game.accepted_words.contains("EPOCH"));
+        for _ in 0..50 {
+            assert!(["CRANE", "EPOCH"].contains(&game.answer.as_str()));
+            game.game_restart();
         }
-        assert_eq!(game.guess_history.len(), 4);
-        assert_eq!(game.solved, false);
-        assert_eq!(game.is_game_over, false);
-
-        /* round 5 */
-        assert_eq!(game.round, 5);
-        let mut guess = Word::from("SOLVE");
-        game.check_guess(&mut guess);
-        game.update_status(&guess);
-        for (&ch, &state) in &game.used_chars {
-            match ch {
-                'C' => assert_eq!(state, TileState::Correct),
-                'E' | 'O' => assert_eq!(state, TileState::Present),
-                'A' | 'B' | 'D' | 'G' | 'K' | 'L' | 'M' | 'N' | 'R' | 'S' | 'T' | 'U' | 'V' => {
-                    assert_eq!(state, TileState::Absent)
-                }
 
-                _ => assert_eq!(state, TileState::Unused),
-            }
+        game.answer = "EPOCH".to_string();
+        for guess in ["XYLYL", "SLATE"] {
+            game.current_guess = guess.to_string();
+            game.submit_guess();
         }
-        assert_eq!(game.guess_history.len(), 5);
-        assert_eq!(game.solved, false);
-        assert_eq!(game.is_game_over, false);
-
-        /* round 6 */
-        assert_eq!(game.round, 6);
-        let mut guess = Word::from("SOLVE");
-        game.check_guess(&mut guess);
-        game.update_status(&guess);
-        for (&ch, &state) in &game.used_chars {
-            match ch {
-                'C' => assert_eq!(state, TileState::Correct),
-                'E' | 'O' => assert_eq!(state, TileState::Present),
-                'A' | 'B' | 'D' | 'G' | 'K' | 'L' | 'M' | 'N' | 'R' | 'S' | 'T' | 'U' | 'V' => {
-                    assert_eq!(state, TileState::Absent)
-                }
-                _ => assert_eq!(state, TileState::Unused),
-            }
+        assert_eq!(game.guess_history.len(), 2);
+        // words only in the guess list are never candidates
+        assert_eq!(game.candidates(), vec!["EPOCH"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn merged_word_lists_test() {
+        let dir = temp_dir("merged_lists");
+        fs::create_dir_all(dir.join("extra")).unwrap();
+        let main = dir.join("main.txt");
+        fs::write(&main, "crane\nslate\nepoch\n").unwrap();
+        let extra = dir.join("extra").join("mine.txt");
+        fs::write(&extra, "xylyl\ncrane\n").unwrap();
+        fs::write(dir.join("extra").join("more.txt"), "zonal\n").unwrap();
+        fs::write(dir.join("extra").join("notes.md"), "not a list\n").unwrap();
+
+        let setup = Setup {
+            wordlist_paths: vec![main.clone(), extra.clone()],
+            ..Setup::default()
+        };
+        let game = Wordle::with_config(Storage::in_memory("test"), setup).unwrap();
+        // CRANE is in both, and counted once
+        assert_eq!(game.accepted_words.len(), 4);
+        assert_eq!(game.answer_words, game.accepted_words);
+        let status = game.messages.status().unwrap();
+        assert!(status.contains("3 from "), "{status}");
+        assert!(status.contains("2 from "), "{status}");
+
+        // a directory brings in its .txt files; answers come from --answers
+        let answers = dir.join("answers.txt");
+        fs::write(&answers, "epoch\n").unwrap();
+        let setup = Setup {
+            wordlist_paths: vec![main.clone(), dir.join("extra")],
+            answer_list: Some(answers),
+            ..Setup::default()
+        };
+        let mut game = Wordle::with_config(Storage::in_memory("test"), setup).unwrap();
+        assert_eq!(game.accepted_words.len(), 5);
+        assert!(game.accepted_words.contains("ZONAL"));
+        for _ in 0..10 {
+            assert_eq!(game.answer, "EPOCH");
+            game.game_restart();
         }
 
-        assert_eq!(game.guess_history.len(), 6);
-        assert_eq!(game.solved, false);
-        assert_eq!(game.is_game_over, true);
+        // a list that fails to load is named
+        fs::write(&extra, "\n").unwrap();
+        let setup = Setup {
+            wordlist_paths: vec![main, extra],
+            ..Setup::default()
+        };
+        let err = Wordle::with_config(Storage::in_memory("test"), setup)
+            .err()
+            .unwrap();
+        assert!(
+            format!("{err:#}").contains("mine.txt has no words"),
+            "{err:#}"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn external_words_fallback_test() {
+        let dir = temp_dir("external_words");
+        let storage = || Storage::open(dir.clone());
+        let game = Wordle::with_config(storage(), Setup::default()).unwrap();
+        assert!(game.accepted_words.len() > 2000);
+        assert_eq!(game.messages.status(), None);
+
+        storage().save(WORDS.file, "crane\nepoch\n").unwrap();
+        let game = Wordle::with_config(storage(), Setup::default()).unwrap();
+        assert_eq!(game.accepted_words.len(), 2);
+        assert!(game.messages.status().unwrap().contains("words.txt"));
+
+        // stray lines are left out, and counted
+        storage()
+            .save(WORDS.file, "crane\nepoch\nab\nsl4te\nslate\n")
+            .unwrap();
+        let game = Wordle::with_config(storage(), Setup::default()).unwrap();
+        assert_eq!(game.accepted_words.len(), 3);
+        let status = game.messages.status().unwrap();
+        assert!(
+            status.ends_with("words.txt (data directory): loaded 3 words, skipped 2 invalid lines"),
+            "{status}"
+        );
+        // notes from later steps are added, not put in their place
+        let setup = Setup {
+            difficulty: Some(crate::utils::difficulty::Difficulty::Easy),
+            ..Setup::default()
+        };
+        let game = Wordle::with_config(storage(), setup).unwrap();
+        let status = game.messages.status().unwrap();
+        assert!(status.contains("skipped 2 invalid lines"), "{status}");
+        assert!(status.contains("the difficulty only applies"), "{status}");
+
+        storage().save(WORDS.file, "not a word list\n").unwrap();
+        let game = Wordle::with_config(storage(), Setup::default()).unwrap();
+        assert!(game.accepted_words.len() > 2000);
+        assert!(
+            game.messages
+                .error()
+                .unwrap()
+                .ends_with("using the built-in list")
+        );
+
+        // a configured list that has gone missing
+        fs::remove_file(dir.join(WORDS.file)).unwrap();
+        let config = Config {
+            word_list: Some(dir.join("missing.txt")),
+            ..Config::default()
+        };
+        config.save(&storage()).unwrap();
+        let game = Wordle::with_config(storage(), Setup::default()).unwrap();
+        assert!(game.accepted_words.len() > 2000);
+        assert!(game.messages.error().unwrap().contains("missing.txt"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn six_letter_game_test() {
+        let dir = temp_dir("six_letters");
+        fs::create_dir_all(&dir).unwrap();
+        let list = dir.join("long.txt");
+        fs::write(&list, "planet\nplants\nstrand\ncastle\ncrane\n").unwrap();
+        let setup = Setup {
+            wordlist_paths: vec![list],
+            ..Setup::default()
+        };
+        let mut game = Wordle::with_config(Storage::in_memory("test"), setup).unwrap();
+        assert_eq!(game.word_len, 6);
+        assert!(!game.accepted_words.contains("CRANE"));
+
+        game.answer = "PLANET".to_string();
+        game.current_guess = "CRANE".to_string();
+        game.submit_guess();
+        assert!(game.guess_history.is_empty());
+
+        game.current_guess = "PLANTS".to_string();
+        game.submit_guess();
+        let states: Vec<TileState> = game.guess_history[0]
+            .letters
+            .iter()
+            .map(|tile| tile.state)
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                TileState::Correct,
+                TileState::Correct,
+                TileState::Correct,
+                TileState::Correct,
+                TileState::Present,
+                TileState::Absent
+            ]
+        );
+        assert_eq!(game.used_chars[&'T'], TileState::Present);
+        assert_eq!(game.used_chars[&'S'], TileState::Absent);
+        assert_eq!(game.candidates(), vec!["PLANET"]);
+
+        game.pass_turn();
+        assert_eq!(game.guess_history[1].letters.len(), 6);
+        game.current_guess = "PLANET".to_string();
+        game.submit_guess();
+        assert!(game.solved);
+        assert_eq!(game.record().rows().unwrap().len(), 3);
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 }
@@ -0,0 +1,220 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Margin, Rect},
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Paragraph, Widget},
+};
+use serde::{Deserialize, Serialize};
+use wordle::engine::{Tile, TileState};
+
+use super::explain::describe_row;
+use super::tile::render_tile;
+use super::ui::{TileSize, tile_area};
+use super::wordle::Wordle;
+
+/// How the board is drawn. Zoom shows only the row being played, at the
+/// largest tile size, with earlier rows and the keyboard as text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BoardLayout {
+    #[default]
+    Normal,
+    Large,
+    Zoom,
+}
+
+impl BoardLayout {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BoardLayout::Normal => "normal",
+            BoardLayout::Large => "large",
+            BoardLayout::Zoom => "zoom",
+        }
+    }
+
+    pub fn bigger(&self) -> Self {
+        match self {
+            BoardLayout::Normal => BoardLayout::Large,
+            BoardLayout::Large | BoardLayout::Zoom => BoardLayout::Zoom,
+        }
+    }
+
+    pub fn smaller(&self) -> Self {
+        match self {
+            BoardLayout::Normal | BoardLayout::Large => BoardLayout::Normal,
+            BoardLayout::Zoom => BoardLayout::Large,
+        }
+    }
+
+    /// Settings cycles through every layout and wraps around.
+    pub fn next(&self) -> Self {
+        match self {
+            BoardLayout::Normal => BoardLayout::Large,
+            BoardLayout::Large => BoardLayout::Zoom,
+            BoardLayout::Zoom => BoardLayout::Normal,
+        }
+    }
+
+    pub fn tiles(&self) -> TileSize {
+        match self {
+            BoardLayout::Normal => TileSize::NORMAL,
+            BoardLayout::Large => TileSize::LARGE,
+            BoardLayout::Zoom => TileSize::ZOOM,
+        }
+    }
+}
+
+/// Letters played so far grouped by keyboard color, e.g. `✓ C  ~ A E  ✗ R T`.
+pub fn keyboard_summary(game: &Wordle) -> String {
+    let mut groups = Vec::new();
+    for (mark, state) in [
+        ('✓', TileState::Correct),
+        ('~', TileState::Present),
+        ('✗', TileState::Absent),
+    ] {
+        let letters: Vec<String> = game
+            .alphabet
+            .letters()
+            .iter()
+            .filter(|ch| game.used_chars.get(*ch) == Some(&state))
+            .map(char::to_string)
+            .collect();
+        if !letters.is_empty() {
+            groups.push(format!("{mark} {}", letters.join(" ")));
+        }
+    }
+    groups.join("  ")
+}
+
+impl Wordle {
+    pub fn set_layout(&mut self, layout: BoardLayout) {
+        if layout == self.config.layout {
+            return;
+        }
+        self.config.layout = layout;
+        if let Err(err) = self.config.save(&self.storage) {
+            self.show_error(format!("failed to save config: {err}"));
+        }
+    }
+
+    /// The zoom layout: one line per earlier row, the active row as large
+    /// as it gets and a one-line keyboard.
+    pub fn render_zoom(&self, area: Rect, buf: &mut Buffer) {
+        let size = TileSize::ZOOM;
+        let [rows_area, board_area, keyboard_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(size.height + 2),
+            Constraint::Length(3),
+        ])
+        .areas(area);
+
+        // while typing, earlier rows are text and the typed row is drawn;
+        // once the game is over the last row stays big
+        let typing = self.is_accepting_guesses();
+        let summarized = if typing {
+            &self.guess_history[..]
+        } else {
+            &self.guess_history[..self.guess_history.len().saturating_sub(1)]
+        };
+        let lines: Vec<Line> = summarized
+            .iter()
+            .enumerate()
+            .map(|(index, word)| Line::from(format!("{}: {}", index + 1, describe_row(word))))
+            .collect();
+        Paragraph::new(lines).render(rows_area, buf);
+
+        Block::bordered()
+            .border_type(BorderType::Rounded)
+            .render(board_area, buf);
+        let center_x = (board_area.left() + board_area.right()) / 2;
+        let tiles: Vec<Tile> = match self.guess_history.last() {
+            Some(word) if !typing => word.letters.clone(),
+            _ => (0..self.word_len)
+                .map(|col| match self.current_guess.chars().nth(col) {
+                    Some(letter) => Tile {
+                        letter,
+                        state: TileState::Absent,
+                    },
+                    None => Tile {
+                        letter: ' ',
+                        state: TileState::Unused,
+                    },
+                })
+                .collect(),
+        };
+        for (col, tile) in tiles.into_iter().enumerate() {
+            let area = tile_area(center_x, board_area.y + 1, 0, col, self.word_len, size);
+            render_tile(
+                tile,
+                &self.theme,
+                area.intersection(board_area.inner(Margin::new(1, 1))),
+                buf,
+            );
+        }
+
+        Paragraph::new(keyboard_summary(self))
+            .centered()
+            .fg(Color::White)
+            .block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .title_bottom(self.progress_title()),
+            )
+            .render(keyboard_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn text(buf: &Buffer) -> String {
+        let area = buf.area;
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn zoom_keeps_state_test() {
+        let mut game = Wordle::new();
+        game.answer = "EPOCH".to_string();
+        game.current_guess = "CRANE".to_string();
+        game.submit_guess();
+        game.current_guess = "CH".to_string();
+
+        let ctrl = |code| KeyEvent::new(code, KeyModifiers::CONTROL);
+        game.on_key(ctrl(KeyCode::Char('+')));
+        game.on_key(ctrl(KeyCode::Char('=')));
+        assert_eq!(game.config.layout, BoardLayout::Zoom);
+        assert_eq!(game.current_guess, "CH");
+        assert_eq!(game.guess_history.len(), 1);
+
+        let area = Rect::new(0, 0, 44, 20);
+        let mut buf = Buffer::empty(area);
+        game.render_zoom(area, &mut buf);
+        let screen = text(&buf);
+        assert!(screen.contains("1: C~ R✗ A✗ N✗ E~"));
+        assert!(screen.contains("~ C E  ✗ A N R"));
+        // the board sits above the 3-row keyboard, letters on the tiles' middle row
+        let y = area.height - 3 - (TileSize::ZOOM.height + 2) + 1 + TileSize::ZOOM.height / 2;
+        let typed: Vec<&str> = (0..area.width)
+            .map(|x| buf[(x, y)].symbol())
+            .filter(|symbol| *symbol == "C" || *symbol == "H")
+            .collect();
+        assert_eq!(typed, vec!["C", "H"]);
+
+        game.on_key(ctrl(KeyCode::Char('-')));
+        game.on_key(ctrl(KeyCode::Char('-')));
+        game.on_key(ctrl(KeyCode::Char('-')));
+        assert_eq!(game.config.layout, BoardLayout::Normal);
+        assert_eq!(game.current_guess, "CH");
+    }
+}
@@ -0,0 +1,63 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const SNAPSHOT: &str = "tests/fixtures/public_api.txt";
+
+/// The first line of every `pub` item in the engine and the variants of
+/// its enums, file by file. Tests at the bottom of each file are left out,
+/// and so is `internal.rs`, which is hidden and may change in any release.
+fn public_items(root: &Path) -> String {
+    let mut files: Vec<_> = fs::read_dir(root.join("src/engine"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| !path.ends_with("internal.rs"))
+        .collect();
+    files.sort();
+    let mut items = String::new();
+    for path in files {
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let source = fs::read_to_string(&path).unwrap();
+        let mut in_enum = false;
+        for line in source
+            .lines()
+            .take_while(|line| !line.starts_with("#[cfg(test)]"))
+        {
+            let item = line.trim();
+            if item.starts_with("pub ") {
+                in_enum = item.starts_with("pub enum ");
+                let item = item.trim_end_matches('{').trim_end();
+                items.push_str(&format!("{name}: {item}\n"));
+            } else if in_enum && line == "}" {
+                in_enum = false;
+            } else if in_enum && line.starts_with("    ") && !line.starts_with("     ") {
+                let variant: String = item
+                    .chars()
+                    .take_while(char::is_ascii_alphanumeric)
+                    .collect();
+                if variant.starts_with(|ch: char| ch.is_ascii_uppercase()) {
+                    items.push_str(&format!("{name}:     {variant}\n"));
+                }
+            }
+        }
+    }
+    items
+}
+
+/// Fails when the library's public surface changes by accident. After a
+/// deliberate change, rerun with `UPDATE_PUBLIC_API=1` and note it in
+/// `CHANGELOG.md`.
+#[test]
+fn public_api_test() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let items = public_items(root);
+    if env::var_os("UPDATE_PUBLIC_API").is_some() {
+        fs::write(root.join(SNAPSHOT), &items).unwrap();
+        return;
+    }
+    let snapshot = fs::read_to_string(root.join(SNAPSHOT)).unwrap_or_default();
+    assert!(
+        items == snapshot,
+        "the public API changed; rerun with UPDATE_PUBLIC_API=1 if that's deliberate\n{items}"
+    );
+}
@@ -0,0 +1,115 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn run(dir: &PathBuf, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rust_wordle"))
+        .args(args)
+        .env("XDG_DATA_HOME", dir.join("data"))
+        .current_dir(dir)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn install_and_list_test() {
+    let dir = std::env::temp_dir().join(format!("rust_wordle_assets_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    assert_eq!(
+        stdout(&run(&dir, &["assets", "list"])),
+        "words (word list): built in\n\
+         answers (answer list): the whole word list\n\
+         themes (theme colors): built in\n\
+         layouts (keyboard layouts): built in\n"
+    );
+
+    // answers have no built-in copy to install
+    let installed = stdout(&run(&dir, &["assets", "install"]));
+    let lines: Vec<&str> = installed.lines().collect();
+    assert_eq!(lines.len(), 3, "{installed}");
+    assert!(lines[0].starts_with("installed ") && lines[0].ends_with("words.txt"));
+    assert!(lines[1].ends_with("themes.toml") && lines[2].ends_with("layouts.toml"));
+    let listed = stdout(&run(&dir, &["assets", "list"]));
+    assert!(listed.contains("words.txt (data directory)\n"), "{listed}");
+    assert!(
+        listed.ends_with("layouts.toml (data directory)\n"),
+        "{listed}"
+    );
+
+    // an edited copy survives a second install, and a broken one is reported
+    let path = PathBuf::from(lines[0]["installed ".len()..].trim());
+    fs::write(&path, "not a word list\n").unwrap();
+    let kept = stdout(&run(&dir, &["assets", "install"]));
+    assert!(kept.contains("--force"), "{kept}");
+    let listed = stdout(&run(&dir, &["assets", "list"]));
+    assert!(
+        listed.starts_with("words (word list): built in\n"),
+        "{listed}"
+    );
+    assert!(listed.contains("skipped:"), "{listed}");
+
+    // the command line wins over everything
+    fs::write(dir.join("mine.txt"), "EPOCH\n").unwrap();
+    let listed = stdout(&run(&dir, &["--wordlist", "mine.txt", "assets", "list"]));
+    assert!(listed.starts_with("words (word list): mine.txt (command line)\nanswers"));
+    fs::write(dir.join("mine.txt"), "EPOCH\nCR4NE\nCRANE\n").unwrap();
+    let listed = stdout(&run(&dir, &["--wordlist", "mine.txt", "assets", "list"]));
+    assert!(listed.starts_with(
+        "words (word list): mine.txt (command line)\n  loaded 2 words, skipped 1 invalid line\n"
+    ));
+
+    // and answers can be given there too
+    let listed = stdout(&run(&dir, &["--answers", "mine.txt", "assets", "list"]));
+    assert!(
+        listed.contains("answers (answer list): mine.txt (command line)\n  loaded 2 words"),
+        "{listed}"
+    );
+
+    // theme colors fall back to the built-in ones when the copy is broken
+    let themes = path.with_file_name("themes.toml");
+    let plaid = "[plaid]\ncorrect = [1, 2, 3]\npresent = [1, 2, 3]\nabsent = [1, 2, 3]\nunused = [1, 2, 3]\n";
+    fs::write(&themes, plaid).unwrap();
+    let listed = stdout(&run(&dir, &["assets", "list"]));
+    assert!(
+        listed.contains("themes (theme colors): built in\n  skipped: "),
+        "{listed}"
+    );
+    assert!(listed.contains("plaid, which isn't a theme"), "{listed}");
+
+    // every list given is shown, a directory's one by one
+    fs::create_dir_all(dir.join("lists")).unwrap();
+    fs::write(dir.join("lists/a.txt"), "CRANE\n").unwrap();
+    fs::write(dir.join("lists/b.txt"), "SLATE\n").unwrap();
+    let listed = stdout(&run(&dir, &["--wordlist", "lists", "assets", "list"]));
+    assert!(listed.starts_with(
+        "words (word list): lists/a.txt (command line)\nwords (word list): lists/b.txt (command line)\nanswers"
+    ));
+    let listed = stdout(&run(
+        &dir,
+        &[
+            "--wordlist",
+            "lists/b.txt",
+            "--wordlist",
+            "mine.txt",
+            "assets",
+            "list",
+        ],
+    ));
+    assert!(listed.starts_with("words (word list): lists/b.txt (command line)\n"));
+    assert!(listed.contains("mine.txt (command line)\n  loaded 2 words"));
+
+    stdout(&run(&dir, &["assets", "install", "--force"]));
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        fs::read_to_string("words.txt").unwrap()
+    );
+    assert_eq!(run(&dir, &["assets", "remove"]).status.code(), Some(2));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
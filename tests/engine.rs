@@ -0,0 +1,46 @@
+use std::collections::HashSet;
+use wordle::engine::{Game, ROUND, TileState, Word, WordList, is_consistent};
+
+fn words() -> HashSet<String> {
+    ["CRANE", "EPOCH", "PORCH", "SLATE"]
+        .map(String::from)
+        .into()
+}
+
+fn colors(row: &Word) -> String {
+    row.letters
+        .iter()
+        .map(|tile| match tile.state {
+            TileState::Correct => 'G',
+            TileState::Present => 'Y',
+            _ => 'B',
+        })
+        .collect()
+}
+
+#[test]
+fn scripted_win_test() {
+    let mut game = Game::new("EPOCH", ROUND, words());
+    assert_eq!(colors(game.guess("crane").unwrap()), "YBBBY");
+    game.pass().unwrap();
+    assert_eq!(colors(game.guess("PORCH").unwrap()), "YYBGG");
+    assert!(!game.is_over());
+    assert_eq!(game.guesses_remaining(), ROUND - 3);
+
+    // every candidate left fits the rows played
+    let list = WordList::from_set(&words());
+    let left: Vec<&String> = list
+        .words()
+        .iter()
+        .filter(|word| is_consistent(word, game.history()))
+        .collect();
+    assert_eq!(left, ["EPOCH"]);
+
+    assert_eq!(colors(game.guess("EPOCH").unwrap()), "GGGGG");
+    assert!(game.is_solved() && game.is_over());
+    assert_eq!(game.history().len(), 4);
+    assert_eq!(game.letter_state('R'), TileState::Absent);
+    assert_eq!(game.letter_state('C'), TileState::Correct);
+    assert_eq!(game.letter_state('Z'), TileState::Unused);
+    assert!(game.guess("SLATE").is_err());
+}
@@ -0,0 +1,108 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+/// A scratch data directory and word lists where EPOCH is the only answer.
+fn setup(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rust_wordle_exit_{name}_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("words.txt"), "EPOCH\nCRANE\n").unwrap();
+    fs::write(dir.join("answers.txt"), "EPOCH\n").unwrap();
+    dir
+}
+
+fn run(dir: &PathBuf, args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rust_wordle"))
+        .args(args)
+        .env("XDG_DATA_HOME", dir.join("data"))
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn json(dir: &PathBuf, guesses: &[&str]) -> Output {
+    let input: String = guesses
+        .iter()
+        .map(|guess| format!("{{\"guess\": \"{guess}\"}}\n"))
+        .collect();
+    let args = [
+        "--wordlist",
+        "words.txt",
+        "--answers",
+        "answers.txt",
+        "--json",
+    ];
+    run(dir, &args, &input)
+}
+
+#[test]
+fn won_and_lost_test() {
+    let dir = setup("games");
+    assert_eq!(json(&dir, &["CRANE", "EPOCH"]).status.code(), Some(0));
+    assert_eq!(json(&dir, &["CRANE"; 6]).status.code(), Some(1));
+    // running out of input before the end isn't a win
+    assert_eq!(json(&dir, &["CRANE"]).status.code(), Some(1));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn usage_errors_test() {
+    let dir = setup("usage");
+    for args in [
+        &["--rounds", "many"][..],
+        &["--profile", "../elsewhere"],
+        &["--frobnicate"],
+        &["frobnicate"],
+        &["score", "--answer", "CRANE", "--guess", "CRANES"],
+        &["--daily", "--adaptive"],
+        &["--adaptive", "--difficulty", "easy"],
+    ] {
+        let output = run(&dir, args, "");
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        assert!(!output.stderr.is_empty(), "{args:?}");
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn environment_errors_test() {
+    let dir = setup("environment");
+    for args in [
+        &["score", "--answer", "CRANE", "--guess-file", "missing.txt"][..],
+        &["compare", "missing.json", "missing.json"],
+    ] {
+        let output = run(&dir, args, "");
+        assert_eq!(output.status.code(), Some(3), "{args:?}");
+    }
+    // a word list that can't be read has a code of its own
+    for args in [
+        &["--wordlist", "missing.txt", "--json"][..],
+        &["--answers", "missing.txt", "--json"],
+    ] {
+        let output = run(&dir, args, "");
+        assert_eq!(output.status.code(), Some(4), "{args:?}");
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn help_test() {
+    let dir = setup("help");
+    let output = run(&dir, &["--help"], "");
+    assert_eq!(output.status.code(), Some(0));
+    let help = String::from_utf8(output.stdout).unwrap();
+    assert!(help.contains("exit codes:"));
+    assert!(help.contains("3  environment error"));
+    fs::remove_dir_all(&dir).unwrap();
+}
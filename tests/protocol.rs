@@ -0,0 +1,129 @@
+use serde_json::Value;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// A scratch data directory, so games never touch the real profile.
+fn data_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "rust_wordle_protocol_{name}_{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn play(dir: &PathBuf, args: &[&str], input: &[u8]) -> (Vec<Value>, Option<i32>) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rust_wordle"))
+        .args(args)
+        .env("XDG_DATA_HOME", dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    let lines = BufReader::new(child.stdout.take().unwrap())
+        .lines()
+        .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+        .collect();
+    (lines, child.wait().unwrap().code())
+}
+
+#[test]
+fn malformed_lines_then_full_game_test() {
+    let garbage: [&[u8]; 9] = [
+        b"not json",
+        b"{\"guess\": ",
+        b"[\"CRANE\"]",
+        b"{\"word\": \"CRANE\"}",
+        b"{\"guess\": \"CRANE\", \"extra\": true}",
+        b"{\"guess\": \"crane\"}",
+        b"{\"guess\": \"CRANES\"}",
+        b"{\"guess\": \"QQQQQ\"}",
+        b"{\"guess\": \"\xc3\x28\"}",
+    ];
+    let mut input = Vec::new();
+    for line in garbage {
+        input.extend_from_slice(line);
+        input.push(b'\n');
+    }
+    for word in ["CRANE", "SLATE", "EPOCH", "CRANE", "SLATE", "EPOCH"] {
+        input.extend_from_slice(format!("{{\"guess\": \"{word}\"}}\n").as_bytes());
+    }
+
+    let dir = data_dir("malformed");
+    let (responses, code) = play(&dir, &["--json", "--strict"], &input);
+    assert_eq!(responses.len(), 1 + garbage.len() + 6);
+    assert_eq!(responses[0]["type"], "hello");
+    assert_eq!(responses[0]["protocol-version"], 1);
+
+    let codes: Vec<&str> = responses[1..=garbage.len()]
+        .iter()
+        .map(|response| response["code"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        codes,
+        [
+            "malformed_json",
+            "malformed_json",
+            "malformed_json",
+            "malformed_json",
+            "unknown_field",
+            "invalid_characters",
+            "invalid_length",
+            "unknown_word",
+            "invalid_utf8",
+        ]
+    );
+
+    // garbage never used up a round; the game still ends normally
+    let guesses = &responses[1 + garbage.len()..];
+    assert_eq!(guesses[0]["round"], 1);
+    let last = guesses
+        .iter()
+        .find(|response| response["game_over"] == true)
+        .unwrap();
+    assert!(last["answer"].is_string());
+    // 0 for a win, 1 for a loss
+    let expected = if last["solved"] == true { 0 } else { 1 };
+    assert_eq!(code, Some(expected));
+    for response in guesses
+        .iter()
+        .skip_while(|response| response["game_over"] != true)
+        .skip(1)
+    {
+        assert_eq!(response["code"], "game_over");
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn refused_word_test() {
+    let dir = data_dir("refused");
+    let (words, answers) = (dir.join("words.txt"), dir.join("answers.txt"));
+    fs::write(&words, "crane\nbooby\nslate\n").unwrap();
+    fs::write(&answers, "epoch\n").unwrap();
+    let input = b"{\"guess\": \"CRANE\"}\n{\"guess\": \"BOOBY\"}\n{\"guess\": \"SLATE\"}\n";
+    let (responses, _) = play(
+        &dir,
+        &[
+            "--json",
+            "--family-mode",
+            "--wordlist",
+            words.to_str().unwrap(),
+            "--answers",
+            answers.to_str().unwrap(),
+        ],
+        input,
+    );
+    assert_eq!(responses.len(), 4);
+    assert_eq!(responses[1]["round"], 1);
+    // refused without a row, not answered with the last one
+    assert_eq!(responses[2]["type"], "error");
+    assert_eq!(responses[2]["code"], "not_allowed");
+    assert_eq!(responses[2]["message"], "word not allowed");
+    assert_eq!(responses[3]["guess"], "SLATE");
+    assert_eq!(responses[3]["round"], 2);
+    fs::remove_dir_all(&dir).unwrap();
+}
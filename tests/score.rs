@@ -0,0 +1,110 @@
+use std::fs;
+use std::process::{Command, Output};
+
+fn score(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rust_wordle"))
+        .arg("score")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn single_guess_test() {
+    let output = score(&["--answer", "CRANE", "--guess", "cater"]);
+    // not a terminal, so no color escapes
+    assert_eq!(stdout(&output), "CRANE CATER GYBYY 🟩🟨⬛🟨🟨\n");
+}
+
+#[test]
+fn duplicate_letters_test() {
+    for (answer, guess, letters) in [
+        // a repeated letter is yellow only as often as the answer has it
+        ("ABIDE", "SPEED", "BBYBY"),
+        ("SPEED", "ABIDE", "BBBYY"),
+        // exact matches use up copies before yellows do
+        ("HELLO", "LOLLY", "BYGGB"),
+        ("CRANE", "EERIE", "BBYBG"),
+        // both copies are yellow when the answer has two
+        ("HELLO", "LLAMA", "YYBBB"),
+    ] {
+        let output = score(&["--answer", answer, "--guess", guess]);
+        let line = stdout(&output);
+        assert_eq!(line.split(' ').nth(2), Some(letters), "{answer} {guess}");
+    }
+}
+
+#[test]
+fn batch_test() {
+    let dir = std::env::temp_dir().join(format!("rust_wordle_score_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let answers = dir.join("answers.txt");
+    let guesses = dir.join("guesses.txt");
+    fs::write(&answers, "CRANE\nEPOCH\nHELLO\n").unwrap();
+    fs::write(&guesses, "CATER\nCHEAP\nLOLLY\n").unwrap();
+    let (answers, guesses) = (answers.to_str().unwrap(), guesses.to_str().unwrap());
+
+    let output = score(&["--answer-file", answers, "--guess-file", guesses]);
+    assert_eq!(
+        stdout(&output),
+        "CRANE CATER GYBYY 🟩🟨⬛🟨🟨\n\
+         EPOCH CHEAP YYYBY 🟨🟨🟨⬛🟨\n\
+         HELLO LOLLY BYGGB ⬛🟨🟩🟩⬛\n"
+    );
+
+    // one answer against every guess of a file
+    let output = score(&["--answer", "EPOCH", "--guess-file", guesses]);
+    let lines: Vec<String> = stdout(&output).lines().map(String::from).collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines.iter().all(|line| line.starts_with("EPOCH ")));
+
+    // a bad line fails the whole batch before anything is printed
+    fs::write(dir.join("short.txt"), "CATER\nCHEA\nLOLLY\n").unwrap();
+    let short = dir.join("short.txt");
+    let output = score(&[
+        "--answer-file",
+        answers,
+        "--guess-file",
+        short.to_str().unwrap(),
+    ]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let error = String::from_utf8(output.stderr).unwrap();
+    assert!(error.contains("line 2"), "{error}");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn invalid_input_test() {
+    for args in [
+        &["--answer", "CRANE", "--guess", "CRANES"][..],
+        &["--answer", "CRANE", "--guess", "CR4NE"],
+        &["--answer", "CRANE"],
+        &["--guess", "CRANE"],
+        &["--answer", "CRANE", "--guess", "SLATE", "extra"],
+        &[
+            "--answer",
+            "CRANE",
+            "--answer-file",
+            "a.txt",
+            "--guess",
+            "SLATE",
+        ],
+        &[
+            "--answer-file",
+            "/nonexistent/answers.txt",
+            "--guess",
+            "SLATE",
+        ],
+    ] {
+        let output = score(args);
+        assert!(!output.status.success(), "{args:?}");
+        assert!(output.stdout.is_empty(), "{args:?}");
+    }
+}